@@ -83,7 +83,7 @@ impl IntoCompact for StorageLayout {
 /// A concrete range of keys.
 ///
 /// Basically a thin-wrapper around keys from `ink_core` library for serialization purposes.
-#[derive(Debug, PartialEq, Eq, From, Serialize)]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, From, Serialize)]
 #[serde(transparent)]
 pub struct LayoutKey(
     /// Internals must be compatible with `ink_primitives::Key`.
@@ -224,6 +224,204 @@ impl LayoutRange {
     }
 }
 
+impl<F> LayoutRange<F>
+where
+    F: Form,
+{
+    /// Returns `true` if `self` and `other` share at least one key.
+    fn overlaps(&self, other: &Self) -> bool {
+        let contains = |range: &Self, key: &LayoutKey| {
+            match key_distance(&range.offset, key) {
+                Some(distance) => distance < range.len,
+                None => false,
+            }
+        };
+        contains(self, &other.offset) || contains(other, &self.offset)
+    }
+}
+
+/// Returns the distance from the `from` key to the `to` key if it fits into a `u32`.
+///
+/// # Note
+///
+/// Keys are big-endian 256-bit numbers that wrap around upon overflow,
+/// the same as the keys of `ink_primitives`.
+fn key_distance(from: &LayoutKey, to: &LayoutKey) -> Option<u32> {
+    let mut distance = [0x00_u8; 32];
+    let mut borrow = false;
+    for ((byte, to), from) in distance.iter_mut().zip(&to.0).zip(&from.0).rev() {
+        let (diff, borrow_1) = to.overflowing_sub(*from);
+        let (diff, borrow_2) = diff.overflowing_sub(borrow as u8);
+        *byte = diff;
+        borrow = borrow_1 || borrow_2;
+    }
+    if distance[..28].iter().any(|&byte| byte != 0x00) {
+        return None
+    }
+    let mut low = [0x00_u8; 4];
+    low.copy_from_slice(&distance[28..]);
+    Some(u32::from_be_bytes(low))
+}
+
+/// An incompatibility between the storage layouts of two versions of a contract.
+///
+/// The path lists the names of the affected field and its enclosing fields,
+/// e.g. `["balances", "entries"]`.
+#[derive(Debug, PartialEq, Eq)]
+pub enum LayoutIncompatibility<S = <MetaForm as Form>::String> {
+    /// A field of the old layout is missing in the new layout.
+    MissingField { path: Vec<S> },
+    /// A field changed between a direct key range and a sub-struct.
+    ChangedKind { path: Vec<S> },
+    /// The keys associated to a field have been moved.
+    MovedKeys {
+        path: Vec<S>,
+        old: LayoutKey,
+        new: LayoutKey,
+    },
+    /// The amount of keys associated to a field changed.
+    ChangedLen { path: Vec<S>, old: u32, new: u32 },
+    /// The type stored under the keys of a field changed.
+    ///
+    /// This means that the encoding of the stored values might have changed.
+    ChangedType { path: Vec<S> },
+    /// The keys of a field in the new layout overlap the keys of another
+    /// field of the old layout.
+    ///
+    /// The other field is either still part of the new layout or has been
+    /// removed, in which case its values are still found in the storage.
+    OverlappingKeys { path: Vec<S>, other: Vec<S> },
+}
+
+/// Checks if the new storage layout is compatible with the old one.
+///
+/// The new layout is compatible if all fields of the old layout are still
+/// found under the same keys and store values of the same types.
+/// Fields that are only found in the new layout are compatible as long as
+/// their keys do not overlap the keys of any field of the old layout.
+///
+/// # Note
+///
+/// - Upgradeable contracts can use this to check that a new version
+///   is able to operate on the storage of the deployed version.
+/// - Compact layouts refer to their types and field names through their
+///   registry and thus have to be compacted into the same registry.
+///
+/// # Errors
+///
+/// Returns all found incompatibilities otherwise.
+pub fn check_layout_compatibility<F>(
+    old: &StorageLayout<F>,
+    new: &StorageLayout<F>,
+) -> Result<(), Vec<LayoutIncompatibility<F::String>>>
+where
+    F: Form,
+    F::TypeId: PartialEq,
+    F::String: PartialEq + Clone,
+{
+    let mut incompatibilities = Vec::new();
+    collect_incompatibilities(&mut Vec::new(), old, new, &mut incompatibilities);
+    let mut old_ranges = Vec::new();
+    collect_ranges(&mut Vec::new(), old, &mut old_ranges);
+    let mut new_ranges = Vec::new();
+    collect_ranges(&mut Vec::new(), new, &mut new_ranges);
+    for (path, new_range) in &new_ranges {
+        for (other, old_range) in &old_ranges {
+            if path != other && new_range.overlaps(old_range) {
+                incompatibilities.push(LayoutIncompatibility::OverlappingKeys {
+                    path: path.clone(),
+                    other: other.clone(),
+                })
+            }
+        }
+    }
+    if !incompatibilities.is_empty() {
+        return Err(incompatibilities)
+    }
+    Ok(())
+}
+
+/// Collects the incompatibilities of the new layout with the old one at the path.
+fn collect_incompatibilities<F>(
+    path: &mut Vec<F::String>,
+    old: &StorageLayout<F>,
+    new: &StorageLayout<F>,
+    incompatibilities: &mut Vec<LayoutIncompatibility<F::String>>,
+) where
+    F: Form,
+    F::TypeId: PartialEq,
+    F::String: PartialEq + Clone,
+{
+    match (old, new) {
+        (StorageLayout::Range(old), StorageLayout::Range(new)) => {
+            if old.offset != new.offset {
+                incompatibilities.push(LayoutIncompatibility::MovedKeys {
+                    path: path.clone(),
+                    old: old.offset,
+                    new: new.offset,
+                })
+            }
+            if old.len != new.len {
+                incompatibilities.push(LayoutIncompatibility::ChangedLen {
+                    path: path.clone(),
+                    old: old.len,
+                    new: new.len,
+                })
+            }
+            if old.elem_ty != new.elem_ty {
+                incompatibilities
+                    .push(LayoutIncompatibility::ChangedType { path: path.clone() })
+            }
+        }
+        (StorageLayout::Struct(old), StorageLayout::Struct(new)) => {
+            for old_field in &old.fields {
+                path.push(old_field.name.clone());
+                match new.fields.iter().find(|field| field.name == old_field.name) {
+                    Some(new_field) => {
+                        collect_incompatibilities(
+                            path,
+                            &old_field.sub_layout,
+                            &new_field.sub_layout,
+                            incompatibilities,
+                        )
+                    }
+                    None => {
+                        incompatibilities.push(LayoutIncompatibility::MissingField {
+                            path: path.clone(),
+                        })
+                    }
+                }
+                path.pop();
+            }
+        }
+        _ => {
+            incompatibilities
+                .push(LayoutIncompatibility::ChangedKind { path: path.clone() })
+        }
+    }
+}
+
+/// Collects all key ranges of the layout together with their paths.
+fn collect_ranges<'a, F>(
+    path: &mut Vec<F::String>,
+    layout: &'a StorageLayout<F>,
+    ranges: &mut Vec<(Vec<F::String>, &'a LayoutRange<F>)>,
+) where
+    F: Form,
+    F::String: Clone,
+{
+    match layout {
+        StorageLayout::Range(range) => ranges.push((path.clone(), range)),
+        StorageLayout::Struct(layout_struct) => {
+            for field in &layout_struct.fields {
+                path.push(field.name.clone());
+                collect_ranges(path, &field.sub_layout, ranges);
+                path.pop();
+            }
+        }
+    }
+}
+
 fn serialize_key<S>(key: &LayoutKey, serializer: S) -> Result<S::Ok, S::Error>
 where
     S: Serializer,
@@ -271,4 +469,136 @@ mod tests {
             "{\"range.offset\":\"0x0101010101010101010101010101010101010101010101010101010101010101\",\"range.len\":1337,\"range.elem_type\":1}"
         );
     }
+
+    fn layout(fields: Vec<(&'static str, StorageLayout)>) -> StorageLayout {
+        LayoutStruct::new(
+            <MetaForm as Form>::TypeId::new::<()>(),
+            fields
+                .into_iter()
+                .map(|(name, sub_layout)| LayoutField::new(name, sub_layout)),
+        )
+        .into()
+    }
+
+    fn cell<T: type_metadata::Metadata + 'static>(key: u8) -> StorageLayout {
+        LayoutRange::cell(LayoutKey([key; 32]), <MetaForm as Form>::TypeId::new::<T>())
+            .into()
+    }
+
+    #[test]
+    fn appended_fields_are_compatible() {
+        // given
+        let old = layout(vec![("owner", cell::<u32>(0))]);
+        let new = layout(vec![("owner", cell::<u32>(0)), ("total", cell::<u64>(1))]);
+
+        // then
+        assert_eq!(check_layout_compatibility(&old, &new), Ok(()));
+    }
+
+    #[test]
+    fn changed_fields_are_incompatible() {
+        // given
+        let old = layout(vec![
+            ("owner", cell::<u32>(0)),
+            ("total", cell::<u64>(1)),
+            ("nested", layout(vec![("value", cell::<bool>(2))])),
+            ("removed", cell::<u8>(3)),
+        ]);
+        let new = layout(vec![
+            ("owner", cell::<u32>(4)),
+            ("total", cell::<u128>(1)),
+            ("nested", layout(vec![("value", cell::<bool>(5))])),
+        ]);
+
+        // then
+        assert_eq!(
+            check_layout_compatibility(&old, &new),
+            Err(vec![
+                LayoutIncompatibility::MovedKeys {
+                    path: vec!["owner"],
+                    old: LayoutKey([0; 32]),
+                    new: LayoutKey([4; 32]),
+                },
+                LayoutIncompatibility::ChangedType {
+                    path: vec!["total"]
+                },
+                LayoutIncompatibility::MovedKeys {
+                    path: vec!["nested", "value"],
+                    old: LayoutKey([2; 32]),
+                    new: LayoutKey([5; 32]),
+                },
+                LayoutIncompatibility::MissingField {
+                    path: vec!["removed"]
+                },
+            ])
+        );
+    }
+
+    fn chunk<T: type_metadata::Metadata + 'static>(key: u8) -> StorageLayout {
+        LayoutRange::chunk(LayoutKey([key; 32]), <MetaForm as Form>::TypeId::new::<T>())
+            .into()
+    }
+
+    #[test]
+    fn overlapping_fields_are_incompatible() {
+        // given
+        let mut into_chunk = [0x01; 32];
+        into_chunk[31] = 0x42;
+        let old = layout(vec![
+            ("owner", cell::<u32>(0)),
+            ("entries", chunk::<u64>(1)),
+            ("removed", cell::<u8>(2)),
+        ]);
+        let new = layout(vec![
+            ("owner", cell::<u32>(0)),
+            ("entries", chunk::<u64>(1)),
+            (
+                "added",
+                LayoutRange::cell(
+                    LayoutKey(into_chunk),
+                    <MetaForm as Form>::TypeId::new::<bool>(),
+                )
+                .into(),
+            ),
+            ("reused", cell::<u8>(2)),
+            ("fresh", cell::<u8>(3)),
+        ]);
+
+        // then
+        assert_eq!(
+            check_layout_compatibility(&old, &new),
+            Err(vec![
+                LayoutIncompatibility::MissingField {
+                    path: vec!["removed"]
+                },
+                LayoutIncompatibility::OverlappingKeys {
+                    path: vec!["added"],
+                    other: vec!["entries"],
+                },
+                LayoutIncompatibility::OverlappingKeys {
+                    path: vec!["reused"],
+                    other: vec!["removed"],
+                },
+            ])
+        );
+    }
+
+    #[test]
+    fn compact_layouts_are_checked() {
+        // given
+        let mut registry = Registry::new();
+        let old = layout(vec![("owner", cell::<u32>(0)), ("total", cell::<u64>(1))])
+            .into_compact(&mut registry);
+        let new = layout(vec![("owner", cell::<u32>(0)), ("total", cell::<u128>(1))])
+            .into_compact(&mut registry);
+
+        // then
+        assert_eq!(check_layout_compatibility(&old, &old), Ok(()));
+        assert_eq!(
+            check_layout_compatibility(&old, &new),
+            Err(vec![LayoutIncompatibility::ChangedType {
+                path: vec![registry.register_string("total")],
+            }])
+        );
+    }
 }
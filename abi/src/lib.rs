@@ -25,8 +25,10 @@ pub use ink_abi_derive::HasLayout;
 
 pub use self::{
     layout::{
+        check_layout_compatibility,
         HasLayout,
         LayoutField,
+        LayoutIncompatibility,
         LayoutKey,
         LayoutRange,
         LayoutStruct,
@@ -38,6 +40,7 @@ pub use self::{
         ContractSpec,
         ContractSpecBuilder,
         DisplayName,
+        EnvironmentSpec,
         EventParamSpec,
         EventParamSpecBuilder,
         EventSpec,
@@ -82,4 +85,19 @@ impl InkProject {
             registry,
         }
     }
+
+    /// Returns the registry of all types and strings referred to by the project.
+    pub fn registry(&self) -> &Registry {
+        &self.registry
+    }
+
+    /// Returns the storage layout of the contract.
+    pub fn layout(&self) -> &StorageLayout<CompactForm> {
+        &self.layout
+    }
+
+    /// Returns the specification of the contract.
+    pub fn spec(&self) -> &ContractSpec<CompactForm> {
+        &self.spec
+    }
 }
@@ -49,6 +49,9 @@ pub struct ContractSpec<F: Form = MetaForm> {
     messages: Vec<MessageSpec<F>>,
     /// The events of the contract.
     events: Vec<EventSpec<F>>,
+    /// The environmental types of the chain the contract is deployed to.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    environment: Option<EnvironmentSpec<F>>,
     /// The contract documentation.
     docs: Vec<&'static str>,
 }
@@ -74,11 +77,49 @@ impl IntoCompact for ContractSpec {
                 .into_iter()
                 .map(|event| event.into_compact(registry))
                 .collect::<Vec<_>>(),
+            environment: self
+                .environment
+                .map(|environment| environment.into_compact(registry)),
             docs: self.docs,
         }
     }
 }
 
+impl<F> ContractSpec<F>
+where
+    F: Form,
+{
+    /// Returns the name of the contract.
+    pub fn name(&self) -> &F::String {
+        &self.name
+    }
+
+    /// Returns the constructors of the contract.
+    pub fn constructors(&self) -> &[ConstructorSpec<F>] {
+        &self.constructors
+    }
+
+    /// Returns the external messages of the contract.
+    pub fn messages(&self) -> &[MessageSpec<F>] {
+        &self.messages
+    }
+
+    /// Returns the events of the contract.
+    pub fn events(&self) -> &[EventSpec<F>] {
+        &self.events
+    }
+
+    /// Returns the environmental types of the contract if any.
+    pub fn environment(&self) -> Option<&EnvironmentSpec<F>> {
+        self.environment.as_ref()
+    }
+
+    /// Returns the contract documentation.
+    pub fn docs(&self) -> &[&'static str] {
+        &self.docs
+    }
+}
+
 /// The message builder is ready to finalize construction.
 pub enum Valid {}
 /// The message builder is not ready to finalize construction.
@@ -140,6 +181,18 @@ impl<S> ContractSpecBuilder<S> {
         }
     }
 
+    /// Sets the environmental types of the contract specification.
+    pub fn environment(self, environment: EnvironmentSpec) -> Self {
+        debug_assert!(self.spec.environment.is_none());
+        Self {
+            spec: ContractSpec {
+                environment: Some(environment),
+                ..self.spec
+            },
+            ..self
+        }
+    }
+
     /// Sets the documentation of the contract specification.
     pub fn docs<D>(self, docs: D) -> Self
     where
@@ -180,6 +233,7 @@ impl ContractSpec {
                 constructors: Vec::new(),
                 messages: Vec::new(),
                 events: Vec::new(),
+                environment: None,
                 docs: Vec::new(),
             },
             marker: PhantomData,
@@ -196,8 +250,23 @@ pub struct ConstructorSpec<F: Form = MetaForm> {
     /// The selector hash of the message.
     #[serde(serialize_with = "serialize_selector")]
     selector: [u8; 4],
+    /// The namespace of the implemented ink! trait if any.
+    ///
+    /// The namespace is part of the selector derivation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<F::String>,
+    /// If the constructor accepts any `value` from the caller.
+    ///
+    /// This is `true` for all constructors generated by ink! since they
+    /// always accept the endowment of the instantiation.
+    payable: bool,
     /// The parameters of the deploy handler.
     args: Vec<MessageParamSpec<F>>,
+    /// The error type of a constructor returning `Result<(), E>`.
+    ///
+    /// The instantiation is reverted upon returning an error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<TypeSpec<F>>,
     /// The deploy handler documentation.
     docs: Vec<&'static str>,
 }
@@ -209,16 +278,61 @@ impl IntoCompact for ConstructorSpec {
         ConstructorSpec {
             name: registry.register_string(&self.name),
             selector: self.selector,
+            namespace: self
+                .namespace
+                .map(|namespace| registry.register_string(namespace)),
+            payable: self.payable,
             args: self
                 .args
                 .into_iter()
                 .map(|arg| arg.into_compact(registry))
                 .collect::<Vec<_>>(),
+            error: self.error.map(|error| error.into_compact(registry)),
             docs: self.docs,
         }
     }
 }
 
+impl<F> ConstructorSpec<F>
+where
+    F: Form,
+{
+    /// Returns the name of the constructor.
+    pub fn name(&self) -> &F::String {
+        &self.name
+    }
+
+    /// Returns the selector of the constructor.
+    pub fn selector(&self) -> [u8; 4] {
+        self.selector
+    }
+
+    /// Returns the namespace of the implemented ink! trait if any.
+    pub fn namespace(&self) -> Option<&F::String> {
+        self.namespace.as_ref()
+    }
+
+    /// Returns `true` if the constructor accepts any `value` from the caller.
+    pub fn payable(&self) -> bool {
+        self.payable
+    }
+
+    /// Returns the parameters of the constructor.
+    pub fn args(&self) -> &[MessageParamSpec<F>] {
+        &self.args
+    }
+
+    /// Returns the error type of a constructor returning `Result<(), E>` if any.
+    pub fn error(&self) -> Option<&TypeSpec<F>> {
+        self.error.as_ref()
+    }
+
+    /// Returns the constructor documentation.
+    pub fn docs(&self) -> &[&'static str] {
+        &self.docs
+    }
+}
+
 /// A builder for constructors.
 ///
 /// # Dev
@@ -240,7 +354,11 @@ impl ConstructorSpec {
             spec: Self {
                 name,
                 selector: [0u8; 4],
+                namespace: None,
+                // Constructors always accept the endowment of the instantiation.
+                payable: true,
                 args: Vec::new(),
+                error: None,
                 docs: Vec::new(),
             },
             marker: PhantomData,
@@ -273,6 +391,29 @@ impl<S> ConstructorSpecBuilder<S> {
         this
     }
 
+    /// Sets the namespace of the implemented ink! trait.
+    pub fn namespace(self, namespace: <MetaForm as Form>::String) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.namespace.is_none());
+        this.spec.namespace = Some(namespace);
+        this
+    }
+
+    /// Sets if the constructor accepts any `value` from the caller.
+    pub fn payable(self, is_payable: bool) -> Self {
+        let mut this = self;
+        this.spec.payable = is_payable;
+        this
+    }
+
+    /// Sets the error type of a constructor returning `Result<(), E>`.
+    pub fn error(self, error: TypeSpec) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.error.is_none());
+        this.spec.error = Some(error);
+        this
+    }
+
     /// Sets the documentation of the message specification.
     pub fn docs<D>(self, docs: D) -> Self
     where
@@ -301,12 +442,24 @@ pub struct MessageSpec<F: Form = MetaForm> {
     /// The selector hash of the message.
     #[serde(serialize_with = "serialize_selector")]
     selector: [u8; 4],
+    /// The namespace of the implemented ink! trait if any.
+    ///
+    /// The namespace is part of the selector derivation.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    namespace: Option<F::String>,
     /// If the message is allowed to mutate the contract state.
     mutates: bool,
+    /// If the message accepts any `value` from the caller.
+    payable: bool,
     /// The parameters of the message.
     args: Vec<MessageParamSpec<F>>,
     /// The return type of the message.
     return_type: ReturnTypeSpec<F>,
+    /// The error type of a message returning `Result<T, E>`.
+    ///
+    /// Messages revert their state changes upon returning an error.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    error: Option<TypeSpec<F>>,
     /// The message documentation.
     docs: Vec<&'static str>,
 }
@@ -340,9 +493,13 @@ impl MessageSpec {
             spec: Self {
                 name,
                 selector: [0u8; 4],
+                namespace: None,
                 mutates: false,
+                // By default messages do not accept any value.
+                payable: false,
                 args: Vec::new(),
                 return_type: ReturnTypeSpec::new(None),
+                error: None,
                 docs: Vec::new(),
             },
             marker: PhantomData,
@@ -420,6 +577,29 @@ impl<S, M, R> MessageSpecBuilder<S, M, R> {
         this
     }
 
+    /// Sets if the message accepts any `value` from the caller.
+    pub fn payable(self, is_payable: bool) -> Self {
+        let mut this = self;
+        this.spec.payable = is_payable;
+        this
+    }
+
+    /// Sets the error type of a message returning `Result<T, E>`.
+    pub fn error(self, error: TypeSpec) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.error.is_none());
+        this.spec.error = Some(error);
+        this
+    }
+
+    /// Sets the namespace of the implemented ink! trait.
+    pub fn namespace(self, namespace: <MetaForm as Form>::String) -> Self {
+        let mut this = self;
+        debug_assert!(this.spec.namespace.is_none());
+        this.spec.namespace = Some(namespace);
+        this
+    }
+
     /// Sets the documentation of the message specification.
     pub fn docs<D>(self, docs: D) -> Self
     where
@@ -446,24 +626,87 @@ impl IntoCompact for MessageSpec {
         MessageSpec {
             name: registry.register_string(&self.name),
             selector: self.selector,
+            namespace: self
+                .namespace
+                .map(|namespace| registry.register_string(namespace)),
             mutates: self.mutates,
+            payable: self.payable,
             args: self
                 .args
                 .into_iter()
                 .map(|arg| arg.into_compact(registry))
                 .collect::<Vec<_>>(),
             return_type: self.return_type.into_compact(registry),
+            error: self.error.map(|error| error.into_compact(registry)),
             docs: self.docs,
         }
     }
 }
 
+impl<F> MessageSpec<F>
+where
+    F: Form,
+{
+    /// Returns the name of the message.
+    pub fn name(&self) -> &F::String {
+        &self.name
+    }
+
+    /// Returns the selector of the message.
+    pub fn selector(&self) -> [u8; 4] {
+        self.selector
+    }
+
+    /// Returns the namespace of the implemented ink! trait if any.
+    pub fn namespace(&self) -> Option<&F::String> {
+        self.namespace.as_ref()
+    }
+
+    /// Returns `true` if the message is allowed to mutate the contract storage.
+    pub fn mutates(&self) -> bool {
+        self.mutates
+    }
+
+    /// Returns `true` if the message accepts any `value` from the caller.
+    pub fn payable(&self) -> bool {
+        self.payable
+    }
+
+    /// Returns the parameters of the message.
+    pub fn args(&self) -> &[MessageParamSpec<F>] {
+        &self.args
+    }
+
+    /// Returns the return type of the message.
+    pub fn return_type(&self) -> &ReturnTypeSpec<F> {
+        &self.return_type
+    }
+
+    /// Returns the error type of a message returning a `Result` if any.
+    pub fn error(&self) -> Option<&TypeSpec<F>> {
+        self.error.as_ref()
+    }
+
+    /// Returns the message documentation.
+    pub fn docs(&self) -> &[&'static str] {
+        &self.docs
+    }
+}
+
 /// Describes an event definition.
 #[derive(Debug, PartialEq, Eq, Serialize)]
 #[serde(bound = "F::TypeId: Serialize")]
 pub struct EventSpec<F: Form = MetaForm> {
     /// The name of the event.
     name: F::String,
+    /// If the event is anonymous and thus has no signature topic.
+    anonymous: bool,
+    /// The first topic of every emitted non-anonymous event.
+    #[serde(
+        skip_serializing_if = "Option::is_none",
+        serialize_with = "serialize_topic"
+    )]
+    signature_topic: Option<Vec<u8>>,
     /// The event arguments.
     args: Vec<EventParamSpec<F>>,
     /// The event documentation.
@@ -476,6 +719,24 @@ pub struct EventSpecBuilder {
 }
 
 impl EventSpecBuilder {
+    /// Sets if the event is anonymous.
+    pub fn anonymous(self, is_anonymous: bool) -> Self {
+        let mut this = self;
+        this.spec.anonymous = is_anonymous;
+        this
+    }
+
+    /// Sets the topic of the event signature.
+    pub fn signature_topic<T>(self, topic: T) -> Self
+    where
+        T: AsRef<[u8]>,
+    {
+        let mut this = self;
+        debug_assert!(this.spec.signature_topic.is_none());
+        this.spec.signature_topic = Some(topic.as_ref().to_vec());
+        this
+    }
+
     /// Sets the input arguments of the event specification.
     pub fn args<A>(self, args: A) -> Self
     where
@@ -510,6 +771,8 @@ impl IntoCompact for EventSpec {
     fn into_compact(self, registry: &mut Registry) -> Self::Output {
         EventSpec {
             name: registry.register_string(&self.name),
+            anonymous: self.anonymous,
+            signature_topic: self.signature_topic,
             args: self
                 .args
                 .into_iter()
@@ -520,12 +783,45 @@ impl IntoCompact for EventSpec {
     }
 }
 
+impl<F> EventSpec<F>
+where
+    F: Form,
+{
+    /// Returns the name of the event.
+    pub fn name(&self) -> &F::String {
+        &self.name
+    }
+
+    /// Returns `true` if the event is anonymous.
+    pub fn anonymous(&self) -> bool {
+        self.anonymous
+    }
+
+    /// Returns the signature topic of a non-anonymous event.
+    pub fn signature_topic(&self) -> Option<&[u8]> {
+        self.signature_topic.as_ref().map(|topic| &topic[..])
+    }
+
+    /// Returns the arguments of the event.
+    pub fn args(&self) -> &[EventParamSpec<F>] {
+        &self.args
+    }
+
+    /// Returns the event documentation.
+    pub fn docs(&self) -> &[&'static str] {
+        &self.docs
+    }
+}
+
 impl EventSpec {
     /// Creates a new event specification builder.
     pub fn new(name: &'static str) -> EventSpecBuilder {
         EventSpecBuilder {
             spec: Self {
                 name,
+                // By default events have a signature topic.
+                anonymous: false,
+                signature_topic: None,
                 args: Vec::new(),
                 docs: Vec::new(),
             },
@@ -589,6 +885,21 @@ impl IntoCompact for TypeSpec {
     }
 }
 
+impl<F> TypeSpec<F>
+where
+    F: Form,
+{
+    /// Returns the actual type.
+    pub fn ty(&self) -> &F::TypeId {
+        &self.ty
+    }
+
+    /// Returns the compile-time known displayed representation of the type.
+    pub fn display_name(&self) -> &DisplayName<F> {
+        &self.display_name
+    }
+}
+
 impl TypeSpec {
     /// Creates a new type specification with a display name.
     ///
@@ -671,6 +982,31 @@ impl IntoCompact for EventParamSpec {
     }
 }
 
+impl<F> EventParamSpec<F>
+where
+    F: Form,
+{
+    /// Returns the name of the parameter.
+    pub fn name(&self) -> &F::String {
+        &self.name
+    }
+
+    /// Returns `true` if the parameter is a topic of the event.
+    pub fn indexed(&self) -> bool {
+        self.indexed
+    }
+
+    /// Returns the type of the parameter.
+    pub fn ty(&self) -> &TypeSpec<F> {
+        &self.ty
+    }
+
+    /// Returns the parameter documentation.
+    pub fn docs(&self) -> &[&'static str] {
+        &self.docs
+    }
+}
+
 impl EventParamSpec {
     /// Creates a new event parameter specification builder.
     pub fn new(name: &'static str) -> EventParamSpecBuilder {
@@ -729,6 +1065,98 @@ impl EventParamSpecBuilder {
     }
 }
 
+/// Describes the environmental types of the chain a contract is deployed to.
+#[derive(Debug, PartialEq, Eq, Serialize)]
+#[serde(bound = "F::TypeId: Serialize")]
+pub struct EnvironmentSpec<F: Form = MetaForm> {
+    /// The type of an address.
+    account_id: TypeSpec<F>,
+    /// The type of balances.
+    balance: TypeSpec<F>,
+    /// The type of hashes.
+    hash: TypeSpec<F>,
+    /// The type of timestamps.
+    timestamp: TypeSpec<F>,
+    /// The type of block numbers.
+    block_number: TypeSpec<F>,
+}
+
+impl IntoCompact for EnvironmentSpec {
+    type Output = EnvironmentSpec<CompactForm>;
+
+    fn into_compact(self, registry: &mut Registry) -> Self::Output {
+        EnvironmentSpec {
+            account_id: self.account_id.into_compact(registry),
+            balance: self.balance.into_compact(registry),
+            hash: self.hash.into_compact(registry),
+            timestamp: self.timestamp.into_compact(registry),
+            block_number: self.block_number.into_compact(registry),
+        }
+    }
+}
+
+impl<F> EnvironmentSpec<F>
+where
+    F: Form,
+{
+    /// Returns the `AccountId` type of the environment.
+    pub fn account_id(&self) -> &TypeSpec<F> {
+        &self.account_id
+    }
+
+    /// Returns the `Balance` type of the environment.
+    pub fn balance(&self) -> &TypeSpec<F> {
+        &self.balance
+    }
+
+    /// Returns the `Hash` type of the environment.
+    pub fn hash(&self) -> &TypeSpec<F> {
+        &self.hash
+    }
+
+    /// Returns the `Timestamp` type of the environment.
+    pub fn timestamp(&self) -> &TypeSpec<F> {
+        &self.timestamp
+    }
+
+    /// Returns the `BlockNumber` type of the environment.
+    pub fn block_number(&self) -> &TypeSpec<F> {
+        &self.block_number
+    }
+}
+
+impl EnvironmentSpec {
+    /// Creates a new environment specification from the given types.
+    ///
+    /// # Examples
+    ///
+    /// ```no_run
+    /// # use ink_abi::{EnvironmentSpec, TypeSpec};
+    /// EnvironmentSpec::new(
+    ///     TypeSpec::new::<[u8; 32]>(),
+    ///     TypeSpec::new::<u128>(),
+    ///     TypeSpec::new::<[u8; 32]>(),
+    ///     TypeSpec::new::<u64>(),
+    ///     TypeSpec::new::<u64>(),
+    /// );
+    /// ```
+    pub fn new(
+        account_id: TypeSpec,
+        balance: TypeSpec,
+        hash: TypeSpec,
+        timestamp: TypeSpec,
+        block_number: TypeSpec,
+    ) -> Self {
+        Self {
+            account_id,
+            balance,
+            hash,
+            timestamp,
+            block_number,
+        }
+    }
+}
+
 /// Describes the return type of a contract message.
 #[derive(Debug, PartialEq, Eq, Serialize)]
 #[serde(transparent)]
@@ -750,6 +1178,16 @@ impl IntoCompact for ReturnTypeSpec {
     }
 }
 
+impl<F> ReturnTypeSpec<F>
+where
+    F: Form,
+{
+    /// Returns the return type if any.
+    pub fn opt_type(&self) -> Option<&TypeSpec<F>> {
+        self.opt_type.as_ref()
+    }
+}
+
 impl ReturnTypeSpec {
     /// Creates a new return type specification from the given type or `None`.
     ///
@@ -792,6 +1230,21 @@ impl IntoCompact for MessageParamSpec {
     }
 }
 
+impl<F> MessageParamSpec<F>
+where
+    F: Form,
+{
+    /// Returns the name of the parameter.
+    pub fn name(&self) -> &F::String {
+        &self.name
+    }
+
+    /// Returns the type of the parameter.
+    pub fn ty(&self) -> &TypeSpec<F> {
+        &self.ty
+    }
+}
+
 impl MessageParamSpec {
     /// Constructs a new message parameter specification via builder.
     pub fn new(name: &'static str) -> MessageParamSpecBuilder {
@@ -837,6 +1290,19 @@ where
     serializer.serialize_str(&hex)
 }
 
+fn serialize_topic<S>(topic: &Option<Vec<u8>>, serializer: S) -> Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let bytes = topic.as_ref().map(Vec::as_slice).unwrap_or_default();
+    let hex = bytes
+        .iter()
+        .map(|byte| format!("{:02X}", byte))
+        .collect::<Vec<_>>()
+        .concat();
+    serializer.serialize_str(&format!("0x{}", hex))
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -848,7 +1314,10 @@ mod tests {
         let cs: ConstructorSpec<MetaForm> = ConstructorSpec {
             name,
             selector: 123_456_789u32.to_be_bytes(),
+            namespace: None,
+            payable: true,
             args: Vec::new(),
+            error: None,
             docs: Vec::new(),
         };
         let mut registry = Registry::new();
@@ -859,7 +1328,141 @@ mod tests {
         // then
         assert_eq!(
             json,
-            r#"{"name":1,"selector":"[\"0x07\",\"0x5B\",\"0xCD\",\"0x15\"]","args":[],"docs":[]}"#
+            r#"{"name":1,"selector":"[\"0x07\",\"0x5B\",\"0xCD\",\"0x15\"]","payable":true,"args":[],"docs":[]}"#
+        );
+    }
+
+    #[test]
+    fn event_spec_must_serialize_anonymous() {
+        // given
+        let es = EventSpec::new("Flipped").anonymous(true).done();
+        let mut registry = Registry::new();
+
+        // when
+        let json = serde_json::to_string(&es.into_compact(&mut registry)).unwrap();
+
+        // then
+        assert_eq!(json, r#"{"name":1,"anonymous":true,"args":[],"docs":[]}"#);
+    }
+
+    #[test]
+    fn event_spec_must_serialize_signature_topic_to_hex() {
+        // given
+        let es = EventSpec::new("Flipped")
+            .signature_topic([0x01, 0x23, 0xAB, 0xCD])
+            .done();
+        let mut registry = Registry::new();
+
+        // when
+        let json = serde_json::to_string(&es.into_compact(&mut registry)).unwrap();
+
+        // then
+        assert_eq!(
+            json,
+            r#"{"name":1,"anonymous":false,"signature_topic":"0x0123ABCD","args":[],"docs":[]}"#
+        );
+    }
+
+    #[test]
+    fn message_spec_must_serialize_error_if_any() {
+        // given
+        let infallible = MessageSpec::new("get")
+            .selector([0x00; 4])
+            .mutates(false)
+            .returns(ReturnTypeSpec::new(TypeSpec::new::<u32>()))
+            .done();
+        let fallible = MessageSpec::new("try_get")
+            .selector([0x01; 4])
+            .mutates(false)
+            .returns(ReturnTypeSpec::new(TypeSpec::new::<Result<u32, u8>>()))
+            .error(TypeSpec::new::<u8>())
+            .done();
+        let mut registry = Registry::new();
+
+        // when
+        let infallible =
+            serde_json::to_string(&infallible.into_compact(&mut registry)).unwrap();
+        let fallible =
+            serde_json::to_string(&fallible.into_compact(&mut registry)).unwrap();
+
+        // then
+        assert!(!infallible.contains(r#""error""#));
+        assert!(fallible.contains(r#""error":{"#));
+    }
+
+    #[test]
+    fn message_spec_must_serialize_namespace_if_any() {
+        // given
+        let plain = MessageSpec::new("flip")
+            .selector([0x00; 4])
+            .mutates(true)
+            .returns(ReturnTypeSpec::new(None))
+            .done();
+        let namespaced = MessageSpec::new("flip")
+            .selector([0x01; 4])
+            .namespace("tokens")
+            .mutates(true)
+            .returns(ReturnTypeSpec::new(None))
+            .done();
+        let mut registry = Registry::new();
+
+        // when
+        let plain = serde_json::to_string(&plain.into_compact(&mut registry)).unwrap();
+        let namespaced =
+            serde_json::to_string(&namespaced.into_compact(&mut registry)).unwrap();
+
+        // then
+        assert!(!plain.contains(r#""namespace""#));
+        assert!(namespaced.contains(r#""namespace":"#));
+    }
+
+    #[test]
+    fn message_spec_must_serialize_payable() {
+        // given
+        let ms = MessageSpec::new("deposit")
+            .selector([0x00; 4])
+            .mutates(true)
+            .payable(true)
+            .returns(ReturnTypeSpec::new(None))
+            .done();
+        let mut registry = Registry::new();
+
+        // when
+        let json = serde_json::to_string(&ms.into_compact(&mut registry)).unwrap();
+
+        // then
+        assert!(json.contains(r#""mutates":true,"payable":true"#));
+    }
+
+    #[test]
+    fn compact_specs_are_inspectable() {
+        // given
+        let mut registry = Registry::new();
+        let cs = ConstructorSpec::new("new")
+            .selector([0x01; 4])
+            .args(vec![MessageParamSpec::new("init_value")
+                .of_type(TypeSpec::new::<bool>())
+                .done()])
+            .done()
+            .into_compact(&mut registry);
+        let ms = MessageSpec::new("get")
+            .selector([0x02; 4])
+            .mutates(false)
+            .returns(ReturnTypeSpec::new(TypeSpec::new::<bool>()))
+            .done()
+            .into_compact(&mut registry);
+
+        // then
+        assert_eq!(cs.name(), &registry.register_string("new"));
+        assert_eq!(cs.selector(), [0x01; 4]);
+        assert!(cs.payable());
+        assert_eq!(cs.args()[0].name(), &registry.register_string("init_value"));
+        assert_eq!(ms.selector(), [0x02; 4]);
+        assert!(!ms.mutates());
+        assert!(!ms.payable());
+        assert_eq!(
+            ms.return_type().opt_type().map(TypeSpec::ty),
+            Some(cs.args()[0].ty().ty())
         );
     }
 }
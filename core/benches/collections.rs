@@ -0,0 +1,258 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Benchmarks of the storage collections on the off-chain environment.
+//!
+//! Measures the wall time of common operations across collection sizes
+//! using criterion. Since wall time on the off-chain environment is only a
+//! rough proxy for the costs on-chain, the numbers of contract storage
+//! reads and writes of every benchmarked operation are exported as JSON to
+//! `target/ink-bench/storage_ops.json` as well. Every read decodes and every
+//! write encodes a single cell, so these double as decode and encode counts.
+//!
+//! Run with `cargo bench -p ink_core`.
+
+use criterion::{
+    BatchSize,
+    BenchmarkId,
+    Criterion,
+};
+use ink_core::{
+    env::{
+        self,
+        DefaultEnvTypes,
+    },
+    storage::{
+        self,
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+    },
+};
+use ink_primitives::Key;
+
+/// The numbers of elements the operations are benchmarked for.
+const SIZES: [u32; 3] = [10, 100, 1000];
+
+/// Returns an empty storage vector.
+///
+/// All vectors share the same storage cells so that benchmark iterations
+/// overwrite each other instead of growing the contract storage.
+fn empty_vec() -> storage::Vec<u64> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        storage::Vec::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Returns a storage vector of `size` elements that has been written
+/// to the contract storage and whose cache is empty.
+fn filled_vec(size: u32) -> storage::Vec<u64> {
+    let mut vec = empty_vec();
+    vec.extend((0..size).map(u64::from));
+    vec.flush();
+    vec.invalidate_cache();
+    vec
+}
+
+/// Returns an empty storage stash.
+fn empty_stash() -> storage::Stash<u64> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        storage::Stash::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Returns a storage stash of `size` entries that has been written
+/// to the contract storage and whose cache is empty.
+fn filled_stash(size: u32) -> storage::Stash<u64> {
+    let mut stash = empty_stash();
+    for n in 0..size {
+        stash.put(u64::from(n));
+    }
+    stash.flush();
+    stash.invalidate_cache();
+    stash
+}
+
+fn vec_push(mut vec: storage::Vec<u64>, size: u32) -> storage::Vec<u64> {
+    for n in 0..size {
+        vec.push(u64::from(n));
+    }
+    vec.flush();
+    vec
+}
+
+fn vec_get(vec: storage::Vec<u64>, size: u32) -> storage::Vec<u64> {
+    for n in 0..size {
+        criterion::black_box(vec.get(n));
+    }
+    vec
+}
+
+fn vec_pop(mut vec: storage::Vec<u64>, size: u32) -> storage::Vec<u64> {
+    for _ in 0..size {
+        criterion::black_box(vec.pop());
+    }
+    vec.flush();
+    vec
+}
+
+fn stash_put(mut stash: storage::Stash<u64>, size: u32) -> storage::Stash<u64> {
+    for n in 0..size {
+        stash.put(u64::from(n));
+    }
+    stash.flush();
+    stash
+}
+
+fn stash_get(stash: storage::Stash<u64>, size: u32) -> storage::Stash<u64> {
+    for n in 0..size {
+        criterion::black_box(stash.get(n));
+    }
+    stash
+}
+
+fn stash_take(mut stash: storage::Stash<u64>, size: u32) -> storage::Stash<u64> {
+    for n in 0..size {
+        criterion::black_box(stash.take(n));
+    }
+    stash.flush();
+    stash
+}
+
+/// A benchmarked operation together with the setup of its input.
+struct Bench<C> {
+    /// The name of the benchmark.
+    name: &'static str,
+    /// Creates the collection the operation is applied to.
+    setup: fn(u32) -> C,
+    /// Applies the operation to the collection for the given size.
+    routine: fn(C, u32) -> C,
+}
+
+/// The benchmarked operations on storage vectors.
+fn vec_benches() -> Vec<Bench<storage::Vec<u64>>> {
+    vec![
+        Bench {
+            name: "vec_push",
+            setup: |_| empty_vec(),
+            routine: vec_push,
+        },
+        Bench {
+            name: "vec_get",
+            setup: filled_vec,
+            routine: vec_get,
+        },
+        Bench {
+            name: "vec_pop",
+            setup: filled_vec,
+            routine: vec_pop,
+        },
+    ]
+}
+
+/// The benchmarked operations on storage stashes.
+fn stash_benches() -> Vec<Bench<storage::Stash<u64>>> {
+    vec![
+        Bench {
+            name: "stash_put",
+            setup: |_| empty_stash(),
+            routine: stash_put,
+        },
+        Bench {
+            name: "stash_get",
+            setup: filled_stash,
+            routine: stash_get,
+        },
+        Bench {
+            name: "stash_take",
+            setup: filled_stash,
+            routine: stash_take,
+        },
+    ]
+}
+
+/// Returns the contract storage operations of every benchmark as JSON.
+fn count_storage_ops<C>(benches: &[Bench<C>]) -> env::Result<Vec<serde_json::Value>> {
+    let contract = env::account_id::<DefaultEnvTypes>()?;
+    let mut results = Vec::new();
+    for bench in benches {
+        for &size in &SIZES {
+            let collection = (bench.setup)(size);
+            let (_, ops) =
+                env::test::count_storage_ops::<DefaultEnvTypes, _, _>(&contract, || {
+                    (bench.routine)(collection, size)
+                })?;
+            results.push(serde_json::json!({
+                "bench": bench.name,
+                "size": size,
+                "reads": ops.reads,
+                "writes": ops.writes,
+                "clears": ops.clears,
+            }));
+        }
+    }
+    Ok(results)
+}
+
+/// Measures the wall time of every benchmark.
+fn measure<C>(c: &mut Criterion, benches: &[Bench<C>]) {
+    for bench in benches {
+        let mut group = c.benchmark_group(bench.name);
+        for &size in &SIZES {
+            group.bench_with_input(
+                BenchmarkId::from_parameter(size),
+                &size,
+                |b, &size| {
+                    b.iter_batched(
+                        || (bench.setup)(size),
+                        |collection| (bench.routine)(collection, size),
+                        BatchSize::SmallInput,
+                    )
+                },
+            );
+        }
+        group.finish();
+    }
+}
+
+/// Writes the storage operation counts to `target/ink-bench/storage_ops.json`.
+fn export(results: Vec<serde_json::Value>) -> std::io::Result<()> {
+    let dir = std::path::Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("..")
+        .join("target")
+        .join("ink-bench");
+    std::fs::create_dir_all(&dir)?;
+    let json = serde_json::to_string_pretty(&serde_json::Value::Array(results))?;
+    std::fs::write(dir.join("storage_ops.json"), json)
+}
+
+fn main() {
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let vec_benches = vec_benches();
+        let stash_benches = stash_benches();
+        let mut results = count_storage_ops(&vec_benches)?;
+        results.extend(count_storage_ops(&stash_benches)?);
+        export(results).expect("could not export the storage operation counts");
+        let mut c = Criterion::default().configure_from_args();
+        measure(&mut c, &vec_benches);
+        measure(&mut c, &stash_benches);
+        c.final_summary();
+        Ok(())
+    })
+    .expect("could not set up the off-chain environment")
+}
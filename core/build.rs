@@ -0,0 +1,54 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Emits the capacity of the static environmental buffers.
+//!
+//! The capacity is given in bytes by the `INK_STATIC_BUFFER_SIZE`
+//! environment variable at compile time and defaults to 16kB.
+
+use std::{
+    env,
+    fs,
+    path::Path,
+};
+
+/// The default capacity of the static buffers.
+const DEFAULT_CAPACITY: usize = 1 << 14; // 16kB
+
+fn main() {
+    println!("cargo:rerun-if-env-changed=INK_STATIC_BUFFER_SIZE");
+    let capacity = match env::var("INK_STATIC_BUFFER_SIZE") {
+        Ok(capacity) => {
+            match capacity.trim().parse::<usize>() {
+                Ok(capacity) if capacity > 0 => capacity,
+                _ => {
+                    panic!(
+                        "INK_STATIC_BUFFER_SIZE must be a positive decimal number of bytes but is {:?}",
+                        capacity
+                    )
+                }
+            }
+        }
+        Err(env::VarError::NotPresent) => DEFAULT_CAPACITY,
+        Err(env::VarError::NotUnicode(_)) => {
+            panic!("INK_STATIC_BUFFER_SIZE must be a positive decimal number of bytes")
+        }
+    };
+    let out_dir = env::var("OUT_DIR").expect("OUT_DIR is set by cargo");
+    fs::write(
+        Path::new(&out_dir).join("buffer_capacity.rs"),
+        capacity.to_string(),
+    )
+    .expect("failed to write the static buffer capacity");
+}
@@ -14,10 +14,12 @@
 
 //! The public raw interface towards the host Wasm engine.
 
+#[cfg(all(feature = "ink-alloc-free", not(feature = "std")))]
+use crate::env::static_bytes::StaticBytes;
 use crate::env::{
     backend::{
-        Env,
-        TypedEnv,
+        EnvBackend,
+        TypedEnvBackend,
     },
     call::{
         CallData,
@@ -29,10 +31,18 @@ use crate::env::{
         EnvInstance,
         OnInstance,
     },
+    Clear,
+    EcdsaPublicKey,
+    EnvError,
     EnvTypes,
+    FootprintDelta,
     Result,
+    ReturnFlags,
+    StorageBatch,
     Topics,
+    Weight,
 };
+use core::convert::TryInto;
 use ink_primitives::Key;
 
 /// Returns the address of the caller of the executed contract.
@@ -44,7 +54,9 @@ pub fn caller<T>() -> Result<T::AccountId>
 where
     T: EnvTypes,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| TypedEnv::caller::<T>(instance))
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::caller::<T>(instance)
+    })
 }
 
 /// Returns the transferred balance for the contract execution.
@@ -57,10 +69,58 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::transferred_balance::<T>(instance)
+        TypedEnvBackend::transferred_balance::<T>(instance)
     })
 }
 
+/// Returns the value transferred for the contract execution.
+///
+/// # Note
+///
+/// This is an alias of [`transferred_balance`](fn.transferred_balance.html).
+///
+/// # Errors
+///
+/// If the returned value cannot be properly decoded.
+pub fn transferred_value<T>() -> Result<T::Balance>
+where
+    T: EnvTypes,
+{
+    transferred_balance::<T>()
+}
+
+/// Ensures that no value has been transferred for the contract execution.
+///
+/// # Errors
+///
+/// - If value has been transferred for the contract execution.
+/// - If the transferred value cannot be properly decoded.
+pub fn ensure_no_value<T>() -> Result<()>
+where
+    T: EnvTypes,
+{
+    if transferred_value::<T>()? != T::Balance::from(0u32) {
+        return Err(EnvError::UnexpectedTransferredValue)
+    }
+    Ok(())
+}
+
+/// Ensures that at least `min_value` has been transferred for the contract execution.
+///
+/// # Errors
+///
+/// - If less than `min_value` has been transferred for the contract execution.
+/// - If the transferred value cannot be properly decoded.
+pub fn ensure_min_value<T>(min_value: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    if transferred_value::<T>()? < min_value {
+        return Err(EnvError::InsufficientTransferredValue)
+    }
+    Ok(())
+}
+
 /// Returns the current price for gas.
 ///
 /// # Errors
@@ -71,10 +131,30 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::gas_price::<T>(instance)
+        TypedEnvBackend::gas_price::<T>(instance)
     })
 }
 
+/// Returns `true` if the caller of the executed contract is the origin
+/// of the extrinsic, i.e. not another contract.
+///
+/// # Note
+///
+/// This allows contracts to reject being called by other contracts.
+pub fn caller_is_origin() -> bool {
+    <EnvInstance as OnInstance>::on_instance(EnvBackend::caller_is_origin)
+}
+
+/// Returns the depth of the current call stack.
+///
+/// # Note
+///
+/// A contract called directly by the origin of the extrinsic
+/// executes at a call depth of `1`.
+pub fn call_depth() -> u32 {
+    <EnvInstance as OnInstance>::on_instance(EnvBackend::call_depth)
+}
+
 /// Returns the amount of gas left for the contract execution.
 ///
 /// # Errors
@@ -84,21 +164,55 @@ pub fn gas_left<T>() -> Result<T::Balance>
 where
     T: EnvTypes,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| TypedEnv::gas_left::<T>(instance))
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::gas_left::<T>(instance)
+    })
+}
+
+/// Returns the amount of gas left for the contract execution as [`Weight`].
+///
+/// # Note
+///
+/// Use this with the checked arithmetic of [`Weight`] in order to stop
+/// bounded iterations before running out of gas.
+///
+/// # Errors
+///
+/// - If the returned value cannot be properly decoded.
+/// - If the gas left does not fit into a [`Weight`].
+pub fn weight_left<T>() -> Result<Weight>
+where
+    T: EnvTypes,
+    T::Balance: TryInto<u64>,
+{
+    gas_left::<T>()?
+        .try_into()
+        .map(Weight::new)
+        .map_err(|_| scale::Error::from("gas left does not fit into a weight").into())
 }
 
 /// Returns the current block timestamp.
 ///
+/// # Note
+///
+/// The timestamp is returned as [`Timestamp`](ink_primitives::Timestamp) so that
+/// it cannot be mixed up with durations, block numbers or other integers.
+///
 /// # Errors
 ///
-/// If the returned value cannot be properly decoded.
-pub fn block_timestamp<T>() -> Result<T::Timestamp>
+/// - If the returned value cannot be properly decoded.
+/// - If the timestamp does not fit into a `u64`.
+pub fn block_timestamp<T>() -> Result<ink_primitives::Timestamp>
 where
     T: EnvTypes,
+    T::Timestamp: TryInto<u64>,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::block_timestamp::<T>(instance)
-    })
+        TypedEnvBackend::block_timestamp::<T>(instance)
+    })?
+    .try_into()
+    .map(ink_primitives::Timestamp::from)
+    .map_err(|_| scale::Error::from("block timestamp does not fit into a u64").into())
 }
 
 /// Returns the account ID of the executed contract.
@@ -115,11 +229,17 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::account_id::<T>(instance)
+        TypedEnvBackend::account_id::<T>(instance)
     })
 }
 
-/// Returns the balance of the executed contract.
+/// Returns the free balance of the executed contract.
+///
+/// # Note
+///
+/// The free balance does not include the balance reserved for the
+/// storage deposit of the executed contract.
+/// Use [`reserved_balance`] in order to query it.
 ///
 /// # Errors
 ///
@@ -128,7 +248,28 @@ pub fn balance<T>() -> Result<T::Balance>
 where
     T: EnvTypes,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| TypedEnv::balance::<T>(instance))
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::balance::<T>(instance)
+    })
+}
+
+/// Returns the reserved balance of the executed contract.
+///
+/// # Note
+///
+/// The reserved balance covers the storage deposit of the executed contract
+/// and is not available for transfers.
+///
+/// # Errors
+///
+/// If the returned value cannot be properly decoded.
+pub fn reserved_balance<T>() -> Result<T::Balance>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::reserved_balance::<T>(instance)
+    })
 }
 
 /// Returns the current rent allowance for the executed contract.
@@ -141,24 +282,66 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::rent_allowance::<T>(instance)
+        TypedEnvBackend::rent_allowance::<T>(instance)
     })
 }
 
-/// Returns the current block number.
+/// Returns the code hash of the executed contract.
+///
+/// # Errors
+///
+/// If the returned value cannot be properly decoded.
+pub fn own_code_hash<T>() -> Result<T::Hash>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::own_code_hash::<T>(instance)
+    })
+}
+
+/// Returns the code hash of the contract at the given account ID.
+///
+/// # Note
+///
+/// Returns `None` if the account is not a contract account.
 ///
 /// # Errors
 ///
 /// If the returned value cannot be properly decoded.
-pub fn block_number<T>() -> Result<T::BlockNumber>
+pub fn code_hash<T>(account_id: &T::AccountId) -> Result<Option<T::Hash>>
 where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::block_number::<T>(instance)
+        TypedEnvBackend::code_hash::<T>(instance, account_id)
     })
 }
 
+/// Returns the current block number.
+///
+/// # Note
+///
+/// The block number is returned as [`BlockNumber`](ink_primitives::BlockNumber)
+/// so that it cannot be mixed up with timestamps or other integers.
+///
+/// # Errors
+///
+/// - If the returned value cannot be properly decoded.
+/// - If the block number does not fit into a `u64`.
+pub fn block_number<T>() -> Result<ink_primitives::BlockNumber>
+where
+    T: EnvTypes,
+    T::BlockNumber: TryInto<u64>,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::block_number::<T>(instance)
+    })?
+    .try_into()
+    .map(ink_primitives::BlockNumber::from)
+    .map_err(|_| scale::Error::from("block number does not fit into a u64").into())
+}
+
 /// Returns the minimum balance for the contracts chain.
 ///
 /// # Errors
@@ -169,7 +352,7 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::minimum_balance::<T>(instance)
+        TypedEnvBackend::minimum_balance::<T>(instance)
     })
 }
 
@@ -183,7 +366,21 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::tombstone_deposit::<T>(instance)
+        TypedEnvBackend::tombstone_deposit::<T>(instance)
+    })
+}
+
+/// Returns the price for the specified amount of gas.
+///
+/// # Errors
+///
+/// If the returned value cannot be properly decoded.
+pub fn weight_to_fee<T>(gas: u64) -> Result<T::Balance>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        TypedEnvBackend::weight_to_fee::<T>(instance, gas)
     })
 }
 
@@ -194,17 +391,98 @@ where
     Event: Topics<T> + scale::Encode,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::emit_event::<T, Event>(instance, event)
+        TypedEnvBackend::emit_event::<T, Event>(instance, event)
     })
 }
 
+/// Returns the topic hash of the given indexed event field value.
+///
+/// # Note
+///
+/// - The SCALE encoding of the value is used as topic directly
+///   if it fits into the hash, padded with zeros at the end.
+/// - Otherwise the BLAKE2 256-bit hash of its SCALE encoding is used.
+#[cfg(not(all(feature = "ink-alloc-free", not(feature = "std"))))]
+pub fn topic<T, V>(value: &V) -> T::Hash
+where
+    T: EnvTypes,
+    V: scale::Encode,
+{
+    let encoded = value.encode();
+    let mut topic = <T::Hash as Clear>::clear();
+    let topic_bytes = topic.as_mut();
+    if encoded.len() <= topic_bytes.len() {
+        topic_bytes[..encoded.len()].copy_from_slice(&encoded);
+    } else {
+        let hash = hash_bytes::<hash::Blake2x256>(&encoded);
+        let len = core::cmp::min(topic_bytes.len(), hash.len());
+        topic_bytes[..len].copy_from_slice(&hash[..len]);
+    }
+    topic
+}
+
+/// Returns the topic hash of the given indexed event field value.
+///
+/// # Note
+///
+/// - The SCALE encoding of the value is used as topic directly
+///   if it fits into the hash, padded with zeros at the end.
+/// - Otherwise the BLAKE2 256-bit hash of its SCALE encoding is used.
+#[cfg(all(feature = "ink-alloc-free", not(feature = "std")))]
+pub fn topic<T, V>(value: &V) -> T::Hash
+where
+    T: EnvTypes,
+    V: scale::Encode,
+{
+    /// Encodes into the topic until the encoding no longer fits.
+    struct TopicOutput<'a> {
+        bytes: &'a mut [u8],
+        len: usize,
+        overflowed: bool,
+    }
+
+    impl scale::Output for TopicOutput<'_> {
+        fn write(&mut self, bytes: &[u8]) {
+            let end = self.len + bytes.len();
+            if self.overflowed || end > self.bytes.len() {
+                self.overflowed = true;
+                return
+            }
+            self.bytes[self.len..end].copy_from_slice(bytes);
+            self.len = end;
+        }
+    }
+
+    let mut topic = <T::Hash as Clear>::clear();
+    let mut output = TopicOutput {
+        bytes: topic.as_mut(),
+        len: 0,
+        overflowed: false,
+    };
+    value.encode_to(&mut output);
+    if output.overflowed {
+        // Topics are computed while the environment is in use
+        // so we cannot encode into its static buffer here.
+        let mut encoded = StaticBytes::new();
+        value.encode_to(&mut encoded);
+        let hash = hash_bytes::<hash::Blake2x256>(&encoded);
+        let topic_bytes = output.bytes;
+        for byte in topic_bytes.iter_mut() {
+            *byte = 0;
+        }
+        let len = core::cmp::min(topic_bytes.len(), hash.len());
+        topic_bytes[..len].copy_from_slice(&hash[..len]);
+    }
+    topic
+}
+
 /// Sets the rent allowance of the executed contract to the new value.
 pub fn set_rent_allowance<T>(new_value: T::Balance)
 where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::set_rent_allowance::<T>(instance, new_value)
+        TypedEnvBackend::set_rent_allowance::<T>(instance, new_value)
     })
 }
 
@@ -214,7 +492,7 @@ where
     V: scale::Encode,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        Env::set_contract_storage::<V>(instance, key, value)
+        EnvBackend::set_contract_storage::<V>(instance, key, value)
     })
 }
 
@@ -228,14 +506,147 @@ where
     R: scale::Decode,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        Env::get_contract_storage::<R>(instance, key)
+        EnvBackend::get_contract_storage::<R>(instance, key)
     })
 }
 
 /// Clears the contract's storage key entry.
 pub fn clear_contract_storage(key: Key) {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        Env::clear_contract_storage(instance, key)
+        EnvBackend::clear_contract_storage(instance, key)
+    })
+}
+
+/// Registers the contract's storage key entry to be cleared if the contract
+/// returns through [`return_value`] without reverting.
+///
+/// # Note
+///
+/// - Only the most recently registered entry is cleared.
+///   Registering `None` unregisters the entry again.
+/// - Reverting returns and traps need no cleanup since they
+///   discard all storage changes of the contract execution.
+pub fn clear_contract_storage_on_return(key: Option<Key>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::clear_contract_storage_on_return(instance, key)
+    })
+}
+
+/// Applies all writes of the batch to the contract's storage.
+///
+/// # Note
+///
+/// This performs a single host call for every part of the batch that fits into
+/// the static environmental buffer which is more efficient than calling
+/// [`set_contract_storage`] and [`clear_contract_storage`] for every entry.
+/// Requires a contracts runtime providing the `ext_set_storage_batch` host
+/// function.
+///
+/// # Panics
+///
+/// If the encoded entry of a single key exceeds the static environmental buffer.
+pub fn set_contract_storage_batch(batch: &StorageBatch) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::set_contract_storage_batch(instance, batch)
+    })
+}
+
+/// Decodes only the length of the encoded value stored in a cell.
+struct EncodedLen(u32);
+
+impl scale::Decode for EncodedLen {
+    fn decode<I: scale::Input>(
+        input: &mut I,
+    ) -> core::result::Result<Self, scale::Error> {
+        input
+            .remaining_len()?
+            .map(|len| Self(len as u32))
+            .ok_or_else(|| scale::Error::from("unknown length of the encoded value"))
+    }
+}
+
+/// Returns how the contract storage footprint changes upon applying the batch.
+///
+/// # Note
+///
+/// Reads every written key from the contract storage once in order to
+/// compare the sizes of the stored and the new values. Keys written more than
+/// once are found by comparing every entry with its preceding entries instead
+/// of allocating, so the cost grows quadratically with the size of the batch.
+///
+/// # Errors
+///
+/// If the size of a stored value cannot be determined.
+pub fn storage_footprint_delta(batch: &StorageBatch) -> Result<FootprintDelta> {
+    let mut delta = FootprintDelta::default();
+    for (n, (key, value)) in batch.iter().enumerate() {
+        let new_size = value.map(|value| value.len() as u32);
+        // The size left by the last preceding entry of the same key if any.
+        let preceding = batch
+            .iter()
+            .take(n)
+            .filter(|(preceding, _)| *preceding == key)
+            .last();
+        let old_size = match preceding {
+            Some((_, value)) => value.map(|value| value.len() as u32),
+            None => {
+                get_contract_storage::<EncodedLen>(key)
+                    .transpose()?
+                    .map(|EncodedLen(len)| len)
+            }
+        };
+        match (old_size, new_size) {
+            (None, Some(_)) => delta.added_items += 1,
+            (Some(_), None) => delta.removed_items += 1,
+            _ => (),
+        }
+        let (old_size, new_size) = (old_size.unwrap_or(0), new_size.unwrap_or(0));
+        if new_size > old_size {
+            delta.added_bytes += new_size - old_size;
+        } else {
+            delta.removed_bytes += old_size - new_size;
+        }
+    }
+    Ok(delta)
+}
+
+/// Estimates the storage deposit required to apply the batch.
+///
+/// # Note
+///
+/// The deposit per occupied cell and per stored byte are parameters of the
+/// chain. Use this to refuse operations whose storage deposit cannot be
+/// covered, e.g. by the transferred value.
+///
+/// # Errors
+///
+/// If the size of a stored value cannot be determined.
+pub fn estimate_storage_deposit<T>(
+    batch: &StorageBatch,
+    per_item: T::Balance,
+    per_byte: T::Balance,
+) -> Result<T::Balance>
+where
+    T: EnvTypes,
+{
+    storage_footprint_delta(batch).map(|delta| delta.deposit::<T>(per_item, per_byte))
+}
+
+/// Returns the encoded values stored under the given keys in the contract's storage.
+///
+/// # Note
+///
+/// This performs a single host call for all keys.
+/// The values are returned as a batch that writes them back, e.g. in order to
+/// restore them later on. Its entries are in the same order as the keys and
+/// clear the keys without an associated value.
+///
+/// # Errors
+///
+/// If the values returned by the host cannot be properly decoded.
+pub fn get_contract_storage_batch(keys: &[Key]) -> Result<StorageBatch> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::get_contract_storage_batch(instance, keys)
     })
 }
 
@@ -254,7 +665,7 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::invoke_runtime::<T>(instance, params)
+        TypedEnvBackend::invoke_runtime::<T>(instance, params)
     })
 }
 
@@ -278,9 +689,18 @@ pub fn invoke_contract<T>(params: &CallParams<T, ()>) -> Result<()>
 where
     T: EnvTypes,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::invoke_contract::<T>(instance, params)
-    })
+    // Contracts called off-chain access the environment themselves and
+    // are therefore executed outside of the environmental instance.
+    #[cfg(feature = "std")]
+    {
+        crate::env::engine::off_chain::contracts::invoke_contract::<T>(params)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnvBackend::invoke_contract::<T>(instance, params)
+        })
+    }
 }
 
 /// Evaluates a contract message and returns its result.
@@ -303,9 +723,18 @@ where
     T: EnvTypes,
     R: scale::Decode,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::eval_contract::<T, R>(instance, params)
-    })
+    // Contracts called off-chain access the environment themselves and
+    // are therefore executed outside of the environmental instance.
+    #[cfg(feature = "std")]
+    {
+        crate::env::engine::off_chain::contracts::eval_contract::<T, R>(params)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnvBackend::eval_contract::<T, R>(instance, params)
+        })
+    }
 }
 
 /// Instantiates another contract.
@@ -329,9 +758,47 @@ pub fn instantiate_contract<T, C>(
 where
     T: EnvTypes,
 {
-    <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::instantiate_contract::<T, C>(instance, params)
-    })
+    // Contracts called off-chain access the environment themselves and
+    // are therefore executed outside of the environmental instance.
+    #[cfg(feature = "std")]
+    {
+        crate::env::engine::off_chain::contracts::instantiate_contract::<T, C>(params)
+    }
+    #[cfg(not(feature = "std"))]
+    {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            TypedEnvBackend::instantiate_contract::<T, C>(instance, params)
+        })
+    }
+}
+
+/// Computes the address of a contract instantiated by `deployer` from the
+/// given code hash and salt.
+///
+/// # Note
+///
+/// This mirrors the address derivation of the contracts pallet which is the
+/// BLAKE2 256-bit hash of the concatenation of the deployer, the code hash and
+/// the salt. Factory contracts can use this to know the addresses of their
+/// child contracts before they instantiate them. Using an incrementing nonce
+/// as salt yields a unique address for every child contract.
+///
+/// # Errors
+///
+/// If the computed address cannot be properly decoded as account ID.
+pub fn compute_contract_address<T>(
+    deployer: &T::AccountId,
+    code_hash: &T::Hash,
+    salt: &[u8],
+) -> Result<T::AccountId>
+where
+    T: EnvTypes,
+{
+    let mut buffer = scale::Encode::encode(deployer);
+    buffer.extend_from_slice(code_hash.as_ref());
+    buffer.extend_from_slice(salt);
+    let address = hash_bytes::<hash::Blake2x256>(&buffer);
+    <T::AccountId as scale::Decode>::decode(&mut &address[..]).map_err(Into::into)
 }
 
 /// Restores a smart contract in tombstone state.
@@ -385,7 +852,7 @@ pub fn restore_contract<T>(
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::restore_contract::<T>(
+        TypedEnvBackend::restore_contract::<T>(
             instance,
             account_id,
             code_hash,
@@ -410,7 +877,7 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::terminate_contract::<T>(instance, beneficiary)
+        TypedEnvBackend::terminate_contract::<T>(instance, beneficiary)
     })
 }
 
@@ -430,7 +897,7 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::transfer::<T>(instance, destination, value)
+        TypedEnvBackend::transfer::<T>(instance, destination, value)
     })
 }
 
@@ -451,7 +918,7 @@ where
 ///     - This happens only if the host runtime provides less than 4 bytes for
 ///       the function selector upon this query.
 pub fn input() -> Result<CallData> {
-    <EnvInstance as OnInstance>::on_instance(|instance| Env::input(instance))
+    <EnvInstance as OnInstance>::on_instance(|instance| EnvBackend::input(instance))
 }
 
 /// Returns the value back to the caller of the executed contract.
@@ -465,7 +932,32 @@ where
     R: scale::Encode,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        Env::output::<R>(instance, return_value)
+        EnvBackend::output::<R>(instance, return_value)
+    })
+}
+
+/// Returns the value back to the caller of the executed contract
+/// and halts its execution.
+///
+/// # Note
+///
+/// - Use [`ReturnFlags::REVERT`] in order to revert all state changes
+///   of the executed contract while still handing back the encoded value,
+///   e.g. structured error data, to the caller.
+/// - This function never returns.
+///
+/// # Example
+///
+/// ```no_run
+/// # use ink_core::env::{self, ReturnFlags};
+/// env::return_value(ReturnFlags::REVERT, &Err::<(), u8>(42));
+/// ```
+pub fn return_value<R>(flags: ReturnFlags, return_value: &R) -> !
+where
+    R: scale::Encode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::return_value::<R>(instance, flags, return_value)
     })
 }
 
@@ -484,13 +976,62 @@ where
     T: EnvTypes,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        TypedEnv::random::<T>(instance, subject)
+        TypedEnvBackend::random::<T>(instance, subject)
     })
 }
 
 /// Prints the given contents to the environmental log.
 pub fn println(content: &str) {
-    <EnvInstance as OnInstance>::on_instance(|instance| Env::println(instance, content))
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::println(instance, content)
+    })
+}
+
+/// Appends the given message to the debug message buffer of the node.
+///
+/// # Note
+///
+/// - The node only records debug messages for RPC calls such as dry-runs
+///   and discards them otherwise.
+/// - The off-chain environment records debug messages which can be
+///   inspected using `test::debug_messages`.
+/// - Prefer using the [`debug_println!`](../macro.debug_println.html) macro
+///   which is compiled out in release builds.
+pub fn debug_message(message: &str) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::debug_message(instance, message)
+    })
+}
+
+/// Formats the arguments of [`debug_println!`](../macro.debug_println.html).
+#[doc(hidden)]
+pub fn format_debug_message(args: core::fmt::Arguments) -> ink_prelude::string::String {
+    ink_prelude::format!("{}", args)
+}
+
+/// Formats and appends a message to the debug message buffer of the node.
+///
+/// # Note
+///
+/// Expands to nothing observable unless compiled with debug assertions.
+/// For more details visit: [`ink_core::env::debug_message`]
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::debug_println;
+/// let value = 42;
+/// debug_println!("the value is {}", value);
+/// ```
+#[macro_export]
+macro_rules! debug_println {
+    ( $($arg:tt)* ) => {
+        if cfg!(debug_assertions) {
+            $crate::env::debug_message(
+                &$crate::env::format_debug_message(format_args!($($arg)*))
+            )
+        }
+    };
 }
 
 /// Returns the value from the *runtime* storage at the position of the key if any.
@@ -503,10 +1044,94 @@ where
     R: scale::Decode,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
-        Env::get_runtime_storage::<R>(instance, runtime_key)
+        EnvBackend::get_runtime_storage::<R>(instance, runtime_key)
+    })
+}
+
+/// Recovers the compressed ECDSA public key from the signature of the message hash.
+///
+/// # Note
+///
+/// - The signature consists of the 64 bytes of `r` and `s` followed by
+///   the recovery ID. Ethereum-style recovery IDs of `27` and `28` are
+///   supported as well.
+/// - Use [`EcdsaPublicKey::to_default_account_id`] in order to
+///   compare the signer against an account ID.
+///
+/// # Errors
+///
+/// If the public key could not be recovered from the signature.
+pub fn ecdsa_recover(
+    signature: &[u8; 65],
+    message_hash: &[u8; 32],
+) -> Result<EcdsaPublicKey> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::ecdsa_recover(instance, signature, message_hash)
+    })
+}
+
+/// Calls the chain extension function with the given ID and input.
+///
+/// # Note
+///
+/// - Chain extensions are custom functions registered by the runtime.
+/// - The input is passed to the chain extension in its SCALE encoded form
+///   and the output returned by the chain extension is decoded into `O`.
+/// - Prefer implementing [`ChainExtension`](trait.ChainExtension.html) for
+///   a type safe interface to a chain extension function.
+///
+/// # Errors
+///
+/// - If the chain extension returned a non-zero error code.
+/// - If the returned output failed to decode properly.
+pub fn call_chain_extension<I, O>(func_id: u32, input: &I) -> Result<O>
+where
+    I: scale::Encode,
+    O: scale::Decode,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::call_chain_extension::<I, O>(instance, func_id, input)
     })
 }
 
+/// Conducts the crypto hash of the given input bytes and returns the result.
+///
+/// # Example
+///
+/// ```
+/// use ink_core::env::{
+///     hash::Keccak256,
+///     hash_bytes,
+/// };
+/// let output: [u8; 32] = hash_bytes::<Keccak256>(b"DEAD_BEEF");
+/// ```
+pub fn hash_bytes<H>(input: &[u8]) -> <H as hash::CryptoHash>::Output
+where
+    H: hash::CryptoHash,
+{
+    let mut output = <<H as hash::CryptoHash>::Output as Default>::default();
+    <H as hash::CryptoHash>::hash(input, &mut output);
+    output
+}
+
+/// Conducts the crypto hash of the SCALE encoded input and returns the result.
+///
+/// # Note
+///
+/// Prefer this over encoding the input manually and using [`hash_bytes`]
+/// since this might avoid an intermediate allocation.
+pub fn hash_encoded<H, T>(input: &T) -> <H as hash::CryptoHash>::Output
+where
+    H: hash::CryptoHash,
+    T: scale::Encode,
+{
+    let mut output = <<H as hash::CryptoHash>::Output as Default>::default();
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        EnvBackend::hash_encoded::<H, T>(instance, input, &mut output)
+    });
+    output
+}
+
 /// Built-in efficient cryptographic hash functions.
 pub mod hash {
     use super::*;
@@ -518,7 +1143,7 @@ pub mod hash {
                 pub fn $name(input: &[u8], output: &mut [u8; $output_len]) {
                     // No need to actually access the environmental instance
                     // if we only call one of its inherent methods.
-                    <EnvInstance as Env>::[<hash_ $name>](input, output)
+                    <EnvInstance as EnvBackend>::[<hash_ $name>](input, output)
                 }
             }
         };
@@ -543,4 +1168,70 @@ pub mod hash {
         /// puts the result into the output buffer.
         fn blake2_128(16)
     );
+    impl_hash_fn!(
+        /// Conducts the BLAKE2s 256-bit hash of the given bytes and
+        /// puts the result into the output buffer.
+        ///
+        /// # Panics
+        ///
+        /// In the on-chain environment since the contracts runtime
+        /// provides no host function for BLAKE2s.
+        fn blake2s_256(32)
+    );
+
+    /// Types that implement this trait are marker types that identify
+    /// a cryptographic hash function supported by the environment.
+    pub trait CryptoHash {
+        /// The output of the hash function.
+        ///
+        /// # Note
+        ///
+        /// This is a byte array with varying lengths, e.g. `[u8; 32]`, `[u8; 16]`, etc.
+        type Output: Default + AsRef<[u8]> + AsMut<[u8]>;
+
+        /// Conducts the hash of the input and puts the result into the output buffer.
+        fn hash(input: &[u8], output: &mut Self::Output);
+    }
+
+    macro_rules! impl_crypto_hash_for {
+        ( $(#[$doc:meta])* enum $ty_name:ident($fn_name:ident, $output_len:literal) ) => {
+            $( #[$doc] )*
+            #[derive(Debug, Copy, Clone, PartialEq, Eq)]
+            pub enum $ty_name {}
+
+            impl CryptoHash for $ty_name {
+                type Output = [u8; $output_len];
+
+                fn hash(input: &[u8], output: &mut Self::Output) {
+                    $fn_name(input, output)
+                }
+            }
+        };
+    }
+    impl_crypto_hash_for!(
+        /// The SHA2 crypto hash with 256-bit output.
+        enum Sha2x256(sha2_256, 32)
+    );
+    impl_crypto_hash_for!(
+        /// The KECCAK crypto hash with 256-bit output.
+        enum Keccak256(keccak_256, 32)
+    );
+    impl_crypto_hash_for!(
+        /// The BLAKE2b crypto hash with 256-bit output.
+        enum Blake2x256(blake2_256, 32)
+    );
+    impl_crypto_hash_for!(
+        /// The BLAKE2b crypto hash with 128-bit output.
+        enum Blake2x128(blake2_128, 16)
+    );
+    impl_crypto_hash_for!(
+        /// The BLAKE2s crypto hash with 256-bit output.
+        ///
+        /// # Note
+        ///
+        /// Only available in the off-chain environment. The contracts runtime
+        /// provides no host function for BLAKE2s, so hashing with it panics
+        /// in the on-chain environment.
+        enum Blake2s256(blake2s_256, 32)
+    );
 }
@@ -12,6 +12,25 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+//! The traits to implement for an environmental backend.
+//!
+//! A backend provides the raw functionality of the environment through
+//! [`EnvBackend`], the functionality that depends on the [`EnvTypes`] of
+//! the chain through [`TypedEnvBackend`] and access to its instance
+//! through [`OnInstance`]. All functions of `ink_core::env` are
+//! implemented in terms of these traits.
+//!
+//! The in-tree backends are the on-chain backend calling into the SEAL
+//! interface of the contracts pallet for `no_std` Wasm builds and the
+//! off-chain engine for `std` builds.
+//!
+//! # Note
+//!
+//! The backend is selected at compile time by the target and the crate
+//! features in `ink_core::env::engine`, it cannot be replaced by crates
+//! depending on `ink_core`. Another backend, e.g. for a new target, has to
+//! implement these traits and be added to the selection in `env::engine`.
+
 use crate::env::{
     call::{
         CallData,
@@ -19,14 +38,26 @@ use crate::env::{
         InstantiateParams,
         ReturnType,
     },
+    hash::CryptoHash,
+    EcdsaPublicKey,
     EnvTypes,
     Result,
+    ReturnFlags,
+    StorageBatch,
     Topics,
 };
 use ink_primitives::Key;
 
+/// Provides access to the instance of an environmental backend.
+pub trait OnInstance: EnvBackend + TypedEnvBackend {
+    /// Runs the closure with exclusive access to the backend instance.
+    fn on_instance<F, R>(f: F) -> R
+    where
+        F: FnOnce(&mut Self) -> R;
+}
+
 /// Environmental contract functionality that does not require `EnvTypes`.
-pub trait Env {
+pub trait EnvBackend {
     /// Writes the value to the contract storage under the given key.
     fn set_contract_storage<V>(&mut self, key: Key, value: &V)
     where
@@ -44,6 +75,27 @@ pub trait Env {
     /// Clears the contract's storage key entry.
     fn clear_contract_storage(&mut self, key: Key);
 
+    /// Registers the contract's storage key entry to be cleared upon returning.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::clear_contract_storage_on_return`]
+    fn clear_contract_storage_on_return(&mut self, key: Option<Key>);
+
+    /// Applies all writes of the batch to the contract's storage.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::set_contract_storage_batch`]
+    fn set_contract_storage_batch(&mut self, batch: &StorageBatch);
+
+    /// Returns the encoded values stored under the given keys in the contract's storage.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::get_contract_storage_batch`]
+    fn get_contract_storage_batch(&mut self, keys: &[Key]) -> Result<StorageBatch>;
+
     /// Returns the value from the *runtime* storage at the position of the key if any.
     ///
     /// # Errors
@@ -75,9 +127,40 @@ pub trait Env {
     where
         R: scale::Encode;
 
+    /// Returns the value back to the caller of the executed contract
+    /// and halts its execution.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::return_value`]
+    fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
+    where
+        R: scale::Encode;
+
+    /// Returns `true` if the caller is the origin of the extrinsic.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::caller_is_origin`]
+    fn caller_is_origin(&mut self) -> bool;
+
+    /// Returns the depth of the current call stack.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::call_depth`]
+    fn call_depth(&mut self) -> u32;
+
     /// Prints the given contents to the console log.
     fn println(&mut self, content: &str);
 
+    /// Appends the given message to the debug message buffer.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::debug_message`]
+    fn debug_message(&mut self, message: &str);
+
     /// Conducts the SHA2 256-bit hash of the input
     /// puts the result into the output buffer.
     fn hash_sha2_256(input: &[u8], output: &mut [u8; 32]);
@@ -93,10 +176,53 @@ pub trait Env {
     /// Conducts the BLAKE2 128-bit hash of the input
     /// puts the result into the output buffer.
     fn hash_blake2_128(input: &[u8], output: &mut [u8; 16]);
+
+    /// Conducts the BLAKE2s 256-bit hash of the input
+    /// puts the result into the output buffer.
+    ///
+    /// # Panics
+    ///
+    /// In the on-chain environment since the contracts runtime
+    /// provides no host function for BLAKE2s.
+    fn hash_blake2s_256(input: &[u8], output: &mut [u8; 32]);
+
+    /// Conducts the crypto hash of the SCALE encoded input
+    /// and puts the result into the output buffer.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::hash_encoded`]
+    fn hash_encoded<H, T>(&mut self, input: &T, output: &mut <H as CryptoHash>::Output)
+    where
+        H: CryptoHash,
+        T: scale::Encode;
+
+    /// Recovers the compressed ECDSA public key from the signature
+    /// of the message hash.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::ecdsa_recover`]
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+    ) -> Result<EcdsaPublicKey>;
+
+    /// Calls the chain extension function with the given ID and input
+    /// and returns its decoded output.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::call_chain_extension`]
+    fn call_chain_extension<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
+    where
+        I: scale::Encode,
+        O: scale::Decode;
 }
 
-/// Environmental contract functionality.
-pub trait TypedEnv: Env {
+/// Environmental contract functionality that depends on the `EnvTypes`.
+pub trait TypedEnvBackend: EnvBackend {
     /// Returns the address of the caller of the executed contract.
     ///
     /// # Note
@@ -146,6 +272,13 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::balance`]
     fn balance<T: EnvTypes>(&mut self) -> Result<T::Balance>;
 
+    /// Returns the reserved balance of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::reserved_balance`]
+    fn reserved_balance<T: EnvTypes>(&mut self) -> Result<T::Balance>;
+
     /// Returns the current rent allowance for the executed contract.
     ///
     /// # Note
@@ -153,6 +286,23 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::rent_allowance`]
     fn rent_allowance<T: EnvTypes>(&mut self) -> Result<T::Balance>;
 
+    /// Returns the code hash of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::own_code_hash`]
+    fn own_code_hash<T: EnvTypes>(&mut self) -> Result<T::Hash>;
+
+    /// Returns the code hash of the contract at the given account ID if any.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::code_hash`]
+    fn code_hash<T: EnvTypes>(
+        &mut self,
+        account_id: &T::AccountId,
+    ) -> Result<Option<T::Hash>>;
+
     /// Returns the current block number.
     ///
     /// # Note
@@ -174,6 +324,13 @@ pub trait TypedEnv: Env {
     /// For more details visit: [`ink_core::env::tombstone_deposit`]
     fn tombstone_deposit<T: EnvTypes>(&mut self) -> Result<T::Balance>;
 
+    /// Returns the price for the specified amount of gas.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::weight_to_fee`]
+    fn weight_to_fee<T: EnvTypes>(&mut self, gas: u64) -> Result<T::Balance>;
+
     /// Emits an event with the given event data.
     ///
     /// # Note
@@ -207,6 +364,9 @@ pub trait TypedEnv: Env {
     /// # Note
     ///
     /// For more details visit: [`ink_core::env::invoke_contract`]
+    ///
+    /// The off-chain environment dispatches contract calls outside of
+    /// its environmental instance and does not use this method.
     fn invoke_contract<T>(&mut self, call_data: &CallParams<T, ()>) -> Result<()>
     where
         T: EnvTypes;
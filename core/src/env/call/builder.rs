@@ -18,6 +18,7 @@ use crate::env::{
     call::{
         state,
         CallData,
+        CallFlags,
         Selector,
     },
     EnvTypes,
@@ -37,6 +38,8 @@ where
 {
     /// The account ID of the to-be-called smart contract.
     callee: E::AccountId,
+    /// The flags used to change the behaviour of the call.
+    call_flags: CallFlags,
     /// The maximum gas costs allowed for the call.
     gas_limit: u64,
     /// The transferred value for the call.
@@ -67,10 +70,16 @@ where
         &self.callee
     }
 
+    /// The flags used to change the behaviour of the call.
+    pub fn call_flags(&self) -> &CallFlags {
+        &self.call_flags
+    }
+
     /// The gas limit for the contract instantiation.
     pub fn gas_limit(&self) -> u64 {
         self.gas_limit
     }
+
     /// The transferred value for the called contract.
     pub fn transferred_value(&self) -> &E::Balance {
         &self.transferred_value
@@ -91,6 +100,7 @@ where
     fn new(callee: E::AccountId, selector: Selector) -> Self {
         Self {
             callee,
+            call_flags: CallFlags::default(),
             gas_limit: 0,
             transferred_value: E::Balance::default(),
             return_type: PhantomData,
@@ -138,6 +148,42 @@ where
         self.params.transferred_value = value;
         self
     }
+
+    /// Sets the flags used to change the behaviour of the call.
+    pub fn call_flags(mut self, call_flags: CallFlags) -> Self {
+        self.params.call_flags = call_flags;
+        self
+    }
+
+    /// Allows or forbids the callee to call back into the calling contract.
+    ///
+    /// # Note
+    ///
+    /// Reentrancy is allowed by default.
+    pub fn allow_reentry(mut self, allow_reentry: bool) -> Self {
+        self.params.call_flags = self.params.call_flags.set_allow_reentry(allow_reentry);
+        self
+    }
+
+    /// Forwards the input of the calling contract to the callee.
+    ///
+    /// # Note
+    ///
+    /// Arguments pushed to the call are ignored in this case.
+    pub fn forward_input(mut self) -> Self {
+        self.params.call_flags = self.params.call_flags.set_forward_input(true);
+        self
+    }
+
+    /// Makes the output of the callee the output of the calling contract.
+    ///
+    /// # Note
+    ///
+    /// The calling contract does not resume execution after a tail call.
+    pub fn tail_call(mut self) -> Self {
+        self.params.call_flags = self.params.call_flags.set_tail_call(true);
+        self
+    }
 }
 
 impl<E, R> CallBuilder<E, R, state::Unsealed>
@@ -149,7 +195,7 @@ where
     where
         A: scale::Encode,
     {
-        self.params.call_data.push_arg(arg);
+        self.params.call_data = self.params.call_data.push_arg(arg);
         self
     }
 
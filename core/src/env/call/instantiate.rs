@@ -174,7 +174,7 @@ where
     where
         A: scale::Encode,
     {
-        self.params.call_data.push_arg(arg);
+        self.params.call_data = self.params.call_data.push_arg(arg);
         self
     }
 
@@ -44,6 +44,7 @@ pub use self::{
     },
     utils::{
         CallData,
+        CallFlags,
         Selector,
     },
 };
@@ -12,11 +12,20 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use derive_more::From;
-use ink_prelude::{
-    vec,
-    vec::Vec,
-};
+pub use ink_primitives::Selector;
+
+/// The bytes of the call data.
+#[cfg(not(all(feature = "ink-alloc-free", not(feature = "std"))))]
+type CallDataBytes = ink_prelude::vec::Vec<u8>;
+
+/// The bytes of the call data.
+///
+/// # Note
+///
+/// The call data is limited to the capacity of the static environmental
+/// buffer in the alloc-free environment.
+#[cfg(all(feature = "ink-alloc-free", not(feature = "std")))]
+type CallDataBytes = crate::env::static_bytes::StaticBytes;
 
 /// Seals to guard pushing arguments to already satisfied parameter builders.
 pub mod seal {
@@ -26,42 +35,90 @@ pub mod seal {
     pub enum Unsealed {}
 }
 
-/// The function selector.
-#[derive(Debug, Copy, Clone, PartialEq, Eq, From, scale::Decode, scale::Encode)]
-pub struct Selector {
-    /// The 4 underlying bytes.
-    bytes: [u8; 4],
+/// The flags used to change the behaviour of a cross-contract call.
+///
+/// # Note
+///
+/// The bit layout matches the one of the contracts pallet.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct CallFlags {
+    /// The raw flag bits.
+    bits: u32,
 }
 
-impl<'a> From<&'a [u8]> for Selector {
-    /// Computes the selector from the given input bytes.
+impl Default for CallFlags {
+    /// Returns the default call flags.
     ///
     /// # Note
     ///
-    /// Normally this is invoked through `Selector::from_str`.
-    fn from(input: &'a [u8]) -> Self {
-        let keccak = ink_primitives::hash::keccak256(input);
+    /// By default reentrancy is allowed in order to preserve the behaviour
+    /// of cross-contract calls that do not specify any flags.
+    fn default() -> Self {
         Self {
-            bytes: [keccak[0], keccak[1], keccak[2], keccak[3]],
+            bits: Self::ALLOW_REENTRY,
         }
     }
 }
 
-impl Selector {
-    /// Returns the selector for the given name.
-    #[allow(clippy::should_implement_trait)]
-    pub fn from_str(name: &str) -> Self {
-        From::from(name.as_bytes())
+impl CallFlags {
+    /// Forwards the input of the executed contract to the callee.
+    const FORWARD_INPUT: u32 = 0b0000_0001;
+    /// Returns the output of the callee as output of the executed contract.
+    const TAIL_CALL: u32 = 0b0000_0100;
+    /// Allows the callee to call back into the executed contract.
+    const ALLOW_REENTRY: u32 = 0b0000_1000;
+
+    /// Sets or clears the flag bits in `mask`.
+    fn set(mut self, mask: u32, value: bool) -> Self {
+        if value {
+            self.bits |= mask;
+        } else {
+            self.bits &= !mask;
+        }
+        self
     }
 
-    /// Creates a selector directly from 4 bytes.
-    pub const fn new(bytes: [u8; 4]) -> Self {
-        Self { bytes }
+    /// Allows or forbids the callee to call back into the executed contract.
+    pub fn set_allow_reentry(self, allow_reentry: bool) -> Self {
+        self.set(Self::ALLOW_REENTRY, allow_reentry)
     }
 
-    /// Returns the underlying bytes of the selector.
-    pub const fn to_bytes(self) -> [u8; 4] {
-        self.bytes
+    /// Forwards the input of the executed contract to the callee.
+    ///
+    /// # Note
+    ///
+    /// The call data of the call is ignored in this case.
+    pub fn set_forward_input(self, forward_input: bool) -> Self {
+        self.set(Self::FORWARD_INPUT, forward_input)
+    }
+
+    /// Makes the output of the callee the output of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// The executed contract does not resume execution after a tail call.
+    pub fn set_tail_call(self, tail_call: bool) -> Self {
+        self.set(Self::TAIL_CALL, tail_call)
+    }
+
+    /// Returns `true` if the callee is allowed to call back into the executed contract.
+    pub fn allow_reentry(&self) -> bool {
+        self.bits & Self::ALLOW_REENTRY != 0
+    }
+
+    /// Returns `true` if the input of the executed contract is forwarded.
+    pub fn forward_input(&self) -> bool {
+        self.bits & Self::FORWARD_INPUT != 0
+    }
+
+    /// Returns `true` if the call is a tail call.
+    pub fn tail_call(&self) -> bool {
+        self.bits & Self::TAIL_CALL != 0
+    }
+
+    /// Returns the raw flag bits.
+    pub fn into_u32(self) -> u32 {
+        self.bits
     }
 }
 
@@ -77,24 +134,34 @@ pub struct CallData {
     /// # Note
     ///
     /// Has the invariant of always holding at least 4 bytes (the selector).
-    bytes: Vec<u8>,
+    bytes: CallDataBytes,
 }
 
 impl CallData {
     /// Creates new call ABI data for the given selector.
     pub fn new(selector: Selector) -> Self {
-        let bytes = selector.to_bytes();
-        Self {
-            bytes: vec![bytes[0], bytes[1], bytes[2], bytes[3]],
-        }
+        let mut bytes = CallDataBytes::with_capacity(4);
+        bytes.extend_from_slice(&selector.to_bytes());
+        Self { bytes }
     }
 
     /// Pushes the given argument onto the call ABI data in encoded form.
-    pub fn push_arg<A>(&mut self, arg: &A)
+    ///
+    /// # Example
+    ///
+    /// ```
+    /// # use ink_core::env::call::{CallData, Selector};
+    /// let call_data = CallData::new(Selector::from_label("transfer"))
+    ///     .push_arg(&[0x01_u8; 32])
+    ///     .push_arg(&100_u128);
+    /// assert_eq!(call_data.params().len(), 32 + 16);
+    /// ```
+    pub fn push_arg<A>(mut self, arg: &A) -> Self
     where
         A: scale::Encode,
     {
-        arg.encode_to(&mut self.bytes)
+        arg.encode_to(&mut self.bytes);
+        self
     }
 
     /// Returns the selector of `self`.
@@ -131,7 +198,7 @@ impl scale::Decode for CallData {
         input: &mut I,
     ) -> core::result::Result<Self, scale::Error> {
         let remaining_len = input.remaining_len().unwrap_or(None).unwrap_or(0);
-        let mut bytes = Vec::with_capacity(remaining_len);
+        let mut bytes = CallDataBytes::with_capacity(remaining_len);
         while let Ok(byte) = input.read_byte() {
             bytes.push(byte);
         }
@@ -0,0 +1,59 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Typed interface to chain extensions registered by the runtime.
+
+use crate::env::Result;
+
+/// A function of a chain extension that has been registered by the runtime.
+///
+/// # Note
+///
+/// Chain extensions are custom functions provided by the runtime
+/// that are identified by a unique `u32` function identifier.
+/// Their inputs and outputs are exchanged in their SCALE encoded form.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::env::ChainExtension;
+/// /// Fetches the price of an asset from the runtime's oracle.
+/// enum FetchPrice {}
+///
+/// impl ChainExtension for FetchPrice {
+///     const FUNC_ID: u32 = 0x0100_0001;
+///     type Input = [u8; 4];
+///     type Output = u128;
+/// }
+/// ```
+pub trait ChainExtension {
+    /// The unique identifier of the chain extension function.
+    const FUNC_ID: u32;
+    /// The type of the input passed to the chain extension function.
+    type Input: scale::Encode;
+    /// The type of the output returned by the chain extension function.
+    type Output: scale::Decode;
+
+    /// Calls the chain extension function with the given input.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::call_chain_extension`]
+    fn call(input: &Self::Input) -> Result<Self::Output> {
+        crate::env::call_chain_extension::<Self::Input, Self::Output>(
+            Self::FUNC_ID,
+            input,
+        )
+    }
+}
@@ -12,18 +12,10 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use crate::env::backend::{
-    Env,
-    TypedEnv,
-};
+pub use crate::env::backend::OnInstance;
 use cfg_if::cfg_if;
 
-pub trait OnInstance: Env + TypedEnv {
-    fn on_instance<F, R>(f: F) -> R
-    where
-        F: FnOnce(&mut Self) -> R;
-}
-
+// Selects the backend implementing the traits of `env::backend` for the target.
 cfg_if! {
     if #[cfg(all(not(feature = "std"), target_arch = "wasm32"))] {
         mod on_chain;
@@ -0,0 +1,95 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::OffChainError;
+use crate::env::{
+    ChainExtension,
+    EnvError,
+    Result,
+};
+use std::collections::BTreeMap;
+
+/// A registered chain extension function operating on encoded bytes.
+///
+/// Returns either the encoded output or a custom error code.
+type ChainExtensionFn = Box<dyn FnMut(&[u8]) -> core::result::Result<Vec<u8>, u32>>;
+
+/// Chain extension handler.
+///
+/// Mocks the chain extension functions registered by the runtime.
+pub struct ChainExtensionHandler {
+    /// The currently registered chain extension functions by their ID.
+    registered: BTreeMap<u32, ChainExtensionFn>,
+}
+
+impl ChainExtensionHandler {
+    /// Creates a new chain extension handler.
+    ///
+    /// Initialized without any registered chain extension functions.
+    pub fn new() -> Self {
+        Self {
+            registered: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the chain extension function.
+    ///
+    /// # Note
+    ///
+    /// Overwrites a chain extension function previously registered
+    /// under the same function ID.
+    pub fn register<E, F>(&mut self, mut f: F)
+    where
+        E: ChainExtension,
+        <E as ChainExtension>::Input: scale::Decode,
+        <E as ChainExtension>::Output: scale::Encode,
+        F: FnMut(<E as ChainExtension>::Input) -> core::result::Result<E::Output, u32>
+            + 'static,
+    {
+        self.register_raw(E::FUNC_ID, move |mut input: &[u8]| {
+            let input = <E::Input as scale::Decode>::decode(&mut input)
+                .expect("could not decode chain extension input");
+            f(input).map(|output| scale::Encode::encode(&output))
+        });
+    }
+
+    /// Registers the chain extension function operating on encoded bytes.
+    ///
+    /// # Note
+    ///
+    /// Overwrites a chain extension function previously registered
+    /// under the same function ID.
+    pub fn register_raw<F>(&mut self, func_id: u32, f: F)
+    where
+        F: FnMut(&[u8]) -> core::result::Result<Vec<u8>, u32> + 'static,
+    {
+        self.registered.insert(func_id, Box::new(f));
+    }
+
+    /// Calls the chain extension function with the given ID and input.
+    pub fn call<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
+    where
+        I: scale::Encode,
+        O: scale::Decode,
+    {
+        match self.registered.get_mut(&func_id) {
+            Some(handler) => {
+                let output = handler(&input.encode())
+                    .map_err(EnvError::ChainExtensionFailState)?;
+                scale::Decode::decode(&mut &output[..]).map_err(Into::into)
+            }
+            None => Err(OffChainError::UnregisteredChainExtension(func_id).into()),
+        }
+    }
+}
@@ -0,0 +1,255 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    randomness::Randomness,
+    Block,
+    EnvInstance,
+    ExecContext,
+    OffAccountId,
+    OffBalance,
+    OnInstance,
+};
+use crate::env::{
+    call::CallData,
+    EnvTypes,
+    Result,
+};
+
+/// Builds an execution context that is installed for the duration of a closure.
+///
+/// # Note
+///
+/// Everything that is not set explicitly is inherited from the current
+/// execution context and the current block.
+///
+/// Unlike [`super::test_api::push_execution_context`] the builder replaces
+/// the current execution context instead of pushing a new one on top of it.
+/// Therefore [`crate::env::call_depth`] is unchanged and
+/// [`crate::env::caller_is_origin`] only depends on the configured caller.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::env::{self, test::ExecutionContextBuilder, DefaultEnvTypes};
+/// env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+///     let caller = ExecutionContextBuilder::<DefaultEnvTypes>::new()
+///         .caller(accounts.bob)
+///         .value(0)
+///         .block_number(42)
+///         .run(|| env::caller::<DefaultEnvTypes>())??;
+///     assert_eq!(caller, accounts.bob);
+///     assert_eq!(env::caller::<DefaultEnvTypes>()?, accounts.alice);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub struct ExecutionContextBuilder<T>
+where
+    T: EnvTypes,
+{
+    caller: Option<T::AccountId>,
+    callee: Option<T::AccountId>,
+    value: Option<T::Balance>,
+    gas: Option<T::Balance>,
+    call_data: Option<CallData>,
+    block_number: Option<T::BlockNumber>,
+    block_timestamp: Option<T::Timestamp>,
+    random_seed: Option<T::Hash>,
+}
+
+impl<T> Default for ExecutionContextBuilder<T>
+where
+    T: EnvTypes,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> ExecutionContextBuilder<T>
+where
+    T: EnvTypes,
+{
+    /// Creates a new builder inheriting everything from the current context.
+    pub fn new() -> Self {
+        Self {
+            caller: None,
+            callee: None,
+            value: None,
+            gas: None,
+            call_data: None,
+            block_number: None,
+            block_timestamp: None,
+            random_seed: None,
+        }
+    }
+
+    /// Sets what [`crate::env::caller`] returns.
+    pub fn caller(mut self, caller: T::AccountId) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Sets what [`crate::env::account_id`] returns.
+    pub fn callee(mut self, callee: T::AccountId) -> Self {
+        self.callee = Some(callee);
+        self
+    }
+
+    /// Sets what [`crate::env::transferred_balance`] returns.
+    ///
+    /// # Note
+    ///
+    /// No balance is moved. Use [`super::test_api::transfer_in`] for that.
+    pub fn value(mut self, value: T::Balance) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Sets what [`crate::env::gas_left`] returns.
+    pub fn gas(mut self, gas: T::Balance) -> Self {
+        self.gas = Some(gas);
+        self
+    }
+
+    /// Sets what [`crate::env::input`] returns.
+    pub fn call_data(mut self, call_data: CallData) -> Self {
+        self.call_data = Some(call_data);
+        self
+    }
+
+    /// Sets what [`crate::env::block_number`] returns.
+    pub fn block_number(mut self, block_number: T::BlockNumber) -> Self {
+        self.block_number = Some(block_number);
+        self
+    }
+
+    /// Sets what [`crate::env::block_timestamp`] returns.
+    pub fn block_timestamp(mut self, block_timestamp: T::Timestamp) -> Self {
+        self.block_timestamp = Some(block_timestamp);
+        self
+    }
+
+    /// Makes [`crate::env::random`] deterministic using the given seed.
+    ///
+    /// # Note
+    ///
+    /// Custom randomness functions are disabled while the context is installed.
+    pub fn random_seed(mut self, seed: T::Hash) -> Self {
+        self.random_seed = Some(seed);
+        self
+    }
+
+    /// Runs `f` with the execution context installed and returns its result.
+    ///
+    /// # Note
+    ///
+    /// The previous execution context, block number, block timestamp and
+    /// randomness are restored afterwards, even if `f` panics. Changes to
+    /// the accounts such as contract storage writes are kept.
+    ///
+    /// # Errors
+    ///
+    /// If there is no current execution context or block.
+    pub fn run<F, R>(self, f: F) -> Result<R>
+    where
+        F: FnOnce() -> R,
+    {
+        let restore =
+            <EnvInstance as OnInstance>::on_instance(|instance| self.install(instance))?;
+        let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+        <EnvInstance as OnInstance>::on_instance(|instance| restore.apply(instance));
+        match outcome {
+            Ok(result) => Ok(result),
+            Err(payload) => std::panic::resume_unwind(payload),
+        }
+    }
+
+    /// Installs the execution context and returns what is needed to restore
+    /// the previous state.
+    fn install(self, instance: &mut EnvInstance) -> Result<Restore> {
+        let previous_exec_context = instance.exec_context()?.clone();
+        let mut exec_context = previous_exec_context.clone();
+        if let Some(caller) = self.caller {
+            exec_context.caller = OffAccountId::new(&caller);
+        }
+        if let Some(callee) = self.callee {
+            exec_context.callee = OffAccountId::new(&callee);
+        }
+        if let Some(value) = self.value {
+            exec_context.transferred_value = OffBalance::new(&value);
+        }
+        if let Some(gas) = self.gas {
+            exec_context.gas = OffBalance::new(&gas);
+        }
+        if let Some(call_data) = self.call_data {
+            exec_context.call_data = call_data;
+        }
+        exec_context.output = None;
+        let block = instance.current_block_mut()?;
+        let previous_block = block.clone();
+        if let Some(number) = self.block_number {
+            block.set_number::<T>(number)?;
+        }
+        if let Some(timestamp) = self.block_timestamp {
+            block.set_timestamp::<T>(timestamp)?;
+        }
+        let previous_randomness = match self.random_seed {
+            Some(seed) => {
+                block.set_entropy::<T>(seed)?;
+                let mut randomness = Randomness::new();
+                randomness.set_seed::<T>(seed);
+                Some(core::mem::replace(&mut instance.randomness, randomness))
+            }
+            None => None,
+        };
+        *instance.exec_context_mut()? = exec_context;
+        Ok(Restore {
+            exec_context_depth: instance.exec_context.len(),
+            previous_exec_context,
+            block_index: instance.blocks.len() - 1,
+            previous_block,
+            previous_randomness,
+        })
+    }
+}
+
+/// The state replaced by an installed execution context.
+struct Restore {
+    /// The number of execution contexts before the installation.
+    exec_context_depth: usize,
+    /// The execution context replaced by the installation.
+    previous_exec_context: ExecContext,
+    /// The index of the block modified by the installation.
+    block_index: usize,
+    /// The block before the installation.
+    previous_block: Block,
+    /// The randomness before the installation if it has been replaced.
+    previous_randomness: Option<Randomness>,
+}
+
+impl Restore {
+    /// Restores the state replaced by the installed execution context.
+    fn apply(self, instance: &mut EnvInstance) {
+        instance.exec_context.truncate(self.exec_context_depth - 1);
+        instance.exec_context.push(self.previous_exec_context);
+        if let Some(block) = instance.blocks.get_mut(self.block_index) {
+            *block = self.previous_block;
+        }
+        if let Some(randomness) = self.previous_randomness {
+            instance.randomness = randomness;
+        }
+    }
+}
@@ -0,0 +1,332 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Dispatch of cross-contract calls to contracts registered off-chain.
+//!
+//! # Note
+//!
+//! Registered contracts access the environment themselves which is why
+//! they are executed outside of the environmental instance.
+
+use super::{
+    impls::gas_to_balance,
+    AccountError,
+    ContractReturned,
+    ContractTerminated,
+    EnvInstance,
+    ExecContext,
+    HostFn,
+    OffAccountId,
+    OffBalance,
+    OffChainError,
+    OnInstance,
+};
+use crate::env::{
+    call::{
+        CallData,
+        CallParams,
+        InstantiateParams,
+        ReturnType,
+    },
+    EnvError,
+    EnvTypes,
+    Result,
+};
+use std::{
+    collections::BTreeMap,
+    rc::Rc,
+};
+
+/// The reason for which a registered contract is executed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum CallKind {
+    /// The contract is instantiated.
+    Instantiate,
+    /// A message of the contract is called.
+    Call,
+}
+
+/// A registered contract dispatching its constructors or messages.
+///
+/// Returns a custom error code if the dispatch failed.
+///
+/// # Note
+///
+/// Contracts are shared so that they can be called recursively.
+type ContractFn = Rc<dyn Fn(CallKind) -> core::result::Result<(), u8>>;
+
+/// The contracts that can be instantiated and called off-chain by their code hash.
+pub struct ContractsRegistry {
+    /// The registered contracts by their encoded code hash.
+    registered: BTreeMap<Vec<u8>, ContractFn>,
+}
+
+impl ContractsRegistry {
+    /// Creates a new contracts registry without any registered contracts.
+    pub fn new() -> Self {
+        Self {
+            registered: BTreeMap::new(),
+        }
+    }
+
+    /// Registers the contract under the given code hash.
+    ///
+    /// # Note
+    ///
+    /// Overwrites a contract previously registered under the same code hash.
+    pub fn register<T, F>(&mut self, code_hash: T::Hash, f: F)
+    where
+        T: EnvTypes,
+        F: Fn(CallKind) -> core::result::Result<(), u8> + 'static,
+    {
+        self.registered
+            .insert(scale::Encode::encode(&code_hash), Rc::new(f));
+    }
+
+    /// Returns the contract registered under the given code hash if any.
+    fn get<T>(&self, code_hash: &T::Hash) -> Option<ContractFn>
+    where
+        T: EnvTypes,
+    {
+        self.registered
+            .get(&scale::Encode::encode(code_hash))
+            .map(Rc::clone)
+    }
+}
+
+/// The state of the emulated chain to return to if an execution fails.
+///
+/// # Note
+///
+/// Only the accounts modified by the execution are copied.
+struct Checkpoint {
+    /// The number of execution contexts before the execution.
+    exec_contexts: usize,
+    /// The number of emitted events before the execution.
+    emitted_events: usize,
+}
+
+impl Checkpoint {
+    /// Opens a new checkpoint of the emulated chain.
+    fn open(instance: &mut EnvInstance) -> Self {
+        instance.accounts.checkpoint();
+        Self {
+            exec_contexts: instance.exec_context.len(),
+            emitted_events: instance.emitted_events.len(),
+        }
+    }
+
+    /// Keeps all modifications of the emulated chain since the checkpoint.
+    fn commit(self, instance: &mut EnvInstance) {
+        instance.accounts.commit();
+    }
+
+    /// Reverts all modifications of the emulated chain since the checkpoint.
+    fn revert(self, instance: &mut EnvInstance) {
+        instance.accounts.revert();
+        instance.exec_context.truncate(self.exec_contexts);
+        instance.emitted_events.truncate(self.emitted_events);
+    }
+}
+
+/// Executes the contract registered for the callee in a new execution context.
+///
+/// Returns the output of the execution.
+///
+/// # Note
+///
+/// The emulated chain is reverted to the checkpoint if the execution fails.
+/// A contract that returns with the revert flag fails with error code `0`.
+fn execute<T>(
+    checkpoint: Checkpoint,
+    callee: &T::AccountId,
+    gas_limit: u64,
+    value: T::Balance,
+    call_data: &CallData,
+    kind: CallKind,
+) -> Result<Vec<u8>>
+where
+    T: EnvTypes,
+{
+    let prepared = <EnvInstance as OnInstance>::on_instance(|instance| -> Result<_> {
+        let host_fn = match kind {
+            CallKind::Instantiate => HostFn::Instantiate,
+            CallKind::Call => HostFn::Call,
+        };
+        instance.gas_meter.charge(host_fn);
+        instance.fail_on_fault(host_fn, OffChainError::InjectedFault(host_fn).into())?;
+        let code_hash = instance
+            .accounts
+            .get_account::<T>(callee)
+            .map(|account| account.code_hash::<T>())
+            .transpose()?
+            .flatten()
+            .ok_or(EnvError::NotAContract)?;
+        let contract = instance
+            .contracts
+            .get::<T>(&code_hash)
+            .ok_or(OffChainError::UnregisteredContract)?;
+        let caller = instance.callee_id::<T>()?;
+        instance.transfer_between::<T>(&caller, callee, value)?;
+        instance.tracer.enter(
+            kind,
+            OffAccountId::new(&caller),
+            OffAccountId::new(callee),
+            OffBalance::new(&value),
+            call_data,
+        );
+        instance.exec_context.push(
+            ExecContext::build::<T>()
+                .caller(caller)
+                .callee(callee.clone())
+                .gas(gas_to_balance::<T>(gas_limit))
+                .transferred_value(value)
+                .call_data(call_data.clone())
+                .finish(),
+        );
+        Ok(contract)
+    });
+    let contract = match prepared {
+        Ok(contract) => contract,
+        Err(error) => {
+            <EnvInstance as OnInstance>::on_instance(|instance| {
+                checkpoint.revert(instance)
+            });
+            return Err(error)
+        }
+    };
+    let outcome =
+        std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| contract(kind)));
+    let failure = match outcome {
+        Ok(Ok(())) => None,
+        Ok(Err(code)) => Some(fail_state(kind, code)),
+        Err(payload) => {
+            match payload.downcast_ref::<ContractReturned>() {
+                Some(returned) if returned.flags.is_reverted() => {
+                    Some(fail_state(kind, 0))
+                }
+                Some(_) => None,
+                None if payload.is::<ContractTerminated>() => None,
+                None => {
+                    Some(match kind {
+                        CallKind::Instantiate => EnvError::ContractInstantiationTrapped,
+                        CallKind::Call => EnvError::ContractCallTrapped,
+                    })
+                }
+            }
+        }
+    };
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        if let Some(error) = failure {
+            instance.tracer.exit(false, Vec::new);
+            checkpoint.revert(instance);
+            return Err(error)
+        }
+        let exec_context = instance
+            .exec_context
+            .pop()
+            .expect("the execution context has been pushed above");
+        checkpoint.commit(instance);
+        let output = exec_context.output.unwrap_or_default();
+        instance.tracer.exit(true, || output.clone());
+        Ok(output)
+    })
+}
+
+/// Returns the error for an execution that failed with the custom error code.
+fn fail_state(kind: CallKind, code: u8) -> EnvError {
+    match kind {
+        CallKind::Instantiate => EnvError::ContractInstantiationFailState(code),
+        CallKind::Call => EnvError::ContractCallFailState(code),
+    }
+}
+
+/// Invokes a contract message of a registered contract.
+pub fn invoke_contract<T>(params: &CallParams<T, ()>) -> Result<()>
+where
+    T: EnvTypes,
+{
+    let checkpoint = <EnvInstance as OnInstance>::on_instance(Checkpoint::open);
+    execute::<T>(
+        checkpoint,
+        params.callee(),
+        params.gas_limit(),
+        *params.transferred_value(),
+        params.input_data(),
+        CallKind::Call,
+    )
+    .map(|_| ())
+}
+
+/// Evaluates a contract message of a registered contract and returns its result.
+pub fn eval_contract<T, R>(params: &CallParams<T, ReturnType<R>>) -> Result<R>
+where
+    T: EnvTypes,
+    R: scale::Decode,
+{
+    let checkpoint = <EnvInstance as OnInstance>::on_instance(Checkpoint::open);
+    let output = execute::<T>(
+        checkpoint,
+        params.callee(),
+        params.gas_limit(),
+        *params.transferred_value(),
+        params.input_data(),
+        CallKind::Call,
+    )?;
+    scale::Decode::decode(&mut &output[..]).map_err(Into::into)
+}
+
+/// Instantiates a registered contract.
+///
+/// # Note
+///
+/// The address of the new contract is computed from the executed contract,
+/// the code hash and the encoded input data using
+/// [`compute_contract_address`](crate::env::compute_contract_address).
+pub fn instantiate_contract<T, C>(
+    params: &InstantiateParams<T, C>,
+) -> Result<T::AccountId>
+where
+    T: EnvTypes,
+{
+    let (checkpoint, account_id) =
+        <EnvInstance as OnInstance>::on_instance(|instance| -> Result<_> {
+            let deployer = instance.callee_id::<T>()?;
+            let account_id = crate::env::compute_contract_address::<T>(
+                &deployer,
+                params.code_hash(),
+                &scale::Encode::encode(params.input_data()),
+            )?;
+            if instance.accounts.get_account::<T>(&account_id).is_some() {
+                return Err(AccountError::account_already_exists::<T>(&account_id).into())
+            }
+            let checkpoint = Checkpoint::open(instance);
+            instance.accounts.add_contract_account::<T>(
+                account_id.clone(),
+                T::Balance::from(0),
+                T::Balance::from(0),
+                *params.code_hash(),
+            );
+            Ok((checkpoint, account_id))
+        })?;
+    execute::<T>(
+        checkpoint,
+        &account_id,
+        params.gas_limit(),
+        *params.endowment(),
+        params.input_data(),
+        CallKind::Instantiate,
+    )?;
+    Ok(account_id)
+}
@@ -19,6 +19,7 @@ use super::{
     },
     OffAccountId,
     OffBalance,
+    OffHash,
 };
 use crate::env::{
     EnvError,
@@ -37,6 +38,8 @@ pub enum AccountError {
     UnexpectedUserAccount,
     #[from(ignore)]
     NoAccountForId(OffAccountId),
+    #[from(ignore)]
+    AccountAlreadyExists(OffAccountId),
 }
 
 impl From<AccountError> for EnvError {
@@ -53,6 +56,14 @@ impl AccountError {
     {
         Self::NoAccountForId(OffAccountId::new(account_id))
     }
+
+    /// Creates a new error to indicate an already existing account.
+    pub fn account_already_exists<T>(account_id: &T::AccountId) -> Self
+    where
+        T: EnvTypes,
+    {
+        Self::AccountAlreadyExists(OffAccountId::new(account_id))
+    }
 }
 
 impl From<scale::Error> for AccountError {
@@ -65,9 +76,14 @@ impl From<scale::Error> for AccountError {
 pub type Result<T> = core::result::Result<T, AccountError>;
 
 /// The database that stores all accounts.
+#[derive(Clone)]
 pub struct AccountsDb {
     /// The mapping from account ID to an actual account.
     accounts: BTreeMap<OffAccountId, Account>,
+    /// The previous states of the accounts modified since each open checkpoint.
+    ///
+    /// An account that did not exist before is recorded as `None`.
+    journal: Vec<BTreeMap<OffAccountId, Option<Account>>>,
 }
 
 impl AccountsDb {
@@ -75,6 +91,55 @@ impl AccountsDb {
     pub fn new() -> Self {
         Self {
             accounts: BTreeMap::new(),
+            journal: Vec::new(),
+        }
+    }
+
+    /// Opens a new checkpoint that the accounts can be reverted to.
+    ///
+    /// # Note
+    ///
+    /// Only the accounts that are modified after the checkpoint are copied.
+    /// Checkpoints nest and must be closed by either
+    /// [`commit`](#method.commit) or [`revert`](#method.revert).
+    pub fn checkpoint(&mut self) {
+        self.journal.push(BTreeMap::new());
+    }
+
+    /// Closes the latest checkpoint keeping all modifications since.
+    pub fn commit(&mut self) {
+        let modified = self
+            .journal
+            .pop()
+            .expect("encountered commit without checkpoint");
+        if let Some(parent) = self.journal.last_mut() {
+            for (at, previous) in modified {
+                parent.entry(at).or_insert(previous);
+            }
+        }
+    }
+
+    /// Closes the latest checkpoint reverting all modifications since.
+    pub fn revert(&mut self) {
+        let modified = self
+            .journal
+            .pop()
+            .expect("encountered revert without checkpoint");
+        for (at, previous) in modified {
+            match previous {
+                Some(account) => self.accounts.insert(at, account),
+                None => self.accounts.remove(&at),
+            };
+        }
+    }
+
+    /// Records the state of the account before its first modification
+    /// since the latest checkpoint.
+    fn touch(&mut self, at: &OffAccountId) {
+        if let Some(modified) = self.journal.last_mut() {
+            if !modified.contains_key(at) {
+                modified.insert(at.clone(), self.accounts.get(at).cloned());
+            }
         }
     }
 
@@ -110,7 +175,7 @@ impl AccountsDb {
     where
         T: EnvTypes,
     {
-        self.accounts.get_mut(&OffAccountId::new(at))
+        self.get_account_off_mut(&OffAccountId::new(at))
     }
 
     /// Returns the account for the given off-account ID if any.
@@ -120,9 +185,20 @@ impl AccountsDb {
 
     /// Returns the account for the given off-account ID if any.
     pub fn get_account_off_mut(&mut self, at: &OffAccountId) -> Option<&mut Account> {
+        self.touch(at);
         self.accounts.get_mut(at)
     }
 
+    /// Removes the account for the given account ID and returns it if any.
+    pub fn remove_account<T>(&mut self, at: &T::AccountId) -> Option<Account>
+    where
+        T: EnvTypes,
+    {
+        let at = OffAccountId::new(at);
+        self.touch(&at);
+        self.accounts.remove(&at)
+    }
+
     /// Adds the given user account with the initial balance.
     pub fn add_user_account<T>(
         &mut self,
@@ -131,10 +207,13 @@ impl AccountsDb {
     ) where
         T: EnvTypes,
     {
+        let at = OffAccountId::new(&account_id);
+        self.touch(&at);
         self.accounts.insert(
-            OffAccountId::new(&account_id),
+            at,
             Account {
                 balance: OffBalance::new(&initial_balance),
+                reserved_balance: OffBalance::new(&T::Balance::from(0)),
                 kind: AccountKind::User,
             },
         );
@@ -146,23 +225,33 @@ impl AccountsDb {
         account_id: T::AccountId,
         initial_balance: T::Balance,
         rent_allowance: T::Balance,
+        code_hash: T::Hash,
     ) where
         T: EnvTypes,
     {
+        let at = OffAccountId::new(&account_id);
+        self.touch(&at);
         self.accounts.insert(
-            OffAccountId::new(&account_id),
+            at,
             Account {
                 balance: OffBalance::new(&initial_balance),
-                kind: AccountKind::Contract(ContractAccount::new::<T>(rent_allowance)),
+                reserved_balance: OffBalance::new(&T::Balance::from(0)),
+                kind: AccountKind::Contract(ContractAccount::new::<T>(
+                    rent_allowance,
+                    code_hash,
+                )),
             },
         );
     }
 }
 
 /// An account within the chain.
+#[derive(Clone)]
 pub struct Account {
-    /// The balance of the account.
+    /// The free balance of the account.
     balance: OffBalance,
+    /// The reserved balance of the account.
+    reserved_balance: OffBalance,
     /// The kind of the account.
     kind: AccountKind,
 }
@@ -184,6 +273,35 @@ impl Account {
         self.balance.assign(&new_balance).map_err(Into::into)
     }
 
+    /// Returns the reserved balance of the account.
+    pub fn reserved_balance<T>(&self) -> Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        self.reserved_balance.decode().map_err(Into::into)
+    }
+
+    /// Sets the reserved balance of the account.
+    pub fn set_reserved_balance<T>(
+        &mut self,
+        new_reserved_balance: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.reserved_balance
+            .assign(&new_reserved_balance)
+            .map_err(Into::into)
+    }
+
+    /// Returns `true` if the account is a contract account.
+    pub fn is_contract(&self) -> bool {
+        match self.kind {
+            AccountKind::User => false,
+            AccountKind::Contract(_) => true,
+        }
+    }
+
     /// Returns the contract account or an error if it is a user account.
     fn contract_or_err(&self) -> Result<&ContractAccount> {
         match &self.kind {
@@ -226,13 +344,42 @@ impl Account {
         })
     }
 
-    /// Sets the contract storage of key to the new value.
-    pub fn set_storage<T>(&mut self, at: Key, new_value: &T) -> Result<()>
+    /// Returns the code hash of the account or `None` if it is a user account.
+    pub fn code_hash<T>(&self) -> Result<Option<T::Hash>>
+    where
+        T: EnvTypes,
+    {
+        match &self.kind {
+            AccountKind::User => Ok(None),
+            AccountKind::Contract(contract) => {
+                contract.code_hash.decode().map(Some).map_err(Into::into)
+            }
+        }
+    }
+
+    /// Sets the code hash of the contract account or returns an error.
+    pub fn set_code_hash<T>(&mut self, new_code_hash: T::Hash) -> Result<()>
     where
-        T: scale::Encode,
+        T: EnvTypes,
     {
+        self.contract_or_err_mut().and_then(|contract| {
+            contract
+                .code_hash
+                .assign(&new_code_hash)
+                .map_err(Into::into)
+        })
+    }
+
+    /// Sets the contract storage of key to the new encoded value.
+    pub fn set_storage_raw(&mut self, at: Key, new_value: Vec<u8>) -> Result<()> {
         self.contract_or_err_mut()
-            .map(|contract| contract.storage.set_storage::<T>(at, new_value))
+            .map(|contract| contract.storage.set_storage_raw(at, new_value))
+    }
+
+    /// Returns the encoded value stored in the contract storage at the given key.
+    pub fn get_storage_raw(&self, at: Key) -> Result<Option<Vec<u8>>> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.get_storage_raw(at))
     }
 
     /// Clears the contract storage at key.
@@ -241,46 +388,54 @@ impl Account {
             .map(|contract| contract.storage.clear_storage(at))
     }
 
-    /// Returns the value stored in the contract storage at the given key.
-    pub fn get_storage<T>(&self, at: Key) -> Option<Result<T>>
-    where
-        T: scale::Decode,
-    {
-        self.contract_or_err()
-            .and_then(|contract| contract.storage.get_storage::<T>(at))
-            .transpose()
-    }
-
     /// Returns the total number of reads and write from and to the contract's storage.
     pub fn get_storage_rw(&self) -> Result<(usize, usize)> {
         self.contract_or_err().map(|contract| contract.get_rw())
     }
+
+    /// Returns the counts of all operations on the contract's storage so far.
+    pub fn get_storage_ops(&self) -> Result<StorageOps> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.get_ops())
+    }
+
+    /// Returns the number of occupied cells in the contract's storage.
+    pub fn count_used_storage_cells(&self) -> Result<usize> {
+        self.contract_or_err()
+            .map(|contract| contract.storage.count_used_cells())
+    }
 }
 
 /// The kind of the account.
 ///
 /// Can be either a user account or a (more complicated) contract account.
+#[derive(Clone)]
 pub enum AccountKind {
     User,
     Contract(ContractAccount),
 }
 
 /// Extraneous fields for contract accounts.
+#[derive(Clone)]
 pub struct ContractAccount {
     /// The contract's rent allowance.
     rent_allowance: OffBalance,
+    /// The hash of the contract's code.
+    code_hash: OffHash,
     /// The contract storage.
     pub storage: ContractStorage,
 }
 
 impl ContractAccount {
-    /// Creates a new contract account with the given initial rent allowance.
-    pub fn new<T>(rent_allowance: T::Balance) -> Self
+    /// Creates a new contract account with the given initial rent allowance
+    /// and code hash.
+    pub fn new<T>(rent_allowance: T::Balance, code_hash: T::Hash) -> Self
     where
         T: EnvTypes,
     {
         Self {
             rent_allowance: OffBalance::new(&rent_allowance),
+            code_hash: OffHash::new(&code_hash),
             storage: ContractStorage::new(),
         }
     }
@@ -291,7 +446,35 @@ impl ContractAccount {
     }
 }
 
+/// The counts of operations on a contract storage.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct StorageOps {
+    /// The number of reads from the storage.
+    pub reads: usize,
+    /// The number of writes to the storage.
+    ///
+    /// # Note
+    ///
+    /// This includes the clears of storage cells.
+    pub writes: usize,
+    /// The number of clears of storage cells.
+    pub clears: usize,
+}
+
+impl core::ops::Sub for StorageOps {
+    type Output = Self;
+
+    fn sub(self, rhs: Self) -> Self::Output {
+        Self {
+            reads: self.reads - rhs.reads,
+            writes: self.writes - rhs.writes,
+            clears: self.clears - rhs.clears,
+        }
+    }
+}
+
 /// The storage of a contract instance.
+#[derive(Clone)]
 pub struct ContractStorage {
     /// The entries within the contract storage.
     entries: BTreeMap<Key, Vec<u8>>,
@@ -299,6 +482,8 @@ pub struct ContractStorage {
     count_reads: Cell<usize>,
     /// The total number of writes to the storage.
     count_writes: usize,
+    /// The total number of clears of the storage.
+    count_clears: usize,
 }
 
 impl ContractStorage {
@@ -308,6 +493,7 @@ impl ContractStorage {
             entries: BTreeMap::new(),
             count_reads: Cell::new(0),
             count_writes: 0,
+            count_clears: 0,
         }
     }
 
@@ -316,31 +502,36 @@ impl ContractStorage {
         (self.count_reads.get(), self.count_writes)
     }
 
-    /// Returns the decoded storage at the key if any.
-    pub fn get_storage<T>(&self, at: Key) -> Result<Option<T>>
-    where
-        T: scale::Decode,
-    {
+    /// Returns the counts of all operations on the contract storage.
+    pub fn get_ops(&self) -> StorageOps {
+        StorageOps {
+            reads: self.count_reads.get(),
+            writes: self.count_writes,
+            clears: self.count_clears,
+        }
+    }
+
+    /// Returns the number of occupied cells in the contract storage.
+    pub fn count_used_cells(&self) -> usize {
+        self.entries.len()
+    }
+
+    /// Returns the encoded storage at the key if any.
+    pub fn get_storage_raw(&self, at: Key) -> Option<Vec<u8>> {
         self.count_reads.set(self.count_reads.get() + 1);
-        self.entries
-            .get(&at)
-            .map(|encoded| T::decode(&mut &encoded[..]))
-            .transpose()
-            .map_err(Into::into)
+        self.entries.get(&at).cloned()
     }
 
-    /// Writes the encoded value into the contract storage at the given key.
-    pub fn set_storage<T>(&mut self, at: Key, new_value: &T)
-    where
-        T: scale::Encode,
-    {
+    /// Writes the already encoded value into the contract storage at the given key.
+    pub fn set_storage_raw(&mut self, at: Key, new_value: Vec<u8>) {
         self.count_writes += 1;
-        self.entries.insert(at, new_value.encode());
+        self.entries.insert(at, new_value);
     }
 
     /// Removes the value from storage entries at the given key.
     pub fn clear_storage(&mut self, at: Key) {
         self.count_writes += 1;
+        self.count_clears += 1;
         self.entries.remove(&at);
     }
 }
@@ -24,6 +24,7 @@ use super::{
 use crate::env::EnvTypes;
 
 /// An emulated block in the chain.
+#[derive(Clone)]
 pub struct Block {
     /// The current block number.
     number: OffBlockNumber,
@@ -71,6 +72,22 @@ impl Block {
         self.timestamp.decode().map_err(Into::into)
     }
 
+    /// Sets the number of this block to the given number.
+    pub fn set_number<T>(&mut self, new_number: T::BlockNumber) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.number.assign(&new_number).map_err(Into::into)
+    }
+
+    /// Sets the timestamp of this block to the given timestamp.
+    pub fn set_timestamp<T>(&mut self, new_timestamp: T::Timestamp) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.timestamp.assign(&new_timestamp).map_err(Into::into)
+    }
+
     /// Sets the entropy of this block to the given entropy.
     ///
     /// # Note
@@ -20,6 +20,7 @@ use super::{
 use crate::env::EnvTypes;
 
 /// The chain specification.
+#[derive(Clone)]
 pub struct ChainSpec {
     /// The current gas price.
     gas_price: OffBalance,
@@ -29,8 +30,13 @@ pub struct ChainSpec {
     tombstone_deposit: OffBalance,
     /// The targeted block time.
     block_time: OffTimestamp,
+    /// The maximum size of an encoded contract storage cell if any.
+    max_cell_size: Option<usize>,
 }
 
+/// The maximum size of an encoded contract storage cell on-chain.
+const DEFAULT_MAX_CELL_SIZE: usize = 16 * 1024;
+
 impl ChainSpec {
     /// Creates a new uninitialized chain specification.
     pub fn uninitialized() -> Self {
@@ -39,6 +45,7 @@ impl ChainSpec {
             minimum_balance: OffBalance::uninitialized(),
             tombstone_deposit: OffBalance::uninitialized(),
             block_time: OffTimestamp::uninitialized(),
+            max_cell_size: None,
         }
     }
 
@@ -46,7 +53,6 @@ impl ChainSpec {
     pub fn initialize_as_default<T>(&mut self) -> crate::env::Result<()>
     where
         T: EnvTypes,
-        <T as EnvTypes>::AccountId: From<[u8; 32]>,
     {
         self.gas_price
             .try_initialize::<T::Balance>(&T::Balance::from(100))?;
@@ -56,6 +62,7 @@ impl ChainSpec {
             .try_initialize::<T::Balance>(&T::Balance::from(16))?;
         self.block_time
             .try_initialize::<T::Timestamp>(&T::Timestamp::from(5))?;
+        self.max_cell_size = Some(DEFAULT_MAX_CELL_SIZE);
         Ok(())
     }
 
@@ -90,4 +97,58 @@ impl ChainSpec {
     {
         self.block_time.decode().map_err(Into::into)
     }
+
+    /// Sets the gas price for the chain.
+    pub fn set_gas_price<T>(&mut self, new_gas_price: T::Balance) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.gas_price.assign(&new_gas_price).map_err(Into::into)
+    }
+
+    /// Sets the minimum balance for an account on the chain.
+    pub fn set_minimum_balance<T>(
+        &mut self,
+        new_minimum_balance: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.minimum_balance
+            .assign(&new_minimum_balance)
+            .map_err(Into::into)
+    }
+
+    /// Sets the tombstone deposit for the chain.
+    pub fn set_tombstone_deposit<T>(
+        &mut self,
+        new_tombstone_deposit: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.tombstone_deposit
+            .assign(&new_tombstone_deposit)
+            .map_err(Into::into)
+    }
+
+    /// Sets the targeted block time for the chain.
+    pub fn set_block_time<T>(&mut self, new_block_time: T::Timestamp) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        self.block_time.assign(&new_block_time).map_err(Into::into)
+    }
+
+    /// Returns the maximum size of an encoded contract storage cell if any.
+    pub fn max_cell_size(&self) -> Option<usize> {
+        self.max_cell_size
+    }
+
+    /// Sets the maximum size of an encoded contract storage cell.
+    ///
+    /// `None` allows contract storage cells of any size.
+    pub fn set_max_cell_size(&mut self, new_max_cell_size: Option<usize>) {
+        self.max_cell_size = new_max_cell_size;
+    }
 }
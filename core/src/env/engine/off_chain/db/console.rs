@@ -15,9 +15,12 @@
 use ink_prelude::string::String;
 
 /// A debug console used to print console contents and store them.
+#[derive(Clone)]
 pub struct Console {
     /// The buffer to store the already pasted contents.
     past_prints: Vec<String>,
+    /// The buffer to store the debug messages.
+    debug_messages: Vec<String>,
 }
 
 impl Console {
@@ -25,6 +28,7 @@ impl Console {
     pub fn new() -> Self {
         Self {
             past_prints: Vec::new(),
+            debug_messages: Vec::new(),
         }
     }
 
@@ -36,11 +40,21 @@ impl Console {
 
     /// Returns an iterator over the past console prints.
     pub fn past_prints(&self) -> PastPrints {
-        PastPrints::new(self)
+        PastPrints::new(&self.past_prints)
+    }
+
+    /// Stores the debug message without printing it.
+    pub fn debug_message(&mut self, message: &str) {
+        self.debug_messages.push(message.to_string());
+    }
+
+    /// Returns an iterator over the stored debug messages.
+    pub fn debug_messages(&self) -> PastPrints<'_> {
+        PastPrints::new(&self.debug_messages)
     }
 }
 
-/// Iterator over the past prints to the console.
+/// Iterator over the past prints or debug messages of the console.
 pub struct PastPrints<'a> {
     /// Iterator over the past printlns.
     iter: core::slice::Iter<'a, String>,
@@ -48,9 +62,9 @@ pub struct PastPrints<'a> {
 
 impl<'a> PastPrints<'a> {
     /// Creates a new iterator over the past console prints.
-    fn new(console: &'a Console) -> Self {
+    fn new(prints: &'a [String]) -> Self {
         Self {
-            iter: console.past_prints.iter(),
+            iter: prints.iter(),
         }
     }
 }
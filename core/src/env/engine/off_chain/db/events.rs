@@ -15,11 +15,12 @@
 use super::super::OffHash;
 use crate::env::{
     EnvTypes,
+    Result,
     Topics,
 };
 
 /// Record for an emitted event.
-#[derive(Debug, Clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
 pub struct EmittedEvent {
     /// Recorded topics of the emitted event.
     pub topics: Vec<OffHash>,
@@ -43,9 +44,37 @@ impl EmittedEvent {
             data: emitted_event.encode(),
         }
     }
+
+    /// Returns the decoded topics of the emitted event.
+    ///
+    /// # Errors
+    ///
+    /// If the recorded topics do not match the given hash type.
+    pub fn decoded_topics<T>(&self) -> Result<Vec<T::Hash>>
+    where
+        T: EnvTypes,
+    {
+        self.topics
+            .iter()
+            .map(|topic| topic.decode::<T::Hash>().map_err(Into::into))
+            .collect()
+    }
+
+    /// Returns the decoded data of the emitted event.
+    ///
+    /// # Errors
+    ///
+    /// If the recorded data cannot be decoded as `E`.
+    pub fn decoded_data<E>(&self) -> Result<E>
+    where
+        E: scale::Decode,
+    {
+        <E as scale::Decode>::decode(&mut &self.data[..]).map_err(Into::into)
+    }
 }
 
 /// Records all emitted events for later inspection.
+#[derive(Clone)]
 pub struct EmittedEventsRecorder {
     emitted_events: Vec<EmittedEvent>,
 }
@@ -59,12 +88,18 @@ impl EmittedEventsRecorder {
     }
 
     /// Records a new emitted event.
-    pub fn record<T, E>(&mut self, new_event: E)
-    where
-        T: EnvTypes,
-        E: Topics<T> + scale::Encode,
-    {
-        self.emitted_events.push(EmittedEvent::new(new_event));
+    pub fn record(&mut self, new_event: EmittedEvent) {
+        self.emitted_events.push(new_event);
+    }
+
+    /// Returns the number of recorded events.
+    pub fn len(&self) -> usize {
+        self.emitted_events.len()
+    }
+
+    /// Drops all events recorded after the first `len` events.
+    pub fn truncate(&mut self, len: usize) {
+        self.emitted_events.truncate(len)
     }
 
     /// Returns an iterator over the emitted events in their emission order.
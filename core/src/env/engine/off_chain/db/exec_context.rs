@@ -25,10 +25,12 @@ use crate::env::{
     EnvTypes,
 };
 use ink_prelude::vec::Vec;
+use ink_primitives::Key;
 
 pub type Bytes = Vec<u8>;
 
 /// The context of a contract execution.
+#[derive(Clone)]
 pub struct ExecContext {
     /// The caller of the contract execution.
     ///
@@ -48,6 +50,8 @@ pub struct ExecContext {
     pub call_data: CallData,
     /// The output of the contract execution.
     pub output: Option<Bytes>,
+    /// The storage cell cleared if the contract returns without reverting.
+    pub clear_on_return: Option<Key>,
 }
 
 impl ExecContext {
@@ -228,6 +232,7 @@ where
             gas: TypedEncoded::new(&gas),
             call_data: self.call_data.unwrap(),
             output: None,
+            clear_on_return: None,
         }
     }
 }
@@ -27,6 +27,7 @@ pub use self::{
         AccountsDb,
         ContractAccount,
         ContractStorage,
+        StorageOps,
     },
     block::Block,
     chain_spec::ChainSpec,
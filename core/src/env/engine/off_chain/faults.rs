@@ -0,0 +1,90 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::HostFn;
+
+/// A fault injected into a call of a host function.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Fault {
+    /// The host function returns its error.
+    ///
+    /// # Note
+    ///
+    /// Host functions that cannot return an error, e.g. storage writes,
+    /// trap instead. Calls and instantiations of contracts fail with
+    /// [`OffChainError::InjectedFault`](super::OffChainError::InjectedFault)
+    /// so that they can be told apart from trapped contracts.
+    Fail,
+    /// The host function traps which panics the executed contract.
+    Trap,
+}
+
+/// A fault that is planned for a future call of a host function.
+struct PlannedFault {
+    /// The host function to inject the fault into.
+    host_fn: HostFn,
+    /// The number of calls of the host function until the fault is injected.
+    remaining: u32,
+    /// The fault to inject.
+    fault: Fault,
+}
+
+/// Injects planned faults into the calls of host functions.
+pub struct FaultInjector {
+    /// The faults that have not yet been injected.
+    planned: Vec<PlannedFault>,
+}
+
+impl FaultInjector {
+    /// Creates a new fault injector without any planned faults.
+    pub fn new() -> Self {
+        Self {
+            planned: Vec::new(),
+        }
+    }
+
+    /// Plans the fault for the `nth` call of the host function from now on.
+    ///
+    /// # Panics
+    ///
+    /// If `nth` is zero.
+    pub fn plan(&mut self, host_fn: HostFn, nth: u32, fault: Fault) {
+        assert!(nth > 0, "the first call of a host function is the 1st call");
+        self.planned.push(PlannedFault {
+            host_fn,
+            remaining: nth,
+            fault,
+        });
+    }
+
+    /// Removes all planned faults.
+    pub fn clear(&mut self) {
+        self.planned.clear();
+    }
+
+    /// Counts a call of the host function.
+    ///
+    /// Returns the fault to inject into this call if any.
+    pub fn hit(&mut self, host_fn: HostFn) -> Option<Fault> {
+        let mut injected = None;
+        for planned in self.planned.iter_mut().filter(|p| p.host_fn == host_fn) {
+            planned.remaining -= 1;
+            if planned.remaining == 0 && injected.is_none() {
+                injected = Some(planned.fault);
+            }
+        }
+        self.planned.retain(|planned| planned.remaining != 0);
+        injected
+    }
+}
@@ -0,0 +1,188 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use std::collections::BTreeMap;
+
+/// The host functions charged by the off-chain gas meter.
+///
+/// Mirrors the host functions of the on-chain environment.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum HostFn {
+    Instantiate,
+    Call,
+    Transfer,
+    DepositEvent,
+    SetStorage,
+    ClearStorage,
+    GetStorage,
+    SetStorageBatch,
+    GetStorageBatch,
+    GetRuntimeStorage,
+    RestoreTo,
+    Terminate,
+    Input,
+    Return,
+    DispatchCall,
+    CallChainExtension,
+    Caller,
+    BlockNumber,
+    Address,
+    Balance,
+    OwnCodeHash,
+    CodeHash,
+    ReservedBalance,
+    GasPrice,
+    GasLeft,
+    ValueTransferred,
+    Now,
+    RentAllowance,
+    MinimumBalance,
+    TombstoneDeposit,
+    WeightToFee,
+    SetRentAllowance,
+    RandomSeed,
+    CallerIsOrigin,
+    CallDepth,
+    Println,
+    DebugMessage,
+    Hash,
+    EcdsaRecover,
+}
+
+/// The weights charged by the off-chain gas meter.
+///
+/// # Note
+///
+/// The default weights are rough approximations of the on-chain costs.
+/// They are meant to compare different implementations with each other
+/// and are no replacement for on-chain benchmarks.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct GasWeights {
+    /// The weights of the host functions that differ from `base`.
+    host_fns: BTreeMap<HostFn, u64>,
+    /// The weight charged for every other host function call.
+    base: u64,
+    /// The weight charged for every byte written to the contract storage.
+    per_byte_stored: u64,
+    /// The weight charged for every byte read from the contract storage.
+    per_byte_loaded: u64,
+}
+
+impl Default for GasWeights {
+    fn default() -> Self {
+        Self::uniform(1_000)
+            .host_fn(HostFn::Instantiate, 100_000)
+            .host_fn(HostFn::Call, 50_000)
+            .host_fn(HostFn::Transfer, 20_000)
+            .host_fn(HostFn::DispatchCall, 20_000)
+            .host_fn(HostFn::SetStorage, 10_000)
+            .host_fn(HostFn::ClearStorage, 10_000)
+            .host_fn(HostFn::GetStorage, 5_000)
+            .host_fn(HostFn::SetStorageBatch, 10_000)
+            .host_fn(HostFn::GetStorageBatch, 5_000)
+            .host_fn(HostFn::GetRuntimeStorage, 5_000)
+            .host_fn(HostFn::DepositEvent, 5_000)
+            .host_fn(HostFn::EcdsaRecover, 50_000)
+            .per_byte_stored(100)
+            .per_byte_loaded(10)
+    }
+}
+
+impl GasWeights {
+    /// Returns weights that charge `base` for every host function call
+    /// and nothing for the bytes stored or loaded.
+    pub fn uniform(base: u64) -> Self {
+        Self {
+            host_fns: BTreeMap::new(),
+            base,
+            per_byte_stored: 0,
+            per_byte_loaded: 0,
+        }
+    }
+
+    /// Sets the weight charged for every call of the host function.
+    pub fn host_fn(mut self, host_fn: HostFn, weight: u64) -> Self {
+        self.host_fns.insert(host_fn, weight);
+        self
+    }
+
+    /// Sets the weight charged for every byte written to the contract storage.
+    pub fn per_byte_stored(mut self, weight: u64) -> Self {
+        self.per_byte_stored = weight;
+        self
+    }
+
+    /// Sets the weight charged for every byte read from the contract storage.
+    pub fn per_byte_loaded(mut self, weight: u64) -> Self {
+        self.per_byte_loaded = weight;
+        self
+    }
+
+    /// Returns the weight charged for every call of the host function.
+    pub fn get(&self, host_fn: HostFn) -> u64 {
+        self.host_fns.get(&host_fn).copied().unwrap_or(self.base)
+    }
+}
+
+/// Accounts the gas consumed by the host function calls.
+pub struct GasMeter {
+    /// The weights charged for the host function calls.
+    weights: GasWeights,
+    /// The gas consumed so far.
+    consumed: u64,
+}
+
+impl GasMeter {
+    /// Creates a new gas meter using the default weights.
+    pub fn new() -> Self {
+        Self {
+            weights: GasWeights::default(),
+            consumed: 0,
+        }
+    }
+
+    /// Replaces the weights charged for future host function calls.
+    pub fn set_weights(&mut self, weights: GasWeights) {
+        self.weights = weights;
+    }
+
+    /// Returns the gas consumed so far.
+    pub fn consumed(&self) -> u64 {
+        self.consumed
+    }
+
+    /// Resets the consumed gas to zero.
+    pub fn reset(&mut self) {
+        self.consumed = 0;
+    }
+
+    /// Charges a call of the host function.
+    pub fn charge(&mut self, host_fn: HostFn) {
+        self.add(self.weights.get(host_fn));
+    }
+
+    /// Charges the bytes written to the contract storage.
+    pub fn charge_stored(&mut self, len: usize) {
+        self.add(self.weights.per_byte_stored.saturating_mul(len as u64));
+    }
+
+    /// Charges the bytes read from the contract storage.
+    pub fn charge_loaded(&mut self, len: usize) {
+        self.add(self.weights.per_byte_loaded.saturating_mul(len as u64));
+    }
+
+    fn add(&mut self, gas: u64) {
+        self.consumed = self.consumed.saturating_add(gas);
+    }
+}
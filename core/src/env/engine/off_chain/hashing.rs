@@ -24,6 +24,11 @@ pub fn blake2_128(input: &[u8], output: &mut [u8; 16]) {
     output.copy_from_slice(blake2_rfc::blake2b::blake2b(16, &[], input).as_bytes());
 }
 
+/// Conduct the BLAKE2s 256-bit hash and place the result into `output`.
+pub fn blake2s_256(input: &[u8], output: &mut [u8; 32]) {
+    output.copy_from_slice(blake2_rfc::blake2s::blake2s(32, &[], input).as_bytes());
+}
+
 /// Conduct the KECCAK 256-bit hash and place the result into `output`.
 pub fn keccak_256(input: &[u8], output: &mut [u8; 32]) {
     use ::tiny_keccak::{
@@ -15,25 +15,70 @@
 use super::{
     hashing,
     Account,
+    AccountError,
+    ContractReturned,
+    ContractTerminated,
+    EmittedEvent,
     EnvInstance,
+    HostFn,
+    OffAccountId,
+    OffBalance,
+    OffChainError,
+    TraceOp,
 };
 use crate::env::{
+    backend::{
+        EnvBackend,
+        TypedEnvBackend,
+    },
     call::{
         CallData,
         CallParams,
         InstantiateParams,
         ReturnType,
     },
-    Env,
+    hash::CryptoHash,
+    EcdsaPublicKey,
     EnvError,
     EnvTypes,
     Result,
+    ReturnFlags,
+    StorageBatch,
     Topics,
-    TypedEnv,
+    BUFFER_CAPACITY,
 };
 use ink_primitives::Key;
 
 impl EnvInstance {
+    /// Returns the account ID of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// Unlike `TypedEnvBackend::account_id` this is not charged by the gas meter.
+    pub(super) fn callee_id<T>(&self) -> Result<T::AccountId>
+    where
+        T: EnvTypes,
+    {
+        self.exec_context()
+            .expect("uninitialized execution context")
+            .callee::<T>()
+            .map_err(|_| scale::Error::from("could not decode callee"))
+            .map_err(Into::into)
+    }
+
+    /// Traps if the encoded value exceeds the maximum contract storage cell size.
+    fn ensure_cell_size(&self, encoded_len: usize) {
+        if let Some(max_cell_size) = self.chain_spec.max_cell_size() {
+            if encoded_len > max_cell_size {
+                panic!(
+                    "encoded value of {} bytes exceeds the \
+                     maximum storage cell size of {} bytes",
+                    encoded_len, max_cell_size,
+                )
+            }
+        }
+    }
+
     /// Returns the callee account.
     fn callee_account(&self) -> &Account {
         let callee = self
@@ -57,15 +102,96 @@ impl EnvInstance {
             .get_account_off_mut(&callee)
             .expect("callee account does not exist")
     }
+
+    /// Transfers `value` from the `source` account to the `destination` account.
+    ///
+    /// # Note
+    ///
+    /// The destination account is created if it does not exist, yet.
+    ///
+    /// # Errors
+    ///
+    /// - If the source account does not exist.
+    /// - If the source account has insufficient free balance.
+    pub(super) fn transfer_between<T>(
+        &mut self,
+        source: &T::AccountId,
+        destination: &T::AccountId,
+        value: T::Balance,
+    ) -> Result<()>
+    where
+        T: EnvTypes,
+    {
+        let src_value = self
+            .accounts
+            .get_account::<T>(source)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(source))
+            .map_err(OffChainError::from)?
+            .balance::<T>()?;
+        if src_value < value {
+            return Err(EnvError::TransferCallFailed)
+        }
+        let dst_value = self
+            .accounts
+            .get_or_create_account::<T>(destination)
+            .balance::<T>()?;
+        self.accounts
+            .get_account_mut::<T>(source)
+            .expect("the source account has been checked above")
+            .set_balance::<T>(src_value - value)?;
+        self.accounts
+            .get_account_mut::<T>(destination)
+            .expect("the account must exist already or has just been created")
+            .set_balance::<T>(dst_value + value)?;
+        Ok(())
+    }
+}
+
+/// Converts the `u64` amount of gas into the balance type.
+///
+/// # Note
+///
+/// Saturates at the maximum balance if the gas does not fit into it.
+pub(super) fn gas_to_balance<T>(gas: u64) -> T::Balance
+where
+    T: EnvTypes,
+{
+    use num_traits::Bounded as _;
+    let max = T::Balance::max_value();
+    let saturating_mul = |lhs: T::Balance, rhs: T::Balance| {
+        if lhs > max / rhs {
+            return max
+        }
+        lhs * rhs
+    };
+    // Balances can only be converted from `u32` so we have to
+    // convert the higher and lower half of the gas separately.
+    let shift = T::Balance::from(1 << 16);
+    let high = saturating_mul(
+        saturating_mul(T::Balance::from((gas >> 32) as u32), shift),
+        shift,
+    );
+    let low = T::Balance::from(gas as u32);
+    if high > max - low {
+        return max
+    }
+    high + low
 }
 
-impl Env for EnvInstance {
+impl EnvBackend for EnvInstance {
     fn set_contract_storage<V>(&mut self, key: Key, value: &V)
     where
         V: scale::Encode,
     {
+        let encoded = value.encode();
+        self.ensure_cell_size(encoded.len());
+        self.gas_meter.charge(HostFn::SetStorage);
+        self.trap_on_fault(HostFn::SetStorage);
+        self.gas_meter.charge_stored(encoded.len());
+        let len = encoded.len();
+        self.tracer.record(|| TraceOp::StorageWrite { key, len });
         self.callee_account_mut()
-            .set_storage(key, value)
+            .set_storage_raw(key, encoded)
             .expect("callee account is not a smart contract");
     }
 
@@ -73,25 +199,110 @@ impl Env for EnvInstance {
     where
         R: scale::Decode,
     {
-        self.callee_account()
-            .get_storage::<R>(key)
-            .map(|result| result.map_err(Into::into))
+        self.gas_meter.charge(HostFn::GetStorage);
+        let encoded = match self.callee_account().get_storage_raw(key) {
+            Ok(encoded) => encoded,
+            Err(error) => return Some(Err(error.into())),
+        };
+        let len = encoded.as_ref().map(Vec::len);
+        self.tracer.record(|| TraceOp::StorageRead { key, len });
+        let encoded = encoded?;
+        self.gas_meter.charge_loaded(encoded.len());
+        // Report decoding errors the same as the on-chain environment.
+        Some(scale::Decode::decode(&mut &encoded[..]).map_err(Into::into))
     }
 
     fn clear_contract_storage(&mut self, key: Key) {
+        self.gas_meter.charge(HostFn::ClearStorage);
+        self.tracer.record(|| TraceOp::StorageClear { key });
         self.callee_account_mut()
             .clear_storage(key)
             .expect("callee account is not a smart contract");
     }
 
+    fn clear_contract_storage_on_return(&mut self, key: Option<Key>) {
+        self.exec_context_mut()
+            .expect("uninitialized execution context")
+            .clear_on_return = key;
+    }
+
+    fn set_contract_storage_batch(&mut self, batch: &StorageBatch) {
+        // The on-chain environment submits batches exceeding its static
+        // buffer in parts, each with its own host call.
+        for part in batch.parts(BUFFER_CAPACITY) {
+            if part.encoded_len() > BUFFER_CAPACITY {
+                panic!(
+                    "encoded storage batch entry of {} bytes exceeds the \
+                     static buffer capacity of {} bytes",
+                    part.encoded_len(),
+                    BUFFER_CAPACITY,
+                )
+            }
+            self.gas_meter.charge(HostFn::SetStorageBatch);
+        }
+        for (_, value) in batch.iter() {
+            let len = value.map(<[u8]>::len).unwrap_or(0);
+            self.ensure_cell_size(len);
+            // Faults are injected into the writes of the batch one by one
+            // the same as into single storage writes.
+            if value.is_some() {
+                self.trap_on_fault(HostFn::SetStorage);
+            }
+            self.gas_meter.charge_stored(len);
+        }
+        for (key, value) in batch.iter() {
+            self.tracer.record(|| {
+                match value {
+                    Some(value) => {
+                        TraceOp::StorageWrite {
+                            key,
+                            len: value.len(),
+                        }
+                    }
+                    None => TraceOp::StorageClear { key },
+                }
+            });
+        }
+        let account = self.callee_account_mut();
+        for (key, value) in batch.iter() {
+            match value {
+                Some(value) => account.set_storage_raw(key, value.to_vec()),
+                None => account.clear_storage(key),
+            }
+            .expect("callee account is not a smart contract");
+        }
+    }
+
+    fn get_contract_storage_batch(&mut self, keys: &[Key]) -> Result<StorageBatch> {
+        self.gas_meter.charge(HostFn::GetStorageBatch);
+        let account = self.callee_account();
+        let values = keys
+            .iter()
+            .map(|key| account.get_storage_raw(*key).map_err(Into::into))
+            .collect::<Result<Vec<_>>>()?;
+        let mut batch = StorageBatch::new();
+        for (key, value) in keys.iter().zip(&values) {
+            let len = value.as_ref().map(Vec::len);
+            self.gas_meter.charge_loaded(len.unwrap_or(0));
+            self.tracer.record(|| TraceOp::StorageRead { key: *key, len });
+            match value {
+                Some(value) => batch.set_encoded(*key, value),
+                None => batch.clear(*key),
+            }
+        }
+        Ok(batch)
+    }
+
     fn get_runtime_storage<R>(&mut self, runtime_key: &[u8]) -> Option<Result<R>>
     where
         R: scale::Decode,
     {
+        self.gas_meter.charge(HostFn::GetRuntimeStorage);
         self.runtime_storage.load::<R>(runtime_key)
     }
 
     fn input(&mut self) -> Result<CallData> {
+        self.gas_meter.charge(HostFn::Input);
         self.exec_context()
             .map(|exec_ctx| &exec_ctx.call_data)
             .map(Clone::clone)
@@ -103,16 +314,62 @@ impl Env for EnvInstance {
     where
         R: scale::Encode,
     {
+        self.gas_meter.charge(HostFn::Return);
         let ctx = self
             .exec_context_mut()
             .expect("uninitialized execution context");
         ctx.output = Some(return_value.encode());
     }
 
+    fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
+    where
+        R: scale::Encode,
+    {
+        self.gas_meter.charge(HostFn::Return);
+        let data = return_value.encode();
+        let ctx = self
+            .exec_context_mut()
+            .expect("uninitialized execution context");
+        ctx.output = Some(data.clone());
+        if let Some(key) = ctx.clear_on_return.take() {
+            if !flags.is_reverted() {
+                self.clear_contract_storage(key);
+            }
+        }
+        // Unwinding is the only way to halt the execution of the contract.
+        // The payload is caught by `test::assert_return_value`.
+        std::panic::resume_unwind(Box::new(ContractReturned { flags, data }))
+    }
+
+    fn caller_is_origin(&mut self) -> bool {
+        self.gas_meter.charge(HostFn::CallerIsOrigin);
+        // Only contracts are able to call other contracts so every
+        // non-contract caller must be the origin of the extrinsic.
+        let caller = &self
+            .exec_context()
+            .expect("uninitialized execution context")
+            .caller;
+        self.accounts
+            .get_account_off(caller)
+            .map(|account| !account.is_contract())
+            .unwrap_or(true)
+    }
+
+    fn call_depth(&mut self) -> u32 {
+        self.gas_meter.charge(HostFn::CallDepth);
+        self.exec_context.len() as u32
+    }
+
     fn println(&mut self, content: &str) {
+        self.gas_meter.charge(HostFn::Println);
         self.console.println(content)
     }
 
+    fn debug_message(&mut self, message: &str) {
+        self.gas_meter.charge(HostFn::DebugMessage);
+        self.console.debug_message(message)
+    }
+
     fn hash_keccak_256(input: &[u8], output: &mut [u8; 32]) {
         hashing::keccak_256(input, output)
     }
@@ -125,13 +382,65 @@ impl Env for EnvInstance {
         hashing::blake2_128(input, output)
     }
 
+    fn hash_blake2s_256(input: &[u8], output: &mut [u8; 32]) {
+        hashing::blake2s_256(input, output)
+    }
+
     fn hash_sha2_256(input: &[u8], output: &mut [u8; 32]) {
         hashing::sha2_256(input, output)
     }
+
+    fn hash_encoded<H, T>(&mut self, input: &T, output: &mut <H as CryptoHash>::Output)
+    where
+        H: CryptoHash,
+        T: scale::Encode,
+    {
+        self.gas_meter.charge(HostFn::Hash);
+        <H as CryptoHash>::hash(&input.encode(), output)
+    }
+
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+    ) -> Result<EcdsaPublicKey> {
+        self.gas_meter.charge(HostFn::EcdsaRecover);
+        use secp256k1::{
+            recover,
+            Message,
+            RecoveryId,
+            Signature,
+        };
+        // Ethereum-style signatures encode the recovery ID with an offset of 27.
+        let recovery_byte = if signature[64] > 26 {
+            signature[64] - 27
+        } else {
+            signature[64]
+        };
+        let recovery_id =
+            RecoveryId::parse(recovery_byte).map_err(|_| EnvError::EcdsaRecoverFailed)?;
+        let mut rs = [0x00; 64];
+        rs.copy_from_slice(&signature[..64]);
+        let signature = Signature::parse(&rs);
+        let message = Message::parse(message_hash);
+        let public_key = recover(&message, &signature, &recovery_id)
+            .map_err(|_| EnvError::EcdsaRecoverFailed)?;
+        Ok(EcdsaPublicKey::from(public_key.serialize_compressed()))
+    }
+
+    fn call_chain_extension<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
+    where
+        I: scale::Encode,
+        O: scale::Decode,
+    {
+        self.gas_meter.charge(HostFn::CallChainExtension);
+        self.chain_extension_handler.call::<I, O>(func_id, input)
+    }
 }
 
-impl TypedEnv for EnvInstance {
+impl TypedEnvBackend for EnvInstance {
     fn caller<T: EnvTypes>(&mut self) -> Result<T::AccountId> {
+        self.gas_meter.charge(HostFn::Caller);
         self.exec_context()
             .expect("uninitialized execution context")
             .caller::<T>()
@@ -140,6 +449,7 @@ impl TypedEnv for EnvInstance {
     }
 
     fn transferred_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::ValueTransferred);
         self.exec_context()
             .expect("uninitialized execution context")
             .transferred_value::<T>()
@@ -148,6 +458,7 @@ impl TypedEnv for EnvInstance {
     }
 
     fn gas_price<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::GasPrice);
         self.chain_spec
             .gas_price::<T>()
             .map_err(|_| scale::Error::from("could not decode gas price"))
@@ -155,6 +466,7 @@ impl TypedEnv for EnvInstance {
     }
 
     fn gas_left<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::GasLeft);
         self.exec_context()
             .expect("uninitialized execution context")
             .gas::<T>()
@@ -163,6 +475,7 @@ impl TypedEnv for EnvInstance {
     }
 
     fn block_timestamp<T: EnvTypes>(&mut self) -> Result<T::Timestamp> {
+        self.gas_meter.charge(HostFn::Now);
         self.current_block()
             .expect("uninitialized execution context")
             .timestamp::<T>()
@@ -171,21 +484,48 @@ impl TypedEnv for EnvInstance {
     }
 
     fn account_id<T: EnvTypes>(&mut self) -> Result<T::AccountId> {
-        self.exec_context()
-            .expect("uninitialized execution context")
-            .callee::<T>()
-            .map_err(|_| scale::Error::from("could not decode callee"))
-            .map_err(Into::into)
+        self.gas_meter.charge(HostFn::Address);
+        self.callee_id::<T>()
     }
 
     fn balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::Balance);
         self.callee_account()
             .balance::<T>()
             .map_err(|_| scale::Error::from("could not decode callee balance"))
             .map_err(Into::into)
     }
 
+    fn reserved_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::ReservedBalance);
+        self.callee_account()
+            .reserved_balance::<T>()
+            .map_err(|_| scale::Error::from("could not decode callee reserved balance"))
+            .map_err(Into::into)
+    }
+
+    fn own_code_hash<T: EnvTypes>(&mut self) -> Result<T::Hash> {
+        self.gas_meter.charge(HostFn::OwnCodeHash);
+        self.callee_account()
+            .code_hash::<T>()
+            .map_err(|_| scale::Error::from("could not decode callee code hash"))?
+            .ok_or_else(|| scale::Error::from("callee account is not a smart contract"))
+            .map_err(Into::into)
+    }
+
+    fn code_hash<T: EnvTypes>(
+        &mut self,
+        account_id: &T::AccountId,
+    ) -> Result<Option<T::Hash>> {
+        self.gas_meter.charge(HostFn::CodeHash);
+        match self.accounts.get_account::<T>(account_id) {
+            Some(account) => account.code_hash::<T>().map_err(Into::into),
+            None => Ok(None),
+        }
+    }
+
     fn rent_allowance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::RentAllowance);
         self.callee_account()
             .rent_allowance::<T>()
             .map_err(|_| scale::Error::from("could not decode callee rent allowance"))
@@ -193,6 +533,7 @@ impl TypedEnv for EnvInstance {
     }
 
     fn block_number<T: EnvTypes>(&mut self) -> Result<T::BlockNumber> {
+        self.gas_meter.charge(HostFn::BlockNumber);
         self.current_block()
             .expect("uninitialized execution context")
             .number::<T>()
@@ -201,6 +542,7 @@ impl TypedEnv for EnvInstance {
     }
 
     fn minimum_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::MinimumBalance);
         self.chain_spec
             .minimum_balance::<T>()
             .map_err(|_| scale::Error::from("could not decode minimum balance"))
@@ -208,24 +550,39 @@ impl TypedEnv for EnvInstance {
     }
 
     fn tombstone_deposit<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::TombstoneDeposit);
         self.chain_spec
             .tombstone_deposit::<T>()
             .map_err(|_| scale::Error::from("could not decode tombstone deposit"))
             .map_err(Into::into)
     }
 
+    fn weight_to_fee<T: EnvTypes>(&mut self, gas: u64) -> Result<T::Balance> {
+        self.gas_meter.charge(HostFn::WeightToFee);
+        let gas = gas_to_balance::<T>(gas);
+        let gas_price = self
+            .chain_spec
+            .gas_price::<T>()
+            .map_err(|_| scale::Error::from("could not decode gas price"))?;
+        Ok(gas_price * gas)
+    }
+
     fn emit_event<T, Event>(&mut self, new_event: Event)
     where
         T: EnvTypes,
         Event: Topics<T> + scale::Encode,
     {
-        self.emitted_events.record::<T, Event>(new_event)
+        self.gas_meter.charge(HostFn::DepositEvent);
+        let event = EmittedEvent::new::<T, Event>(new_event);
+        self.tracer.record(|| TraceOp::Event(event.clone()));
+        self.emitted_events.record(event)
     }
 
     fn set_rent_allowance<T>(&mut self, new_rent_allowance: T::Balance)
     where
         T: EnvTypes,
     {
+        self.gas_meter.charge(HostFn::SetRentAllowance);
         self.callee_account_mut()
             .set_rent_allowance::<T>(new_rent_allowance)
             .expect("could not encode rent allowance")
@@ -235,13 +592,14 @@ impl TypedEnv for EnvInstance {
     where
         T: EnvTypes,
     {
-        unimplemented!("off-chain environment does not support contract invokation")
+        unreachable!("off-chain contract calls are dispatched by `off_chain::contracts`")
     }
 
     fn invoke_runtime<T>(&mut self, params: &T::Call) -> Result<()>
     where
         T: EnvTypes,
     {
+        self.gas_meter.charge(HostFn::DispatchCall);
         self.runtime_call_handler.invoke::<T>(params)
     }
 
@@ -253,7 +611,7 @@ impl TypedEnv for EnvInstance {
         T: EnvTypes,
         R: scale::Decode,
     {
-        unimplemented!("off-chain environment does not support contract evaluation")
+        unreachable!("off-chain contract calls are dispatched by `off_chain::contracts`")
     }
 
     fn instantiate_contract<T, C>(
@@ -263,14 +621,37 @@ impl TypedEnv for EnvInstance {
     where
         T: EnvTypes,
     {
-        unimplemented!("off-chain environment does not support contract instantiation")
+        unreachable!("off-chain contract calls are dispatched by `off_chain::contracts`")
     }
 
-    fn terminate_contract<T>(&mut self, _beneficiary: T::AccountId) -> !
+    fn terminate_contract<T>(&mut self, beneficiary: T::AccountId) -> !
     where
         T: EnvTypes,
     {
-        unimplemented!("off-chain environment does not support contract termination")
+        self.gas_meter.charge(HostFn::Terminate);
+        let contract_id = self
+            .callee_id::<T>()
+            .expect("could not decode executed contract account ID");
+        let transferred = self
+            .callee_account()
+            .balance::<T>()
+            .expect("could not decode executed contract balance");
+        let beneficiary_account = self.accounts.get_or_create_account::<T>(&beneficiary);
+        let beneficiary_balance = beneficiary_account
+            .balance::<T>()
+            .expect("could not decode beneficiary balance");
+        beneficiary_account
+            .set_balance::<T>(beneficiary_balance + transferred)
+            .expect("could not encode beneficiary balance");
+        self.accounts
+            .remove_account::<T>(&contract_id)
+            .expect("account of executed contract must exist");
+        // Unwinding is the only way to halt the execution of the terminated
+        // contract. The payload is caught by `test::assert_contract_termination`.
+        std::panic::resume_unwind(Box::new(ContractTerminated {
+            beneficiary: OffAccountId::new(&beneficiary),
+            transferred: OffBalance::new(&transferred),
+        }))
     }
 
     fn restore_contract<T>(
@@ -289,34 +670,20 @@ impl TypedEnv for EnvInstance {
     where
         T: EnvTypes,
     {
-        let src_id = self.account_id::<T>()?;
-        let src_value = self
-            .accounts
-            .get_account::<T>(&src_id)
-            .expect("account of executed contract must exist")
-            .balance::<T>()?;
-        if src_value < value {
-            return Err(EnvError::TransferCallFailed)
-        }
-        let dst_value = self
-            .accounts
-            .get_or_create_account::<T>(&destination)
-            .balance::<T>()?;
-        self.accounts
-            .get_account_mut::<T>(&src_id)
-            .expect("account of executed contract must exist")
-            .set_balance::<T>(src_value - value)?;
-        self.accounts
-            .get_account_mut::<T>(&destination)
-            .expect("the account must exist already or has just been created")
-            .set_balance::<T>(dst_value + value)?;
-        Ok(())
+        self.gas_meter.charge(HostFn::Transfer);
+        self.fail_on_fault(HostFn::Transfer, EnvError::TransferCallFailed)?;
+        let src_id = self.callee_id::<T>()?;
+        self.transfer_between::<T>(&src_id, &destination, value)
     }
 
     fn random<T>(&mut self, subject: &[u8]) -> Result<T::Hash>
     where
         T: EnvTypes,
     {
+        self.gas_meter.charge(HostFn::RandomSeed);
+        if let Some(random) = self.randomness.random::<T>(subject) {
+            return random.map_err(Into::into)
+        }
         self.current_block()
             .expect("uninitialized execution context")
             .random::<T>(subject)
@@ -12,12 +12,21 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod chain_extension;
+mod context_builder;
+pub mod contracts;
 mod db;
+mod faults;
+mod gas_meter;
 mod hashing;
 mod impls;
+mod randomness;
 mod runtime_calls;
 mod runtime_storage;
+mod snapshots;
 pub mod test_api;
+mod test_setup;
+mod trace;
 mod typed_encoded;
 mod types;
 
@@ -25,18 +34,30 @@ mod types;
 mod tests;
 
 use self::{
+    chain_extension::ChainExtensionHandler,
+    contracts::ContractsRegistry,
     db::{
         Account,
         AccountsDb,
         Block,
         ChainSpec,
         Console,
-        EmittedEvent,
         EmittedEventsRecorder,
         ExecContext,
     },
+    faults::FaultInjector,
+    gas_meter::GasMeter,
+    randomness::Randomness,
     runtime_calls::RuntimeCallHandler,
     runtime_storage::RuntimeStorage,
+    snapshots::{
+        ChainState,
+        Snapshots,
+    },
+    trace::{
+        TraceOp,
+        Tracer,
+    },
     typed_encoded::TypedEncoded,
     types::{
         OffAccountId,
@@ -50,12 +71,26 @@ use self::{
 pub use self::{
     db::{
         AccountError,
+        EmittedEvent,
         PastPrints,
+        StorageOps,
+    },
+    faults::Fault,
+    gas_meter::{
+        GasWeights,
+        HostFn,
     },
+    snapshots::SnapshotId,
+    trace::TraceEvent,
     typed_encoded::TypedEncodedError,
 };
 use super::OnInstance;
-use crate::env::EnvTypes;
+use crate::env::{
+    Clear,
+    EnvError,
+    EnvTypes,
+    ReturnFlags,
+};
 use core::cell::RefCell;
 use derive_more::From;
 
@@ -69,10 +104,43 @@ pub enum OffChainError {
     UninitializedExecutionContext,
     #[from(ignore)]
     UnregisteredRuntimeCallHandler,
+    #[from(ignore)]
+    UnregisteredChainExtension(u32),
+    #[from(ignore)]
+    UnknownSnapshot(SnapshotId),
+    #[from(ignore)]
+    UnregisteredContract,
+    /// An injected failure of the host function.
+    #[from(ignore)]
+    InjectedFault(HostFn),
 }
 
 pub type Result<T> = core::result::Result<T, OffChainError>;
 
+/// The unwinding payload of a contract terminated in the off-chain environment.
+///
+/// # Note
+///
+/// Use [`test_api::assert_contract_termination`] to inspect it.
+pub struct ContractTerminated {
+    /// The beneficiary of the terminated contract.
+    beneficiary: OffAccountId,
+    /// The balance transferred to the beneficiary.
+    transferred: OffBalance,
+}
+
+/// The unwinding payload of a contract that returned in the off-chain environment.
+///
+/// # Note
+///
+/// Use [`test_api::assert_return_value`] to inspect it.
+pub struct ContractReturned {
+    /// The flags the contract returned with.
+    flags: ReturnFlags,
+    /// The encoded returned value.
+    data: Vec<u8>,
+}
+
 /// The off-chain environment.
 ///
 /// Mainly used for off-chain testing.
@@ -91,8 +159,24 @@ pub struct EnvInstance {
     runtime_storage: RuntimeStorage,
     /// The runtime calls handler.
     runtime_call_handler: RuntimeCallHandler,
+    /// The chain extension functions handler.
+    chain_extension_handler: ChainExtensionHandler,
     /// Emitted events recorder.
     emitted_events: EmittedEventsRecorder,
+    /// The randomness source.
+    randomness: Randomness,
+    /// The snapshots taken of the emulated chain state.
+    snapshots: Snapshots,
+    /// The contracts that can be instantiated and called.
+    contracts: ContractsRegistry,
+    /// The gas consumed by the host function calls.
+    gas_meter: GasMeter,
+    /// The trace of the executed calls and their operations.
+    tracer: Tracer,
+    /// The faults injected into host function calls.
+    faults: FaultInjector,
+    /// Is `true` while a test set up by [`test_api::TestSetup`] is running.
+    is_running_test_setup: bool,
 }
 
 impl EnvInstance {
@@ -106,7 +190,15 @@ impl EnvInstance {
             console: Console::new(),
             runtime_storage: RuntimeStorage::new(),
             runtime_call_handler: RuntimeCallHandler::new(),
+            chain_extension_handler: ChainExtensionHandler::new(),
             emitted_events: EmittedEventsRecorder::new(),
+            randomness: Randomness::new(),
+            snapshots: Snapshots::new(),
+            contracts: ContractsRegistry::new(),
+            gas_meter: GasMeter::new(),
+            tracer: Tracer::new(),
+            faults: FaultInjector::new(),
+            is_running_test_setup: false,
         }
     }
 
@@ -126,7 +218,6 @@ impl EnvInstance {
     pub fn initialize_as_default<T>(&mut self) -> crate::env::Result<()>
     where
         T: EnvTypes,
-        <T as EnvTypes>::AccountId: From<[u8; 32]>,
     {
         use core::ops::Div as _;
         use num_traits::{
@@ -161,11 +252,12 @@ impl EnvInstance {
         // Initialize chain specification.
         self.chain_spec.initialize_as_default::<T>()?;
         // Initialize the called contract account.
-        let contract_account_id = T::AccountId::from([0x07; 32]);
+        let contract_account_id = test_api::default_account_id::<T>(0x07)?;
         self.accounts.add_contract_account::<T>(
             contract_account_id.clone(),
             T::Balance::from(0),
             T::Balance::from(20),
+            T::Hash::clear(),
         );
         // Initialize the execution context for the first contract execution.
         use crate::env::call::{
@@ -178,7 +270,7 @@ impl EnvInstance {
                 .callee(contract_account_id)
                 .gas(T::Balance::from(500_000))
                 .transferred_value(T::Balance::from(500))
-                .call_data(CallData::new(Selector::from_str("call")))
+                .call_data(CallData::new(Selector::from_label("call")))
                 .finish(),
         );
         Ok(())
@@ -189,14 +281,92 @@ impl EnvInstance {
     where
         T: EnvTypes,
     {
-        let new_block_number = T::BlockNumber::from(self.blocks.len() as u32);
-        let new_timestamp = self.current_block()?.timestamp::<T>()?
-            + self.chain_spec.block_time::<T>()?;
-        self.blocks
-            .push(Block::new::<T>(new_block_number, new_timestamp));
+        let current_block = self.current_block()?;
+        let new_block_number =
+            current_block.number::<T>()? + <T::BlockNumber as From<u32>>::from(1);
+        let new_timestamp =
+            current_block.timestamp::<T>()? + self.chain_spec.block_time::<T>()?;
+        let mut new_block = Block::new::<T>(new_block_number, new_timestamp);
+        if let Some(seed) = self.randomness.seed() {
+            new_block.set_entropy::<T>(seed.decode::<T::Hash>()?)?;
+        }
+        self.blocks.push(new_block);
         Ok(())
     }
 
+    /// Returns a copy of the emulated chain state.
+    fn chain_state(&self) -> ChainState {
+        ChainState {
+            accounts: self.accounts.clone(),
+            exec_context: self.exec_context.clone(),
+            chain_spec: self.chain_spec.clone(),
+            blocks: self.blocks.clone(),
+            console: self.console.clone(),
+            runtime_storage: self.runtime_storage.clone(),
+            emitted_events: self.emitted_events.clone(),
+        }
+    }
+
+    /// Replaces the emulated chain state with the given state.
+    fn set_chain_state(&mut self, state: ChainState) {
+        self.accounts = state.accounts;
+        self.exec_context = state.exec_context;
+        self.chain_spec = state.chain_spec;
+        self.blocks = state.blocks;
+        self.console = state.console;
+        self.runtime_storage = state.runtime_storage;
+        self.emitted_events = state.emitted_events;
+    }
+
+    /// Takes a snapshot of the emulated chain state.
+    pub fn snapshot(&mut self) -> SnapshotId {
+        let state = self.chain_state();
+        self.snapshots.take(state)
+    }
+
+    /// Restores the emulated chain state of the snapshot.
+    ///
+    /// # Note
+    ///
+    /// The snapshot is kept and can be restored again later on.
+    pub fn restore(&mut self, id: SnapshotId) -> Result<()> {
+        let state = self
+            .snapshots
+            .get(id)
+            .ok_or(OffChainError::UnknownSnapshot(id))?
+            .clone();
+        self.set_chain_state(state);
+        Ok(())
+    }
+
+    /// Counts a call of the host function and traps upon an injected fault.
+    ///
+    /// # Note
+    ///
+    /// Used by host functions that cannot return an error.
+    fn trap_on_fault(&mut self, host_fn: HostFn) {
+        if let Some(fault) = self.faults.hit(host_fn) {
+            panic!("injected {:?} of host function {:?}", fault, host_fn)
+        }
+    }
+
+    /// Counts a call of the host function and injects the planned fault.
+    ///
+    /// Returns `error` upon an injected failure and traps upon an injected trap.
+    fn fail_on_fault(
+        &mut self,
+        host_fn: HostFn,
+        error: EnvError,
+    ) -> crate::env::Result<()> {
+        match self.faults.hit(host_fn) {
+            Some(Fault::Fail) => Err(error),
+            Some(Fault::Trap) => {
+                panic!("injected {:?} of host function {:?}", Fault::Trap, host_fn)
+            }
+            None => Ok(()),
+        }
+    }
+
     /// Returns the current execution context.
     fn exec_context(&self) -> Result<&ExecContext> {
         self.exec_context
@@ -0,0 +1,84 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    OffHash,
+    TypedEncodedError,
+};
+use crate::env::EnvTypes;
+
+/// A registered randomness function mapping a subject to an encoded hash.
+type RandomFn = Box<dyn FnMut(&[u8]) -> OffHash>;
+
+/// Controls the randomness source of the off-chain environment.
+///
+/// # Note
+///
+/// By default every block is initialized with random entropy.
+pub struct Randomness {
+    /// The seed used as entropy for all blocks if any.
+    seed: Option<OffHash>,
+    /// The custom randomness function if any.
+    handler: Option<RandomFn>,
+}
+
+impl Randomness {
+    /// Creates a new randomness source that uses random block entropy.
+    pub fn new() -> Self {
+        Self {
+            seed: None,
+            handler: None,
+        }
+    }
+
+    /// Fixes the entropy of all blocks to the given seed.
+    pub fn set_seed<T>(&mut self, seed: T::Hash)
+    where
+        T: EnvTypes,
+    {
+        self.seed = Some(OffHash::new(&seed));
+    }
+
+    /// Returns the fixed seed if any.
+    pub fn seed(&self) -> Option<&OffHash> {
+        self.seed.as_ref()
+    }
+
+    /// Registers a custom randomness function.
+    ///
+    /// # Note
+    ///
+    /// The function receives the subject and replaces the block entropy
+    /// based randomness computation entirely.
+    pub fn register<T, F>(&mut self, mut f: F)
+    where
+        T: EnvTypes,
+        F: FnMut(&[u8]) -> <T as EnvTypes>::Hash + 'static,
+    {
+        self.handler = Some(Box::new(move |subject: &[u8]| OffHash::new(&f(subject))));
+    }
+
+    /// Returns the output of the custom randomness function for the subject if any.
+    pub fn random<T>(
+        &mut self,
+        subject: &[u8],
+    ) -> Option<Result<T::Hash, TypedEncodedError>>
+    where
+        T: EnvTypes,
+    {
+        self.handler
+            .as_mut()
+            .map(|handler| handler(subject).decode::<T::Hash>())
+    }
+}
@@ -21,6 +21,7 @@ use ink_prelude::{
 /// Runtime storage.
 ///
 /// More generically a mapping from bytes to bytes.
+#[derive(Clone)]
 pub struct RuntimeStorage {
     /// The underlying storage mapping.
     entries: BTreeMap<Vec<u8>, Vec<u8>>,
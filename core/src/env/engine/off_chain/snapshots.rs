@@ -0,0 +1,73 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    AccountsDb,
+    Block,
+    ChainSpec,
+    Console,
+    EmittedEventsRecorder,
+    ExecContext,
+    RuntimeStorage,
+};
+
+/// Identifies a snapshot of the emulated chain state.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct SnapshotId(usize);
+
+/// The emulated chain state captured by a snapshot.
+#[derive(Clone)]
+pub struct ChainState {
+    /// The accounts including their balances and contract storages.
+    pub accounts: AccountsDb,
+    /// The stack of execution contexts.
+    pub exec_context: Vec<ExecContext>,
+    /// The general chain spec.
+    pub chain_spec: ChainSpec,
+    /// The blocks of the chain.
+    pub blocks: Vec<Block>,
+    /// The past prints to the console.
+    pub console: Console,
+    /// The emulated runtime storage.
+    pub runtime_storage: RuntimeStorage,
+    /// The recorded emitted events.
+    pub emitted_events: EmittedEventsRecorder,
+}
+
+/// Stores the snapshots taken of the emulated chain state.
+pub struct Snapshots {
+    /// The snapshots in the order they have been taken.
+    snapshots: Vec<ChainState>,
+}
+
+impl Snapshots {
+    /// Creates a new empty snapshot storage.
+    pub fn new() -> Self {
+        Self {
+            snapshots: Vec::new(),
+        }
+    }
+
+    /// Stores the chain state and returns the identifier of its snapshot.
+    pub fn take(&mut self, state: ChainState) -> SnapshotId {
+        let id = SnapshotId(self.snapshots.len());
+        self.snapshots.push(state);
+        id
+    }
+
+    /// Returns the chain state of the snapshot if any.
+    pub fn get(&self, id: SnapshotId) -> Option<&ChainState> {
+        self.snapshots.get(id.0)
+    }
+}
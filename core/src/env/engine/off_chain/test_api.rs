@@ -17,17 +17,39 @@
 use super::{
     db::ExecContext,
     AccountError,
-    EmittedEvent,
+    ContractReturned,
+    ContractTerminated,
     EnvInstance,
+    OffAccountId,
+    OffBalance,
     OnInstance,
 };
 use crate::env::{
     call::CallData,
+    ChainExtension,
     EnvTypes,
     Result,
+    ReturnFlags,
 };
 use ink_prelude::string::String;
 
+pub use super::{
+    context_builder::ExecutionContextBuilder,
+    contracts::CallKind,
+    test_setup::{
+        is_running_test_setup,
+        TestSetup,
+    },
+    trace::TraceOp,
+    EmittedEvent,
+    Fault,
+    GasWeights,
+    HostFn,
+    SnapshotId,
+    StorageOps,
+    TraceEvent,
+};
+
 /// Pushes a contract execution context.
 ///
 /// This is the data behind a single instance of a contract call.
@@ -70,6 +92,178 @@ pub fn pop_execution_context() {
     })
 }
 
+/// Applies `f` to the top contract execution context.
+fn update_exec_context<F>(f: F) -> Result<()>
+where
+    F: FnOnce(&mut ExecContext),
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.exec_context_mut().map(f)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the caller of the top contract execution context.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::caller`] returns.
+pub fn set_caller<T>(caller: T::AccountId) -> Result<()>
+where
+    T: EnvTypes,
+{
+    update_exec_context(|exec_context| exec_context.caller = OffAccountId::new(&caller))
+}
+
+/// Sets the callee of the top contract execution context.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::account_id`] returns.
+pub fn set_callee<T>(callee: T::AccountId) -> Result<()>
+where
+    T: EnvTypes,
+{
+    update_exec_context(|exec_context| exec_context.callee = OffAccountId::new(&callee))
+}
+
+/// Sets the value transferred with the top contract execution context.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::transferred_balance`] returns
+/// and thus to emulate calls to payable and non-payable messages.
+pub fn set_value_transferred<T>(value: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    update_exec_context(|exec_context| {
+        exec_context.transferred_value = OffBalance::new(&value)
+    })
+}
+
+/// Emulates a payable call of the caller into the callee of the top
+/// contract execution context that transfers `value` along.
+///
+/// # Note
+///
+/// Moves `value` from the free balance of the caller to the free balance
+/// of the callee and sets the value transferred with the execution context.
+///
+/// # Errors
+///
+/// - If there is no execution context.
+/// - If the caller account does not exist.
+/// - If the caller has insufficient free balance.
+pub fn transfer_in<T>(value: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        let exec_context = instance.exec_context()?;
+        let caller = exec_context.caller::<T>()?;
+        let callee = exec_context.callee::<T>()?;
+        instance.transfer_between::<T>(&caller, &callee, value)?;
+        instance.exec_context_mut()?.transferred_value = OffBalance::new(&value);
+        Ok(())
+    })
+}
+
+/// Sets the gas provided for the top contract execution context.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::gas_left`] returns.
+pub fn set_gas<T>(gas: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    update_exec_context(|exec_context| exec_context.gas = OffBalance::new(&gas))
+}
+
+/// Asserts that the closure terminates the executed contract.
+///
+/// # Note
+///
+/// Upon termination of a contract the off-chain environment transfers its
+/// remaining balance to the beneficiary, removes its account and then halts
+/// its execution by unwinding.
+///
+/// # Panics
+///
+/// - If the closure does not terminate the contract.
+/// - If the contract was terminated in favor of another beneficiary.
+/// - If the transferred balance does not match the expected balance.
+pub fn assert_contract_termination<T, F>(
+    should_terminate: F,
+    expected_beneficiary: T::AccountId,
+    expected_balance: T::Balance,
+) where
+    T: EnvTypes,
+    F: FnOnce() + std::panic::UnwindSafe,
+{
+    let payload = std::panic::catch_unwind(should_terminate)
+        .expect_err("the closure did not terminate the contract");
+    let terminated = match payload.downcast::<ContractTerminated>() {
+        Ok(terminated) => terminated,
+        Err(payload) => std::panic::resume_unwind(payload),
+    };
+    let beneficiary = terminated
+        .beneficiary
+        .decode::<T::AccountId>()
+        .expect("could not decode beneficiary");
+    let transferred = terminated
+        .transferred
+        .decode::<T::Balance>()
+        .expect("could not decode transferred balance");
+    assert!(
+        beneficiary == expected_beneficiary,
+        "the contract was terminated in favor of another beneficiary"
+    );
+    assert!(
+        transferred == expected_balance,
+        "the transferred balance does not match the expected balance"
+    );
+}
+
+/// Asserts that the closure returns the expected value with the expected flags.
+///
+/// # Note
+///
+/// Upon [`crate::env::return_value`] the off-chain environment records the
+/// output of the executed contract and then halts its execution by unwinding.
+///
+/// # Panics
+///
+/// - If the closure does not return a value.
+/// - If the returned flags do not match the expected flags.
+/// - If the returned value does not match the expected value.
+pub fn assert_return_value<R, F>(
+    should_return: F,
+    expected_flags: ReturnFlags,
+    expected_value: R,
+) where
+    R: scale::Decode + PartialEq + core::fmt::Debug,
+    F: FnOnce() + std::panic::UnwindSafe,
+{
+    let payload = std::panic::catch_unwind(should_return)
+        .expect_err("the closure did not return a value");
+    let returned = match payload.downcast::<ContractReturned>() {
+        Ok(returned) => returned,
+        Err(payload) => std::panic::resume_unwind(payload),
+    };
+    assert_eq!(
+        returned.flags, expected_flags,
+        "the returned flags do not match the expected flags"
+    );
+    let value = <R as scale::Decode>::decode(&mut &returned.data[..])
+        .expect("could not decode returned value");
+    assert_eq!(
+        value, expected_value,
+        "the returned value does not match the expected value"
+    );
+}
+
 /// Sets the balance of the account to the given balance.
 ///
 /// # Note
@@ -125,6 +319,71 @@ where
     })
 }
 
+/// Sets the reserved balance of the account to the given balance.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::reserved_balance`] returns.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If the underlying `account` type does not match.
+/// - If the underlying `new_reserved_balance` type does not match.
+pub fn set_account_reserved_balance<T>(
+    account_id: T::AccountId,
+    new_reserved_balance: T::Balance,
+) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account_mut::<T>(&account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(&account_id))
+            .map_err(Into::into)
+            .and_then(|account| {
+                account
+                    .set_reserved_balance::<T>(new_reserved_balance)
+                    .map_err(Into::into)
+            })
+    })
+}
+
+/// Sets the code hash of the contract account to the given code hash.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::own_code_hash`] and
+/// [`crate::env::code_hash`] return.
+///
+/// # Errors
+///
+/// - If `account` does not exist.
+/// - If the underlying `account` type does not match.
+/// - If the underlying `new_code_hash` type does not match.
+pub fn set_contract_code_hash<T>(
+    account_id: T::AccountId,
+    new_code_hash: T::Hash,
+) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account_mut::<T>(&account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(&account_id))
+            .map_err(Into::into)
+            .and_then(|account| {
+                account
+                    .set_code_hash::<T>(new_code_hash)
+                    .map_err(Into::into)
+            })
+    })
+}
+
 /// Sets the rent allowance of the contract account to the given rent allowance.
 ///
 /// # Errors
@@ -195,6 +454,214 @@ where
     })
 }
 
+/// Registers the handler mocking the given chain extension function.
+///
+/// # Note
+///
+/// - The handler receives the decoded input of the chain extension call
+///   and returns either its output or a custom error code.
+/// - Replaces any handler previously registered for the same function ID.
+pub fn register_chain_extension<E, F>(f: F)
+where
+    E: ChainExtension,
+    <E as ChainExtension>::Input: scale::Decode,
+    <E as ChainExtension>::Output: scale::Encode,
+    F: FnMut(<E as ChainExtension>::Input) -> core::result::Result<E::Output, u32>
+        + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.chain_extension_handler.register::<E, F>(f)
+    })
+}
+
+/// Registers the handler mocking the chain extension function with the given ID.
+///
+/// # Note
+///
+/// - The handler receives the encoded input of the chain extension call
+///   and returns either its encoded output or a custom error code.
+/// - Useful for chain extensions without a [`ChainExtension`] definition
+///   or in order to test how contracts handle malformed outputs.
+/// - Replaces any handler previously registered for the same function ID.
+pub fn register_raw_chain_extension<F>(func_id: u32, f: F)
+where
+    F: FnMut(&[u8]) -> core::result::Result<Vec<u8>, u32> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .chain_extension_handler
+            .register_raw::<F>(func_id, f)
+    })
+}
+
+/// Registers the contract code under the given code hash.
+///
+/// # Note
+///
+/// - The handler dispatches the instantiation or call of a contract instance
+///   using [`crate::env::input`] and [`crate::env::output`] and returns a
+///   custom error code upon failure. For ink! contracts this is what their
+///   `DispatchUsingMode` implementation does.
+/// - Cross-contract calls and instantiations through [`crate::env::invoke_contract`],
+///   [`crate::env::eval_contract`] and [`crate::env::instantiate_contract`] are
+///   dispatched to the handler registered for the code hash of the callee.
+/// - The state changes of failed or trapped executions are reverted.
+/// - Contracts may call into themselves recursively. Use interior mutability
+///   for state kept by the handler itself.
+/// - Replaces any handler previously registered for the same code hash.
+pub fn register_contract<T, F>(code_hash: T::Hash, f: F)
+where
+    T: EnvTypes,
+    F: Fn(CallKind) -> core::result::Result<(), u8> + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.contracts.register::<T, F>(code_hash, f)
+    })
+}
+
+/// Adds a contract instance of the given code hash under the account ID.
+///
+/// # Note
+///
+/// The contract instance starts with an empty storage and zero balance
+/// without running any of its constructors.
+///
+/// # Errors
+///
+/// If an account already exists under the account ID.
+pub fn add_contract_account<T>(account_id: T::AccountId, code_hash: T::Hash) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        if instance.accounts.get_account::<T>(&account_id).is_some() {
+            return Err(AccountError::account_already_exists::<T>(&account_id).into())
+        }
+        instance.accounts.add_contract_account::<T>(
+            account_id,
+            T::Balance::from(0),
+            T::Balance::from(0),
+            code_hash,
+        );
+        Ok(())
+    })
+}
+
+/// Sets the gas price of the chain.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::gas_price`] and
+/// [`crate::env::weight_to_fee`] return.
+pub fn set_gas_price<T>(new_gas_price: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.chain_spec.set_gas_price::<T>(new_gas_price)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the minimum balance of accounts on the chain.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::minimum_balance`] returns.
+pub fn set_minimum_balance<T>(new_minimum_balance: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .chain_spec
+            .set_minimum_balance::<T>(new_minimum_balance)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the tombstone deposit of the chain.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::tombstone_deposit`] returns.
+pub fn set_tombstone_deposit<T>(new_tombstone_deposit: T::Balance) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .chain_spec
+            .set_tombstone_deposit::<T>(new_tombstone_deposit)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the targeted block time of the chain.
+///
+/// # Note
+///
+/// This controls by how much [`advance_block`] increases the block timestamp.
+pub fn set_block_time<T>(new_block_time: T::Timestamp) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.chain_spec.set_block_time::<T>(new_block_time)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the maximum size of an encoded contract storage cell.
+///
+/// # Note
+///
+/// - Writing a larger value to the contract storage traps the contract,
+///   which allows to detect packed values that would exceed the limits
+///   of the chain before deployment.
+/// - Defaults to 16 KiB. `None` allows contract storage cells of any size.
+pub fn set_max_cell_size(new_max_cell_size: Option<usize>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.chain_spec.set_max_cell_size(new_max_cell_size)
+    })
+}
+
+/// Sets the number of the current block.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::block_number`] returns.
+/// Subsequent calls to [`advance_block`] continue counting from the new number.
+pub fn set_block_number<T>(new_block_number: T::BlockNumber) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .current_block_mut()?
+            .set_number::<T>(new_block_number)
+    })
+    .map_err(Into::into)
+}
+
+/// Sets the timestamp of the current block.
+///
+/// # Note
+///
+/// This allows to control what [`crate::env::block_timestamp`] returns.
+/// Subsequent calls to [`advance_block`] add the block time to the new timestamp.
+pub fn set_block_timestamp<T>(new_block_timestamp: T::Timestamp) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .current_block_mut()?
+            .set_timestamp::<T>(new_block_timestamp)
+    })
+    .map_err(Into::into)
+}
+
 /// Set the entropy hash of the current block.
 ///
 /// # Note
@@ -210,6 +677,38 @@ where
     .map_err(Into::into)
 }
 
+/// Fixes the entropy of the current and all future blocks to the given seed.
+///
+/// # Note
+///
+/// This makes [`crate::env::random`] deterministic across blocks.
+pub fn set_random_seed<T>(seed: T::Hash) -> Result<()>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.randomness.set_seed::<T>(seed);
+        instance.current_block_mut()?.set_entropy::<T>(seed)
+    })
+    .map_err(Into::into)
+}
+
+/// Registers a custom randomness function.
+///
+/// # Note
+///
+/// The function is called with the subject given to [`crate::env::random`]
+/// and its output is returned instead of the block entropy based randomness.
+pub fn register_random_handler<T, F>(f: F)
+where
+    T: EnvTypes,
+    F: FnMut(&[u8]) -> <T as EnvTypes>::Hash + 'static,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.randomness.register::<T, F>(f)
+    })
+}
+
 /// Returns the contents of the past performed environmental `println` in order.
 pub fn recorded_printlns() -> impl Iterator<Item = String> {
     <EnvInstance as OnInstance>::on_instance(|instance| {
@@ -227,6 +726,39 @@ pub fn recorded_printlns() -> impl Iterator<Item = String> {
     })
 }
 
+/// Returns the debug messages of the executed contracts in order.
+///
+/// # Note
+///
+/// Debug messages are recorded instead of being printed to stdout.
+/// They usually stem from the [`debug_println!`](../../macro.debug_println.html) macro.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::{debug_println, env};
+/// env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+///     debug_println!("the value is {}", 42);
+///     assert_eq!(
+///         env::test::debug_messages().collect::<Vec<_>>(),
+///         vec!["the value is 42"],
+///     );
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn debug_messages() -> impl Iterator<Item = String> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        // Cloned for the same reasons as in `recorded_printlns`.
+        instance
+            .console
+            .debug_messages()
+            .map(ToOwned::to_owned)
+            .collect::<Vec<_>>()
+            .into_iter()
+    })
+}
+
 /// Returns the recorded emitted events in order.
 pub fn recorded_events() -> impl Iterator<Item = EmittedEvent> {
     <EnvInstance as OnInstance>::on_instance(|instance| {
@@ -244,6 +776,70 @@ pub fn recorded_events() -> impl Iterator<Item = EmittedEvent> {
     })
 }
 
+/// Asserts that the given event has been emitted during the test.
+///
+/// # Note
+///
+/// The event is compared to the [`recorded_events`] by its topics and data.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::{assert_emitted, env::{self, DefaultEnvTypes, Hash, Topics}};
+/// #[derive(Debug, scale::Encode)]
+/// struct Flipped(bool);
+///
+/// impl Topics<DefaultEnvTypes> for Flipped {
+///     fn topics(&self) -> Vec<Hash> {
+///         Vec::new()
+///     }
+/// }
+///
+/// env::test::run_test::<DefaultEnvTypes, _>(|_| {
+///     env::emit_event::<DefaultEnvTypes, _>(Flipped(true));
+///     assert_emitted!(DefaultEnvTypes, Flipped(true));
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+#[macro_export]
+macro_rules! assert_emitted {
+    ( $env_types:ty, $event:expr $(,)? ) => {{
+        let expected = $crate::env::test::EmittedEvent::new::<$env_types, _>($event);
+        assert!(
+            $crate::env::test::recorded_events().any(|event| event == expected),
+            "expected event has not been emitted: {:?}",
+            expected,
+        )
+    }};
+}
+
+/// Takes a snapshot of the emulated chain state.
+///
+/// # Note
+///
+/// The snapshot covers the accounts with their balances and contract storages,
+/// the execution contexts, the blocks, the chain spec, the runtime storage as
+/// well as the recorded printlns and events.
+/// Registered handlers such as chain extensions are not part of a snapshot.
+pub fn snapshot() -> SnapshotId {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.snapshot())
+}
+
+/// Restores the emulated chain state of the snapshot.
+///
+/// # Note
+///
+/// The snapshot is kept so that tests can restore a common fixture repeatedly.
+///
+/// # Errors
+///
+/// If no snapshot with the given identifier has been taken.
+pub fn restore(id: SnapshotId) -> Result<()> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.restore(id))
+        .map_err(Into::into)
+}
+
 /// Advances the chain by a single block.
 pub fn advance_block<T>() -> Result<()>
 where
@@ -270,18 +866,30 @@ where
 pub fn default_accounts<T>() -> Result<DefaultAccounts<T>>
 where
     T: EnvTypes,
-    <T as EnvTypes>::AccountId: From<[u8; 32]>,
 {
     Ok(DefaultAccounts {
-        alice: T::AccountId::from([0x01; 32]),
-        bob: T::AccountId::from([0x02; 32]),
-        charlie: T::AccountId::from([0x03; 32]),
-        django: T::AccountId::from([0x04; 32]),
-        eve: T::AccountId::from([0x05; 32]),
-        frank: T::AccountId::from([0x06; 32]),
+        alice: default_account_id::<T>(0x01)?,
+        bob: default_account_id::<T>(0x02)?,
+        charlie: default_account_id::<T>(0x03)?,
+        django: default_account_id::<T>(0x04)?,
+        eve: default_account_id::<T>(0x05)?,
+        frank: default_account_id::<T>(0x06)?,
     })
 }
 
+/// Returns the default account ID with all bytes set to `byte`.
+///
+/// # Note
+///
+/// The account ID is decoded from 32 bytes in order to support
+/// account ID types of different sizes, e.g. 20 bytes.
+pub(super) fn default_account_id<T>(byte: u8) -> Result<T::AccountId>
+where
+    T: EnvTypes,
+{
+    <T::AccountId as scale::Decode>::decode(&mut &[byte; 32][..]).map_err(Into::into)
+}
+
 /// Initializes the whole off-chain environment.
 ///
 /// # Note
@@ -292,22 +900,54 @@ where
 pub fn initialize_as_default<T>() -> Result<()>
 where
     T: EnvTypes,
-    <T as EnvTypes>::AccountId: From<[u8; 32]>,
 {
     <EnvInstance as OnInstance>::on_instance(|instance| {
         instance.initialize_as_default::<T>()
     })
 }
 
+/// Returns `true` if the off-chain environment has already been initialized.
+pub fn is_initialized() -> bool {
+    <EnvInstance as OnInstance>::on_instance(|instance| !instance.blocks.is_empty())
+}
+
 /// Runs the given closure test function with the default configuartion
 /// for the off-chain environment.
+///
+/// # Note
+///
+/// Every thread has its own off-chain environment so that tests running in
+/// parallel do not interfere with each other. The test runs in a fresh
+/// environment even if the current thread has run other tests before.
+/// Use [`run_test_shared`] to run it on the environment of the current thread.
 pub fn run_test<T, F>(f: F) -> Result<()>
 where
     T: EnvTypes,
     F: FnOnce(DefaultAccounts<T>) -> Result<()>,
-    <T as EnvTypes>::AccountId: From<[u8; 32]>,
 {
-    initialize_as_default::<T>()?;
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        *instance = EnvInstance::uninitialized();
+    });
+    run_test_shared::<T, F>(f)
+}
+
+/// Runs the given closure test function on the off-chain environment that the
+/// current thread has set up before.
+///
+/// # Note
+///
+/// The environment is initialized with the default configuration only if
+/// the current thread has not yet initialized it. Use this to explicitly
+/// share state between test functions that run one after another on the
+/// same thread. The environment is never shared with other threads.
+pub fn run_test_shared<T, F>(f: F) -> Result<()>
+where
+    T: EnvTypes,
+    F: FnOnce(DefaultAccounts<T>) -> Result<()>,
+{
+    if !is_initialized() {
+        initialize_as_default::<T>()?;
+    }
     let default_accounts = default_accounts::<T>()?;
     f(default_accounts)
 }
@@ -326,3 +966,241 @@ where
             .and_then(|account| account.get_storage_rw().map_err(Into::into))
     })
 }
+
+/// Returns the number of occupied cells in the contract's storage.
+pub fn count_used_storage_cells<T>(account_id: &T::AccountId) -> Result<usize>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.count_used_storage_cells().map_err(Into::into))
+    })
+}
+
+/// Returns the total counts of operations on the contract's storage.
+pub fn get_contract_storage_ops<T>(account_id: &T::AccountId) -> Result<StorageOps>
+where
+    T: EnvTypes,
+{
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .accounts
+            .get_account::<T>(account_id)
+            .ok_or_else(|| AccountError::no_account_for_id::<T>(account_id))
+            .map_err(Into::into)
+            .and_then(|account| account.get_storage_ops().map_err(Into::into))
+    })
+}
+
+/// Returns the counts of operations on the contract's storage performed by `f`.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::env::{self, DefaultEnvTypes};
+/// # use ink_primitives::Key;
+/// env::test::run_test::<DefaultEnvTypes, _>(|_| {
+///     let contract = env::account_id::<DefaultEnvTypes>()?;
+///     let ((), ops) = env::test::count_storage_ops::<DefaultEnvTypes, _, _>(
+///         &contract,
+///         || {
+///             env::set_contract_storage(Key([0x42; 32]), &1_u32);
+///             env::clear_contract_storage(Key([0x42; 32]));
+///         },
+///     )?;
+///     assert_eq!((ops.reads, ops.writes, ops.clears), (0, 2, 1));
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn count_storage_ops<T, F, R>(
+    account_id: &T::AccountId,
+    f: F,
+) -> Result<(R, StorageOps)>
+where
+    T: EnvTypes,
+    F: FnOnce() -> R,
+{
+    let before = get_contract_storage_ops::<T>(account_id)?;
+    let result = f();
+    let after = get_contract_storage_ops::<T>(account_id)?;
+    Ok((result, after - before))
+}
+
+/// Returns the gas consumed by the host function calls so far.
+///
+/// # Note
+///
+/// The consumed gas is an approximation based on the [`GasWeights`] set via
+/// [`set_gas_weights`]. Use it to compare different implementations with each
+/// other; it does not predict the gas consumed on-chain.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::env::{self, DefaultEnvTypes};
+/// # use ink_primitives::Key;
+/// env::test::run_test::<DefaultEnvTypes, _>(|_| {
+///     env::test::set_gas_weights(
+///         env::test::GasWeights::uniform(0)
+///             .host_fn(env::test::HostFn::SetStorage, 10)
+///             .per_byte_stored(1),
+///     );
+///     env::set_contract_storage(Key([0x42; 32]), &1_u32);
+///     assert_eq!(env::test::gas_consumed(), 10 + 4);
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn gas_consumed() -> u64 {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.gas_meter.consumed())
+}
+
+/// Resets the gas consumed by the host function calls to zero.
+pub fn reset_gas_consumed() {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.gas_meter.reset())
+}
+
+/// Sets the weights charged for the host function calls from now on.
+///
+/// # Note
+///
+/// The gas consumed so far is not affected.
+pub fn set_gas_weights(weights: GasWeights) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.gas_meter.set_weights(weights)
+    })
+}
+
+/// Enables or disables tracing of the off-chain execution.
+///
+/// # Note
+///
+/// Tracing is disabled by default. The trace covers the calls of registered
+/// contracts, their returns, the emitted events and the contract storage reads,
+/// writes and clears. Unlike the recorded events it is not affected by snapshots
+/// or reverted calls.
+pub fn set_tracing(enabled: bool) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.tracer.set_enabled(enabled)
+    })
+}
+
+/// Sets the maximum number of traced operations that are kept.
+///
+/// # Note
+///
+/// Only the most recent operations are kept, by default up to 10,000.
+/// Use `None` in order to keep all operations.
+pub fn set_trace_limit(limit: Option<usize>) {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.tracer.set_limit(limit))
+}
+
+/// Returns the operations traced since the last [`clear_trace`] or [`take_trace`] in order.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::env::{self, DefaultEnvTypes, test::TraceOp};
+/// # use ink_primitives::Key;
+/// env::test::run_test::<DefaultEnvTypes, _>(|_| {
+///     env::test::set_tracing(true);
+///     env::set_contract_storage(Key([0x42; 32]), &1_u32);
+///     let trace = env::test::trace();
+///     assert_eq!(trace.len(), 1);
+///     assert_eq!(trace[0].depth, 0);
+///     match trace[0].op {
+///         TraceOp::StorageWrite { len, .. } => assert_eq!(len, 4),
+///         _ => panic!("expected a storage write"),
+///     }
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn trace() -> Vec<TraceEvent> {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.tracer.events().cloned().collect()
+    })
+}
+
+/// Removes and returns the traced operations in order.
+///
+/// # Note
+///
+/// Use this in order to inspect the trace of long running tests piecewise.
+pub fn take_trace() -> Vec<TraceEvent> {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.tracer.drain())
+}
+
+/// Returns the traced operations as a call tree with one operation per line.
+///
+/// # Note
+///
+/// Operations within a call are indented below the call.
+/// Use e.g. `println!("{}", env::test::trace_tree())` to inspect the trace
+/// of a failing test.
+pub fn trace_tree() -> String {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance
+            .tracer
+            .events()
+            .map(|event| format!("{}\n", event))
+            .collect()
+    })
+}
+
+/// Removes all traced operations.
+pub fn clear_trace() {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.tracer.clear())
+}
+
+/// Injects the fault into the `nth` call of the host function from now on.
+///
+/// # Note
+///
+/// Supported host functions are [`HostFn::SetStorage`], [`HostFn::Transfer`],
+/// [`HostFn::Call`] and [`HostFn::Instantiate`]. The calls are counted across
+/// all executed contracts and the fault is injected only once.
+/// The writes of storage batches are counted as [`HostFn::SetStorage`]
+/// one by one.
+///
+/// # Panics
+///
+/// If `nth` is zero.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::env::{self, DefaultEnvTypes, EnvError};
+/// # use ink_core::env::test::{Fault, HostFn};
+/// env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+///     env::test::set_account_balance::<DefaultEnvTypes>(
+///         env::account_id::<DefaultEnvTypes>()?,
+///         100,
+///     )?;
+///     env::test::inject_fault(HostFn::Transfer, 2, Fault::Fail);
+///     assert_eq!(env::transfer::<DefaultEnvTypes>(accounts.bob, 10), Ok(()));
+///     assert_eq!(
+///         env::transfer::<DefaultEnvTypes>(accounts.bob, 10),
+///         Err(EnvError::TransferCallFailed)
+///     );
+///     assert_eq!(env::transfer::<DefaultEnvTypes>(accounts.bob, 10), Ok(()));
+///     Ok(())
+/// })
+/// .unwrap();
+/// ```
+pub fn inject_fault(host_fn: HostFn, nth: u32, fault: Fault) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.faults.plan(host_fn, nth, fault)
+    })
+}
+
+/// Removes all faults that have not yet been injected.
+pub fn clear_faults() {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.faults.clear())
+}
@@ -0,0 +1,179 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    test_api::{
+        self,
+        DefaultAccounts,
+    },
+    EnvInstance,
+    OnInstance,
+};
+use crate::env::{
+    EnvTypes,
+    Result,
+};
+
+/// Returns `true` if the current thread runs a test set up by [`TestSetup`].
+///
+/// # Note
+///
+/// Contracts instantiated by such a test must not initialize the off-chain
+/// environment again since that would discard the configured setup.
+pub fn is_running_test_setup() -> bool {
+    <EnvInstance as OnInstance>::on_instance(|instance| instance.is_running_test_setup)
+}
+
+/// Resets [`is_running_test_setup`] once the test has finished, even if it panics.
+struct RunningGuard;
+
+impl RunningGuard {
+    fn new() -> Self {
+        set_running_test_setup(true);
+        Self
+    }
+}
+
+impl Drop for RunningGuard {
+    fn drop(&mut self) {
+        set_running_test_setup(false);
+    }
+}
+
+fn set_running_test_setup(is_running: bool) {
+    <EnvInstance as OnInstance>::on_instance(|instance| {
+        instance.is_running_test_setup = is_running;
+    })
+}
+
+/// Sets up a fresh off-chain environment for a single test.
+///
+/// This is what `#[ink_lang::test]` uses to run its tests.
+///
+/// # Note
+///
+/// The environment is initialized like [`test_api::initialize_as_default`]
+/// but starts at block number `1` unless configured otherwise.
+///
+/// # Example
+///
+/// ```
+/// # use ink_core::env::{self, test::TestSetup, DefaultEnvTypes};
+/// let bob = env::test::default_accounts::<DefaultEnvTypes>().unwrap().bob;
+/// TestSetup::<DefaultEnvTypes>::new()
+///     .caller(bob)
+///     .balance(bob, 1000)
+///     .run(|accounts| {
+///         assert_eq!(env::caller::<DefaultEnvTypes>()?, accounts.bob);
+///         assert_eq!(env::test::get_account_balance::<DefaultEnvTypes>(bob)?, 1000);
+///         assert_eq!(env::block_number::<DefaultEnvTypes>()?.get(), 1);
+///         Ok(())
+///     })
+///     .unwrap();
+/// ```
+pub struct TestSetup<T>
+where
+    T: EnvTypes,
+{
+    block_number: T::BlockNumber,
+    caller: Option<T::AccountId>,
+    value: Option<T::Balance>,
+    balances: Vec<(T::AccountId, T::Balance)>,
+}
+
+impl<T> Default for TestSetup<T>
+where
+    T: EnvTypes,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> TestSetup<T>
+where
+    T: EnvTypes,
+{
+    /// Creates a new test setup using the default configuration.
+    pub fn new() -> Self {
+        Self {
+            block_number: T::BlockNumber::from(1),
+            caller: None,
+            value: None,
+            balances: Vec::new(),
+        }
+    }
+
+    /// Sets the number of the block the test starts at.
+    pub fn block_number(mut self, block_number: T::BlockNumber) -> Self {
+        self.block_number = block_number;
+        self
+    }
+
+    /// Sets the caller of the executed contract.
+    pub fn caller(mut self, caller: T::AccountId) -> Self {
+        self.caller = Some(caller);
+        self
+    }
+
+    /// Sets the value transferred to the executed contract.
+    pub fn value_transferred(mut self, value: T::Balance) -> Self {
+        self.value = Some(value);
+        self
+    }
+
+    /// Sets the balance of an existing account.
+    ///
+    /// # Note
+    ///
+    /// Can be called multiple times in order to set several balances.
+    pub fn balance(mut self, account_id: T::AccountId, balance: T::Balance) -> Self {
+        self.balances.push((account_id, balance));
+        self
+    }
+
+    /// Runs the test in a fresh off-chain environment set up as configured.
+    ///
+    /// # Note
+    ///
+    /// Everything the current thread has set up in the off-chain environment
+    /// before, including registered handlers and snapshots, is discarded.
+    ///
+    /// # Errors
+    ///
+    /// - If the configuration refers to an account that does not exist.
+    /// - If the test itself returns an error.
+    pub fn run<F>(self, f: F) -> Result<()>
+    where
+        F: FnOnce(DefaultAccounts<T>) -> Result<()>,
+    {
+        <EnvInstance as OnInstance>::on_instance(|instance| {
+            *instance = EnvInstance::uninitialized();
+        });
+        test_api::initialize_as_default::<T>()?;
+        test_api::set_block_number::<T>(self.block_number)?;
+        if let Some(caller) = self.caller {
+            test_api::set_caller::<T>(caller)?;
+        }
+        if let Some(value) = self.value {
+            test_api::set_value_transferred::<T>(value)?;
+        }
+        for (account_id, balance) in self.balances {
+            test_api::set_account_balance::<T>(account_id, balance)?;
+        }
+        let accounts = test_api::default_accounts::<T>()?;
+        let _guard = RunningGuard::new();
+        f(accounts)
+    }
+}
@@ -12,9 +12,14 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::OffChainError;
 use crate::{
     env,
-    env::Result,
+    env::{
+        ChainExtension,
+        EnvError,
+        Result,
+    },
 };
 use ink_primitives::Key;
 
@@ -68,3 +73,1190 @@ fn key_add_sub() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn call_chain_extension() -> Result<()> {
+    enum Double {}
+    impl ChainExtension for Double {
+        const FUNC_ID: u32 = 42;
+        type Input = u32;
+        type Output = u64;
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert_eq!(
+            Double::call(&5),
+            Err(EnvError::OffChain(
+                OffChainError::UnregisteredChainExtension(42)
+            )),
+        );
+        env::test::register_chain_extension::<Double, _>(|input| {
+            if input == 0 {
+                return Err(1)
+            }
+            Ok(input as u64 * 2)
+        });
+        assert_eq!(Double::call(&5), Ok(10));
+        assert_eq!(Double::call(&0), Err(EnvError::ChainExtensionFailState(1)));
+        assert_eq!(env::call_chain_extension::<u32, u64>(42, &21), Ok(42));
+        // Raw handlers operate on the encoded input and output.
+        env::test::register_raw_chain_extension(42, |input| {
+            match input {
+                [] => Err(2),
+                _ => Ok(input.repeat(2)),
+            }
+        });
+        assert_eq!(Double::call(&1), Ok(1 << 32 | 1));
+        assert_eq!(
+            env::call_chain_extension::<(), u64>(42, &()),
+            Err(EnvError::ChainExtensionFailState(2))
+        );
+        assert!(env::call_chain_extension::<u8, u64>(42, &1).is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn emit_event_with_topics() -> Result<()> {
+    use env::{
+        DefaultEnvTypes,
+        Hash,
+        Topics,
+    };
+    #[derive(scale::Encode)]
+    struct Transferred {
+        from: [u8; 32],
+        data: [u8; 40],
+        value: u32,
+    }
+    impl Topics<DefaultEnvTypes> for Transferred {
+        fn topics(&self) -> Vec<Hash> {
+            vec![
+                env::topic::<DefaultEnvTypes, _>(&self.from),
+                env::topic::<DefaultEnvTypes, _>(&self.data),
+            ]
+        }
+    }
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let event = Transferred {
+            from: [0x01; 32],
+            data: [0x02; 40],
+            value: 42,
+        };
+        let expected_data = scale::Encode::encode(&event);
+        env::emit_event::<DefaultEnvTypes, _>(event);
+        let recorded = env::test::recorded_events().collect::<Vec<_>>();
+        assert_eq!(recorded.len(), 1);
+        assert_eq!(recorded[0].data, expected_data);
+        let topics = recorded[0].decoded_topics::<DefaultEnvTypes>()?;
+        // Values that fit into a hash are used as topic directly.
+        assert_eq!(topics[0], Hash::from([0x01; 32]));
+        // Oversized values are hashed.
+        assert_eq!(
+            topics[1],
+            Hash::from(env::hash_bytes::<env::hash::Blake2x256>(
+                &scale::Encode::encode(&[0x02_u8; 40])
+            )),
+        );
+        assert_eq!(topics.len(), 2);
+        assert_eq!(
+            recorded[0].decoded_data::<([u8; 32], [u8; 40], u32)>()?,
+            ([0x01; 32], [0x02; 40], 42)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn topic_pads_small_values() {
+    use env::{
+        DefaultEnvTypes,
+        Hash,
+    };
+    let mut expected = [0x00; 32];
+    expected[..4].copy_from_slice(&42_u32.to_le_bytes());
+    assert_eq!(
+        env::topic::<DefaultEnvTypes, _>(&42_u32),
+        Hash::from(expected)
+    );
+}
+
+#[test]
+fn transfer_updates_balances() -> Result<()> {
+    use env::DefaultEnvTypes;
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        env::test::set_account_balance::<DefaultEnvTypes>(contract, 100)?;
+        let eve_balance =
+            env::test::get_account_balance::<DefaultEnvTypes>(accounts.eve)?;
+        env::transfer::<DefaultEnvTypes>(accounts.eve, 30)?;
+        assert_eq!(env::balance::<DefaultEnvTypes>(), Ok(70));
+        assert_eq!(
+            env::test::get_account_balance::<DefaultEnvTypes>(accounts.eve),
+            Ok(eve_balance + 30),
+        );
+        // Transfers exceeding the contract balance fail without side effects.
+        assert_eq!(
+            env::transfer::<DefaultEnvTypes>(accounts.eve, 71),
+            Err(EnvError::TransferCallFailed),
+        );
+        assert_eq!(env::balance::<DefaultEnvTypes>(), Ok(70));
+        Ok(())
+    })
+}
+
+#[test]
+fn terminate_contract_transfers_remaining_balance() -> Result<()> {
+    use env::DefaultEnvTypes;
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        env::test::set_account_balance::<DefaultEnvTypes>(contract, 100)?;
+        env::test::assert_contract_termination::<DefaultEnvTypes, _>(
+            || env::terminate_contract::<DefaultEnvTypes>(accounts.charlie),
+            accounts.charlie,
+            100,
+        );
+        assert_eq!(
+            env::test::get_account_balance::<DefaultEnvTypes>(accounts.charlie),
+            Ok(100),
+        );
+        assert!(env::test::get_account_balance::<DefaultEnvTypes>(contract).is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn chain_spec_is_configurable() -> Result<()> {
+    use env::DefaultEnvTypes;
+    use ink_primitives::{
+        BlockNumber,
+        Duration,
+        Timestamp,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        assert_eq!(env::weight_to_fee::<DefaultEnvTypes>(10), Ok(1_000));
+        env::test::set_gas_price::<DefaultEnvTypes>(3)?;
+        assert_eq!(env::gas_price::<DefaultEnvTypes>(), Ok(3));
+        assert_eq!(
+            env::weight_to_fee::<DefaultEnvTypes>(1 << 40),
+            Ok((1 << 40) * 3),
+        );
+        env::test::set_minimum_balance::<DefaultEnvTypes>(1)?;
+        assert_eq!(env::minimum_balance::<DefaultEnvTypes>(), Ok(1));
+        env::test::set_tombstone_deposit::<DefaultEnvTypes>(2)?;
+        assert_eq!(env::tombstone_deposit::<DefaultEnvTypes>(), Ok(2));
+        env::test::set_block_time::<DefaultEnvTypes>(10)?;
+        let timestamp = env::block_timestamp::<DefaultEnvTypes>()?;
+        let block_number = env::block_number::<DefaultEnvTypes>()?;
+        env::test::advance_block::<DefaultEnvTypes>()?;
+        assert_eq!(
+            env::block_timestamp::<DefaultEnvTypes>(),
+            Ok(timestamp + Duration::from_millis(10))
+        );
+        assert_eq!(env::block_number::<DefaultEnvTypes>(), Ok(block_number + 1));
+        // Jumping to another block and time continues from there on.
+        env::test::set_block_number::<DefaultEnvTypes>(1000)?;
+        env::test::set_block_timestamp::<DefaultEnvTypes>(50_000)?;
+        assert_eq!(
+            env::block_number::<DefaultEnvTypes>(),
+            Ok(BlockNumber::new(1000))
+        );
+        assert_eq!(
+            env::block_timestamp::<DefaultEnvTypes>(),
+            Ok(Timestamp::from_millis(50_000))
+        );
+        env::test::advance_block::<DefaultEnvTypes>()?;
+        assert_eq!(
+            env::block_number::<DefaultEnvTypes>(),
+            Ok(BlockNumber::new(1001))
+        );
+        assert_eq!(
+            env::block_timestamp::<DefaultEnvTypes>(),
+            Ok(Timestamp::from_millis(50_010))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn random_is_controllable() -> Result<()> {
+    use env::{
+        DefaultEnvTypes,
+        Hash,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        env::test::set_random_seed::<DefaultEnvTypes>(Hash::from([0x01; 32]))?;
+        let random = env::random::<DefaultEnvTypes>(b"subject")?;
+        env::test::advance_block::<DefaultEnvTypes>()?;
+        // The fixed seed is also used by all future blocks.
+        assert_eq!(env::random::<DefaultEnvTypes>(b"subject"), Ok(random));
+        assert_ne!(env::random::<DefaultEnvTypes>(b"other"), Ok(random));
+        env::test::register_random_handler::<DefaultEnvTypes, _>(|subject| {
+            Hash::from([subject.len() as u8; 32])
+        });
+        assert_eq!(
+            env::random::<DefaultEnvTypes>(b"subject"),
+            Ok(Hash::from([0x07; 32])),
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn weight_left_and_fees() -> Result<()> {
+    use env::{
+        DefaultEnvTypes,
+        Weight,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let weight_left = env::weight_left::<DefaultEnvTypes>()?;
+        assert_eq!(
+            u128::from(weight_left.get()),
+            env::gas_left::<DefaultEnvTypes>()?
+        );
+        let per_item = Weight::new(100_000);
+        let affordable = (0..)
+            .scan(weight_left, |left, _| {
+                *left = left.checked_sub(per_item)?;
+                Some(())
+            })
+            .count();
+        assert_eq!(affordable, 5);
+        let max_weight = Weight::new(u64::max_value());
+        assert_eq!(max_weight.checked_add(Weight::new(1)), None);
+        assert_eq!(per_item.checked_mul(3), Some(Weight::new(300_000)));
+        assert_eq!(per_item.saturating_sub(weight_left), Weight::new(0));
+        assert_eq!(per_item.to_fee::<DefaultEnvTypes>(), Ok(10_000_000));
+        // Gas left beyond the range of a weight is not truncated.
+        env::test::set_gas::<DefaultEnvTypes>(u128::from(max_weight.get()) + 1)?;
+        assert!(env::weight_left::<DefaultEnvTypes>().is_err());
+        env::test::set_gas::<DefaultEnvTypes>(u128::from(max_weight.get()))?;
+        assert_eq!(env::weight_left::<DefaultEnvTypes>(), Ok(max_weight));
+        Ok(())
+    })
+}
+
+#[test]
+fn custom_env_types() -> Result<()> {
+    /// Environmental types of a chain with 20 byte accounts and `u64` balances.
+    enum CustomEnvTypes {}
+    impl env::EnvTypes for CustomEnvTypes {
+        type AccountId = [u8; 20];
+        type Balance = u64;
+        type Hash = env::Hash;
+        type Timestamp = u64;
+        type BlockNumber = u32;
+        type Call = ();
+    }
+    env::test::run_test::<CustomEnvTypes, _>(|accounts| {
+        assert_eq!(accounts.alice, [0x01; 20]);
+        assert_eq!(env::caller::<CustomEnvTypes>(), Ok([0x01; 20]));
+        assert_eq!(env::account_id::<CustomEnvTypes>(), Ok([0x07; 20]));
+        env::test::set_account_balance::<CustomEnvTypes>([0x07; 20], 100)?;
+        env::transfer::<CustomEnvTypes>(accounts.eve, 30)?;
+        assert_eq!(env::balance::<CustomEnvTypes>(), Ok(70));
+        env::test::advance_block::<CustomEnvTypes>()?;
+        assert_eq!(
+            env::block_number::<CustomEnvTypes>(),
+            Ok(ink_primitives::BlockNumber::new(1))
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn gas_to_balance_saturates() {
+    use super::impls::gas_to_balance;
+    /// Environmental types of a chain with `u32` balances.
+    enum SmallBalanceEnvTypes {}
+    impl env::EnvTypes for SmallBalanceEnvTypes {
+        type AccountId = env::AccountId;
+        type Balance = u32;
+        type Hash = env::Hash;
+        type Timestamp = u64;
+        type BlockNumber = u64;
+        type Call = ();
+    }
+    assert_eq!(gas_to_balance::<SmallBalanceEnvTypes>(42), 42);
+    assert_eq!(
+        gas_to_balance::<SmallBalanceEnvTypes>(u64::from(u32::max_value())),
+        u32::max_value()
+    );
+    assert_eq!(
+        gas_to_balance::<SmallBalanceEnvTypes>(1 << 32),
+        u32::max_value()
+    );
+    assert_eq!(
+        gas_to_balance::<env::DefaultEnvTypes>(u64::max_value()),
+        u128::from(u64::max_value())
+    );
+}
+
+#[test]
+fn return_value_with_revert_flag() -> Result<()> {
+    use env::ReturnFlags;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert!(ReturnFlags::REVERT.is_reverted());
+        assert!(!ReturnFlags::REVERT.set_reverted(false).is_reverted());
+        env::test::assert_return_value(
+            || env::return_value(ReturnFlags::REVERT, &Err::<(), u8>(42)),
+            ReturnFlags::REVERT,
+            Err::<(), u8>(42),
+        );
+        env::test::assert_return_value(
+            || env::return_value(ReturnFlags::default(), &1337_u32),
+            ReturnFlags::default(),
+            1337_u32,
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn caller_is_origin_and_call_depth() -> Result<()> {
+    use env::{
+        call::{
+            CallData,
+            Selector,
+        },
+        DefaultEnvTypes,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        assert!(env::caller_is_origin());
+        assert_eq!(env::call_depth(), 1);
+        // Emulate a call of the executed contract into another contract.
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        env::test::push_execution_context::<DefaultEnvTypes>(
+            contract,
+            accounts.frank,
+            100_000,
+            0,
+            CallData::new(Selector::from_label("call")),
+        );
+        assert!(!env::caller_is_origin());
+        assert_eq!(env::call_depth(), 2);
+        env::test::pop_execution_context();
+        assert!(env::caller_is_origin());
+        assert_eq!(env::call_depth(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn reserved_balance_is_separate() -> Result<()> {
+    use env::DefaultEnvTypes;
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        assert_eq!(env::reserved_balance::<DefaultEnvTypes>(), Ok(0));
+        env::test::set_account_balance::<DefaultEnvTypes>(contract, 100)?;
+        env::test::set_account_reserved_balance::<DefaultEnvTypes>(contract, 40)?;
+        assert_eq!(env::balance::<DefaultEnvTypes>(), Ok(100));
+        assert_eq!(env::reserved_balance::<DefaultEnvTypes>(), Ok(40));
+        // The reserved balance is not available for transfers.
+        env::transfer::<DefaultEnvTypes>(accounts.eve, 100)?;
+        assert_eq!(env::balance::<DefaultEnvTypes>(), Ok(0));
+        assert_eq!(env::reserved_balance::<DefaultEnvTypes>(), Ok(40));
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_batch() -> Result<()> {
+    use env::StorageBatch;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key_a = Key([0x01; 32]);
+        let key_b = Key([0x02; 32]);
+        env::set_contract_storage(key_b, &1_u32);
+        let mut batch = StorageBatch::new();
+        batch.set(key_a, &42_u32);
+        batch.set(key_a, &1337_u32);
+        batch.clear(key_b);
+        assert_eq!(batch.len(), 3);
+        env::set_contract_storage_batch(&batch);
+        assert_eq!(env::get_contract_storage::<u32>(key_a), Some(Ok(1337)));
+        assert_eq!(env::get_contract_storage::<u32>(key_b), None);
+        let values = env::get_contract_storage_batch(&[key_a, key_b])?;
+        let encoded = scale::Encode::encode(&1337_u32);
+        assert_eq!(
+            values.iter().collect::<Vec<_>>(),
+            vec![(key_a, Some(&encoded[..])), (key_b, None)],
+        );
+        // Applying the returned batch restores the values.
+        env::clear_contract_storage(key_a);
+        env::set_contract_storage(key_b, &1_u32);
+        env::set_contract_storage_batch(&values);
+        assert_eq!(env::get_contract_storage::<u32>(key_a), Some(Ok(1337)));
+        assert_eq!(env::get_contract_storage::<u32>(key_b), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_batch_respects_buffer_capacity() -> Result<()> {
+    use env::{
+        StorageBatch,
+        BUFFER_CAPACITY,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        // Batches exceeding the static buffer are submitted in parts.
+        let value = vec![0x05_u8; BUFFER_CAPACITY / 2];
+        let mut batch = StorageBatch::new();
+        for n in 0..3 {
+            batch.set(Key([n; 32]), &value);
+        }
+        assert_eq!(batch.parts(BUFFER_CAPACITY).count(), 3);
+        env::set_contract_storage_batch(&batch);
+        for n in 0..3 {
+            assert_eq!(
+                env::get_contract_storage::<Vec<u8>>(Key([n; 32])),
+                Some(Ok(value.clone())),
+            );
+        }
+        // Entries exceeding the static buffer on their own trap
+        // the same as they do in the on-chain environment.
+        let mut batch = StorageBatch::new();
+        batch.set(Key([0x03; 32]), &vec![0x05_u8; BUFFER_CAPACITY]);
+        let trapped =
+            std::panic::catch_unwind(|| env::set_contract_storage_batch(&batch));
+        assert!(trapped.is_err());
+        assert_eq!(env::get_contract_storage::<Vec<u8>>(Key([0x03; 32])), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn storage_deposit_estimate() -> Result<()> {
+    use env::{
+        DefaultEnvTypes,
+        FootprintDelta,
+        StorageBatch,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let key_a = Key([0x01; 32]);
+        let key_b = Key([0x02; 32]);
+        let key_c = Key([0x03; 32]);
+        env::set_contract_storage(key_a, &1_u32);
+        env::set_contract_storage(key_b, &2_u16);
+        let mut batch = StorageBatch::new();
+        batch.set(key_a, &3_u64);
+        batch.clear(key_b);
+        batch.set(key_c, &4_u8);
+        batch.set(key_c, &5_u16);
+        assert_eq!(
+            env::storage_footprint_delta(&batch),
+            Ok(FootprintDelta {
+                added_items: 1,
+                removed_items: 1,
+                added_bytes: 4 + 2,
+                removed_bytes: 2,
+            })
+        );
+        assert_eq!(
+            env::estimate_storage_deposit::<DefaultEnvTypes>(&batch, 100, 10),
+            Ok(40)
+        );
+        // Shrinking the footprint requires no deposit.
+        let mut batch = StorageBatch::new();
+        batch.clear(key_a);
+        assert_eq!(
+            env::estimate_storage_deposit::<DefaultEnvTypes>(&batch, 100, 10),
+            Ok(0)
+        );
+        // Nothing has been written by the estimation.
+        assert_eq!(env::get_contract_storage::<u32>(key_a), Some(Ok(1)));
+        assert_eq!(env::get_contract_storage::<u32>(key_c), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn code_hash_of_contracts() -> Result<()> {
+    use env::{
+        DefaultEnvTypes,
+        Hash,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        let code_hash = Hash::from([0x42; 32]);
+        env::test::set_contract_code_hash::<DefaultEnvTypes>(contract, code_hash)?;
+        assert_eq!(env::own_code_hash::<DefaultEnvTypes>(), Ok(code_hash));
+        assert_eq!(
+            env::code_hash::<DefaultEnvTypes>(&contract),
+            Ok(Some(code_hash))
+        );
+        // User accounts and unknown accounts have no code hash.
+        assert_eq!(env::code_hash::<DefaultEnvTypes>(&accounts.alice), Ok(None));
+        assert_eq!(
+            env::code_hash::<DefaultEnvTypes>(&[0xFF; 32].into()),
+            Ok(None)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn transferred_value_guards() -> Result<()> {
+    use env::{
+        call::{
+            CallData,
+            Selector,
+        },
+        DefaultEnvTypes,
+        EnvError,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        let push_call = |value| {
+            env::test::push_execution_context::<DefaultEnvTypes>(
+                accounts.alice,
+                contract,
+                100_000,
+                value,
+                CallData::new(Selector::from_label("pay")),
+            )
+        };
+        push_call(0);
+        assert_eq!(env::ensure_no_value::<DefaultEnvTypes>(), Ok(()));
+        assert_eq!(
+            env::ensure_min_value::<DefaultEnvTypes>(1),
+            Err(EnvError::InsufficientTransferredValue)
+        );
+        env::test::pop_execution_context();
+        push_call(10);
+        assert_eq!(env::transferred_value::<DefaultEnvTypes>(), Ok(10));
+        assert_eq!(
+            env::ensure_no_value::<DefaultEnvTypes>(),
+            Err(EnvError::UnexpectedTransferredValue)
+        );
+        assert_eq!(env::ensure_min_value::<DefaultEnvTypes>(10), Ok(()));
+        assert_eq!(
+            env::ensure_min_value::<DefaultEnvTypes>(11),
+            Err(EnvError::InsufficientTransferredValue)
+        );
+        env::test::pop_execution_context();
+        Ok(())
+    })
+}
+
+#[test]
+fn modify_execution_context() -> Result<()> {
+    use env::DefaultEnvTypes;
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        env::test::set_caller::<DefaultEnvTypes>(accounts.bob)?;
+        env::test::set_callee::<DefaultEnvTypes>(accounts.charlie)?;
+        env::test::set_value_transferred::<DefaultEnvTypes>(1337)?;
+        env::test::set_gas::<DefaultEnvTypes>(42)?;
+        assert_eq!(env::caller::<DefaultEnvTypes>(), Ok(accounts.bob));
+        assert_eq!(env::account_id::<DefaultEnvTypes>(), Ok(accounts.charlie));
+        assert_eq!(env::transferred_balance::<DefaultEnvTypes>(), Ok(1337));
+        assert_eq!(env::gas_left::<DefaultEnvTypes>(), Ok(42));
+        // Without any execution context there is nothing to modify.
+        env::test::pop_execution_context();
+        assert!(env::test::set_caller::<DefaultEnvTypes>(accounts.bob).is_err());
+        Ok(())
+    })
+}
+
+#[test]
+fn transfer_in_moves_balance() -> Result<()> {
+    use env::{
+        DefaultEnvTypes,
+        EnvError,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        env::test::set_caller::<DefaultEnvTypes>(accounts.bob)?;
+        env::test::set_account_balance::<DefaultEnvTypes>(accounts.bob, 100)?;
+        env::test::set_account_balance::<DefaultEnvTypes>(contract, 0)?;
+        env::test::transfer_in::<DefaultEnvTypes>(60)?;
+        assert_eq!(env::transferred_balance::<DefaultEnvTypes>(), Ok(60));
+        assert_eq!(env::balance::<DefaultEnvTypes>(), Ok(60));
+        assert_eq!(
+            env::test::get_account_balance::<DefaultEnvTypes>(accounts.bob),
+            Ok(40)
+        );
+        // The caller cannot transfer more than its free balance.
+        assert_eq!(
+            env::test::transfer_in::<DefaultEnvTypes>(41),
+            Err(EnvError::TransferCallFailed)
+        );
+        assert_eq!(env::balance::<DefaultEnvTypes>(), Ok(60));
+        Ok(())
+    })
+}
+
+#[test]
+fn count_storage_ops_and_cells() -> Result<()> {
+    use env::DefaultEnvTypes;
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        let (value, ops) =
+            env::test::count_storage_ops::<DefaultEnvTypes, _, _>(&contract, || {
+                env::set_contract_storage(Key([0x01; 32]), &1_u8);
+                env::set_contract_storage(Key([0x02; 32]), &2_u8);
+                env::clear_contract_storage(Key([0x01; 32]));
+                env::get_contract_storage::<u8>(Key([0x02; 32]))
+            })?;
+        assert_eq!(value, Some(Ok(2)));
+        assert_eq!(
+            ops,
+            env::test::StorageOps {
+                reads: 1,
+                writes: 3,
+                clears: 1,
+            }
+        );
+        assert_eq!(
+            env::test::count_used_storage_cells::<DefaultEnvTypes>(&contract),
+            Ok(1)
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn snapshot_and_restore() -> Result<()> {
+    use env::{
+        DefaultEnvTypes,
+        Hash,
+        Topics,
+    };
+    #[derive(scale::Encode)]
+    struct Incremented;
+    impl Topics<DefaultEnvTypes> for Incremented {
+        fn topics(&self) -> Vec<Hash> {
+            Vec::new()
+        }
+    }
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let contract = env::account_id::<DefaultEnvTypes>()?;
+        let key = Key([0x42; 32]);
+        env::set_contract_storage(key, &1_u32);
+        env::test::set_account_balance::<DefaultEnvTypes>(contract, 100)?;
+        let fixture = env::test::snapshot();
+        for value in 2..5_u32 {
+            env::set_contract_storage(key, &value);
+            env::transfer::<DefaultEnvTypes>(accounts.eve, 10)?;
+            env::emit_event::<DefaultEnvTypes, _>(Incremented);
+            env::test::advance_block::<DefaultEnvTypes>()?;
+            env::test::restore(fixture)?;
+            assert_eq!(env::get_contract_storage::<u32>(key), Some(Ok(1)));
+            assert_eq!(
+                env::test::get_account_balance::<DefaultEnvTypes>(accounts.eve),
+                Ok(0)
+            );
+            assert_eq!(env::test::recorded_events().count(), 0);
+            assert_eq!(
+                env::block_number::<DefaultEnvTypes>(),
+                Ok(ink_primitives::BlockNumber::new(0))
+            );
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn call_registered_contracts() -> Result<()> {
+    use env::{
+        call::{
+            CallParams,
+            FromAccountId,
+            InstantiateParams,
+            Selector,
+        },
+        test::CallKind,
+        AccountId,
+        DefaultEnvTypes,
+        Hash,
+    };
+    struct Counter(AccountId);
+    impl FromAccountId<DefaultEnvTypes> for Counter {
+        fn from_account_id(account_id: AccountId) -> Self {
+            Self(account_id)
+        }
+    }
+    let key = Key([0x01; 32]);
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let code_hash = Hash::from([0x42; 32]);
+        // A counter that is initialized with a value and incremented by calls.
+        env::test::register_contract::<DefaultEnvTypes, _>(code_hash, move |kind| {
+            let input = env::input().map_err(|_| 1)?;
+            let by =
+                <u32 as scale::Decode>::decode(&mut input.params()).map_err(|_| 2)?;
+            if kind == CallKind::Instantiate {
+                env::set_contract_storage(key, &by);
+                return Ok(())
+            }
+            let value = env::get_contract_storage::<u32>(key)
+                .expect("the counter has been initialized")
+                .expect("the counter is a valid u32");
+            env::set_contract_storage(key, &(value + by));
+            match by {
+                0 => Err(3),
+                99 => panic!("the counter has trapped"),
+                _ => {
+                    env::output(&(value + by));
+                    Ok(())
+                }
+            }
+        });
+        let instantiate = |init_value: u32| {
+            InstantiateParams::<DefaultEnvTypes, Counter>::build(Selector::from_label(
+                "new",
+            ))
+            .using_code(code_hash)
+            .push_arg(&init_value)
+            .instantiate()
+        };
+        let increment = |callee: AccountId, by: u32| {
+            CallParams::<DefaultEnvTypes, u32>::eval(callee, Selector::from_label("inc"))
+                .push_arg(&by)
+                .fire()
+        };
+        let first = instantiate(10)?.0;
+        let second = instantiate(20)?.0;
+        assert_ne!(first, second);
+        assert_eq!(increment(first, 5), Ok(15));
+        assert_eq!(increment(second, 1), Ok(21));
+        // Failed and trapped calls revert their state changes.
+        assert_eq!(increment(first, 0), Err(EnvError::ContractCallFailState(3)));
+        assert_eq!(increment(first, 99), Err(EnvError::ContractCallTrapped));
+        assert_eq!(increment(first, 1), Ok(16));
+        // Instantiating at the address of an existing account fails.
+        match instantiate(10) {
+            Err(EnvError::OffChain(OffChainError::Account(
+                super::AccountError::AccountAlreadyExists(_),
+            ))) => (),
+            _ => panic!("expected instantiation at an existing account to fail"),
+        }
+        // Only registered contracts can be called.
+        assert_eq!(increment(accounts.alice, 1), Err(EnvError::NotAContract));
+        // The storage of the executed contract is untouched.
+        assert_eq!(env::get_contract_storage::<u32>(key), None);
+        assert_eq!(env::call_depth(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn call_registered_contracts_recursively() -> Result<()> {
+    use env::{
+        call::{
+            CallParams,
+            Selector,
+        },
+        AccountId,
+        DefaultEnvTypes,
+        Hash,
+    };
+    let key = Key([0x01; 32]);
+    let count_down = |callee: AccountId, n: u32| {
+        CallParams::<DefaultEnvTypes, ()>::invoke(callee, Selector::from_label("down"))
+            .push_arg(&n)
+            .fire()
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let code_hash = Hash::from([0x42; 32]);
+        let callee = AccountId::from([0x10; 32]);
+        env::test::add_contract_account::<DefaultEnvTypes>(callee, code_hash)?;
+        // Adds `n` to the stored total and calls itself with `n - 1`.
+        // The innermost call fails and has its changes reverted.
+        env::test::register_contract::<DefaultEnvTypes, _>(code_hash, move |_| {
+            let input = env::input().map_err(|_| 1)?;
+            let n = <u32 as scale::Decode>::decode(&mut input.params()).map_err(|_| 2)?;
+            let total = env::get_contract_storage::<u32>(key)
+                .unwrap_or(Ok(0))
+                .map_err(|_| 3)?;
+            if n == 0 {
+                env::set_contract_storage(key, &u32::max_value());
+                return Err(4)
+            }
+            env::set_contract_storage(key, &(total + n));
+            let own = env::account_id::<DefaultEnvTypes>().map_err(|_| 5)?;
+            assert_eq!(
+                count_down(own, n - 1).is_err(),
+                n == 1,
+                "only the innermost call fails"
+            );
+            Ok(())
+        });
+        count_down(callee, 3)?;
+        env::test::set_callee::<DefaultEnvTypes>(callee)?;
+        assert_eq!(env::get_contract_storage::<u32>(key), Some(Ok(3 + 2 + 1)));
+        Ok(())
+    })
+}
+
+#[test]
+fn gas_metering() -> Result<()> {
+    use env::test::{
+        GasWeights,
+        HostFn,
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key([0x42; 32]);
+        env::test::set_gas_weights(
+            GasWeights::uniform(1)
+                .host_fn(HostFn::SetStorage, 100)
+                .host_fn(HostFn::GetStorage, 50)
+                .per_byte_stored(10)
+                .per_byte_loaded(5),
+        );
+        env::test::reset_gas_consumed();
+        env::set_contract_storage(key, &[0x01_u8; 3]);
+        assert_eq!(env::test::gas_consumed(), 100 + 3 * 10);
+        env::test::reset_gas_consumed();
+        assert_eq!(
+            env::get_contract_storage::<[u8; 3]>(key),
+            Some(Ok([0x01; 3]))
+        );
+        assert_eq!(env::test::gas_consumed(), 50 + 3 * 5);
+        // Missing entries only charge for the host function call.
+        env::test::reset_gas_consumed();
+        assert_eq!(env::get_contract_storage::<u8>(Key([0x00; 32])), None);
+        env::clear_contract_storage(key);
+        let _ = env::caller::<env::DefaultEnvTypes>()?;
+        assert_eq!(env::test::gas_consumed(), 50 + 1 + 1);
+        // Host functions reusing each other are charged only once.
+        env::test::reset_gas_consumed();
+        env::transfer::<env::DefaultEnvTypes>([0x01; 32].into(), 0)?;
+        assert_eq!(env::test::gas_consumed(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn record_debug_messages() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert_eq!(env::test::debug_messages().next(), None);
+        env::debug_message("first");
+        env::println("printed");
+        crate::debug_println!("second {}", 2);
+        assert_eq!(
+            env::test::debug_messages().collect::<Vec<_>>(),
+            vec!["first", "second 2"],
+        );
+        assert_eq!(
+            env::test::recorded_printlns().collect::<Vec<_>>(),
+            vec!["printed"],
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn execution_context_builder() -> Result<()> {
+    use env::{
+        test::ExecutionContextBuilder,
+        DefaultEnvTypes,
+    };
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let key = Key([0x42; 32]);
+        let (caller, value, gas, number, timestamp, random, depth) =
+            ExecutionContextBuilder::<DefaultEnvTypes>::new()
+                .caller(accounts.bob)
+                .value(7)
+                .gas(100)
+                .block_number(42)
+                .block_timestamp(1337)
+                .random_seed([0x01; 32].into())
+                .run(|| {
+                    env::set_contract_storage(key, &1_u32);
+                    (
+                        env::caller::<DefaultEnvTypes>(),
+                        env::transferred_balance::<DefaultEnvTypes>(),
+                        env::gas_left::<DefaultEnvTypes>(),
+                        env::block_number::<DefaultEnvTypes>(),
+                        env::block_timestamp::<DefaultEnvTypes>(),
+                        env::random::<DefaultEnvTypes>(b"subject"),
+                        env::call_depth(),
+                    )
+                })?;
+        assert_eq!(caller?, accounts.bob);
+        assert_eq!(value?, 7);
+        assert_eq!(gas?, 100);
+        assert_eq!(number?, ink_primitives::BlockNumber::new(42));
+        assert_eq!(timestamp?, ink_primitives::Timestamp::from_millis(1337));
+        // The current execution context is replaced instead of nested.
+        assert_eq!(depth, 1);
+        // The same seed yields the same randomness.
+        let seeded = ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .random_seed([0x01; 32].into())
+            .run(|| env::random::<DefaultEnvTypes>(b"subject"))?;
+        assert_eq!(seeded?, random?);
+        // The previous context is restored while storage changes are kept.
+        assert_eq!(env::caller::<DefaultEnvTypes>()?, accounts.alice);
+        assert_eq!(env::transferred_balance::<DefaultEnvTypes>()?, 500);
+        assert_eq!(env::block_number::<DefaultEnvTypes>()?.get(), 0);
+        assert_eq!(env::get_contract_storage::<u32>(key), Some(Ok(1)));
+        // The context is also restored if the closure panics.
+        let result = std::panic::catch_unwind(|| {
+            ExecutionContextBuilder::<DefaultEnvTypes>::new()
+                .caller(accounts.bob)
+                .run(|| panic!("the closure panics"))
+        });
+        assert!(result.is_err());
+        assert_eq!(env::caller::<DefaultEnvTypes>()?, accounts.alice);
+        assert_eq!(env::call_depth(), 1);
+        Ok(())
+    })
+}
+
+#[test]
+fn max_cell_size() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let key = Key([0x42; 32]);
+        // A vector of 1000 bytes is encoded using 1002 bytes.
+        let value = vec![0x01_u8; 1000];
+        env::test::set_max_cell_size(Some(1001));
+        let oversized = std::panic::catch_unwind(|| {
+            env::set_contract_storage(key, &value);
+        });
+        assert!(oversized.is_err());
+        let mut batch = env::StorageBatch::new();
+        batch.set(key, &value);
+        let oversized = std::panic::catch_unwind(|| {
+            env::set_contract_storage_batch(&batch);
+        });
+        assert!(oversized.is_err());
+        assert_eq!(env::get_contract_storage::<Vec<u8>>(key), None);
+        env::test::set_max_cell_size(Some(1002));
+        env::set_contract_storage(key, &value);
+        env::test::set_max_cell_size(None);
+        env::set_contract_storage(key, &vec![0x01_u8; 100_000]);
+        Ok(())
+    })
+}
+
+#[test]
+fn trace_contract_calls() -> Result<()> {
+    use env::{
+        call::{
+            CallParams,
+            Selector,
+        },
+        test::TraceOp,
+        AccountId,
+        DefaultEnvTypes,
+        Hash,
+        Topics,
+    };
+    #[derive(scale::Encode)]
+    struct Stored;
+    impl Topics<DefaultEnvTypes> for Stored {
+        fn topics(&self) -> Vec<Hash> {
+            Vec::new()
+        }
+    }
+    let key = Key([0x01; 32]);
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let code_hash = Hash::from([0x42; 32]);
+        let callee = AccountId::from([0x10; 32]);
+        env::test::add_contract_account::<DefaultEnvTypes>(callee, code_hash)?;
+        env::test::register_contract::<DefaultEnvTypes, _>(code_hash, move |_| {
+            let input = env::input().map_err(|_| 1)?;
+            let value = <u32 as scale::Decode>::decode(&mut input.params()).unwrap();
+            let _ = env::get_contract_storage::<u32>(key);
+            env::set_contract_storage(key, &value);
+            env::emit_event::<DefaultEnvTypes, _>(Stored);
+            if value == 0 {
+                return Err(2)
+            }
+            env::output(&value);
+            Ok(())
+        });
+        let store = |value: u32| {
+            CallParams::<DefaultEnvTypes, ()>::invoke(
+                callee,
+                Selector::from_label("store"),
+            )
+            .push_arg(&value)
+            .fire()
+        };
+        env::clear_contract_storage(key);
+        env::test::set_tracing(true);
+        store(1)?;
+        assert_eq!(store(0), Err(EnvError::ContractCallFailState(2)));
+        let summary = env::test::trace()
+            .into_iter()
+            .map(|event| {
+                let op = match event.op {
+                    TraceOp::Call { selector, args, .. } => {
+                        assert_eq!(selector, Selector::from_label("store"));
+                        assert_eq!(args.len(), 4);
+                        "call"
+                    }
+                    TraceOp::Return { success: true, .. } => "return",
+                    TraceOp::Return { success: false, .. } => "revert",
+                    TraceOp::Event(_) => "event",
+                    TraceOp::StorageRead { len: None, .. } => "read empty",
+                    TraceOp::StorageRead { .. } => "read",
+                    TraceOp::StorageWrite { .. } => "write",
+                    TraceOp::StorageClear { .. } => "clear",
+                };
+                (event.depth, op)
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(
+            summary,
+            vec![
+                (0, "call"),
+                (1, "read empty"),
+                (1, "write"),
+                (1, "event"),
+                (0, "return"),
+                (0, "call"),
+                (1, "read"),
+                (1, "write"),
+                (1, "event"),
+                (0, "revert"),
+            ]
+        );
+        // Reverted calls keep their trace but not their events.
+        assert_eq!(env::test::recorded_events().count(), 1);
+        let tree = env::test::trace_tree();
+        assert_eq!(tree.lines().count(), summary.len());
+        assert!(tree.lines().nth(1).unwrap().starts_with("  read "));
+        Ok(())
+    })
+}
+
+#[test]
+fn trace_is_opt_in_and_bounded() -> Result<()> {
+    use env::test::TraceOp;
+    let key = |n: u8| Key([n; 32]);
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::set_contract_storage(key(0), &0_u32);
+        assert!(env::test::trace().is_empty());
+        env::test::set_tracing(true);
+        env::test::set_trace_limit(Some(2));
+        for n in 1..=3 {
+            env::set_contract_storage(key(n), &u32::from(n));
+        }
+        // Only the most recent operations are kept.
+        let written = env::test::take_trace()
+            .into_iter()
+            .map(|event| {
+                match event.op {
+                    TraceOp::StorageWrite { key, .. } => key,
+                    _ => panic!("expected a storage write"),
+                }
+            })
+            .collect::<Vec<_>>();
+        assert_eq!(written, vec![key(2), key(3)]);
+        assert!(env::test::trace().is_empty());
+        env::test::set_tracing(false);
+        env::clear_contract_storage(key(1));
+        assert!(env::test::trace().is_empty());
+        Ok(())
+    })
+}
+
+#[test]
+fn thread_local_instances() -> Result<()> {
+    let key = Key([0x42; 32]);
+    let handles = (0..4_u32)
+        .map(|n| {
+            std::thread::spawn(move || {
+                env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+                    assert_eq!(env::get_contract_storage::<u32>(key), None);
+                    env::set_contract_storage(key, &n);
+                    assert_eq!(env::get_contract_storage::<u32>(key), Some(Ok(n)));
+                    Ok(())
+                })
+            })
+        })
+        .collect::<Vec<_>>();
+    for handle in handles {
+        handle.join().expect("test thread panicked")?;
+    }
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        env::set_contract_storage(key, &1_u32);
+        Ok(())
+    })?;
+    // Sharing the state of the current thread is opt-in.
+    env::test::run_test_shared::<env::DefaultEnvTypes, _>(|_| {
+        assert_eq!(env::get_contract_storage::<u32>(key), Some(Ok(1)));
+        Ok(())
+    })?;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        assert_eq!(env::get_contract_storage::<u32>(key), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn inject_faults() -> Result<()> {
+    use env::{
+        call::{
+            CallParams,
+            Selector,
+        },
+        test::{
+            Fault,
+            HostFn,
+        },
+        AccountId,
+        DefaultEnvTypes,
+        Hash,
+    };
+    let key = Key([0x01; 32]);
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let code_hash = Hash::from([0x42; 32]);
+        let callee = AccountId::from([0x10; 32]);
+        env::test::add_contract_account::<DefaultEnvTypes>(callee, code_hash)?;
+        env::test::register_contract::<DefaultEnvTypes, _>(code_hash, move |_| {
+            let input = env::input().map_err(|_| 1)?;
+            let value = <u32 as scale::Decode>::decode(&mut input.params()).unwrap();
+            env::set_contract_storage(key, &value);
+            Ok(())
+        });
+        let store = |value: u32| {
+            CallParams::<DefaultEnvTypes, ()>::invoke(
+                callee,
+                Selector::from_label("store"),
+            )
+            .push_arg(&value)
+            .fire()
+        };
+        let stored = || {
+            let own = env::account_id::<DefaultEnvTypes>().unwrap();
+            env::test::set_callee::<DefaultEnvTypes>(callee).unwrap();
+            let stored = env::get_contract_storage::<u32>(key);
+            env::test::set_callee::<DefaultEnvTypes>(own).unwrap();
+            stored
+        };
+        // The 2nd call fails without executing the callee.
+        env::test::inject_fault(HostFn::Call, 2, Fault::Fail);
+        store(1)?;
+        assert_eq!(
+            store(2),
+            Err(EnvError::OffChain(OffChainError::InjectedFault(HostFn::Call)))
+        );
+        assert_eq!(stored(), Some(Ok(1)));
+        store(3)?;
+        assert_eq!(stored(), Some(Ok(3)));
+        // A trapping storage write reverts the callee.
+        env::test::inject_fault(HostFn::SetStorage, 1, Fault::Trap);
+        assert_eq!(store(4), Err(EnvError::ContractCallTrapped));
+        assert_eq!(stored(), Some(Ok(3)));
+        // Pending faults can be removed.
+        env::test::inject_fault(HostFn::SetStorage, 1, Fault::Trap);
+        env::test::clear_faults();
+        store(5)?;
+        assert_eq!(stored(), Some(Ok(5)));
+        // Storage writes cannot fail and trap instead.
+        env::test::inject_fault(HostFn::SetStorage, 1, Fault::Fail);
+        let trapped = std::panic::catch_unwind(|| env::set_contract_storage(key, &6_u32));
+        assert!(trapped.is_err());
+        // The writes of storage batches are counted one by one.
+        let mut batch = env::StorageBatch::new();
+        batch.set(key, &7_u32);
+        batch.clear(Key([0x02; 32]));
+        batch.set(Key([0x03; 32]), &8_u32);
+        env::test::inject_fault(HostFn::SetStorage, 2, Fault::Trap);
+        let trapped = std::panic::catch_unwind(|| env::set_contract_storage_batch(&batch));
+        assert!(trapped.is_err());
+        assert_eq!(env::get_contract_storage::<u32>(key), None);
+        Ok(())
+    })
+}
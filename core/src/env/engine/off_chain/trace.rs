@@ -0,0 +1,296 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::{
+    contracts::CallKind,
+    EmittedEvent,
+    OffAccountId,
+    OffBalance,
+};
+use crate::env::call::{
+    CallData,
+    Selector,
+};
+use core::fmt;
+use ink_primitives::Key;
+use std::collections::VecDeque;
+
+/// An operation recorded by the off-chain tracer.
+#[derive(Debug, Clone)]
+pub enum TraceOp {
+    /// A registered contract has been called or instantiated.
+    Call {
+        /// Whether the contract has been called or instantiated.
+        kind: CallKind,
+        /// The calling account.
+        caller: OffAccountId,
+        /// The called contract.
+        callee: OffAccountId,
+        /// The value transferred to the called contract.
+        value: OffBalance,
+        /// The selector of the called message or constructor.
+        selector: Selector,
+        /// The encoded arguments of the called message or constructor.
+        args: Vec<u8>,
+    },
+    /// The most recent call has ended.
+    Return {
+        /// `false` if the call failed and its changes have been reverted.
+        success: bool,
+        /// The encoded output of a successful call.
+        output: Vec<u8>,
+    },
+    /// An event has been emitted.
+    Event(EmittedEvent),
+    /// A contract storage cell has been read.
+    StorageRead {
+        /// The key of the cell.
+        key: Key,
+        /// The number of bytes read or `None` if the cell was empty.
+        len: Option<usize>,
+    },
+    /// A contract storage cell has been written.
+    StorageWrite {
+        /// The key of the cell.
+        key: Key,
+        /// The number of bytes written.
+        len: usize,
+    },
+    /// A contract storage cell has been cleared.
+    StorageClear {
+        /// The key of the cell.
+        key: Key,
+    },
+}
+
+/// An operation recorded by the off-chain tracer within the call tree.
+#[derive(Debug, Clone)]
+pub struct TraceEvent {
+    /// The number of calls the operation is nested in.
+    ///
+    /// Operations of the test itself have a depth of `0`.
+    /// A call and its return share the depth of the caller.
+    pub depth: usize,
+    /// The recorded operation.
+    pub op: TraceOp,
+}
+
+/// Formats the bytes as a hex string.
+fn fmt_hex(f: &mut fmt::Formatter, bytes: &[u8]) -> fmt::Result {
+    write!(f, "0x")?;
+    for byte in bytes {
+        write!(f, "{:02X}", byte)?;
+    }
+    Ok(())
+}
+
+impl fmt::Display for TraceOp {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        match self {
+            TraceOp::Call {
+                kind,
+                caller,
+                callee,
+                value,
+                selector,
+                args,
+            } => {
+                let kind = match kind {
+                    CallKind::Instantiate => "instantiate",
+                    CallKind::Call => "call",
+                };
+                write!(f, "{} ", kind)?;
+                fmt_hex(f, callee.encoded_bytes().unwrap_or_default())?;
+                write!(f, " from ")?;
+                fmt_hex(f, caller.encoded_bytes().unwrap_or_default())?;
+                write!(f, " selector ")?;
+                fmt_hex(f, &selector.to_bytes())?;
+                write!(f, " args ")?;
+                fmt_hex(f, args)?;
+                write!(f, " value ")?;
+                fmt_hex(f, value.encoded_bytes().unwrap_or_default())
+            }
+            TraceOp::Return {
+                success: true,
+                output,
+            } => {
+                write!(f, "return ")?;
+                fmt_hex(f, output)
+            }
+            TraceOp::Return { success: false, .. } => write!(f, "revert"),
+            TraceOp::Event(event) => {
+                write!(f, "event ")?;
+                fmt_hex(f, &event.data)?;
+                for topic in &event.topics {
+                    write!(f, " topic ")?;
+                    fmt_hex(f, topic.encoded_bytes().unwrap_or_default())?;
+                }
+                Ok(())
+            }
+            TraceOp::StorageRead {
+                key,
+                len: Some(len),
+            } => {
+                write!(f, "read {} ({} bytes)", key, len)
+            }
+            TraceOp::StorageRead { key, len: None } => write!(f, "read {} (empty)", key),
+            TraceOp::StorageWrite { key, len } => {
+                write!(f, "write {} ({} bytes)", key, len)
+            }
+            TraceOp::StorageClear { key } => write!(f, "clear {}", key),
+        }
+    }
+}
+
+impl fmt::Display for TraceEvent {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        write!(f, "{:indent$}{}", "", self.op, indent = 2 * self.depth)
+    }
+}
+
+/// The number of most recent operations kept by default.
+const DEFAULT_LIMIT: usize = 10_000;
+
+/// Records the operations of the off-chain execution as a call tree.
+///
+/// # Note
+///
+/// Tracing is disabled by default. Once enabled only the most recent
+/// operations up to the limit are kept.
+pub struct Tracer {
+    /// If operations are recorded.
+    enabled: bool,
+    /// The maximum number of kept operations if any.
+    limit: Option<usize>,
+    /// The recorded operations in order.
+    events: VecDeque<TraceEvent>,
+    /// The number of calls that have not yet returned.
+    depth: usize,
+}
+
+impl Tracer {
+    /// Creates a new disabled tracer without any recorded operations.
+    pub fn new() -> Self {
+        Self {
+            enabled: false,
+            limit: Some(DEFAULT_LIMIT),
+            events: VecDeque::new(),
+            depth: 0,
+        }
+    }
+
+    /// Enables or disables recording of operations.
+    ///
+    /// # Note
+    ///
+    /// Already recorded operations are kept.
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    /// Sets the maximum number of kept operations.
+    ///
+    /// # Note
+    ///
+    /// Drops the oldest operations that exceed the new limit.
+    pub fn set_limit(&mut self, limit: Option<usize>) {
+        self.limit = limit;
+        self.truncate();
+    }
+
+    /// Drops the oldest operations that exceed the limit.
+    fn truncate(&mut self) {
+        if let Some(limit) = self.limit {
+            while self.events.len() > limit {
+                self.events.pop_front();
+            }
+        }
+    }
+
+    /// Records the operation at the current depth if tracing is enabled.
+    ///
+    /// # Note
+    ///
+    /// The operation is only built if it is actually recorded.
+    pub fn record<F>(&mut self, op: F)
+    where
+        F: FnOnce() -> TraceOp,
+    {
+        if !self.enabled {
+            return
+        }
+        self.events.push_back(TraceEvent {
+            depth: self.depth,
+            op: op(),
+        });
+        self.truncate();
+    }
+
+    /// Records the start of a call and nests the following operations.
+    pub fn enter(
+        &mut self,
+        kind: CallKind,
+        caller: OffAccountId,
+        callee: OffAccountId,
+        value: OffBalance,
+        call_data: &CallData,
+    ) {
+        self.record(|| {
+            TraceOp::Call {
+                kind,
+                caller,
+                callee,
+                value,
+                selector: call_data.selector(),
+                args: call_data.params().to_vec(),
+            }
+        });
+        self.depth += 1;
+    }
+
+    /// Records the end of the most recent call.
+    pub fn exit<F>(&mut self, success: bool, output: F)
+    where
+        F: FnOnce() -> Vec<u8>,
+    {
+        self.depth = self.depth.saturating_sub(1);
+        self.record(|| TraceOp::Return {
+            success,
+            output: output(),
+        });
+    }
+
+    /// Returns the recorded operations in order.
+    pub fn events(&self) -> impl Iterator<Item = &TraceEvent> {
+        self.events.iter()
+    }
+
+    /// Removes and returns all recorded operations in order.
+    ///
+    /// # Note
+    ///
+    /// Calls that have not yet returned stay nested.
+    pub fn drain(&mut self) -> Vec<TraceEvent> {
+        self.events.drain(..).collect()
+    }
+
+    /// Removes all recorded operations.
+    ///
+    /// # Note
+    ///
+    /// Calls that have not yet returned stay nested.
+    pub fn clear(&mut self) {
+        self.events.clear();
+    }
+}
@@ -12,9 +12,16 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-/// A static buffer with 16kB of capacity.
+/// A static buffer with 16kB of capacity by default.
+///
+/// # Note
+///
+/// The capacity can be configured at compile time by setting the
+/// `INK_STATIC_BUFFER_SIZE` environment variable to the number of bytes,
+/// e.g. to raise it for contracts exchanging large payloads or to shrink
+/// it for tiny contracts.
 pub struct StaticBuffer {
-    /// The static buffer with a total capacity of 16kB.
+    /// The static buffer with a total capacity of `CAPACITY` bytes.
     buffer: [u8; Self::CAPACITY],
     /// The number of elements currently in use by the buffer
     /// counting from the start.
@@ -23,7 +30,7 @@ pub struct StaticBuffer {
 
 impl StaticBuffer {
     /// The capacity of the static buffer.
-    const CAPACITY: usize = 1 << 14; // 16kB
+    const CAPACITY: usize = crate::env::BUFFER_CAPACITY;
 
     /// Creates a new static buffer.
     pub const fn new() -> Self {
@@ -47,6 +47,17 @@ mod sys {
             input_data_len: u32,
         ) -> u32;
 
+        pub fn ext_call_with_flags(
+            flags: u32,
+            callee_ptr: u32,
+            callee_len: u32,
+            gas: u64,
+            value_ptr: u32,
+            value_len: u32,
+            input_data_ptr: u32,
+            input_data_len: u32,
+        ) -> u32;
+
         pub fn ext_transfer(
             account_id_ptr: u32,
             account_id_len: u32,
@@ -64,6 +75,8 @@ mod sys {
         pub fn ext_set_storage(key_ptr: u32, value_ptr: u32, value_len: u32);
         pub fn ext_clear_storage(key_ptr: u32);
         pub fn ext_get_storage(key_ptr: u32) -> u32;
+        pub fn ext_set_storage_batch(batch_ptr: u32, batch_len: u32);
+        pub fn ext_get_storage_batch(keys_ptr: u32, keys_len: u32);
 
         pub fn ext_get_runtime_storage(key_ptr: u32, key_len: u32) -> u32;
 
@@ -79,8 +92,16 @@ mod sys {
         );
         pub fn ext_terminate(beneficiary_ptr: u32, beneficiary_len: u32) -> !;
 
+        pub fn ext_return(flags: u32, data_ptr: u32, data_len: u32) -> !;
+
         pub fn ext_dispatch_call(call_ptr: u32, call_len: u32);
 
+        pub fn ext_call_chain_extension(
+            func_id: u32,
+            input_ptr: u32,
+            input_len: u32,
+        ) -> u32;
+
         pub fn ext_scratch_size() -> u32;
         pub fn ext_scratch_read(dst_ptr: u32, offset: u32, len: u32);
         pub fn ext_scratch_write(src_ptr: u32, len: u32);
@@ -89,6 +110,9 @@ mod sys {
         pub fn ext_block_number();
         pub fn ext_address();
         pub fn ext_balance();
+        pub fn ext_own_code_hash();
+        pub fn ext_code_hash(account_id_ptr: u32, account_id_len: u32) -> u32;
+        pub fn ext_reserved_balance();
         pub fn ext_gas_price();
         pub fn ext_gas_left();
         pub fn ext_value_transferred();
@@ -96,16 +120,27 @@ mod sys {
         pub fn ext_rent_allowance();
         pub fn ext_minimum_balance();
         pub fn ext_tombstone_deposit();
+        pub fn ext_weight_to_fee(gas: u64);
 
         pub fn ext_set_rent_allowance(value_ptr: u32, value_len: u32);
 
         pub fn ext_random_seed(subject_ptr: u32, subject_len: u32);
+        pub fn ext_caller_is_origin() -> u32;
+        pub fn ext_call_depth() -> u32;
+
         pub fn ext_println(str_ptr: u32, str_len: u32);
+        pub fn ext_debug_message(str_ptr: u32, str_len: u32) -> u32;
 
         pub fn ext_hash_keccak_256(input_ptr: u32, input_len: u32, output_ptr: u32);
         pub fn ext_hash_blake2_256(input_ptr: u32, input_len: u32, output_ptr: u32);
         pub fn ext_hash_blake2_128(input_ptr: u32, input_len: u32, output_ptr: u32);
         pub fn ext_hash_sha2_256(input_ptr: u32, input_len: u32, output_ptr: u32);
+
+        pub fn ext_ecdsa_recover(
+            signature_ptr: u32,
+            message_hash_ptr: u32,
+            output_ptr: u32,
+        ) -> u32;
     }
 }
 
@@ -148,8 +183,35 @@ pub fn call(callee: &[u8], gas_limit: u64, value: &[u8], call_data: &[u8]) -> Re
     };
     match ret_code {
         0 => Ok(()),
-        c if c == TRAP_RETURN_CODE => Err(EnvError::ContractInstantiationTrapped),
-        err if err <= 0xFF => Err(EnvError::ContractInstantiationFailState(err as u8)),
+        c if c == TRAP_RETURN_CODE => Err(EnvError::ContractCallTrapped),
+        err if err <= 0xFF => Err(EnvError::ContractCallFailState(err as u8)),
+        _unknown => panic!("encountered unknown error code upon contract call"),
+    }
+}
+
+pub fn call_with_flags(
+    flags: u32,
+    callee: &[u8],
+    gas_limit: u64,
+    value: &[u8],
+    call_data: &[u8],
+) -> Result<()> {
+    let ret_code = unsafe {
+        sys::ext_call_with_flags(
+            flags,
+            callee.as_ptr() as u32,
+            callee.len() as u32,
+            gas_limit,
+            value.as_ptr() as u32,
+            value.len() as u32,
+            call_data.as_ptr() as u32,
+            call_data.len() as u32,
+        )
+    };
+    match ret_code {
+        0 => Ok(()),
+        c if c == TRAP_RETURN_CODE => Err(EnvError::ContractCallTrapped),
+        err if err <= 0xFF => Err(EnvError::ContractCallFailState(err as u8)),
         _unknown => panic!("encountered unknown error code upon contract call"),
     }
 }
@@ -204,6 +266,24 @@ pub fn get_storage(key: &[u8]) -> Result<()> {
     }
 }
 
+pub fn set_storage_batch(encoded_batch: &[u8]) {
+    unsafe {
+        sys::ext_set_storage_batch(
+            encoded_batch.as_ptr() as u32,
+            encoded_batch.len() as u32,
+        )
+    }
+}
+
+pub fn get_storage_batch(encoded_keys: &[u8]) {
+    unsafe {
+        sys::ext_get_storage_batch(
+            encoded_keys.as_ptr() as u32,
+            encoded_keys.len() as u32,
+        )
+    }
+}
+
 pub fn get_runtime_storage(runtime_key: &[u8]) -> Result<()> {
     let ret_code = unsafe {
         sys::ext_get_runtime_storage(
@@ -253,10 +333,24 @@ pub fn terminate(beneficiary: &[u8]) -> ! {
     unsafe { sys::ext_terminate(beneficiary.as_ptr() as u32, beneficiary.len() as u32) }
 }
 
+pub fn return_value(flags: u32, data: &[u8]) -> ! {
+    unsafe { sys::ext_return(flags, data.as_ptr() as u32, data.len() as u32) }
+}
+
 pub fn dispatch_call(call: &[u8]) {
     unsafe { sys::ext_dispatch_call(call.as_ptr() as u32, call.len() as u32) }
 }
 
+pub fn call_chain_extension(func_id: u32, input: &[u8]) -> Result<()> {
+    let ret_code = unsafe {
+        sys::ext_call_chain_extension(func_id, input.as_ptr() as u32, input.len() as u32)
+    };
+    match ret_code {
+        0 => Ok(()),
+        err => Err(EnvError::ChainExtensionFailState(err)),
+    }
+}
+
 pub fn scratch_size() -> usize {
     (unsafe { sys::ext_scratch_size() }) as usize
 }
@@ -285,6 +379,8 @@ impl_ext_wrapper_for! {
     (block_number => ext_block_number),
     (address => ext_address),
     (balance => ext_balance),
+    (own_code_hash => ext_own_code_hash),
+    (reserved_balance => ext_reserved_balance),
     (gas_price => ext_gas_price),
     (gas_left => ext_gas_left),
     (value_transferred => ext_value_transferred),
@@ -294,6 +390,21 @@ impl_ext_wrapper_for! {
     (tombstone_deposit => ext_tombstone_deposit),
 }
 
+pub fn code_hash(account_id: &[u8]) -> Result<()> {
+    let ret_code = unsafe {
+        sys::ext_code_hash(account_id.as_ptr() as u32, account_id.len() as u32)
+    };
+    match ret_code {
+        0 => Ok(()),
+        1 => Err(EnvError::NotAContract),
+        _unknown => panic!("encountered unexpected return code"),
+    }
+}
+
+pub fn weight_to_fee(gas: u64) {
+    unsafe { sys::ext_weight_to_fee(gas) }
+}
+
 pub fn set_rent_allowance(value: &[u8]) {
     unsafe { sys::ext_set_rent_allowance(value.as_ptr() as u32, value.len() as u32) }
 }
@@ -302,11 +413,26 @@ pub fn random_seed(subject: &[u8]) {
     unsafe { sys::ext_random_seed(subject.as_ptr() as u32, subject.len() as u32) }
 }
 
+pub fn caller_is_origin() -> bool {
+    unsafe { sys::ext_caller_is_origin() != 0 }
+}
+
+pub fn call_depth() -> u32 {
+    unsafe { sys::ext_call_depth() }
+}
+
 pub fn println(content: &str) {
     let bytes = content.as_bytes();
     unsafe { sys::ext_println(bytes.as_ptr() as u32, bytes.len() as u32) }
 }
 
+pub fn debug_message(content: &str) {
+    let bytes = content.as_bytes();
+    // A non-zero return code signals that the debug buffer is disabled
+    // which is the case outside of RPC calls, so we simply ignore it.
+    let _ = unsafe { sys::ext_debug_message(bytes.as_ptr() as u32, bytes.len() as u32) };
+}
+
 macro_rules! impl_hash_fn {
     ( $name:ident, $bytes_result:literal ) => {
         paste::item! {
@@ -326,3 +452,22 @@ impl_hash_fn!(sha2_256, 32);
 impl_hash_fn!(keccak_256, 32);
 impl_hash_fn!(blake2_256, 32);
 impl_hash_fn!(blake2_128, 16);
+
+pub fn ecdsa_recover(
+    signature: &[u8; 65],
+    message_hash: &[u8; 32],
+    output: &mut [u8; 33],
+) -> Result<()> {
+    let ret_code = unsafe {
+        sys::ext_ecdsa_recover(
+            signature.as_ptr() as u32,
+            message_hash.as_ptr() as u32,
+            output.as_mut_ptr() as u32,
+        )
+    };
+    match ret_code {
+        0 => Ok(()),
+        1 => Err(EnvError::EcdsaRecoverFailed),
+        _unknown => panic!("encountered unknown error code upon ECDSA recovery"),
+    }
+}
@@ -17,17 +17,25 @@ use super::{
     EnvInstance,
 };
 use crate::env::{
+    backend::{
+        EnvBackend,
+        TypedEnvBackend,
+    },
     call::{
         CallData,
+        CallFlags,
         CallParams,
         InstantiateParams,
         ReturnType,
     },
-    Env,
+    hash::CryptoHash,
+    EcdsaPublicKey,
     EnvTypes,
     Result,
+    ReturnFlags,
+    StorageBatch,
     Topics,
-    TypedEnv,
+    BUFFER_CAPACITY,
 };
 use ink_primitives::Key;
 
@@ -121,7 +129,20 @@ impl EnvInstance {
         let transferred_value = &self.buffer[transferred_value];
         let call_data = &self.buffer[call_data];
         // Perform the actual contract call.
-        ext::call(
+        //
+        // Calls without custom flags use the plain call to stay compatible
+        // with hosts that do not support call flags.
+        let call_flags = *call_params.call_flags();
+        if call_flags == CallFlags::default() {
+            return ext::call(
+                callee,
+                call_params.gas_limit(),
+                transferred_value,
+                call_data,
+            )
+        }
+        ext::call_with_flags(
+            call_flags.into_u32(),
             callee,
             call_params.gas_limit(),
             transferred_value,
@@ -130,7 +151,7 @@ impl EnvInstance {
     }
 }
 
-impl Env for EnvInstance {
+impl EnvBackend for EnvInstance {
     fn set_contract_storage<V>(&mut self, key: Key, value: &V)
     where
         V: scale::Encode,
@@ -153,6 +174,46 @@ impl Env for EnvInstance {
         ext::clear_storage(key.as_bytes())
     }
 
+    fn clear_contract_storage_on_return(&mut self, key: Option<Key>) {
+        self.clear_on_return = key;
+    }
+
+    fn set_contract_storage_batch(&mut self, batch: &StorageBatch) {
+        // Batches exceeding the static buffer are submitted in parts.
+        for part in batch.parts(BUFFER_CAPACITY) {
+            self.encode_into_buffer(part);
+            ext::set_storage_batch(&self.buffer[..]);
+        }
+    }
+
+    fn get_contract_storage_batch(&mut self, keys: &[Key]) -> Result<StorageBatch> {
+        self.encode_into_buffer(keys);
+        ext::get_storage_batch(&self.buffer[..]);
+        // The host returns the values encoded as `Vec<Option<Vec<u8>>>` which
+        // is decoded into the batch without allocating a vector for each value.
+        let req_len = self.read_scratch_buffer();
+        let mut values = &self.buffer[0..req_len];
+        let len = <scale::Compact<u32> as scale::Decode>::decode(&mut values)?.0;
+        if len as usize != keys.len() {
+            return Err(scale::Error::from("unexpected number of values").into())
+        }
+        let mut batch = StorageBatch::new();
+        for key in keys {
+            if <u8 as scale::Decode>::decode(&mut values)? == 0 {
+                batch.clear(*key);
+                continue
+            }
+            let len = <scale::Compact<u32> as scale::Decode>::decode(&mut values)?.0;
+            if values.len() < len as usize {
+                return Err(scale::Error::from("not enough data for the value").into())
+            }
+            let (value, rest) = values.split_at(len as usize);
+            batch.set_encoded(*key, value);
+            values = rest;
+        }
+        Ok(batch)
+    }
+
     fn get_runtime_storage<R>(&mut self, runtime_key: &[u8]) -> Option<Result<R>>
     where
         R: scale::Decode,
@@ -175,10 +236,35 @@ impl Env for EnvInstance {
         ext::scratch_write(&self.buffer[..]);
     }
 
+    fn return_value<R>(&mut self, flags: ReturnFlags, return_value: &R) -> !
+    where
+        R: scale::Encode,
+    {
+        if let Some(key) = self.clear_on_return.take() {
+            if !flags.is_reverted() {
+                ext::clear_storage(key.as_bytes())
+            }
+        }
+        self.encode_into_buffer(return_value);
+        ext::return_value(flags.into_u32(), &self.buffer[..])
+    }
+
+    fn caller_is_origin(&mut self) -> bool {
+        ext::caller_is_origin()
+    }
+
+    fn call_depth(&mut self) -> u32 {
+        ext::call_depth()
+    }
+
     fn println(&mut self, content: &str) {
         ext::println(content)
     }
 
+    fn debug_message(&mut self, message: &str) {
+        ext::debug_message(message)
+    }
+
     fn hash_keccak_256(input: &[u8], output: &mut [u8; 32]) {
         ext::hash_keccak_256(input, output)
     }
@@ -191,12 +277,45 @@ impl Env for EnvInstance {
         ext::hash_blake2_128(input, output)
     }
 
+    fn hash_blake2s_256(_input: &[u8], _output: &mut [u8; 32]) {
+        panic!("the contracts runtime provides no host function for BLAKE2s")
+    }
+
     fn hash_sha2_256(input: &[u8], output: &mut [u8; 32]) {
         ext::hash_sha2_256(input, output)
     }
+
+    fn hash_encoded<H, T>(&mut self, input: &T, output: &mut <H as CryptoHash>::Output)
+    where
+        H: CryptoHash,
+        T: scale::Encode,
+    {
+        self.encode_into_buffer(input);
+        <H as CryptoHash>::hash(&self.buffer[..], output)
+    }
+
+    fn ecdsa_recover(
+        &mut self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+    ) -> Result<EcdsaPublicKey> {
+        let mut output = [0x00; 33];
+        ext::ecdsa_recover(signature, message_hash, &mut output)?;
+        Ok(EcdsaPublicKey::from(output))
+    }
+
+    fn call_chain_extension<I, O>(&mut self, func_id: u32, input: &I) -> Result<O>
+    where
+        I: scale::Encode,
+        O: scale::Decode,
+    {
+        self.encode_into_buffer(input);
+        ext::call_chain_extension(func_id, &self.buffer[..])?;
+        self.decode_scratch_buffer().map_err(Into::into)
+    }
 }
 
-impl TypedEnv for EnvInstance {
+impl TypedEnvBackend for EnvInstance {
     fn caller<T: EnvTypes>(&mut self) -> Result<T::AccountId> {
         self.get_property::<T::AccountId>(ext::caller)
     }
@@ -225,10 +344,29 @@ impl TypedEnv for EnvInstance {
         self.get_property::<T::Balance>(ext::balance)
     }
 
+    fn reserved_balance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
+        self.get_property::<T::Balance>(ext::reserved_balance)
+    }
+
     fn rent_allowance<T: EnvTypes>(&mut self) -> Result<T::Balance> {
         self.get_property::<T::Balance>(ext::rent_allowance)
     }
 
+    fn own_code_hash<T: EnvTypes>(&mut self) -> Result<T::Hash> {
+        self.get_property::<T::Hash>(ext::own_code_hash)
+    }
+
+    fn code_hash<T: EnvTypes>(
+        &mut self,
+        account_id: &T::AccountId,
+    ) -> Result<Option<T::Hash>> {
+        self.encode_into_buffer(account_id);
+        if ext::code_hash(&self.buffer[..]).is_err() {
+            return Ok(None)
+        }
+        self.decode_scratch_buffer().map(Some).map_err(Into::into)
+    }
+
     fn block_number<T: EnvTypes>(&mut self) -> Result<T::BlockNumber> {
         self.get_property::<T::BlockNumber>(ext::block_number)
     }
@@ -241,6 +379,11 @@ impl TypedEnv for EnvInstance {
         self.get_property::<T::Balance>(ext::tombstone_deposit)
     }
 
+    fn weight_to_fee<T: EnvTypes>(&mut self, gas: u64) -> Result<T::Balance> {
+        ext::weight_to_fee(gas);
+        self.decode_scratch_buffer().map_err(Into::into)
+    }
+
     fn emit_event<T, Event>(&mut self, event: Event)
     where
         T: EnvTypes,
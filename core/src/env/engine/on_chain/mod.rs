@@ -19,12 +19,15 @@ mod impls;
 use super::OnInstance;
 
 use self::buffer::StaticBuffer;
+use ink_primitives::Key;
 
 /// The on-chain environment.
 pub struct EnvInstance {
-    /// Encode & decode buffer with static size of 16kB.
+    /// Encode & decode buffer with static size of 16kB by default.
     ///
     /// If operations require more than that they will fail.
+    /// The size can be configured at compile time through the
+    /// `INK_STATIC_BUFFER_SIZE` environment variable.
     /// This limit was chosen after benchmarking Substrate storage
     /// storage and load performance and was found to be a sweet spot.
     ///
@@ -32,6 +35,8 @@ pub struct EnvInstance {
     /// might change. Users should generally avoid storing too big values
     /// into single storage entries.
     buffer: StaticBuffer,
+    /// The storage cell cleared if the contract returns without reverting.
+    clear_on_return: Option<Key>,
 }
 
 impl OnInstance for EnvInstance {
@@ -41,6 +46,7 @@ impl OnInstance for EnvInstance {
     {
         static mut INSTANCE: EnvInstance = EnvInstance {
             buffer: StaticBuffer::new(),
+            clear_on_return: None,
         };
         f(unsafe { &mut INSTANCE })
     }
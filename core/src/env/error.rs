@@ -41,6 +41,17 @@ pub enum EnvError {
     MissingContractStorageEntry,
     /// A call to transfer value from the contract failed.
     TransferCallFailed,
+    /// A called chain extension returned a custom error code.
+    #[from(ignore)]
+    ChainExtensionFailState(u32),
+    /// The recovery of an ECDSA public key from a signature failed.
+    EcdsaRecoverFailed,
+    /// The queried account is not a contract account.
+    NotAContract,
+    /// Value has been transferred to an execution that does not accept value.
+    UnexpectedTransferredValue,
+    /// The transferred value is below the required minimum value.
+    InsufficientTransferredValue,
 }
 
 /// A result of environmental operations.
@@ -18,35 +18,58 @@
 //! with the outside world through its sandbox boundaries.
 
 mod api;
-mod backend;
+pub mod backend;
 pub mod call;
+mod chain_extension;
 mod engine;
 mod error;
+#[cfg(any(test, all(feature = "ink-alloc-free", not(feature = "std"))))]
+#[cfg_attr(test, allow(dead_code))]
+mod static_bytes;
+#[cfg(any(test, all(feature = "ink-alloc-free", not(feature = "std"))))]
+#[cfg_attr(test, allow(dead_code))]
+mod static_topics;
+mod storage_batch;
 mod types;
 
 #[cfg(test)]
 mod tests;
 
+/// The capacity of the static buffers of the environment in bytes.
+///
+/// # Note
+///
+/// Defaults to 16kB and can be configured at compile time by setting the
+/// `INK_STATIC_BUFFER_SIZE` environment variable to the number of bytes.
+#[allow(dead_code)]
+pub(crate) const BUFFER_CAPACITY: usize =
+    include!(concat!(env!("OUT_DIR"), "/buffer_capacity.rs"));
+
 #[cfg(any(feature = "std", test, doc))]
 #[doc(inline)]
 pub use self::engine::off_chain::test_api as test;
 
-use self::backend::{
-    Env,
-    TypedEnv,
-};
 pub use self::{
     api::*,
+    chain_extension::ChainExtension,
     error::{
         EnvError,
         Result,
     },
+    storage_batch::{
+        FootprintDelta,
+        StorageBatch,
+    },
     types::{
         AccountId,
         Clear,
         DefaultEnvTypes,
+        EcdsaPublicKey,
         EnvTypes,
+        EventTopics,
         Hash,
+        ReturnFlags,
         Topics,
+        Weight,
     },
 };
@@ -0,0 +1,169 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixed capacity bytes for the alloc-free environment.
+
+use core::ops::{
+    Deref,
+    DerefMut,
+};
+
+/// A byte buffer with the fixed capacity of the static environmental buffer.
+///
+/// # Note
+///
+/// Used as drop-in replacement of `Vec<u8>` for the call data and
+/// storage batches if the `ink-alloc-free` crate feature is enabled.
+/// The capacity is shared with the encode and decode buffer of the
+/// environment and thus is configured through `INK_STATIC_BUFFER_SIZE`.
+#[derive(Clone)]
+pub struct StaticBytes {
+    /// The fixed capacity buffer.
+    buffer: [u8; Self::CAPACITY],
+    /// The number of bytes in use counting from the start.
+    len: usize,
+}
+
+impl StaticBytes {
+    /// The capacity of the static bytes.
+    const CAPACITY: usize = crate::env::BUFFER_CAPACITY;
+
+    /// Creates new empty static bytes.
+    pub const fn new() -> Self {
+        Self {
+            buffer: [0; Self::CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Creates new empty static bytes.
+    ///
+    /// # Panics
+    ///
+    /// If the capacity is greater than the fixed capacity.
+    pub fn with_capacity(capacity: usize) -> Self {
+        if capacity > Self::CAPACITY {
+            panic!("static bytes overflowed")
+        }
+        Self::new()
+    }
+
+    /// Appends the byte.
+    ///
+    /// # Panics
+    ///
+    /// If the fixed capacity is exceeded.
+    pub fn push(&mut self, byte: u8) {
+        self.extend_from_slice(&[byte])
+    }
+
+    /// Appends all bytes of the slice.
+    ///
+    /// # Panics
+    ///
+    /// If the fixed capacity is exceeded.
+    pub fn extend_from_slice(&mut self, bytes: &[u8]) {
+        let start = self.len;
+        let end = start + bytes.len();
+        if end > Self::CAPACITY {
+            panic!("static bytes overflowed")
+        }
+        self.buffer[start..end].copy_from_slice(bytes);
+        self.len = end;
+    }
+
+    /// Returns the bytes in use as slice.
+    pub fn as_slice(&self) -> &[u8] {
+        &self.buffer[..self.len]
+    }
+}
+
+impl Deref for StaticBytes {
+    type Target = [u8];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl DerefMut for StaticBytes {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.buffer[..self.len]
+    }
+}
+
+impl Default for StaticBytes {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl core::fmt::Debug for StaticBytes {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl PartialEq for StaticBytes {
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl Eq for StaticBytes {}
+
+impl scale::Output for StaticBytes {
+    fn write(&mut self, bytes: &[u8]) {
+        self.extend_from_slice(bytes)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn extend_works() {
+        let mut bytes = StaticBytes::new();
+        assert!(bytes.is_empty());
+        bytes.push(0x01);
+        bytes.extend_from_slice(&[0x02, 0x03]);
+        scale::Output::write(&mut bytes, &[0x04]);
+        assert_eq!(bytes.as_slice(), &[0x01, 0x02, 0x03, 0x04]);
+        bytes[1..].rotate_right(1);
+        assert_eq!(&bytes[..], &[0x01, 0x04, 0x02, 0x03]);
+        assert_eq!(bytes.clone(), bytes);
+    }
+
+    #[test]
+    fn fill_to_capacity_works() {
+        let mut bytes = StaticBytes::with_capacity(StaticBytes::CAPACITY);
+        bytes.extend_from_slice(&[0xFF; StaticBytes::CAPACITY]);
+        assert_eq!(bytes.len(), StaticBytes::CAPACITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "static bytes overflowed")]
+    fn extend_overflow_panics() {
+        let mut bytes = StaticBytes::new();
+        bytes.extend_from_slice(&[0xFF; StaticBytes::CAPACITY]);
+        bytes.push(0x00);
+    }
+
+    #[test]
+    #[should_panic(expected = "static bytes overflowed")]
+    fn with_capacity_overflow_panics() {
+        StaticBytes::with_capacity(StaticBytes::CAPACITY + 1);
+    }
+}
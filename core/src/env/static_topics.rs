@@ -0,0 +1,172 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Fixed capacity event topics for the alloc-free environment.
+
+use crate::env::Clear;
+use core::{
+    iter::FromIterator,
+    ops::Deref,
+};
+
+/// The topic hashes of an event with a fixed capacity.
+///
+/// # Note
+///
+/// Used as drop-in replacement of `Vec<Hash>` for the event topics
+/// if the `ink-alloc-free` crate feature is enabled.
+/// The capacity equals the default maximum number of event topics
+/// of the contracts pallet.
+#[derive(Clone)]
+pub struct StaticTopics<H> {
+    /// The fixed capacity topics.
+    topics: [H; STATIC_TOPICS_CAPACITY],
+    /// The number of topics in use counting from the start.
+    len: usize,
+}
+
+/// The capacity of the static topics.
+const STATIC_TOPICS_CAPACITY: usize = 4;
+
+impl<H> StaticTopics<H>
+where
+    H: Copy + Clear,
+{
+    /// Creates new empty static topics.
+    pub fn new() -> Self {
+        Self {
+            topics: [<H as Clear>::clear(); STATIC_TOPICS_CAPACITY],
+            len: 0,
+        }
+    }
+
+    /// Appends the topic.
+    ///
+    /// # Panics
+    ///
+    /// If the fixed capacity is exceeded.
+    pub fn push(&mut self, topic: H) {
+        if self.len == STATIC_TOPICS_CAPACITY {
+            panic!("static topics overflowed")
+        }
+        self.topics[self.len] = topic;
+        self.len += 1;
+    }
+}
+
+impl<H> StaticTopics<H> {
+    /// Returns the topics in use as slice.
+    pub fn as_slice(&self) -> &[H] {
+        &self.topics[..self.len]
+    }
+}
+
+impl<H> Deref for StaticTopics<H> {
+    type Target = [H];
+
+    fn deref(&self) -> &Self::Target {
+        self.as_slice()
+    }
+}
+
+impl<H> Default for StaticTopics<H>
+where
+    H: Copy + Clear,
+{
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<H> FromIterator<H> for StaticTopics<H>
+where
+    H: Copy + Clear,
+{
+    fn from_iter<I>(iter: I) -> Self
+    where
+        I: IntoIterator<Item = H>,
+    {
+        let mut topics = Self::new();
+        for topic in iter {
+            topics.push(topic);
+        }
+        topics
+    }
+}
+
+impl<H> core::fmt::Debug for StaticTopics<H>
+where
+    H: core::fmt::Debug,
+{
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        core::fmt::Debug::fmt(self.as_slice(), f)
+    }
+}
+
+impl<H> PartialEq for StaticTopics<H>
+where
+    H: PartialEq,
+{
+    fn eq(&self, other: &Self) -> bool {
+        self.as_slice() == other.as_slice()
+    }
+}
+
+impl<H> Eq for StaticTopics<H> where H: Eq {}
+
+impl<H> scale::Encode for StaticTopics<H>
+where
+    H: scale::Encode,
+{
+    fn size_hint(&self) -> usize {
+        scale::Encode::size_hint(self.as_slice())
+    }
+
+    fn encode_to<O: scale::Output>(&self, dest: &mut O) {
+        scale::Encode::encode_to(self.as_slice(), dest)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::env::Hash;
+    use scale::Encode as _;
+
+    #[test]
+    fn collect_works() {
+        let hashes = [Hash::from([0x01; 32]), Hash::from([0x02; 32])];
+        let topics = hashes.iter().copied().collect::<StaticTopics<Hash>>();
+        assert_eq!(topics.as_slice(), &hashes[..]);
+        assert_eq!(topics.encode(), hashes.to_vec().encode());
+        assert_eq!(topics.clone(), topics);
+    }
+
+    #[test]
+    fn fill_to_capacity_works() {
+        let topics = core::iter::repeat(Hash::from([0xFF; 32]))
+            .take(STATIC_TOPICS_CAPACITY)
+            .collect::<StaticTopics<Hash>>();
+        assert_eq!(topics.len(), STATIC_TOPICS_CAPACITY);
+    }
+
+    #[test]
+    #[should_panic(expected = "static topics overflowed")]
+    fn push_overflow_panics() {
+        let mut topics = core::iter::repeat(Hash::from([0xFF; 32]))
+            .take(STATIC_TOPICS_CAPACITY)
+            .collect::<StaticTopics<Hash>>();
+        topics.push(Hash::from([0x00; 32]));
+    }
+}
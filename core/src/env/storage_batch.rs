@@ -0,0 +1,301 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Batched writes to the contract storage.
+
+use crate::env::EnvTypes;
+use ink_primitives::Key;
+use scale::Encode as _;
+
+/// The encoded entries of a storage batch.
+#[cfg(not(all(feature = "ink-alloc-free", not(feature = "std"))))]
+type BatchBytes = ink_prelude::vec::Vec<u8>;
+
+/// The encoded entries of a storage batch.
+///
+/// # Note
+///
+/// The encoded entries are limited to the capacity of the static environmental
+/// buffer in the alloc-free environment.
+#[cfg(all(feature = "ink-alloc-free", not(feature = "std")))]
+type BatchBytes = crate::env::static_bytes::StaticBytes;
+
+/// A batch of contract storage writes that is submitted in a single host call.
+///
+/// # Note
+///
+/// Use [`set_contract_storage_batch`](fn.set_contract_storage_batch.html)
+/// in order to submit the batch. Entries are applied in order.
+///
+/// The batch is encoded as `Vec<(Key, Option<Vec<u8>>)>` without
+/// allocating a vector for each of its entries.
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct StorageBatch {
+    /// The number of entries in the batch.
+    len: u32,
+    /// The encoded entries of the batch, i.e. the keys and their new encoded values.
+    ///
+    /// An entry without a value clears the contract storage at its key.
+    entries: BatchBytes,
+}
+
+impl StorageBatch {
+    /// Creates a new empty storage batch.
+    pub fn new() -> Self {
+        Self {
+            len: 0,
+            entries: BatchBytes::new(),
+        }
+    }
+
+    /// Writes the value to the contract storage under the given key.
+    pub fn set<V>(&mut self, key: Key, value: &V)
+    where
+        V: scale::Encode,
+    {
+        self.push_key(key, true);
+        // The length prefix is only known after encoding the value
+        // and thus is rotated in front of the encoded value afterwards.
+        let start = self.entries.len();
+        value.encode_to(&mut self.entries);
+        let len = (self.entries.len() - start) as u32;
+        let entries = &mut self.entries;
+        scale::Compact(len).using_encoded(|prefix| {
+            entries.extend_from_slice(prefix);
+            entries[start..].rotate_right(prefix.len());
+        });
+    }
+
+    /// Writes the already encoded value to the contract storage under the given key.
+    pub(crate) fn set_encoded(&mut self, key: Key, value: &[u8]) {
+        self.push_key(key, true);
+        value.encode_to(&mut self.entries);
+    }
+
+    /// Clears the contract storage under the given key.
+    pub fn clear(&mut self, key: Key) {
+        self.push_key(key, false);
+    }
+
+    /// Appends the key of an entry and whether it has a value.
+    fn push_key(&mut self, key: Key, has_value: bool) {
+        self.entries.extend_from_slice(key.as_bytes());
+        self.entries.push(has_value as u8);
+        self.len += 1;
+    }
+
+    /// Returns the number of writes in the batch.
+    pub fn len(&self) -> usize {
+        self.len as usize
+    }
+
+    /// Returns `true` if the batch contains no writes.
+    pub fn is_empty(&self) -> bool {
+        self.len == 0
+    }
+
+    /// Returns an iterator over the keys and their new encoded values.
+    pub fn iter(&self) -> impl Iterator<Item = (Key, Option<&[u8]>)> {
+        let mut entries = &self.entries[..];
+        (0..self.len).map(move |_| next_entry(&mut entries))
+    }
+
+    /// Splits the batch into consecutive parts that are encoded into at most
+    /// `capacity` bytes each.
+    ///
+    /// # Note
+    ///
+    /// Entries are never split, so an entry that does not fit into `capacity`
+    /// bytes on its own is returned as a part of its own exceeding it.
+    pub(crate) fn parts(&self, capacity: usize) -> impl Iterator<Item = BatchPart<'_>> {
+        let mut rest = &self.entries[..];
+        let mut remaining = self.len;
+        core::iter::from_fn(move || {
+            if remaining == 0 {
+                return None
+            }
+            let mut len = 0;
+            let mut end = 0;
+            let mut tail = rest;
+            while len < remaining {
+                next_entry(&mut tail);
+                let next_end = rest.len() - tail.len();
+                if len > 0 && compact_len(len + 1) + next_end > capacity {
+                    break
+                }
+                len += 1;
+                end = next_end;
+            }
+            let (entries, tail) = rest.split_at(end);
+            rest = tail;
+            remaining -= len;
+            Some(BatchPart { len, entries })
+        })
+    }
+}
+
+/// A part of a storage batch that is submitted in a single host call.
+///
+/// Created by [`StorageBatch::parts`](struct.StorageBatch.html#method.parts).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub(crate) struct BatchPart<'a> {
+    /// The number of entries in the part.
+    len: u32,
+    /// The encoded entries of the part.
+    entries: &'a [u8],
+}
+
+impl BatchPart<'_> {
+    /// Returns the number of bytes of the encoded part.
+    pub fn encoded_len(&self) -> usize {
+        compact_len(self.len) + self.entries.len()
+    }
+}
+
+impl scale::Encode for BatchPart<'_> {
+    fn encode_to<T: scale::Output>(&self, dest: &mut T) {
+        scale::Compact(self.len).encode_to(dest);
+        dest.write(self.entries);
+    }
+}
+
+/// Returns the number of bytes of the compact encoded length.
+fn compact_len(len: u32) -> usize {
+    scale::Compact(len).using_encoded(|encoded| encoded.len())
+}
+
+/// Decodes the next entry of the encoded entries of a storage batch.
+fn next_entry<'a>(entries: &mut &'a [u8]) -> (Key, Option<&'a [u8]>) {
+    const ENCODED_BY_BATCH: &str = "the entries have been encoded by the batch";
+    let key = <Key as scale::Decode>::decode(entries).expect(ENCODED_BY_BATCH);
+    let has_value = <u8 as scale::Decode>::decode(entries).expect(ENCODED_BY_BATCH);
+    if has_value == 0 {
+        return (key, None)
+    }
+    let len = <scale::Compact<u32> as scale::Decode>::decode(entries)
+        .expect(ENCODED_BY_BATCH)
+        .0;
+    let (value, rest) = entries.split_at(len as usize);
+    *entries = rest;
+    (key, Some(value))
+}
+
+impl scale::Encode for StorageBatch {
+    fn encode_to<T: scale::Output>(&self, dest: &mut T) {
+        scale::Compact(self.len).encode_to(dest);
+        dest.write(&self.entries);
+    }
+}
+
+/// The change of the contract storage footprint upon applying a storage batch.
+///
+/// Created by [`storage_footprint_delta`](fn.storage_footprint_delta.html).
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct FootprintDelta {
+    /// The number of cells that are occupied after the batch but not before.
+    pub added_items: u32,
+    /// The number of cells that are occupied before the batch but not after.
+    pub removed_items: u32,
+    /// The number of bytes by which the stored values grow.
+    pub added_bytes: u32,
+    /// The number of bytes by which the stored values shrink.
+    pub removed_bytes: u32,
+}
+
+impl FootprintDelta {
+    /// Returns the storage deposit required for the footprint change given
+    /// the deposit per occupied cell and per stored byte.
+    ///
+    /// Returns zero if the footprint does not grow overall.
+    pub fn deposit<T>(&self, per_item: T::Balance, per_byte: T::Balance) -> T::Balance
+    where
+        T: EnvTypes,
+    {
+        let grown = per_item * T::Balance::from(self.added_items)
+            + per_byte * T::Balance::from(self.added_bytes);
+        let shrunk = per_item * T::Balance::from(self.removed_items)
+            + per_byte * T::Balance::from(self.removed_bytes);
+        if grown > shrunk {
+            grown - shrunk
+        } else {
+            T::Balance::from(0)
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ink_prelude::vec::Vec;
+
+    #[test]
+    fn batch_encodes_as_entries() {
+        let mut batch = StorageBatch::new();
+        batch.set(Key([0x01; 32]), &42_u32);
+        batch.clear(Key([0x02; 32]));
+        // The length prefix of large values spans multiple bytes.
+        batch.set(Key([0x03; 32]), &ink_prelude::vec![0xFF_u8; 100]);
+        let entries: Vec<(Key, Option<Vec<u8>>)> = vec![
+            (Key([0x01; 32]), Some(42_u32.encode())),
+            (Key([0x02; 32]), None),
+            (
+                Key([0x03; 32]),
+                Some(ink_prelude::vec![0xFF_u8; 100].encode()),
+            ),
+        ];
+        assert_eq!(batch.len(), 3);
+        assert_eq!(batch.encode(), entries.encode());
+        assert_eq!(
+            batch.iter().collect::<Vec<_>>(),
+            entries
+                .iter()
+                .map(|(key, value)| (*key, value.as_ref().map(AsRef::as_ref)))
+                .collect::<Vec<_>>(),
+        );
+    }
+
+    #[test]
+    fn batch_splits_into_parts() {
+        let mut batch = StorageBatch::new();
+        for n in 0..5 {
+            batch.set(Key([n; 32]), &[n; 20]);
+        }
+        batch.clear(Key([0x05; 32]));
+        // Every set entry is encoded into 32 + 1 + 1 + 20 = 54 bytes
+        // and every cleared entry into 32 + 1 = 33 bytes.
+        let parts = batch.parts(1 + 2 * 54).collect::<Vec<_>>();
+        assert_eq!(parts.len(), 3);
+        assert_eq!(
+            parts.iter().map(BatchPart::encoded_len).collect::<Vec<_>>(),
+            vec![1 + 2 * 54, 1 + 2 * 54, 1 + 54 + 33],
+        );
+        let expected = batch.iter().collect::<Vec<_>>();
+        for (part, entries) in parts.iter().zip(expected.chunks(2)) {
+            let entries = entries
+                .iter()
+                .map(|(key, value)| (*key, value.map(<[u8]>::to_vec)))
+                .collect::<Vec<_>>();
+            assert_eq!(part.encode(), entries.encode());
+        }
+        // Entries exceeding the capacity on their own form a part of their own.
+        assert_eq!(
+            batch
+                .parts(10)
+                .map(|part| part.encoded_len())
+                .collect::<Vec<_>>(),
+            vec![1 + 54, 1 + 54, 1 + 54, 1 + 54, 1 + 54, 1 + 33],
+        );
+        assert_eq!(StorageBatch::new().parts(10).count(), 0);
+    }
+}
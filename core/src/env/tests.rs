@@ -64,3 +64,162 @@ fn test_hash_blake2_128() {
         [180, 158, 48, 21, 171, 163, 217, 175, 145, 160, 25, 159, 213, 142, 103, 242]
     );
 }
+
+#[test]
+fn test_hash_blake2s_256() {
+    let mut output = [0x00_u8; 32];
+    env::hash::blake2s_256(TEST_INPUT, &mut output);
+    assert_eq!(
+        output,
+        [
+            235, 23, 159, 169, 145, 119, 154, 243, 166, 244, 194, 252, 254, 202, 13, 132,
+            71, 17, 107, 20, 245, 253, 29, 21, 43, 70, 222, 191, 181, 74, 97, 117
+        ]
+    );
+}
+
+#[test]
+fn test_ecdsa_recover() {
+    let signature = [
+        243, 27, 115, 127, 116, 172, 33, 54, 111, 140, 82, 15, 62, 55, 146, 248, 59, 91,
+        144, 133, 71, 146, 210, 238, 219, 54, 89, 135, 223, 68, 204, 56, 48, 215, 199, 6,
+        177, 214, 57, 3, 107, 196, 224, 70, 245, 121, 101, 226, 56, 184, 18, 34, 3, 212,
+        18, 250, 73, 124, 160, 228, 139, 220, 110, 66, 1,
+    ];
+    let message_hash = [0x11_u8; 32];
+    let expected_public_key = env::EcdsaPublicKey::from([
+        3, 36, 101, 62, 172, 67, 68, 136, 0, 44, 192, 107, 191, 183, 241, 15, 225, 137,
+        145, 227, 95, 159, 228, 48, 45, 190, 166, 210, 53, 61, 192, 171, 28,
+    ]);
+    let public_key = env::ecdsa_recover(&signature, &message_hash);
+    assert_eq!(public_key, Ok(expected_public_key));
+    // Ethereum-style recovery IDs are offset by 27.
+    let mut eth_signature = signature;
+    eth_signature[64] += 27;
+    assert_eq!(
+        env::ecdsa_recover(&eth_signature, &message_hash),
+        Ok(expected_public_key)
+    );
+    assert_eq!(
+        expected_public_key.to_default_account_id(),
+        env::AccountId::from([
+            255, 36, 23, 16, 82, 148, 118, 172, 135, 198, 123, 102, 204, 220, 66, 249,
+            90, 20, 180, 154, 137, 97, 100, 131, 159, 230, 117, 220, 111, 87, 150, 20
+        ])
+    );
+    // A different message hash recovers a different public key.
+    assert_ne!(
+        env::ecdsa_recover(&signature, &[0x22; 32]),
+        Ok(expected_public_key)
+    );
+    // Invalid recovery IDs are rejected.
+    let mut invalid_signature = signature;
+    invalid_signature[64] = 4;
+    assert_eq!(
+        env::ecdsa_recover(&invalid_signature, &message_hash),
+        Err(env::EnvError::EcdsaRecoverFailed)
+    );
+}
+
+#[test]
+fn test_hash_bytes() {
+    use env::hash::{
+        Blake2s256,
+        Blake2x128,
+        Blake2x256,
+        Keccak256,
+        Sha2x256,
+    };
+    let mut output = [0x00_u8; 32];
+    env::hash::keccak_256(TEST_INPUT, &mut output);
+    assert_eq!(env::hash_bytes::<Keccak256>(TEST_INPUT), output);
+    env::hash::sha2_256(TEST_INPUT, &mut output);
+    assert_eq!(env::hash_bytes::<Sha2x256>(TEST_INPUT), output);
+    env::hash::blake2_256(TEST_INPUT, &mut output);
+    assert_eq!(env::hash_bytes::<Blake2x256>(TEST_INPUT), output);
+    env::hash::blake2s_256(TEST_INPUT, &mut output);
+    assert_eq!(env::hash_bytes::<Blake2s256>(TEST_INPUT), output);
+    let mut output = [0x00_u8; 16];
+    env::hash::blake2_128(TEST_INPUT, &mut output);
+    assert_eq!(env::hash_bytes::<Blake2x128>(TEST_INPUT), output);
+}
+
+#[test]
+fn test_hash_encoded() {
+    use env::hash::Blake2x256;
+    let input = (42_u32, TEST_INPUT.to_vec());
+    assert_eq!(
+        env::hash_encoded::<Blake2x256, _>(&input),
+        env::hash_bytes::<Blake2x256>(&scale::Encode::encode(&input)),
+    );
+}
+
+#[test]
+fn test_call_flags() {
+    use env::call::CallFlags;
+    let default = CallFlags::default();
+    assert!(default.allow_reentry());
+    assert!(!default.forward_input());
+    assert!(!default.tail_call());
+    let flags = default
+        .set_allow_reentry(false)
+        .set_forward_input(true)
+        .set_tail_call(true);
+    assert!(!flags.allow_reentry());
+    assert_eq!(flags.into_u32(), 0b0000_0101);
+    assert_eq!(flags.set_allow_reentry(true).into_u32(), 0b0000_1101);
+}
+
+#[test]
+fn test_compute_contract_address() {
+    use env::{
+        hash::Blake2x256,
+        AccountId,
+        DefaultEnvTypes,
+        Hash,
+    };
+    let deployer = AccountId::from([0x01; 32]);
+    let code_hash = Hash::from([0x02; 32]);
+    let address = env::compute_contract_address::<DefaultEnvTypes>(
+        &deployer,
+        &code_hash,
+        &1_u64.to_le_bytes(),
+    );
+    let mut preimage = [0x01; 32].to_vec();
+    preimage.extend_from_slice(&[0x02; 32]);
+    preimage.extend_from_slice(&[1, 0, 0, 0, 0, 0, 0, 0]);
+    let expected = AccountId::from(env::hash_bytes::<Blake2x256>(&preimage));
+    assert_eq!(address, Ok(expected));
+    // Different salts yield different addresses.
+    assert_ne!(
+        env::compute_contract_address::<DefaultEnvTypes>(
+            &deployer,
+            &code_hash,
+            &2_u64.to_le_bytes(),
+        ),
+        Ok(expected)
+    );
+}
+
+#[test]
+fn test_account_id_utilities() {
+    use env::AccountId;
+    assert_eq!(AccountId::ZERO, AccountId::default());
+    assert!(AccountId::ZERO.is_zero());
+    let bytes = [0x01; 32];
+    let account_id = AccountId::from(bytes);
+    assert!(!account_id.is_zero());
+    assert_eq!(<[u8; 32]>::from(account_id), bytes);
+    assert_eq!(account_id.as_bytes(), &bytes);
+    assert_eq!(account_id.as_ref(), &bytes[..]);
+    // Seeds are deterministic and distinct seeds yield distinct accounts.
+    assert_eq!(AccountId::from_seed("alice"), AccountId::from_seed("alice"));
+    assert_ne!(AccountId::from_seed("alice"), AccountId::from_seed("bob"));
+    let mut expected = [0x00; 32];
+    env::hash::blake2_256(b"alice", &mut expected);
+    assert_eq!(AccountId::from_seed("alice"), AccountId::from(expected));
+    assert_eq!(
+        format!("{}", AccountId::from([0xAB; 32])),
+        format!("0x{}", "ab".repeat(32))
+    );
+}
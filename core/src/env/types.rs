@@ -169,15 +169,65 @@ pub trait EnvTypes {
     type Call: 'static + scale::Codec;
 }
 
+/// The flags to indicate further information about the end of a contract execution.
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct ReturnFlags {
+    /// The raw flag bits.
+    bits: u32,
+}
+
+impl ReturnFlags {
+    /// Reverts all state changes of the executed contract.
+    pub const REVERT: Self = Self { bits: 0b0000_0001 };
+
+    /// Sets or clears the revert flag.
+    pub fn set_reverted(mut self, has_reverted: bool) -> Self {
+        if has_reverted {
+            self.bits |= Self::REVERT.bits;
+        } else {
+            self.bits &= !Self::REVERT.bits;
+        }
+        self
+    }
+
+    /// Returns `true` if the execution reverts its state changes.
+    pub fn is_reverted(&self) -> bool {
+        self.bits & Self::REVERT.bits != 0
+    }
+
+    /// Returns the raw flag bits.
+    pub fn into_u32(self) -> u32 {
+        self.bits
+    }
+}
+
 /// Implemented by event types to communicate their topic hashes.
+///
+/// # Note
+///
+/// Use [`topic`](fn.topic.html) in order to compute the topic hash
+/// of an indexed event field.
 pub trait Topics<T>
 where
     T: EnvTypes,
 {
     /// Returns the topic hashes of `self`.
-    fn topics(&self) -> &'static [<T as EnvTypes>::Hash];
+    fn topics(&self) -> EventTopics<<T as EnvTypes>::Hash>;
 }
 
+/// The topic hashes of an event.
+#[cfg(not(all(feature = "ink-alloc-free", not(feature = "std"))))]
+pub type EventTopics<H> = Vec<H>;
+
+/// The topic hashes of an event.
+///
+/// # Note
+///
+/// The number of topics is limited to the default maximum number of event
+/// topics of the contracts pallet in the alloc-free environment.
+#[cfg(all(feature = "ink-alloc-free", not(feature = "std")))]
+pub type EventTopics<H> = crate::env::static_topics::StaticTopics<H>;
+
 /// The fundamental types of the default configuration.
 #[derive(Debug, Clone, PartialEq, Eq)]
 #[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
@@ -201,6 +251,73 @@ pub type Timestamp = u64;
 /// The default block number type.
 pub type BlockNumber = u64;
 
+/// An amount of gas, also known as weight.
+///
+/// # Note
+///
+/// Provides checked arithmetic so that contracts can safely compute
+/// whether they are able to afford further operations.
+#[derive(
+    Debug,
+    Copy,
+    Clone,
+    PartialEq,
+    Eq,
+    Ord,
+    PartialOrd,
+    Hash,
+    Encode,
+    Decode,
+    From,
+    Default,
+)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct Weight(u64);
+
+impl Weight {
+    /// Creates a new weight from the given amount of gas.
+    pub const fn new(gas: u64) -> Self {
+        Self(gas)
+    }
+
+    /// Returns the amount of gas.
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Adds two weights returning `None` upon overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Subtracts two weights returning `None` upon underflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+
+    /// Multiplies the weight by a factor returning `None` upon overflow.
+    pub fn checked_mul(self, factor: u64) -> Option<Self> {
+        self.0.checked_mul(factor).map(Self)
+    }
+
+    /// Subtracts two weights saturating at zero.
+    pub fn saturating_sub(self, rhs: Self) -> Self {
+        Self(self.0.saturating_sub(rhs.0))
+    }
+
+    /// Returns the price of the weight in the current gas price.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::weight_to_fee`]
+    pub fn to_fee<T>(self) -> crate::env::Result<T::Balance>
+    where
+        T: EnvTypes,
+    {
+        crate::env::weight_to_fee::<T>(self.0)
+    }
+}
+
 /// This call type guarantees to never be constructed.
 ///
 /// This has the effect that users of the default env types are
@@ -266,6 +383,64 @@ impl<'a> TryFrom<&'a [u8]> for AccountId {
     }
 }
 
+impl AccountId {
+    /// The account ID that consists only of 0 bits.
+    ///
+    /// # Note
+    ///
+    /// No key pair is known for it so it is commonly used as the
+    /// address of a burned or not yet assigned account.
+    pub const ZERO: Self = Self([0x00; 32]);
+
+    /// Returns the bytes of the account ID.
+    pub fn as_bytes(&self) -> &[u8; 32] {
+        &self.0
+    }
+
+    /// Returns `true` if the account ID is the zero account ID.
+    pub fn is_zero(&self) -> bool {
+        *self == Self::ZERO
+    }
+
+    /// Returns a deterministic account ID derived from the given seed.
+    ///
+    /// # Note
+    ///
+    /// This is the BLAKE2 256-bit hash of the seed and is meant to create
+    /// distinct accounts in off-chain tests, e.g. `AccountId::from_seed("alice")`.
+    /// No key pair is known for the resulting account ID.
+    #[cfg(feature = "std")]
+    pub fn from_seed(seed: &str) -> Self {
+        let mut output = [0x00; 32];
+        crate::env::hash::blake2_256(seed.as_bytes(), &mut output);
+        Self(output)
+    }
+}
+
+impl From<AccountId> for [u8; 32] {
+    fn from(account_id: AccountId) -> Self {
+        account_id.0
+    }
+}
+
+impl AsRef<[u8]> for AccountId {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+/// Displays the account ID as `0x` followed by its bytes in lowercase hex.
+#[cfg(feature = "std")]
+impl core::fmt::Display for AccountId {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        write!(f, "0x")?;
+        for byte in &self.0 {
+            write!(f, "{:02x}", byte)?;
+        }
+        Ok(())
+    }
+}
+
 impl Flush for AccountId {}
 
 /// The default environment `Hash` type.
@@ -334,3 +509,46 @@ impl Clear for Hash {
 }
 
 impl Flush for Hash {}
+
+/// A compressed ECDSA public key as returned by [`ecdsa_recover`](fn.ecdsa_recover.html).
+///
+/// # Note
+///
+/// Consists of a one byte prefix for the parity of the `y` coordinate
+/// followed by the 32 bytes of the `x` coordinate.
+#[derive(Copy, Clone, From)]
+pub struct EcdsaPublicKey([u8; 33]);
+
+impl PartialEq for EcdsaPublicKey {
+    fn eq(&self, other: &Self) -> bool {
+        self.0[..] == other.0[..]
+    }
+}
+
+impl Eq for EcdsaPublicKey {}
+
+impl core::fmt::Debug for EcdsaPublicKey {
+    fn fmt(&self, f: &mut core::fmt::Formatter) -> core::fmt::Result {
+        f.debug_tuple("EcdsaPublicKey").field(&&self.0[..]).finish()
+    }
+}
+
+impl AsRef<[u8]> for EcdsaPublicKey {
+    fn as_ref(&self) -> &[u8] {
+        &self.0[..]
+    }
+}
+
+impl EcdsaPublicKey {
+    /// Returns the default `AccountId` associated to the public key.
+    ///
+    /// # Note
+    ///
+    /// This is the BLAKE2 256-bit hash of the compressed public key
+    /// which mirrors how Substrate derives account IDs from ECDSA keys.
+    pub fn to_default_account_id(&self) -> AccountId {
+        let mut output = [0x00; 32];
+        crate::env::hash::blake2_256(&self.0[..], &mut output);
+        AccountId(output)
+    }
+}
@@ -13,7 +13,13 @@
 // limitations under the License.
 
 #[cfg(feature = "ink-generate-abi")]
-use type_metadata::Metadata;
+use type_metadata::{
+    HasTypeDef,
+    NamedField,
+    TypeDef,
+    TypeDefStruct,
+    TypeId,
+};
 
 use super::*;
 use ink_primitives::Key;
@@ -28,10 +34,19 @@ use ink_primitives::Key;
 ///
 /// Users are recommended to use the [`DynAlloc`](struct.DynAlloc.html)
 /// for dynamic storage allocation purposes instead.
-#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+#[cfg_attr(feature = "ink-generate-abi", derive(TypeId))]
 pub struct BumpAlloc {
     /// The key offset used for all allocations.
     offset_key: Key,
+    /// The name of the storage field that is currently allocated if known.
+    field: Option<&'static str>,
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl HasTypeDef for BumpAlloc {
+    fn type_def() -> TypeDef {
+        TypeDefStruct::new(vec![NamedField::of::<Key>("offset_key")]).into()
+    }
 }
 
 impl BumpAlloc {
@@ -43,12 +58,31 @@ impl BumpAlloc {
     /// This is meant to be used by pDSL internals only.
     #[inline(always)]
     pub unsafe fn from_raw_parts(offset_key: Key) -> Self {
-        Self { offset_key }
+        Self {
+            offset_key,
+            field: None,
+        }
     }
 
     /// Increase the forward alloc offset key by the given amount.
+    ///
+    /// # Panics
+    ///
+    /// If the offset key overflows the key space.
     fn inc_offset_key(&mut self, by: u64) {
-        self.offset_key += by;
+        match self.offset_key.checked_add(by) {
+            Some(offset_key) => self.offset_key = offset_key,
+            None => {
+                ink_panic!(
+                    "[ink_core::BumpAlloc::alloc] Error: \
+                     allocating {} cells for storage field `{}` at {} \
+                     overflows the key space",
+                    by,
+                    self.field.unwrap_or("<unknown>"),
+                    self.offset_key,
+                )
+            }
+        }
     }
 }
 
@@ -56,7 +90,7 @@ impl Allocate for BumpAlloc {
     #[inline]
     fn alloc(&mut self, size: u64) -> Key {
         if size == 0 {
-            panic!(
+            ink_panic!(
                 "[psdl_core::BumpAlloc::alloc] Error: \
                  cannot allocate zero (0) bytes"
             )
@@ -65,6 +99,11 @@ impl Allocate for BumpAlloc {
         self.inc_offset_key(size);
         key
     }
+
+    #[inline]
+    fn enter_field(&mut self, name: &'static str) {
+        self.field = Some(name);
+    }
 }
 
 #[cfg(test)]
@@ -97,6 +136,17 @@ mod tests {
         )
     }
 
+    #[test]
+    #[should_panic(expected = "storage field `cells` at")]
+    fn allocate_overflow() {
+        let offset_key = Key([0xFF; 32]) - 9_u32;
+        let mut bump_alloc = unsafe { BumpAlloc::from_raw_parts(offset_key) };
+        bump_alloc.enter_field("len");
+        assert_eq!(bump_alloc.alloc(1), offset_key);
+        bump_alloc.enter_field("cells");
+        bump_alloc.alloc(u64::from(u32::max_value()));
+    }
+
     #[test]
     #[should_panic]
     fn allocate_zero() {
@@ -21,6 +21,7 @@ use crate::storage::{
     },
     Allocator,
     Flush,
+    InkExpect as _,
 };
 #[cfg(feature = "ink-generate-abi")]
 use ink_abi::{
@@ -35,18 +36,17 @@ use type_metadata::Metadata;
 
 /// Allocator for dynamic contract storage.
 ///
-/// Uses storage effective bit vectors for free list representation.
-/// Searches for free cells and chunks via first-fit approach which
-/// can be slow (more than 2 reads) for more than 3000 dynamic allocations
-/// at the same time. This is subject to change in the future if
-/// experiments show that this is a bottle neck.
+/// Uses bit stashes to track the used cell and chunk slots.
+/// Always hands out the smallest free slot which usually requires
+/// reading only the header, a single counts pack and a single word
+/// of the respective bit stash.
 #[derive(Debug)]
 #[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
 pub struct DynAlloc {
-    /// Bitmap indicating free cell slots.
-    free_cells: storage::BitVec,
-    /// Bitmap indicating free chunk slots.
-    free_chunks: storage::BitVec,
+    /// The used cell slots.
+    cells: storage::BitStash,
+    /// The used chunk slots.
+    chunks: storage::BitStash,
     /// Offset origin key for all cells.
     cells_origin: Key,
     /// Offset origin key for all chunks.
@@ -59,8 +59,8 @@ impl HasLayout for DynAlloc {
         LayoutStruct::new(
             Self::meta_type(),
             vec![
-                LayoutField::of("free_cells", &self.free_cells),
-                LayoutField::of("free_chunks", &self.free_cells),
+                LayoutField::of("cells", &self.cells),
+                LayoutField::of("chunks", &self.chunks),
                 LayoutField::of("cells_origin", &self.cells_origin),
                 LayoutField::of("chunks_origin", &self.chunks_origin),
             ],
@@ -75,8 +75,8 @@ impl AllocateUsing for DynAlloc {
         A: Allocate,
     {
         Self {
-            free_cells: AllocateUsing::allocate_using(alloc),
-            free_chunks: AllocateUsing::allocate_using(alloc),
+            cells: AllocateUsing::allocate_using(alloc),
+            chunks: AllocateUsing::allocate_using(alloc),
             cells_origin: alloc.alloc(u32::max_value().into()),
             chunks_origin: alloc.alloc(u32::max_value().into()),
         }
@@ -88,16 +88,16 @@ impl Initialize for DynAlloc {
 
     #[inline]
     fn initialize(&mut self, _args: Self::Args) {
-        self.free_cells.initialize(());
-        self.free_chunks.initialize(());
+        self.cells.initialize(());
+        self.chunks.initialize(());
     }
 }
 
 impl Flush for DynAlloc {
     #[inline]
     fn flush(&mut self) {
-        self.free_cells.flush();
-        self.free_chunks.flush();
+        self.cells.flush();
+        self.chunks.flush();
     }
 }
 
@@ -115,27 +115,12 @@ impl DynAlloc {
 impl DynAlloc {
     /// Allocates another cell and returns its key.
     fn alloc_cell(&mut self) -> Key {
-        let offset = if let Some(free) = self.free_cells.first_set_position() {
-            self.free_cells.set(free, false);
-            free
-        } else {
-            let len = self.free_cells.len();
-            self.free_cells.push(false);
-            len
-        };
-        self.cells_origin + offset
+        self.cells_origin + self.cells.put()
     }
 
     /// Allocates another chunk and returns its key.
     fn alloc_chunk(&mut self) -> Key {
-        let offset = if let Some(free) = self.free_chunks.first_set_position() {
-            self.free_chunks.set(free, false);
-            free
-        } else {
-            let len = self.free_chunks.len();
-            self.free_chunks.push(false);
-            len
-        };
+        let offset = self.chunks.put();
         self.chunks_origin + ((1 << 32) * u64::from(offset))
     }
 
@@ -146,9 +131,9 @@ impl DynAlloc {
     /// This just frees the associated slot for future allocations.
     fn dealloc_cell(&mut self, key: Key) {
         debug_assert!(key >= self.cells_origin);
-        debug_assert!(key < self.cells_origin + self.free_cells.len());
         let position = self.key_to_cell_position(key);
-        self.free_cells.set(position, true);
+        let was_used = self.cells.take(position);
+        debug_assert!(was_used);
     }
 
     /// Deallocates the chunk key.
@@ -158,18 +143,16 @@ impl DynAlloc {
     /// This just frees the associated slot for future allocations.
     fn dealloc_chunk(&mut self, key: Key) {
         debug_assert!(key >= self.chunks_origin);
-        debug_assert!(
-            key < self.chunks_origin + ((1 << 32) * u64::from(self.free_chunks.len()))
-        );
         let position = self.key_to_chunk_position(key);
-        self.free_chunks.set(position, true);
+        let was_used = self.chunks.take(position);
+        debug_assert!(was_used);
     }
 
     /// Converts a key previously allocated as cell key
     /// back into its offset position.
     fn key_to_cell_position(&self, key: Key) -> u32 {
         let diff = key - self.cells_origin;
-        diff.try_to_u32().expect(
+        diff.try_to_u32().ink_expect(
             "if allocated by this allocator the key difference can
 				 never be greater than u32::MAX; qed",
         )
@@ -179,7 +162,7 @@ impl DynAlloc {
     /// back into its offset position.
     fn key_to_chunk_position(&self, key: Key) -> u32 {
         let diff = key - self.chunks_origin;
-        let position = diff.try_to_u64().expect(
+        let position = diff.try_to_u64().ink_expect(
             "if allocated by this allocator the key difference can
 				 never be greater than u64::MAX; qed",
         );
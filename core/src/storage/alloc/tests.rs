@@ -69,3 +69,41 @@ fn dyn_simple() {
     assert_eq!(alloc.alloc(u32::max_value() as u64), chunk_allocs[1]);
     assert_eq!(alloc.alloc(u32::max_value() as u64), chunk_allocs[3]);
 }
+
+#[test]
+fn allocate_array() -> crate::env::Result<()> {
+    use crate::{
+        env,
+        storage::{
+            self,
+            Flush as _,
+        },
+    };
+
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let (mut values, mut vecs) = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            let values = <[storage::Value<u32>; 3]>::allocate_using(&mut alloc);
+            let vecs = <[storage::Vec<u8>; 32]>::allocate_using(&mut alloc);
+            (values.initialize_into([1, 2, 3]), vecs)
+        };
+        // Arrays of collections that support default initialization support it as well.
+        assert!(<[storage::Vec<u8>; 32]>::default_value().is_some());
+        assert!(<[storage::Value<u32>; 3]>::default_value().is_none());
+        vecs.try_default_initialize();
+        vecs[31].push(42);
+        *values[1] += 40;
+        values.flush();
+        vecs.flush();
+        // Every element occupies its own cells.
+        for n in 0..3 {
+            assert_eq!(
+                env::get_contract_storage::<u32>(Key([0x0; 32]) + n as u32),
+                Some(Ok([1, 42, 3][n]))
+            );
+        }
+        assert_eq!(vecs[31].get(0), Some(&42));
+        assert!(vecs[0].is_empty());
+        Ok(())
+    })
+}
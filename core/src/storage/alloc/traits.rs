@@ -29,6 +29,15 @@ pub trait Allocate {
     /// The returned key denotes a storage region that fits for at
     /// least the given number of cells.
     fn alloc(&mut self, size: u64) -> Key;
+
+    /// Announces the name of the storage field that is allocated next.
+    ///
+    /// # Note
+    ///
+    /// This is only used for diagnostics, e.g. to name the field
+    /// whose allocation overflowed the key space.
+    #[inline(always)]
+    fn enter_field(&mut self, _name: &'static str) {}
 }
 
 /// Types implementing this trait are storage allocators.
@@ -128,3 +137,105 @@ where
         this
     }
 }
+
+/// Expands to the given expression while discarding the token tree.
+///
+/// Used to repeat an expression once for every element of an array.
+macro_rules! replace_expr {
+    ( $_t:tt $sub:expr ) => {
+        $sub
+    };
+}
+
+/// Implements the storage allocation traits for arrays of the given lengths.
+///
+/// # Note
+///
+/// The lengths must be given in descending order and consecutively down to `1`
+/// since the length of every implemented array is derived from the number of
+/// remaining lengths.
+macro_rules! impl_array_alloc_for {
+    ( $n:literal $( , $rest:literal )* ) => {
+        impl<T> AllocateUsing for [T; $n]
+        where
+            T: AllocateUsing,
+        {
+            /// Allocates the elements one after another so that they are spread
+            /// across their own storage cells.
+            #[inline]
+            unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+            where
+                A: Allocate,
+            {
+                [
+                    $( replace_expr!($rest T::allocate_using(alloc)), )*
+                    T::allocate_using(alloc)
+                ]
+            }
+        }
+
+        impl<T> Initialize for [T; $n]
+        where
+            T: Initialize,
+        {
+            type Args = [T::Args; $n];
+
+            /// Supports default initialization if the elements support it.
+            #[inline]
+            fn default_value() -> Option<Self::Args> {
+                Some([
+                    $( replace_expr!($rest T::default_value()?), )*
+                    T::default_value()?
+                ])
+            }
+
+            #[inline]
+            fn initialize(&mut self, args: Self::Args) {
+                // The arguments are moved out one by one and must not be dropped afterwards.
+                let args = core::mem::ManuallyDrop::new(args);
+                for (elem, arg) in self.iter_mut().zip(args.iter()) {
+                    elem.initialize(unsafe { core::ptr::read(arg) })
+                }
+            }
+        }
+
+        impl_array_alloc_for!( $( $rest ),* );
+    };
+    () => {
+        impl<T> AllocateUsing for [T; 0]
+        where
+            T: AllocateUsing,
+        {
+            #[inline]
+            unsafe fn allocate_using<A>(_alloc: &mut A) -> Self
+            where
+                A: Allocate,
+            {
+                []
+            }
+        }
+
+        impl<T> Initialize for [T; 0]
+        where
+            T: Initialize,
+        {
+            type Args = [T::Args; 0];
+
+            #[inline]
+            fn default_value() -> Option<Self::Args> {
+                Some([])
+            }
+
+            #[inline]
+            fn initialize(&mut self, _args: Self::Args) {}
+        }
+    };
+}
+
+#[rustfmt::skip]
+impl_array_alloc_for!(
+	32, 31, 30, 29, 28, 27, 26, 25, 24, 23,
+	22, 21, 20, 19, 18, 17, 16, 15, 14, 13,
+	12, 11, 10,  9,  8,  7,  6,  5,  4,  3,
+	 2,  1
+);
@@ -20,6 +20,8 @@ use crate::storage::{
         AllocateUsing,
     },
     cell::TypedCell,
+    fingerprint,
+    Fingerprint,
     Flush,
 };
 #[cfg(feature = "ink-generate-abi")]
@@ -74,6 +76,11 @@ pub struct SyncCacheEntry<T> {
     /// This is required as soon as there are potential writes to the
     /// value stored in the associated cell.
     dirty: bool,
+    /// The fingerprint of the value in the contract storage.
+    ///
+    /// This is only known if the entry was in sync with the contract
+    /// storage before it became dirty.
+    origin: Option<Fingerprint>,
     /// The value of the cell.
     ///
     /// Being captured in a `Pin` allows to provide robust references to the outside.
@@ -96,6 +103,7 @@ impl<T> SyncCacheEntry<T> {
     pub fn new(val: Option<T>) -> Self {
         Self {
             dirty: false,
+            origin: None,
             cell_val: Box::new(val),
         }
     }
@@ -113,6 +121,7 @@ impl<T> SyncCacheEntry<T> {
     /// Marks the cached value as clean.
     pub fn mark_clean(&mut self) {
         self.dirty = false;
+        self.origin = None;
     }
 
     /// Returns an immutable reference to the synchronized cached value.
@@ -121,6 +130,25 @@ impl<T> SyncCacheEntry<T> {
     }
 }
 
+impl<T> SyncCacheEntry<T>
+where
+    T: scale::Encode,
+{
+    /// Remembers the fingerprint of the cached value if it is clean.
+    ///
+    /// This must be called before the cached value is mutated.
+    pub fn track_origin(&mut self) {
+        if !self.dirty {
+            self.origin = Some(fingerprint(&self.cell_val));
+        }
+    }
+
+    /// Returns `true` if the cached value is encoded the same as in the contract storage.
+    pub fn is_unchanged(&self) -> bool {
+        self.origin == Some(fingerprint(&self.cell_val))
+    }
+}
+
 impl<T> SyncCacheEntry<T> {
     /// Returns a mutable reference to the synchronized cached value.
     ///
@@ -200,7 +228,7 @@ impl<T> CacheEntry<T> {
     pub fn get(&self) -> Option<&T> {
         match self {
             CacheEntry::Desync => {
-                panic!(
+                ink_panic!(
                     "[ink_core::sync_cell::CacheEntry::get] Error: \
                      tried to get the value from a desync cache"
                 )
@@ -217,7 +245,7 @@ impl<T> CacheEntry<T> {
     pub fn get_mut(&mut self) -> Option<&mut T> {
         match self {
             CacheEntry::Desync => {
-                panic!(
+                ink_panic!(
                     "[ink_core::sync_cell::CacheEntry::get_mut] Error: \
                      tried to get the value from a desync cache"
                 )
@@ -227,6 +255,27 @@ impl<T> CacheEntry<T> {
     }
 }
 
+impl<T> CacheEntry<T>
+where
+    T: scale::Encode,
+{
+    /// Remembers the fingerprint of the cached value if it is in sync and clean.
+    pub fn track_origin(&mut self) {
+        match self {
+            CacheEntry::Sync(sync_entry) => sync_entry.track_origin(),
+            CacheEntry::Desync => (),
+        }
+    }
+
+    /// Returns `true` if the cached value is encoded the same as in the contract storage.
+    pub fn is_unchanged(&self) -> bool {
+        match self {
+            CacheEntry::Desync => false,
+            CacheEntry::Sync(sync_entry) => sync_entry.is_unchanged(),
+        }
+    }
+}
+
 /// A cache for synchronizing values between memory and storage.
 #[derive(Debug)]
 pub struct Cache<T> {
@@ -273,6 +322,22 @@ impl<T> Cache<T> {
         self.entry.borrow_mut().mark_clean()
     }
 
+    /// Remembers the fingerprint of the cached value if it is in sync and clean.
+    pub fn track_origin(&mut self)
+    where
+        T: scale::Encode,
+    {
+        self.entry.borrow_mut().track_origin()
+    }
+
+    /// Returns `true` if the cached value is encoded the same as in the contract storage.
+    pub fn is_unchanged(&self) -> bool
+    where
+        T: scale::Encode,
+    {
+        self.entry.borrow().is_unchanged()
+    }
+
     /// Returns an immutable reference to the internal cache entry.
     ///
     /// Used to returns references from the inside to the outside.
@@ -340,12 +405,20 @@ where
     #[inline]
     fn flush(&mut self) {
         if self.cache.is_dirty() {
+            // Values that did not change are not written back.
+            let unchanged = self.cache.is_unchanged();
             match self.cache.get_mut() {
                 Some(val) => {
-                    self.cell.store(val);
+                    if !unchanged {
+                        self.cell.store(val);
+                    }
                     val.flush();
                 }
-                None => self.cell.clear(),
+                None => {
+                    if !unchanged {
+                        self.cell.clear()
+                    }
+                }
             }
             self.cache.mark_clean();
         }
@@ -376,6 +449,15 @@ impl<T> SyncCell<T> {
     pub fn raw_key(&self) -> Key {
         self.cell.key()
     }
+
+    /// Drops the cached value so that it is loaded again upon the next access.
+    ///
+    /// # Note
+    ///
+    /// Changes that have not yet been flushed are lost.
+    pub fn invalidate_cache(&mut self) {
+        self.cache = Default::default();
+    }
 }
 
 impl<T> SyncCell<T>
@@ -398,6 +480,7 @@ where
 {
     /// Sets the value of the cell.
     pub fn set(&mut self, val: T) {
+        self.cache.track_origin();
         self.cache.update(Some(val));
         self.cache.mark_dirty();
     }
@@ -413,6 +496,7 @@ where
             let loaded = self.cell.load();
             self.cache.update(loaded);
         }
+        self.cache.track_origin();
         self.cache.mark_dirty();
         self.cache.get_mut()
     }
@@ -433,6 +517,28 @@ where
     }
 }
 
+impl<T> SyncCell<Option<T>> {
+    /// Returns `true` if the cell holds an optional value that is `Some`.
+    ///
+    /// # Note
+    ///
+    /// If the value has not yet been loaded only its leading byte is decoded
+    /// from the contract storage since SCALE encodes `None` as a single zero
+    /// byte and `Some` with a non-zero leading byte. The value is not cached.
+    pub fn is_some(&self) -> bool {
+        if self.cache.is_synced() {
+            return match self.cache.get() {
+                Some(Some(_)) => true,
+                _ => false,
+            }
+        }
+        match crate::env::get_contract_storage::<u8>(self.raw_key()) {
+            Some(Ok(presence)) => presence != 0,
+            _ => false,
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -554,17 +660,50 @@ mod tests {
             }
 
             // Flush the cell and assert reads and writes.
+            //
+            // The cell is not written back since its value did not change.
             cell.flush();
             assert_eq!(
                 env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(
                     &contract_account_id
                 )?,
-                (1, 1)
+                (1, 0)
             );
             Ok(())
         })
     }
 
+    #[test]
+    fn flush_skips_unchanged() -> Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let contract_account_id = env::account_id::<env::DefaultEnvTypes>()?;
+            let mut cell = dummy_cell();
+            cell.set(42);
+            cell.flush();
+            let rw = || {
+                env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(
+                    &contract_account_id,
+                )
+            };
+            assert_eq!(rw()?, (0, 1));
+
+            // Mutations resulting in the same value are not written back.
+            let mut cell = dummy_cell();
+            cell.mutate_with(|val| *val += 1);
+            cell.mutate_with(|val| *val -= 1);
+            cell.set(42);
+            cell.flush();
+            assert_eq!(rw()?, (1, 1));
+
+            // Mutations resulting in a different value are written back.
+            cell.set(43);
+            cell.flush();
+            assert_eq!(rw()?, (1, 2));
+            assert_eq!(dummy_cell().get(), Some(&43));
+            Ok(())
+        })
+    }
+
     #[test]
     fn count_rw_set() -> Result<()> {
         env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
@@ -14,13 +14,31 @@
 
 use crate::{
     env,
-    storage::alloc::{
-        Allocate,
-        AllocateUsing,
+    storage::{
+        alloc::{
+            Allocate,
+            AllocateUsing,
+        },
+        InkExpect as _,
     },
 };
 use core::marker::PhantomData;
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutRange,
+    StorageLayout,
+};
 use ink_primitives::Key;
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::{
+    HasTypeDef,
+    Metadata,
+    NamedField,
+    TypeDef,
+    TypeDefStruct,
+    TypeId,
+};
 
 /// A typed cell.
 ///
@@ -33,6 +51,7 @@ use ink_primitives::Key;
 ///
 /// Read more about kinds of guarantees and their effect [here](../index.html#guarantees).
 #[derive(Debug, PartialEq, Eq, Hash, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(TypeId))]
 pub struct TypedCell<T> {
     /// The associated storage key.
     key: Key,
@@ -40,6 +59,23 @@ pub struct TypedCell<T> {
     marker: PhantomData<fn() -> T>,
 }
 
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasTypeDef for TypedCell<T> {
+    fn type_def() -> TypeDef {
+        TypeDefStruct::new(vec![NamedField::of::<Key>("key")]).into()
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasLayout for TypedCell<T>
+where
+    T: Metadata,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutRange::cell(self.key, T::meta_type()).into()
+    }
+}
+
 impl<T> AllocateUsing for TypedCell<T> {
     #[inline]
     unsafe fn allocate_using<A>(alloc: &mut A) -> Self
@@ -72,7 +108,7 @@ where
     /// Loads the value stored in the cell if any.
     pub fn load(&self) -> Option<T> {
         env::get_contract_storage::<T>(self.key)
-            .map(|result| result.expect("could not decode T from storage cell"))
+            .map(|result| result.ink_expect("could not decode T from storage cell"))
     }
 }
 
@@ -0,0 +1,287 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use num_traits::{
+    CheckedAdd,
+    CheckedSub,
+    Saturating,
+};
+use scale::{
+    Codec,
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    Flush,
+    Value,
+};
+
+/// Errors of the checked arithmetic operations of a [`CheckedCell`](struct.CheckedCell.html).
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum ArithmeticError {
+    /// The result would be greater than the maximum value of the type.
+    Overflow,
+    /// The result would be less than the minimum value of the type,
+    /// e.g. upon transferring more than the available balance.
+    Underflow,
+}
+
+/// A numeric value on the storage that is only mutated through checked arithmetic.
+///
+/// This is meant for balances and supplies for which a silently wrapping
+/// or saturating operation is a bug.
+///
+/// # Note
+///
+/// Failing operations leave the value untouched and do not mark it as
+/// mutated so that it is not written back to the contract storage.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct CheckedCell<T> {
+    /// The wrapped numeric value.
+    value: Value<T>,
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasLayout for CheckedCell<T>
+where
+    T: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(
+            Self::meta_type(),
+            vec![LayoutField::of("value", &self.value)],
+        )
+        .into()
+    }
+}
+
+impl<T> AllocateUsing for CheckedCell<T> {
+    #[inline]
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            value: Value::allocate_using(alloc),
+        }
+    }
+}
+
+impl<T> Initialize for CheckedCell<T>
+where
+    T: Encode,
+{
+    type Args = T;
+
+    #[inline]
+    fn initialize(&mut self, args: Self::Args) {
+        self.value.initialize(args);
+    }
+}
+
+impl<T> Flush for CheckedCell<T>
+where
+    T: Encode + Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.value.flush()
+    }
+}
+
+impl<T> CheckedCell<T>
+where
+    T: Encode + Flush,
+{
+    /// Writes the cached state of the value back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the value so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.value.invalidate_cache();
+    }
+}
+
+impl<T> CheckedCell<T>
+where
+    T: Codec + Copy + CheckedAdd + CheckedSub + Saturating,
+{
+    /// Returns the wrapped value.
+    pub fn get(&self) -> T {
+        *self.value.get()
+    }
+
+    /// Sets the wrapped value to the given value.
+    pub fn set(&mut self, val: T) {
+        self.value.set(val)
+    }
+
+    /// Adds `amount` to the value.
+    ///
+    /// # Errors
+    ///
+    /// If the addition overflows.
+    pub fn checked_add_assign(&mut self, amount: T) -> Result<(), ArithmeticError> {
+        let new_val = self
+            .get()
+            .checked_add(&amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        self.set(new_val);
+        Ok(())
+    }
+
+    /// Subtracts `amount` from the value.
+    ///
+    /// # Errors
+    ///
+    /// If the subtraction underflows.
+    pub fn checked_sub_assign(&mut self, amount: T) -> Result<(), ArithmeticError> {
+        let new_val = self
+            .get()
+            .checked_sub(&amount)
+            .ok_or(ArithmeticError::Underflow)?;
+        self.set(new_val);
+        Ok(())
+    }
+
+    /// Subtracts `amount` from the value saturating at the minimum value.
+    pub fn saturating_sub_assign(&mut self, amount: T) {
+        let new_val = self.get().saturating_sub(amount);
+        self.set(new_val)
+    }
+
+    /// Moves `amount` from this value to `other`.
+    ///
+    /// Either both values are updated or neither of them is.
+    ///
+    /// # Errors
+    ///
+    /// - `Underflow` if this value is less than `amount`.
+    /// - `Overflow` if `other` would overflow.
+    pub fn try_transfer_to(
+        &mut self,
+        other: &mut Self,
+        amount: T,
+    ) -> Result<(), ArithmeticError> {
+        let from = self
+            .get()
+            .checked_sub(&amount)
+            .ok_or(ArithmeticError::Underflow)?;
+        let to = other
+            .get()
+            .checked_add(&amount)
+            .ok_or(ArithmeticError::Overflow)?;
+        self.set(from);
+        other.set(to);
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        env,
+        storage::alloc::BumpAlloc,
+    };
+    use ink_primitives::Key;
+
+    fn two_cells(from: u8, to: u8) -> (CheckedCell<u8>, CheckedCell<u8>) {
+        unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            let a = CheckedCell::allocate_using(&mut alloc).initialize_into(from);
+            let b = CheckedCell::allocate_using(&mut alloc).initialize_into(to);
+            (a, b)
+        }
+    }
+
+    #[test]
+    fn checked_ops() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let (mut cell, _) = two_cells(250, 0);
+            assert_eq!(cell.checked_add_assign(5), Ok(()));
+            assert_eq!(cell.checked_add_assign(1), Err(ArithmeticError::Overflow));
+            assert_eq!(cell.get(), 255);
+            assert_eq!(cell.checked_sub_assign(55), Ok(()));
+            assert_eq!(cell.get(), 200);
+            cell.saturating_sub_assign(201);
+            assert_eq!(cell.get(), 0);
+            assert_eq!(cell.checked_sub_assign(1), Err(ArithmeticError::Underflow));
+            assert_eq!(cell.get(), 0);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn transfer() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let (mut from, mut to) = two_cells(100, 200);
+            assert_eq!(from.try_transfer_to(&mut to, 50), Ok(()));
+            assert_eq!((from.get(), to.get()), (50, 250));
+            assert_eq!(
+                from.try_transfer_to(&mut to, 51),
+                Err(ArithmeticError::Underflow)
+            );
+            assert_eq!(
+                from.try_transfer_to(&mut to, 6),
+                Err(ArithmeticError::Overflow)
+            );
+            assert_eq!((from.get(), to.get()), (50, 250));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn failed_ops_are_not_written() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let contract = env::account_id::<env::DefaultEnvTypes>()?;
+            let rw =
+                || env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(&contract);
+            let (mut from, mut to) = two_cells(1, 255);
+            from.flush();
+            to.flush();
+            assert_eq!(rw()?, (0, 2));
+            assert!(from.checked_sub_assign(2).is_err());
+            assert!(to.checked_add_assign(1).is_err());
+            assert!(from.try_transfer_to(&mut to, 1).is_err());
+            from.flush();
+            to.flush();
+            assert_eq!(rw()?, (0, 2));
+            Ok(())
+        })
+    }
+}
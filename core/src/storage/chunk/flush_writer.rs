@@ -0,0 +1,69 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::env;
+#[cfg(feature = "ink-batched-flush")]
+use crate::env::StorageBatch;
+use ink_primitives::Key;
+
+/// Writes the dirty cells of a storage chunk back to the contract storage.
+///
+/// # Note
+///
+/// Every cell is written with its own host call unless the `ink-batched-flush`
+/// crate feature is enabled. Then the writes are collected into a storage batch
+/// that is submitted upon [`FlushWriter::finish`] which requires a contracts
+/// runtime providing the `ext_set_storage_batch` host function.
+#[derive(Debug, Default)]
+pub struct FlushWriter {
+    /// The collected writes of the flush.
+    #[cfg(feature = "ink-batched-flush")]
+    batch: StorageBatch,
+}
+
+impl FlushWriter {
+    /// Creates a new flush writer.
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Writes the value to the contract storage under the given key.
+    pub fn set<V>(&mut self, key: Key, value: &V)
+    where
+        V: scale::Encode,
+    {
+        #[cfg(feature = "ink-batched-flush")]
+        self.batch.set(key, value);
+        #[cfg(not(feature = "ink-batched-flush"))]
+        env::set_contract_storage(key, value);
+    }
+
+    /// Clears the contract storage under the given key.
+    pub fn clear(&mut self, key: Key) {
+        #[cfg(feature = "ink-batched-flush")]
+        self.batch.clear(key);
+        #[cfg(not(feature = "ink-batched-flush"))]
+        env::clear_contract_storage(key);
+    }
+
+    /// Submits the collected writes.
+    pub fn finish(self) {
+        #[cfg(feature = "ink-batched-flush")]
+        {
+            if !self.batch.is_empty() {
+                env::set_contract_storage_batch(&self.batch);
+            }
+        }
+    }
+}
@@ -0,0 +1,293 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::hash::{
+        Blake2x256,
+        CryptoHash,
+    },
+    storage::{
+        alloc::{
+            Allocate,
+            AllocateUsing,
+        },
+        chunk::{
+            sync_chunk::CacheValue,
+            FlushWriter,
+        },
+        Flush,
+        InkExpect as _,
+    },
+};
+use core::{
+    cell::RefCell,
+    marker::PhantomData,
+};
+use ink_prelude::{
+    boxed::Box,
+    collections::btree_map::BTreeMap,
+};
+use ink_primitives::Key;
+
+/// A lazily loaded mapping from keys to cells of the contract storage.
+///
+/// Each entry is stored in its own cell at the hash of the map's key and
+/// the encoded entry key. Loaded entries are cached until the map is flushed.
+///
+/// # Note
+///
+/// This is a low-level primitive: it does not store its keys and thus
+/// cannot enumerate its entries or track its length. Use `storage::HashMap`
+/// for a high-level map.
+///
+/// # Guarantees
+///
+/// - `Owned`
+/// - `Typed`
+/// - `Opt. Reads`
+/// - `Mutable`
+///
+/// Read more about kinds of guarantees and their effect [here](../index.html#guarantees).
+#[derive(Debug)]
+pub struct LazyHashMap<K, V, H = Blake2x256> {
+    /// The key the keys of the entries are derived from.
+    key: Key,
+    /// The cached entries indexed by their storage keys.
+    ///
+    /// Boxed so that references to cached values remain valid
+    /// while further entries are inserted.
+    cache: RefCell<BTreeMap<Key, Box<CacheValue<V>>>>,
+    /// Marker to trick the Rust compiler into thinking that we actually make use of `K` and `H`.
+    marker: PhantomData<fn() -> (K, H)>,
+}
+
+/// Returns the key of the cell storing the entry for the given key
+/// of a keyed storage entity at `map_key`.
+///
+/// # Note
+///
+/// The entry key is the hash of the encoded `(map_key, key)` tuple.
+/// Shared by all storage entities that store their entries at hashed
+/// keys so that they agree on where entries are stored.
+pub(crate) fn hashed_entry_key<H, K>(map_key: Key, key: &K) -> Key
+where
+    K: scale::Encode,
+    H: CryptoHash,
+{
+    let hash = env::hash_encoded::<H, _>(&(map_key, key));
+    let hash = hash.as_ref();
+    let mut entry_key = Key([0x00; 32]);
+    let len = core::cmp::min(entry_key.0.len(), hash.len());
+    entry_key.0[..len].copy_from_slice(&hash[..len]);
+    entry_key
+}
+
+impl<K, V, H> scale::Encode for LazyHashMap<K, V, H> {
+    fn encode_to<W: scale::Output>(&self, dest: &mut W) {
+        self.key.encode_to(dest)
+    }
+}
+
+impl<K, V, H> scale::Decode for LazyHashMap<K, V, H> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        Key::decode(input).map(Self::from_key)
+    }
+}
+
+impl<K, V, H> AllocateUsing for LazyHashMap<K, V, H> {
+    #[inline]
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self::from_key(alloc.alloc(1))
+    }
+}
+
+impl<K, V, H> Flush for LazyHashMap<K, V, H>
+where
+    V: scale::Encode + Flush,
+{
+    fn flush(&mut self) {
+        let mut writer = FlushWriter::new();
+        for (key, dirty_val) in self
+            .cache
+            .get_mut()
+            .iter_mut()
+            .filter(|(_, v)| v.is_dirty())
+        {
+            // Values that did not change are not written back.
+            let unchanged = dirty_val.is_unchanged();
+            match dirty_val.get_mut() {
+                Some(val) => {
+                    if !unchanged {
+                        writer.set(*key, val);
+                    }
+                    val.flush();
+                }
+                None => {
+                    if !unchanged {
+                        writer.clear(*key)
+                    }
+                }
+            }
+            dirty_val.mark_clean();
+        }
+        writer.finish();
+    }
+}
+
+impl<K, V, H> LazyHashMap<K, V, H> {
+    /// Creates a new map deriving the keys of its entries from the given key.
+    fn from_key(key: Key) -> Self {
+        Self {
+            key,
+            cache: Default::default(),
+            marker: Default::default(),
+        }
+    }
+
+    /// Returns the key the keys of the entries are derived from.
+    ///
+    /// # Note
+    ///
+    /// This is a low-level utility getter and should
+    /// normally not be required by users.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Drops all cached entries so that they are loaded again upon their next access.
+    ///
+    /// # Note
+    ///
+    /// Changes that have not yet been flushed are lost.
+    pub fn invalidate_cache(&mut self) {
+        self.cache.get_mut().clear();
+    }
+}
+
+impl<K, V, H> LazyHashMap<K, V, H>
+where
+    K: scale::Encode,
+    H: CryptoHash,
+{
+    /// Returns the key of the cell storing the entry for the given key.
+    pub fn entry_key(&self, key: &K) -> Key {
+        hashed_entry_key::<H, K>(self.key, key)
+    }
+
+    /// Replaces the cached value of the entry for the given key.
+    ///
+    /// # Note
+    ///
+    /// Does not load the entry from the contract storage.
+    fn update(&mut self, key: &K, new_val: Option<V>) {
+        let entry_key = self.entry_key(key);
+        self.cache
+            .get_mut()
+            .entry(entry_key)
+            .or_insert_with(|| Box::new(CacheValue::new(None)))
+            .put(new_val);
+    }
+
+    /// Sets the value of the entry for the given key.
+    ///
+    /// # Note
+    ///
+    /// Prefer this over [put](struct.LazyHashMap.html#method.put)
+    /// if you are not interested in the old value.
+    pub fn set(&mut self, key: &K, val: V) {
+        self.update(key, Some(val))
+    }
+
+    /// Clears the entry for the given key.
+    pub fn clear(&mut self, key: &K) {
+        self.update(key, None)
+    }
+}
+
+impl<K, V, H> LazyHashMap<K, V, H>
+where
+    K: scale::Encode,
+    V: scale::Decode,
+    H: CryptoHash,
+{
+    /// Loads the value stored in the cell with the given key if any.
+    fn load(entry_key: Key) -> Option<V> {
+        env::get_contract_storage::<V>(entry_key)
+            .map(|result| result.ink_expect("could not decode V from storage cell"))
+    }
+
+    /// Returns the cached entry for the given key loading it if not yet cached.
+    ///
+    /// # Note
+    ///
+    /// The loaded entry is not marked as dirty.
+    ///
+    /// # Devs & Internals
+    ///
+    /// Note the very critically looking `allow(clippy::mut_from_ref)`.
+    /// Callers must make sure not to hand out overlapping references.
+    #[allow(clippy::mut_from_ref)]
+    fn cache_value(&self, key: &K) -> &mut CacheValue<V> {
+        let entry_key = self.entry_key(key);
+        // SAFETY: The cached values are boxed and never removed from the cache
+        //         while the map is alive so references to them remain valid.
+        let cache = unsafe { &mut *self.cache.as_ptr() };
+        cache
+            .entry(entry_key)
+            .or_insert_with(|| Box::new(CacheValue::new(Self::load(entry_key))))
+    }
+
+    /// Returns the value of the entry for the given key if any.
+    pub fn get(&self, key: &K) -> Option<&V> {
+        self.cache_value(key).get()
+    }
+
+    /// Returns `true` if there is an entry for the given key.
+    pub fn contains_key(&self, key: &K) -> bool {
+        self.get(key).is_some()
+    }
+}
+
+impl<K, V, H> LazyHashMap<K, V, H>
+where
+    K: scale::Encode,
+    V: scale::Codec,
+    H: CryptoHash,
+{
+    /// Returns the cached entry for the given key prepared for mutation.
+    fn cache_value_mut(&mut self, key: &K) -> &mut CacheValue<V> {
+        let cache_value = self.cache_value(key);
+        cache_value.track_origin();
+        cache_value
+    }
+
+    /// Returns the value of the entry for the given key if any.
+    pub fn get_mut(&mut self, key: &K) -> Option<&mut V> {
+        self.cache_value_mut(key).get_mut()
+    }
+
+    /// Replaces the value of the entry for the given key and returns its old value if any.
+    pub fn put(&mut self, key: &K, val: V) -> Option<V> {
+        self.cache_value_mut(key).put(Some(val))
+    }
+
+    /// Takes the value of the entry for the given key if any.
+    pub fn take(&mut self, key: &K) -> Option<V> {
+        self.cache_value_mut(key).take()
+    }
+}
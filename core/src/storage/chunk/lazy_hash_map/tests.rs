@@ -0,0 +1,124 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use super::*;
+use crate::{
+    env,
+    env::Result,
+    storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+        },
+        Flush,
+    },
+};
+use ink_primitives::Key;
+
+fn dummy_map() -> LazyHashMap<u32, u32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        LazyHashMap::allocate_using(&mut alloc)
+    }
+}
+
+#[test]
+fn simple() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = dummy_map();
+
+        // Invariants after initialization
+        assert_eq!(map.get(&5), None);
+        assert!(!map.contains_key(&5));
+
+        // Insert and query some elements
+        assert_eq!(map.put(&5, 42), None);
+        map.set(&6, 1337);
+        assert_eq!(map.get(&5), Some(&42));
+        assert_eq!(map.get(&6), Some(&1337));
+        assert!(map.contains_key(&6));
+
+        // Mutate and take elements
+        *map.get_mut(&5).unwrap() += 1;
+        assert_eq!(map.take(&5), Some(43));
+        assert_eq!(map.get(&5), None);
+
+        // Clear elements
+        map.clear(&6);
+        assert_eq!(map.get(&6), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn distinct_entry_keys() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let map = dummy_map();
+        assert_ne!(map.entry_key(&1), map.entry_key(&2));
+        assert_ne!(map.entry_key(&1), map.key());
+        assert_eq!(map.entry_key(&1), map.entry_key(&1));
+        Ok(())
+    })
+}
+
+#[test]
+fn flush_and_reload() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = dummy_map();
+        map.set(&1, 10);
+        map.set(&2, 20);
+        map.flush();
+
+        let entry_key = map.entry_key(&1);
+        assert_eq!(env::get_contract_storage::<u32>(entry_key), Some(Ok(10)));
+
+        // A freshly decoded map sees the flushed entries.
+        let reloaded: LazyHashMap<u32, u32> =
+            scale::Decode::decode(&mut &scale::Encode::encode(&map)[..]).unwrap();
+        assert_eq!(reloaded.get(&1), Some(&10));
+        assert_eq!(reloaded.get(&2), Some(&20));
+        assert_eq!(reloaded.get(&3), None);
+
+        // Cleared entries are removed from the storage on flush.
+        map.clear(&1);
+        map.flush();
+        assert_eq!(env::get_contract_storage::<u32>(entry_key), None);
+        Ok(())
+    })
+}
+
+fn get_contract_storage_rw() -> (usize, usize) {
+    let contract_account_id = env::account_id::<env::DefaultEnvTypes>().unwrap();
+    env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(&contract_account_id)
+        .unwrap()
+}
+
+#[test]
+fn flush_skips_unchanged() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = dummy_map();
+        map.set(&1, 10);
+        map.set(&2, 20);
+        map.flush();
+        assert_eq!(get_contract_storage_rw(), (0, 2));
+
+        // Mutations resulting in the same values are not written back.
+        let mut map = dummy_map();
+        assert_eq!(map.get_mut(&1), Some(&mut 10));
+        assert_eq!(map.put(&2, 20), Some(20));
+        map.flush();
+        assert_eq!(get_contract_storage_rw(), (2, 2));
+        Ok(())
+    })
+}
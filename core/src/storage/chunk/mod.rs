@@ -14,10 +14,17 @@
 
 //! Provides low-level primitives to operate on chunks of cells.
 
+mod flush_writer;
+mod lazy_hash_map;
 mod sync_chunk;
 mod typed_chunk;
 
+pub(crate) use self::{
+    flush_writer::FlushWriter,
+    lazy_hash_map::hashed_entry_key,
+};
 pub use self::{
+    lazy_hash_map::LazyHashMap,
     sync_chunk::SyncChunk,
     typed_chunk::TypedChunk,
 };
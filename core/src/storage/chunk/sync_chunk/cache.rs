@@ -12,10 +12,15 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::storage::{
+    fingerprint,
+    Fingerprint,
+};
 use core::cell::RefCell;
-use ink_prelude::collections::btree_map::{
-    BTreeMap,
-    Entry,
+use ink_prelude::{
+    boxed::Box,
+    collections::btree_map::BTreeMap,
+    vec::Vec,
 };
 
 /// A single cache entry.
@@ -23,8 +28,15 @@ use ink_prelude::collections::btree_map::{
 pub(crate) struct CacheValue<T> {
     /// If the cache for this entry is dirty.
     dirty: bool,
+    /// The fingerprint of the value in the contract storage.
+    ///
+    /// This is only known if the entry was in sync with the contract
+    /// storage before it became dirty.
+    origin: Option<Fingerprint>,
     /// The value of the cached cell.
     value: Option<T>,
+    /// The tick of the cache clock at the last access of the entry.
+    last_used: u64,
 }
 
 impl<T> CacheValue<T> {
@@ -33,15 +45,17 @@ impl<T> CacheValue<T> {
     /// # Note
     ///
     /// It is marked clean after creation.
-    fn new(cell_val: Option<T>) -> Self {
+    pub fn new(cell_val: Option<T>) -> Self {
         Self {
             dirty: false,
+            origin: None,
             value: cell_val,
+            last_used: 0,
         }
     }
 
     /// Returns `true` if the cached value is dirty.
-    fn is_dirty(&self) -> bool {
+    pub fn is_dirty(&self) -> bool {
         self.dirty
     }
 
@@ -52,7 +66,8 @@ impl<T> CacheValue<T> {
 
     /// Marks the cached value as being clean.
     pub fn mark_clean(&mut self) {
-        self.dirty = false
+        self.dirty = false;
+        self.origin = None;
     }
 
     /// Returns an immutable reference to the cached value.
@@ -111,46 +126,95 @@ impl<T> CacheValue<T> {
     }
 }
 
+impl<T> CacheValue<T>
+where
+    T: scale::Encode,
+{
+    /// Remembers the fingerprint of the cached value if it is clean.
+    ///
+    /// # Note
+    ///
+    /// This must be called before the cached value is mutated.
+    pub fn track_origin(&mut self) {
+        if !self.dirty {
+            self.origin = Some(fingerprint(&self.value));
+        }
+    }
+
+    /// Returns `true` if the cached value is encoded the same as in the contract storage.
+    pub fn is_unchanged(&self) -> bool {
+        self.origin == Some(fingerprint(&self.value))
+    }
+}
+
 /// A cache for synchronized cell values.
 #[derive(Debug)]
 struct Cache<T> {
     /// Cached entries of the cache.
-    entries: BTreeMap<u32, CacheValue<T>>,
+    ///
+    /// # Note
+    ///
+    /// The entries are boxed so that references to them handed out through
+    /// the `CacheGuard` stay valid while other entries are inserted.
+    entries: BTreeMap<u32, Box<CacheValue<T>>>,
+    /// The maximum number of cached entries if any.
+    limit: Option<u32>,
+    /// Advanced upon every access to order the cached entries by recency.
+    clock: u64,
 }
 
 impl<T> Default for Cache<T> {
     fn default() -> Self {
         Self {
             entries: BTreeMap::default(),
+            limit: None,
+            clock: 0,
         }
     }
 }
 
-/// A single cache entry for a copy chunk cell.
-type CacheEntry<'a, T> = Entry<'a, u32, CacheValue<T>>;
-
 impl<T> Cache<T> {
+    /// Advances the cache clock and returns its new tick.
+    fn tick(&mut self) -> u64 {
+        self.clock += 1;
+        self.clock
+    }
+
     /// Returns an immutable reference to the cached value at position `n` if any.
-    fn get(&self, n: u32) -> Option<&CacheValue<T>> {
-        self.entries.get(&n)
+    fn get(&mut self, n: u32) -> Option<&CacheValue<T>> {
+        let tick = self.tick();
+        self.entries.get_mut(&n).map(|cache_value| {
+            cache_value.last_used = tick;
+            &**cache_value
+        })
     }
 
-    /// Returns a mutable reference to the cached value at position `n` if any.
-    fn get_mut(&mut self, n: u32) -> Option<&mut CacheValue<T>> {
-        self.entries.get_mut(&n)
+    /// Returns the cached value at position `n` marked as recently used.
+    fn entry_at(
+        &mut self,
+        n: u32,
+        load: impl FnOnce() -> Option<T>,
+    ) -> &mut CacheValue<T> {
+        let tick = self.tick();
+        let cache_value = self
+            .entries
+            .entry(n)
+            .or_insert_with(|| Box::new(CacheValue::new(load())));
+        cache_value.last_used = tick;
+        cache_value
     }
 
-    /// Returns the cache entry at position `n`.
-    fn entry_at(&mut self, n: u32) -> CacheEntry<T> {
-        self.entries.entry(n)
+    /// Returns the cached value at position `n` loading it with `load` if not cached.
+    pub fn get_or_load<F>(&mut self, n: u32, load: F) -> &mut CacheValue<T>
+    where
+        F: FnOnce() -> Option<T>,
+    {
+        self.entry_at(n, load)
     }
 
     /// Updates the cell value of the cached cell at position `n`.
     pub fn update(&mut self, n: u32, new_val: Option<T>) -> Option<&T> {
-        match self.entry_at(n) {
-            Entry::Occupied(occupied) => occupied.into_mut().update(new_val),
-            Entry::Vacant(vacant) => vacant.insert(CacheValue::new(new_val)).get(),
-        }
+        self.entry_at(n, || None).update(new_val)
     }
 
     /// Updates the cell value of the cached cell at position `n`.
@@ -159,18 +223,57 @@ impl<T> Cache<T> {
     ///
     /// Marks the cached value as dirty.
     pub fn update_mut(&mut self, n: u32, new_val: Option<T>) -> Option<&mut T> {
-        match self.entry_at(n) {
-            Entry::Occupied(occupied) => occupied.into_mut().update_mut(new_val),
-            Entry::Vacant(vacant) => vacant.insert(CacheValue::new(new_val)).get_mut(),
+        self.entry_at(n, || None).update_mut(new_val)
+    }
+
+    /// Sets the maximum number of cached entries.
+    pub fn set_limit(&mut self, limit: Option<u32>) {
+        self.limit = limit;
+    }
+
+    /// Removes all cached entries.
+    pub fn clear(&mut self) {
+        self.entries.clear();
+    }
+
+    /// Evicts the least recently used clean entries exceeding the limit.
+    ///
+    /// # Note
+    ///
+    /// Dirty entries are never evicted so the cache might still
+    /// exceed its limit afterwards.
+    pub fn evict(&mut self) {
+        let limit = match self.limit {
+            Some(limit) => limit as usize,
+            None => return,
+        };
+        let excess = self.entries.len().saturating_sub(limit);
+        if excess == 0 {
+            return
+        }
+        let mut clean = self
+            .entries
+            .iter()
+            .filter(|(_, cache_value)| !cache_value.is_dirty())
+            .map(|(&n, cache_value)| (cache_value.last_used, n))
+            .collect::<Vec<_>>();
+        clean.sort_unstable();
+        for (_, n) in clean.into_iter().take(excess) {
+            self.entries.remove(&n);
         }
     }
 
+    /// Iterator over all cached values.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &CacheValue<T>)> {
+        self.entries.iter().map(|(&k, v)| (k, &**v))
+    }
+
     /// Iterator over all dirty marked cache values.
     pub fn iter_dirty(&mut self) -> impl Iterator<Item = (u32, &mut CacheValue<T>)> {
         self.entries
             .iter_mut()
             .filter(|(_, v)| v.is_dirty())
-            .map(|(&k, v)| (k, v))
+            .map(|(&k, v)| (k, &mut **v))
     }
 }
 
@@ -216,12 +319,19 @@ impl<T> CacheGuard<T> {
 
     /// Returns an immutable reference to the cached value at position `n` if any.
     pub fn get(&self, n: u32) -> Option<&CacheValue<T>> {
-        self.elems().get(n)
+        self.elems_mut().get(n)
     }
 
-    /// Returns a mutable reference to the cached value at position `n` if any.
-    pub fn get_mut(&self, n: u32) -> Option<&mut CacheValue<T>> {
-        self.elems_mut().get_mut(n)
+    /// Returns the cached value at position `n` loading it with `load` if not cached.
+    ///
+    /// # Note
+    ///
+    /// The loaded value is not marked as dirty.
+    pub fn get_or_load<F>(&self, n: u32, load: F) -> &mut CacheValue<T>
+    where
+        F: FnOnce() -> Option<T>,
+    {
+        self.elems_mut().get_or_load(n, load)
     }
 
     /// Updates the cell value of the cached cell at position `n`.
@@ -242,8 +352,32 @@ impl<T> CacheGuard<T> {
         self.elems_mut().update_mut(n, new_val)
     }
 
+    /// Iterator over all cached values.
+    pub fn iter(&self) -> impl Iterator<Item = (u32, &CacheValue<T>)> {
+        self.elems().iter()
+    }
+
     /// Iterator over all dirty marked cache values.
     pub fn iter_dirty(&self) -> impl Iterator<Item = (u32, &mut CacheValue<T>)> {
         self.elems_mut().iter_dirty()
     }
+
+    /// Sets the maximum number of cached entries.
+    pub fn set_limit(&mut self, limit: Option<u32>) {
+        self.cache.get_mut().set_limit(limit)
+    }
+
+    /// Removes all cached entries.
+    pub fn clear(&mut self) {
+        self.cache.get_mut().clear()
+    }
+
+    /// Evicts the least recently used clean entries exceeding the limit.
+    ///
+    /// # Note
+    ///
+    /// Takes `&mut self` so that no references into the cache are alive.
+    pub fn evict(&mut self) {
+        self.cache.get_mut().evict()
+    }
 }
@@ -12,14 +12,23 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
-use super::CacheGuard;
-use crate::storage::{
-    alloc::{
-        Allocate,
-        AllocateUsing,
+use super::{
+    CacheGuard,
+    CacheValue,
+};
+use crate::{
+    env,
+    storage::{
+        alloc::{
+            Allocate,
+            AllocateUsing,
+        },
+        chunk::{
+            FlushWriter,
+            TypedChunk,
+        },
+        Flush,
     },
-    chunk::TypedChunk,
-    Flush,
 };
 #[cfg(feature = "ink-generate-abi")]
 use ink_abi::{
@@ -72,16 +81,28 @@ where
 {
     #[inline]
     fn flush(&mut self) {
+        let mut writer = FlushWriter::new();
         for (n, dirty_val) in self.cache.iter_dirty() {
+            let key = self.chunk.key() + n;
+            // Values that did not change are not written back.
+            let unchanged = dirty_val.is_unchanged();
             match dirty_val.get_mut() {
                 Some(val) => {
-                    self.chunk.store(n, val);
+                    if !unchanged {
+                        writer.set(key, val);
+                    }
                     val.flush();
                 }
-                None => self.chunk.clear(n),
+                None => {
+                    if !unchanged {
+                        writer.clear(key)
+                    }
+                }
             }
             dirty_val.mark_clean();
         }
+        writer.finish();
+        self.cache.evict();
     }
 }
 
@@ -128,9 +149,33 @@ impl<T> AllocateUsing for SyncChunk<T> {
 impl<T> SyncChunk<T> {
     /// Clears the cache value at position `n`.
     pub fn clear(&mut self, n: u32) {
+        self.cache.evict();
         self.cache.update_mut(n, None);
     }
 
+    /// Limits the number of cached cells to `limit` or removes the limit if `None`.
+    ///
+    /// # Note
+    ///
+    /// Clean cells exceeding the limit are evicted in least recently used
+    /// order upon mutable accesses and flushes. Dirty cells are never evicted
+    /// and cells read through shared references stay cached until then, so
+    /// the limit can be exceeded in between.
+    pub fn set_cache_limit(&mut self, limit: Option<u32>) {
+        self.cache.set_limit(limit);
+        self.cache.evict();
+    }
+
+    /// Drops all cached cells so that they are loaded again upon their next access.
+    ///
+    /// # Note
+    ///
+    /// Changes that have not yet been flushed are lost.
+    /// The cache limit is kept.
+    pub fn invalidate_cache(&mut self) {
+        self.cache.clear();
+    }
+
     /// Returns the underlying key to the cells.
     ///
     /// # Note
@@ -140,6 +185,21 @@ impl<T> SyncChunk<T> {
     pub fn cells_key(&self) -> Key {
         self.chunk.key()
     }
+
+    /// Returns an iterator over the cached cells in ascending order of their indices.
+    ///
+    /// Yields the index of each cached cell together with its value if it is occupied.
+    ///
+    /// # Note
+    ///
+    /// Only cells that have been accessed since the chunk was loaded are
+    /// cached. This does not read from the contract storage and thus is
+    /// no replacement for iterating over all occupied cells of the chunk.
+    pub fn cached_entries(&self) -> impl Iterator<Item = (u32, Option<&T>)> {
+        self.cache
+            .iter()
+            .map(|(n, cache_value)| (n, cache_value.get()))
+    }
 }
 
 impl<T> SyncChunk<T>
@@ -156,29 +216,43 @@ where
     }
 
     /// Returns the value of the `n`-th cell if any.
-    #[must_use]
-    pub fn get_mut(&mut self, n: u32) -> Option<&mut T> {
-        match self.cache.get_mut(n) {
-            Some(cache_value) => cache_value.get_mut(),
-            None => self.cache.update_mut(n, self.chunk.load(n)),
+    ///
+    /// # Note
+    ///
+    /// Values that cannot be decoded are not cached so that they can be
+    /// inspected and replaced, e.g. by migrations of outdated encodings.
+    ///
+    /// # Errors
+    ///
+    /// If the value of the cell cannot be decoded as `T`.
+    pub fn try_get(&self, n: u32) -> Option<env::Result<&T>> {
+        if let Some(cache_value) = self.cache.get(n) {
+            return cache_value.get().map(Ok)
         }
+        let loaded = match self.chunk.try_load(n) {
+            Some(Ok(val)) => Some(val),
+            Some(Err(err)) => return Some(Err(err)),
+            None => None,
+        };
+        self.cache.update(n, loaded).map(Ok)
     }
+}
 
-    /// Takes the value of the `n`-th cell if any.
+impl<T> SyncChunk<T>
+where
+    T: scale::Decode + Clone,
+{
+    /// Returns the value of the `n`-th cell if any without caching it.
     ///
     /// # Note
     ///
-    /// Prefer using [clear](struct.SyncChunk.html#method.clear)
-    /// if you are not interested in the return value since it
-    /// is more efficient.
-    #[must_use]
-    pub fn take(&mut self, n: u32) -> Option<T> {
-        match self.cache.get_mut(n) {
-            Some(cache_value) => cache_value.take(),
-            None => {
-                self.cache.update_mut(n, None);
-                self.chunk.load(n)
-            }
+    /// Cached cells are cloned and all other cells are decoded from the
+    /// contract storage without being inserted into the cache. Use this for
+    /// read-only scans over many cells that would otherwise fill the cache.
+    pub fn get_uncached(&self, n: u32) -> Option<T> {
+        match self.cache.get(n) {
+            Some(cache_value) => cache_value.get().cloned(),
+            None => self.chunk.load(n),
         }
     }
 }
@@ -189,6 +263,7 @@ where
 {
     /// Sets the value of the `n`-th cell.
     pub fn set(&mut self, n: u32, val: T) {
+        self.cache.evict();
         self.cache.update_mut(n, Some(val));
     }
 }
@@ -197,6 +272,33 @@ impl<T> SyncChunk<T>
 where
     T: scale::Codec,
 {
+    /// Returns the cached value of the `n`-th cell prepared for mutation.
+    fn cache_value_mut(&mut self, n: u32) -> &mut CacheValue<T> {
+        self.cache.evict();
+        let chunk = &self.chunk;
+        let cache_value = self.cache.get_or_load(n, || chunk.load(n));
+        cache_value.track_origin();
+        cache_value
+    }
+
+    /// Returns the value of the `n`-th cell if any.
+    #[must_use]
+    pub fn get_mut(&mut self, n: u32) -> Option<&mut T> {
+        self.cache_value_mut(n).get_mut()
+    }
+
+    /// Takes the value of the `n`-th cell if any.
+    ///
+    /// # Note
+    ///
+    /// Prefer using [clear](struct.SyncChunk.html#method.clear)
+    /// if you are not interested in the return value since it
+    /// is more efficient.
+    #[must_use]
+    pub fn take(&mut self, n: u32) -> Option<T> {
+        self.cache_value_mut(n).take()
+    }
+
     /// Replaces the value of the `n`-th cell and returns its old value if any.
     ///
     /// # Note
@@ -206,12 +308,20 @@ where
     /// is more efficient.
     #[must_use]
     pub fn put(&mut self, n: u32, new_val: T) -> Option<T> {
-        match self.cache.get_mut(n) {
-            Some(cache_value) => cache_value.put(Some(new_val)),
-            None => {
-                self.cache.update_mut(n, Some(new_val));
-                self.chunk.load(n)
-            }
+        self.cache_value_mut(n).put(Some(new_val))
+    }
+
+    /// Swaps the values of the `a`-th and the `b`-th cells.
+    ///
+    /// # Note
+    ///
+    /// Empty cells are swapped as well.
+    pub fn swap(&mut self, a: u32, b: u32) {
+        if a == b {
+            return
         }
+        let val_a = self.take(a);
+        let val_b = self.cache_value_mut(b).put(val_a);
+        self.cache_value_mut(a).put(val_b);
     }
 }
@@ -18,5 +18,8 @@ mod chunk;
 #[cfg(test)]
 mod tests;
 
-pub(crate) use self::cache::CacheGuard;
+pub(crate) use self::cache::{
+    CacheGuard,
+    CacheValue,
+};
 pub use self::chunk::SyncChunk;
@@ -283,8 +283,10 @@ fn count_rw_take() -> Result<()> {
         assert_eq!(get_contract_storage_rw(), (N as usize, 0));
 
         // Flush and check reads and writes.
+        //
+        // The cells are not written back since they were empty before.
         chunk.flush();
-        assert_eq!(get_contract_storage_rw(), (N as usize, N as usize));
+        assert_eq!(get_contract_storage_rw(), (N as usize, 0));
         Ok(())
     })
 }
@@ -307,8 +309,134 @@ fn count_rw_take_repeat() -> Result<()> {
         assert_eq!(get_contract_storage_rw(), (1, 0));
 
         // Flush and check reads and writes.
+        //
+        // The cell is not written back since it was empty before.
         chunk.flush();
-        assert_eq!(get_contract_storage_rw(), (1, 1));
+        assert_eq!(get_contract_storage_rw(), (1, 0));
+        Ok(())
+    })
+}
+
+#[test]
+fn flush_skips_unchanged() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut chunk = dummy_chunk();
+        for i in 0..4 {
+            chunk.set(i, i);
+        }
+        chunk.flush();
+        assert_eq!(get_contract_storage_rw(), (0, 4));
+
+        // Mutations resulting in the same values are not written back.
+        let mut chunk = dummy_chunk();
+        assert_eq!(chunk.put(0, 0), Some(0));
+        assert_eq!(chunk.get_mut(1), Some(&mut 1));
+        assert_eq!(chunk.take(2), Some(2));
+        chunk.set(2, 2);
+        // Mutations resulting in different values are written back.
+        assert_eq!(chunk.put(3, 42), Some(3));
+        chunk.flush();
+        assert_eq!(get_contract_storage_rw(), (4, 5));
+        assert_eq!(dummy_chunk().get(3), Some(&42));
+        Ok(())
+    })
+}
+
+#[test]
+fn swap() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut chunk = dummy_chunk();
+        chunk.set(0, 5);
+        chunk.set(1, 42);
+        chunk.swap(0, 1);
+        assert_eq!((chunk.get(0), chunk.get(1)), (Some(&42), Some(&5)));
+        // Swapping with an empty cell moves the value.
+        chunk.swap(1, 7);
+        assert_eq!((chunk.get(1), chunk.get(7)), (None, Some(&5)));
+        chunk.swap(0, 0);
+        assert_eq!(chunk.get(0), Some(&42));
+        Ok(())
+    })
+}
+
+#[test]
+fn cached_entries() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut chunk = dummy_chunk();
+        chunk.set(3, 3);
+        chunk.set(1, 1);
+        chunk.flush();
+        let mut chunk = dummy_chunk();
+        assert_eq!(chunk.cached_entries().next(), None);
+        assert_eq!(chunk.get(3), Some(&3));
+        assert_eq!(chunk.get(2), None);
+        chunk.clear(5);
+        assert_eq!(
+            chunk.cached_entries().collect::<Vec<_>>(),
+            vec![(2, None), (3, Some(&3)), (5, None)]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn references_survive_later_loads() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        const TEST_LEN: u32 = 32;
+
+        let mut chunk = dummy_chunk();
+        for i in 0..TEST_LEN {
+            chunk.set(i, i);
+        }
+        chunk.flush();
+
+        // Loading in descending order inserts every entry in front of all
+        // previously cached entries. References handed out before must not
+        // be affected by that.
+        let chunk = dummy_chunk();
+        let loaded = (0..TEST_LEN)
+            .rev()
+            .map(|i| chunk.get(i).unwrap())
+            .collect::<Vec<_>>();
+        for (i, val) in (0..TEST_LEN).rev().zip(loaded) {
+            assert_eq!(*val, i);
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn cache_limit() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut chunk = dummy_chunk();
+        for i in 0..4 {
+            chunk.set(i, i);
+        }
+        chunk.flush();
+
+        let mut chunk = dummy_chunk();
+        for i in 0..4 {
+            assert_eq!(chunk.get(i), Some(&i));
+        }
+        // Touch the first cell so that it is the most recently used.
+        assert_eq!(chunk.get(0), Some(&0));
+        chunk.set_cache_limit(Some(2));
+        let cached = chunk.cached_entries().map(|(n, _)| n).collect::<Vec<_>>();
+        assert_eq!(cached, vec![0, 3]);
+
+        // Dirty cells are never evicted.
+        chunk.set(1, 42);
+        chunk.set(2, 43);
+        assert_eq!(chunk.put(3, 44), Some(3));
+        let cached = chunk.cached_entries().map(|(n, _)| n).collect::<Vec<_>>();
+        assert_eq!(cached, vec![1, 2, 3]);
+
+        // Evicted cells are loaded again from the contract storage.
+        chunk.flush();
+        assert_eq!(chunk.cached_entries().count(), 2);
+        for (i, val) in (0..4).zip(&[0, 42, 43, 44]) {
+            assert_eq!(chunk.get(i), Some(val));
+        }
         Ok(())
     })
 }
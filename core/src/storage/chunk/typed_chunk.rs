@@ -14,13 +14,31 @@
 
 use crate::{
     env,
-    storage::alloc::{
-        Allocate,
-        AllocateUsing,
+    storage::{
+        alloc::{
+            Allocate,
+            AllocateUsing,
+        },
+        InkExpect as _,
     },
 };
 use core::marker::PhantomData;
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutRange,
+    StorageLayout,
+};
 use ink_primitives::Key;
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::{
+    HasTypeDef,
+    Metadata,
+    NamedField,
+    TypeDef,
+    TypeDefStruct,
+    TypeId,
+};
 
 /// A chunk of typed cells.
 ///
@@ -33,6 +51,7 @@ use ink_primitives::Key;
 ///
 /// Read more about kinds of guarantees and their effect [here](../index.html#guarantees).
 #[derive(Debug, PartialEq, Eq)]
+#[cfg_attr(feature = "ink-generate-abi", derive(TypeId))]
 pub struct TypedChunk<T> {
     /// The underlying key into the contract storage.
     key: Key,
@@ -40,6 +59,23 @@ pub struct TypedChunk<T> {
     marker: PhantomData<fn() -> T>,
 }
 
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasTypeDef for TypedChunk<T> {
+    fn type_def() -> TypeDef {
+        TypeDefStruct::new(vec![NamedField::of::<Key>("key")]).into()
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasLayout for TypedChunk<T>
+where
+    T: Metadata,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutRange::chunk(self.key, T::meta_type()).into()
+    }
+}
+
 /// A single cell within a chunk of typed cells.
 #[derive(Debug, PartialEq, Eq, Copy, Clone)]
 pub struct TypedChunkCell<'a, T, M> {
@@ -96,8 +132,17 @@ where
 {
     /// Loads the value from the storage into the cell.
     pub fn load(self) -> Option<T> {
+        self.try_load()
+            .map(|result| result.ink_expect("could not decode T from storage chunk"))
+    }
+
+    /// Loads the value from the storage into the cell.
+    ///
+    /// # Errors
+    ///
+    /// If decoding of the loaded bytes fails.
+    pub fn try_load(self) -> Option<env::Result<T>> {
         env::get_contract_storage(self.key)
-            .map(|result| result.expect("could not decode T from storage chunk"))
     }
 }
 
@@ -162,6 +207,15 @@ where
     pub fn load(&self, index: u32) -> Option<T> {
         self.cell_at(index).load()
     }
+
+    /// Loads the value stored in the storage at the given index if any.
+    ///
+    /// # Errors
+    ///
+    /// If decoding of the loaded bytes fails.
+    pub fn try_load(&self, index: u32) -> Option<env::Result<T>> {
+        self.cell_at(index).try_load()
+    }
 }
 
 impl<T> TypedChunk<T>
@@ -73,6 +73,13 @@ where
     }
 }
 
+impl<T> DuplexSyncChunk<T> {
+    /// Drops all cached groups so that they are loaded again upon their next access.
+    pub fn invalidate_cache(&mut self) {
+        self.0.invalidate_cache()
+    }
+}
+
 #[cfg(feature = "ink-generate-abi")]
 impl<T> HasLayout for DuplexSyncChunk<T>
 where
@@ -178,7 +185,7 @@ fn get_ingroup_index(n: u32) -> usize {
     let group = get_group_index(n);
     match (group, n) {
         (0, 0) => 0,
-        (0, _) => panic!("first group contains only root node"),
+        (0, _) => ink_panic!("first group contains only root node"),
         (_, _) => ((n - 1) % COUNT) as usize,
     }
 }
@@ -42,6 +42,7 @@ use crate::storage::{
     },
     chunk::SyncChunk,
     Flush,
+    InkExpect as _,
 };
 
 /// We implement a binary tree.
@@ -90,6 +91,28 @@ where
     }
 }
 
+impl<T> BinaryHeap<T>
+where
+    T: Encode + Flush,
+    DuplexSyncChunk<T>: Flush,
+{
+    /// Writes the cached state of the heap back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the heap so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.len.invalidate_cache();
+        self.entries.invalidate_cache();
+    }
+}
+
 #[cfg(feature = "ink-generate-abi")]
 impl<T> HasLayout for BinaryHeap<T>
 where
@@ -107,6 +130,20 @@ where
     }
 }
 
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<T> serde::Serialize for BinaryHeap<T>
+where
+    T: Codec + Ord + serde::Serialize,
+{
+    /// Serializes the values of the heap as sequence in their storage order.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.values())
+    }
+}
+
 impl<'a, T> Iterator for Values<'a, T>
 where
     T: Codec + Ord,
@@ -296,7 +333,7 @@ where
             return None
         }
 
-        let tmp = Some(self.entries.take(0).expect("failed fetching root"));
+        let tmp = Some(self.entries.take(0).ink_expect("failed fetching root"));
         if len == 1 {
             self.len.set(len - 1);
             return tmp
@@ -316,13 +353,13 @@ where
         let top_value = self
             .entries
             .take(top_index)
-            .expect("failed taking top element from heap");
+            .ink_expect("failed taking top element from heap");
         let mut succ_index = self.find_successor(top_index);
         while succ_index < self.len() && {
             let succ_value = self
                 .entries
                 .get(succ_index)
-                .expect("failed retrieving successor");
+                .ink_expect("failed retrieving successor");
             top_value < *succ_value
         } {
             self.relocate(succ_index, top_index);
@@ -345,11 +382,11 @@ where
         let left = self
             .entries
             .get(left_index)
-            .expect("failed getting left value");
+            .ink_expect("failed getting left value");
         let right = self
             .entries
             .get(right_index)
-            .expect("failed getting right value");
+            .ink_expect("failed getting right value");
         match left.cmp(right) {
             Ordering::Less => right_index,
             Ordering::Equal => right_index,
@@ -364,7 +401,7 @@ where
     pub fn push(&mut self, val: T) {
         let len = self.len();
         if len == u32::max_value() {
-            panic!(
+            ink_panic!(
                 "[ink_core::Heap::push] Error: \
                  cannot push more elements than `u32::Max`"
             )
@@ -383,7 +420,7 @@ where
             let parent_value = self
                 .entries
                 .get(parent_index)
-                .expect("failed getting parent value");
+                .ink_expect("failed getting parent value");
             val > *parent_value
         } {
             self.relocate(parent_index, index);
@@ -418,7 +455,7 @@ where
     /// Relocate the node at index `from` to `to`.
     /// Overwrites the node at `to`.
     fn relocate(&mut self, from: u32, to: u32) {
-        let entry = self.entries.take(from).expect("failed relocating node");
+        let entry = self.entries.take(from).ink_expect("failed relocating node");
         let _ = self.entries.put(to, entry);
     }
 
@@ -0,0 +1,317 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use scale::{
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    self,
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    collections::internal::InternalError,
+    Flush,
+};
+
+/// An index handed out by a [`BitStash`](struct.BitStash.html).
+pub type Index = u32;
+
+/// 256 bits of which the set bits are the used indices.
+type Word = [u64; 4];
+
+/// The number of used indices of 16 consecutive words.
+type CountsPack = [u16; 16];
+
+/// The number of bits in a word.
+const BITS_PER_WORD: u32 = 256;
+
+/// The number of word counts in a counts pack.
+const COUNTS_PER_PACK: u32 = 16;
+
+/// Allocates and recycles `u32` indices compactly.
+///
+/// # Note
+///
+/// Always hands out the smallest free index so that the used indices stay
+/// dense. This makes it a good fit for the free lists of allocators and
+/// of collections that store their elements by index.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct BitStash {
+    /// The header holding the length and the free word hint.
+    header: storage::Value<BitStashHeader>,
+    /// The words tracking the used indices.
+    words: storage::Vec<Word>,
+    /// The number of used indices of every word.
+    counts: storage::Vec<CountsPack>,
+}
+
+/// Densely stored general information required by a bit stash.
+///
+/// # Note
+///
+/// Keeping these in a single cell allows to query the number of used
+/// indices and to find the next free index without reading all counts.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+struct BitStashHeader {
+    /// The number of used indices.
+    len: u32,
+    /// All words before this word have no free index.
+    ///
+    /// # Note
+    ///
+    /// Searching for a free index starts at this word.
+    free_word_hint: u32,
+}
+
+/// No need to forward flush to fields.
+impl Flush for BitStashHeader {}
+
+impl Flush for BitStash {
+    #[inline]
+    fn flush(&mut self) {
+        self.header.flush();
+        self.words.flush();
+        self.counts.flush();
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl HasLayout for BitStash {
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(
+            Self::meta_type(),
+            vec![
+                LayoutField::of("header", &self.header),
+                LayoutField::of("words", &self.words),
+                LayoutField::of("counts", &self.counts),
+            ],
+        )
+        .into()
+    }
+}
+
+impl AllocateUsing for BitStash {
+    #[inline]
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            header: storage::Value::allocate_using(alloc),
+            words: storage::Vec::allocate_using(alloc),
+            counts: storage::Vec::allocate_using(alloc),
+        }
+    }
+}
+
+impl Initialize for BitStash {
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline]
+    fn initialize(&mut self, _args: Self::Args) {
+        self.header.set(BitStashHeader {
+            len: 0,
+            free_word_hint: 0,
+        });
+        self.words.initialize(());
+        self.counts.initialize(());
+    }
+}
+
+/// Returns the word and the position of the index within the word.
+fn split_index(index: Index) -> (u32, u32) {
+    (index / BITS_PER_WORD, index % BITS_PER_WORD)
+}
+
+/// Returns the counts pack and the position of the word's count within it.
+fn split_word(word: u32) -> (u32, usize) {
+    (word / COUNTS_PER_PACK, (word % COUNTS_PER_PACK) as usize)
+}
+
+/// Returns the first unset bit of the word if any.
+fn first_unset_bit(word: &Word) -> Option<u32> {
+    word.iter()
+        .enumerate()
+        .find(|(_, &bits)| bits != u64::max_value())
+        .map(|(n, &bits)| n as u32 * 64 + (!bits).trailing_zeros())
+}
+
+impl BitStash {
+    /// Writes the cached state of the bit stash back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the bit stash so that it is loaded again
+    /// from the contract storage upon the next access.
+    ///
+    /// # Note
+    ///
+    /// Changes that have not yet been flushed are lost.
+    pub fn invalidate_cache(&mut self) {
+        self.header.invalidate_cache();
+        self.words.invalidate_cache();
+        self.counts.invalidate_cache();
+    }
+
+    /// Returns `true` if the index is in use.
+    pub fn get(&self, index: Index) -> bool {
+        let (word, bit) = split_index(index);
+        self.words
+            .get(word)
+            .map(|bits| bits[(bit / 64) as usize] & (1 << (bit % 64)) != 0)
+            .unwrap_or(false)
+    }
+
+    /// Returns the number of used indices.
+    pub fn len(&self) -> u32 {
+        self.header.len
+    }
+
+    /// Returns `true` if no index is in use.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+
+    /// Returns the first word that has a free index if any.
+    ///
+    /// # Note
+    ///
+    /// Starts searching at the free word hint and thus usually reads
+    /// only a single counts pack.
+    fn first_free_word(&self) -> Option<u32> {
+        let num_words = self.words.len();
+        let (first_pack, first_pos) = split_word(self.header.free_word_hint);
+        (first_pack..self.counts.len())
+            .find_map(|pack| {
+                let counts = match self.counts.get(pack) {
+                    Some(counts) => counts,
+                    None => InternalError::MissingEntry.report("BitStash::put"),
+                };
+                let skip = if pack == first_pack { first_pos } else { 0 };
+                counts
+                    .iter()
+                    .enumerate()
+                    .skip(skip)
+                    .find(|(_, &count)| u32::from(count) < BITS_PER_WORD)
+                    .map(|(pos, _)| pack * COUNTS_PER_PACK + pos as u32)
+            })
+            .filter(|&word| word < num_words)
+    }
+
+    /// Updates the count of used indices of the word and returns the new count.
+    fn update_count(
+        &mut self,
+        word: u32,
+        f: impl FnOnce(u16) -> u16,
+        operation: &'static str,
+    ) -> u16 {
+        let (pack, pos) = split_word(word);
+        if pack == self.counts.len() {
+            self.counts.push([0; COUNTS_PER_PACK as usize]);
+        }
+        let counts = match self.counts.get_mut(pack) {
+            Some(counts) => counts,
+            None => InternalError::MissingEntry.report(operation),
+        };
+        counts[pos] = f(counts[pos]);
+        counts[pos]
+    }
+
+    /// Marks the smallest free index as used and returns it.
+    ///
+    /// # Panics
+    ///
+    /// If all `u32` indices are in use.
+    pub fn put(&mut self) -> Index {
+        let word = match self.first_free_word() {
+            Some(word) => word,
+            None => {
+                let word = self.words.len();
+                if word == u32::max_value() / BITS_PER_WORD + 1 {
+                    ink_panic!(
+                        "[ink_core::BitStash::put] Error: \
+                         all indices of the bit stash are in use"
+                    )
+                }
+                self.words.push([0; 4]);
+                word
+            }
+        };
+        let bits = match self.words.get_mut(word) {
+            Some(bits) => bits,
+            None => InternalError::MissingEntry.report("BitStash::put"),
+        };
+        let bit = match first_unset_bit(bits) {
+            Some(bit) => bit,
+            None => InternalError::UnexpectedOccupied.report("BitStash::put"),
+        };
+        bits[(bit / 64) as usize] |= 1 << (bit % 64);
+        let count = self.update_count(word, |count| count + 1, "BitStash::put");
+        let header = &mut *self.header;
+        header.len += 1;
+        header.free_word_hint = if u32::from(count) == BITS_PER_WORD {
+            word + 1
+        } else {
+            word
+        };
+        word * BITS_PER_WORD + bit
+    }
+
+    /// Marks the index as free so that it can be handed out again.
+    ///
+    /// Returns `true` if the index was in use.
+    pub fn take(&mut self, index: Index) -> bool {
+        let (word, bit) = split_index(index);
+        let bits = match self.words.get_mut(word) {
+            Some(bits) => bits,
+            None => return false,
+        };
+        let mask = 1 << (bit % 64);
+        let bits = &mut bits[(bit / 64) as usize];
+        if *bits & mask == 0 {
+            return false
+        }
+        *bits &= !mask;
+        self.update_count(word, |count| count - 1, "BitStash::take");
+        let header = &mut *self.header;
+        header.len -= 1;
+        if word < header.free_word_hint {
+            header.free_word_hint = word;
+        }
+        true
+    }
+}
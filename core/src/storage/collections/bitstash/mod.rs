@@ -0,0 +1,34 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A compact allocator of `u32` indices that recycles freed indices.
+//!
+//! # Structure
+//!
+//! The used indices are tracked as set bits in 256-bit words. Additionally
+//! the number of used indices of every word is stored with the counts of
+//! 16 consecutive words packed into a single cell. A header holds the
+//! number of used indices and a hint to the first word with a free index.
+//! Finding a free index therefore mostly reads the header, a single counts
+//! cell and a single word.
+
+#[cfg(test)]
+mod tests;
+
+mod impls;
+
+pub use self::impls::{
+    BitStash,
+    Index,
+};
@@ -0,0 +1,105 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+    storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+        BitStash,
+    },
+};
+use ink_primitives::Key;
+
+fn empty_stash() -> BitStash {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        BitStash::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+#[test]
+fn put_hands_out_dense_indices() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = empty_stash();
+        assert!(stash.is_empty());
+        for n in 0..600 {
+            assert_eq!(stash.put(), n);
+        }
+        assert_eq!(stash.len(), 600);
+        assert!(stash.get(0));
+        assert!(stash.get(599));
+        assert!(!stash.get(600));
+        Ok(())
+    })
+}
+
+#[test]
+fn take_recycles_smallest_index() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = empty_stash();
+        for _ in 0..600 {
+            stash.put();
+        }
+        assert!(stash.take(300));
+        assert!(stash.take(5));
+        assert!(!stash.take(5));
+        assert!(!stash.take(10_000));
+        assert!(!stash.get(5));
+        assert_eq!(stash.len(), 598);
+        assert_eq!(stash.put(), 5);
+        assert_eq!(stash.put(), 300);
+        assert_eq!(stash.put(), 600);
+        Ok(())
+    })
+}
+
+#[test]
+fn many_words() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        // Spans more than a single counts pack of 16 words.
+        const N: u32 = 256 * 17 + 3;
+        let mut stash = empty_stash();
+        for n in 0..N {
+            assert_eq!(stash.put(), n);
+        }
+        assert!(stash.take(256 * 16 + 1));
+        assert!(stash.take(N - 1));
+        assert_eq!(stash.put(), 256 * 16 + 1);
+        assert_eq!(stash.put(), N - 1);
+        assert_eq!(stash.len(), N);
+        Ok(())
+    })
+}
+
+#[test]
+fn persists() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = empty_stash();
+        for _ in 0..10 {
+            stash.put();
+        }
+        stash.take(3);
+        stash.flush();
+        stash.invalidate_cache();
+        assert_eq!(stash.len(), 9);
+        assert!(!stash.get(3));
+        assert_eq!(stash.put(), 3);
+        Ok(())
+    })
+}
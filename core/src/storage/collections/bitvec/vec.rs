@@ -32,6 +32,7 @@ use crate::storage::{
     },
     chunk::SyncChunk,
     Flush,
+    InkExpect as _,
 };
 
 /// A space-efficient contiguous growable bit array type.
@@ -58,6 +59,17 @@ impl HasLayout for BitVec {
     }
 }
 
+#[cfg(all(feature = "std", feature = "serde"))]
+impl serde::Serialize for BitVec {
+    /// Serializes the bits of the bit vector as sequence.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
 impl scale::Encode for BitVec {
     fn encode_to<W: scale::Output>(&self, dest: &mut W) {
         self.len.encode_to(dest);
@@ -108,6 +120,24 @@ impl Flush for BitVec {
     }
 }
 
+impl BitVec {
+    /// Writes the cached state of the bit vector back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the bit vector so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.len.invalidate_cache();
+        self.blocks.invalidate_cache();
+    }
+}
+
 impl Drop for BitVec {
     #[inline]
     fn drop(&mut self) {
@@ -209,7 +239,7 @@ impl BitVec {
         } else {
             let new_latest_idx = self.len();
             self.last_block_mut()
-                .expect("there must be at least one block at this point")
+                .ink_expect("there must be at least one block at this point")
                 .set(new_latest_idx % BitBlock::BITS, value)
         }
         self.len += 1;
@@ -226,7 +256,7 @@ impl BitVec {
         let popped_blocks = BitBlock::required_blocks(len - 1);
         let popped = self
 			.last_block()
-			.expect("we already checked that len is greater than 1 so there must be at least one block; qed")
+			.ink_expect("we already checked that len is greater than 1 so there must be at least one block; qed")
 			.get((len - 1) % BitBlock::BITS);
         if popped_blocks < current_blocks {
             // Remove last bit block.
@@ -234,7 +264,7 @@ impl BitVec {
         } else {
             // Set last bit in last bit block to false.
             self.last_block_mut()
-                .expect(
+                .ink_expect(
                     "since we have the same amount of blocks we have at least one; qed",
                 )
                 .set((len - 1) % BitBlock::BITS, false)
@@ -260,7 +290,7 @@ impl BitVec {
         let bit_within_block = n % BitBlock::BITS;
         self.block_mut(n)
             .map(|block| block.set(bit_within_block, value))
-            .expect("n is out of bounds")
+            .ink_expect("n is out of bounds")
     }
 
     /// Flips the n-th bit of the bit vector.
@@ -272,7 +302,7 @@ impl BitVec {
         let bit_within_block = n % BitBlock::BITS;
         self.block_mut(n)
             .map(|block| block.flip(bit_within_block))
-            .expect("n is out of bounds")
+            .ink_expect("n is out of bounds")
     }
 
     /// Returns an iterator over all bits of the bit vector.
@@ -347,7 +377,7 @@ impl<'a> Iterator for BlockIter<'a> {
         if self.begin == self.end {
             return None
         }
-        let next = self.bitvec.blocks.get(self.begin).expect(
+        let next = self.bitvec.blocks.get(self.begin).ink_expect(
             "block are allocated contigeously in storage\
              ; so there has to be a block here; qed",
         );
@@ -371,7 +401,7 @@ impl<'a> DoubleEndedIterator for BlockIter<'a> {
         }
         debug_assert_ne!(self.end, 0);
         self.end -= 1;
-        let block = self.bitvec.blocks.get(self.end).expect(
+        let block = self.bitvec.blocks.get(self.end).ink_expect(
             "block are allocated contigeously in storage\
              ; so there has to be a block here; qed",
         );
@@ -0,0 +1,99 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::{
+    collections::btree_map::test_utils::{
+        empty_map,
+        every_edge_exists_only_once,
+    },
+    test_utils::{
+        self,
+        gen_key,
+    },
+};
+use quickcheck::{
+    Arbitrary,
+    Gen,
+};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// An operation on a storage B-tree map.
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(i32, i32),
+    Remove(i32),
+    Get(i32),
+    OrInsert(i32, i32),
+}
+
+impl Arbitrary for Op {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        match g.gen_range(0, 6) {
+            0 | 1 => Op::Insert(gen_key(g), g.gen()),
+            2 | 3 => Op::Remove(gen_key(g)),
+            4 => Op::Get(gen_key(g)),
+            _ => Op::OrInsert(gen_key(g), g.gen()),
+        }
+    }
+}
+
+/// Applies the operations to a storage B-tree map and a `std` B-tree map
+/// and asserts that both always contain the same key-value pairs.
+///
+/// Also asserts that no node of the tree is referenced more than once.
+fn cross_check(ops: Vec<Op>) -> bool {
+    test_utils::run_isolated(|| {
+        let mut map = empty_map();
+        let mut model = BTreeMap::new();
+        for op in ops {
+            match op {
+                Op::Insert(key, value) => {
+                    assert_eq!(map.insert(key, value), model.insert(key, value));
+                }
+                Op::Remove(key) => assert_eq!(map.remove(&key), model.remove(&key)),
+                Op::Get(key) => {
+                    assert_eq!(map.get(&key), model.get(&key));
+                    assert_eq!(map.contains_key(&key), model.contains_key(&key));
+                }
+                Op::OrInsert(key, value) => {
+                    assert_eq!(
+                        map.entry(key).or_insert(value),
+                        model.entry(key).or_insert(value),
+                    );
+                }
+            }
+            assert_eq!(map.len(), model.len() as u32);
+            assert!(every_edge_exists_only_once(&map));
+        }
+        for (key, value) in &model {
+            assert_eq!(map.get_key_value(key), Some((key, value)));
+        }
+    })
+}
+
+#[test]
+fn btree_map_behaves_like_std_btree_map() {
+    test_utils::check(cross_check)
+}
+
+/// Applies the operations of the reusable harness to a storage B-tree map.
+fn generic_cross_check(ops: Vec<test_utils::MapOp<i32, i32>>) -> bool {
+    test_utils::run_isolated(|| test_utils::cross_check_map(&mut empty_map(), ops))
+}
+
+#[test]
+fn btree_map_passes_generic_cross_check() {
+    test_utils::check(generic_cross_check)
+}
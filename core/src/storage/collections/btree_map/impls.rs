@@ -45,6 +45,7 @@ use crate::storage::{
     },
     chunk::SyncChunk,
     Flush,
+    InkExpect as _,
 };
 use core::{
     borrow::Borrow,
@@ -227,6 +228,29 @@ where
     }
 }
 
+impl<K, V> BTreeMap<K, V>
+where
+    K: Encode + Flush,
+    V: Encode + Flush,
+{
+    /// Writes the cached state of the map back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the map so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.header.invalidate_cache();
+        self.nodes.invalidate_cache();
+        self.kv_pairs.invalidate_cache();
+    }
+}
+
 impl<K, V> Encode for BTreeMap<K, V> {
     fn encode_to<W: scale::Output>(&self, dest: &mut W) {
         self.header.encode_to(dest);
@@ -311,7 +335,7 @@ where
     pub(super) fn get_handle_type(&self, handle: NodeHandle) -> HandleType {
         let children = self
             .get_node(handle)
-            .expect("node must exist")
+            .ink_expect("node must exist")
             .edges_count();
         if children == 0 {
             Leaf
@@ -329,7 +353,7 @@ where
     pub(super) fn descend(&self, handle: KVHandle) -> Option<NodeHandle> {
         let node = self
             .get_node(handle.node())
-            .expect("node to descend from must exist");
+            .ink_expect("node to descend from must exist");
         node.edge(handle.idx())
     }
 
@@ -349,7 +373,7 @@ where
 
     /// Returns the keys stored in this node.
     pub(super) fn keys_in_node(&self, node: NodeHandle) -> [Option<&K>; CAPACITY] {
-        let node = self.get_node(node).expect("node must exist");
+        let node = self.get_node(node).ink_expect("node must exist");
         let mut ks: [Option<&K>; CAPACITY] = Default::default();
 
         node.pairs()
@@ -387,7 +411,7 @@ where
 
     /// Returns a reference to the key/value pair referenced by `handle`, if available.
     fn get_kv(&self, handle: KVHandle) -> Option<KVRef<K, V>> {
-        let node = self.get_node(handle.node()).expect("node must exist");
+        let node = self.get_node(handle.node()).ink_expect("node must exist");
         let key_index = node.pair(handle.idx()).as_ref()?;
         let pair = self.get_kv_ref(*key_index)?;
         Some(KVRef::new(pair))
@@ -395,7 +419,7 @@ where
 
     /// Returns the value referenced by `handle`, if available.
     fn get_value(&self, handle: KVHandle) -> Option<&V> {
-        let node = self.get_node(handle.node()).expect("node must exist");
+        let node = self.get_node(handle.node()).ink_expect("node must exist");
         let value_index = node.pair(handle.idx()).as_ref()?;
         self.get_kv_ref(*value_index).map(|pair| pair.value_ref())
     }
@@ -405,12 +429,12 @@ where
     fn ascend(&self, handle: NodeHandle) -> Option<KVHandle> {
         let node = self
             .get_node(handle)
-            .expect("node to ascend from must exist");
+            .ink_expect("node to ascend from must exist");
 
         node.parent().map(|parent| {
             let idx = node
                 .parent_idx()
-                .expect("if parent exists, parent_idx always exist as well; qed");
+                .ink_expect("if parent exists, parent_idx always exist as well; qed");
             KVHandle::new(parent, idx)
         })
     }
@@ -503,7 +527,7 @@ where
                     let first_edge = self.first_edge(handle);
                     handle = self
                         .descend(first_edge)
-                        .expect("every branch node has children; qed");
+                        .ink_expect("every branch node has children; qed");
                 }
             }
         }
@@ -545,7 +569,7 @@ where
     fn right_kv(&self, handle: KVHandle) -> Option<KVHandle> {
         let node = self
             .get_node(handle.node())
-            .expect("node to descend from must exist");
+            .ink_expect("node to descend from must exist");
         if handle.idx() < node.len() {
             Some(handle)
         } else {
@@ -569,16 +593,19 @@ where
             Branch => {
                 let child = self
                     .right_child(handle)
-                    .expect("every branch node has children; qed");
+                    .ink_expect("every branch node has children; qed");
                 let first_leaf = self.first_leaf_edge(child);
 
-                let to_remove = self.right_kv(first_leaf).expect("right_kv must exist");
+                let to_remove =
+                    self.right_kv(first_leaf).ink_expect("right_kv must exist");
                 let (hole, pair_storage_index, nl) = self.extract_handle(to_remove);
 
-                let node = self.get_node_mut(handle.node()).expect("node must exist");
+                let node = self
+                    .get_node_mut(handle.node())
+                    .ink_expect("node must exist");
                 let old_pair_index = node
                     .set_pair(handle.idx(), Some(pair_storage_index))
-                    .expect("handle must be valid");
+                    .ink_expect("handle must be valid");
                 (hole, old_pair_index, nl)
             }
         };
@@ -587,7 +614,7 @@ where
         while new_len < CAPACITY / 2 {
             match self.handle_underfull_node(handle) {
                 UnderflowResult::AtRoot => break,
-                UnderflowResult::EmptyParent(_) => unreachable!(
+                UnderflowResult::EmptyParent(_) => ink_unreachable!(
                     "[ink_core::BTreeMap::remove_kv] Error: \
                      while handling an underfull node it was found that its parent \
                      node is empty. This can never happen since we always re-balance the tree \
@@ -595,7 +622,7 @@ where
                 ),
                 UnderflowResult::Merged(parent) => {
                     let parent_node =
-                        self.get_node(parent).expect("parent node must exist");
+                        self.get_node(parent).ink_expect("parent node must exist");
                     if parent_node.len() == 0 {
                         self.root_pop_level();
                         break
@@ -612,7 +639,7 @@ where
         if new_len == 0 {
             debug_assert_eq!(
                 self.get_node(handle)
-                    .expect("node must exist")
+                    .ink_expect("node must exist")
                     .edges_count(),
                 0
             );
@@ -668,11 +695,11 @@ where
     fn can_merge(&self, handle: KVHandle) -> bool {
         let len_left = self
             .left_child_node(handle)
-            .expect("left child must exist")
+            .ink_expect("left child must exist")
             .len();
         let len_right = self
             .right_child_node(handle)
-            .expect("right child must exist")
+            .ink_expect("right child must exist")
             .len();
 
         len_left + len_right < CAPACITY
@@ -685,8 +712,10 @@ where
     fn merge(&mut self, handle: KVHandle) {
         let right_child = self
             .right_child(handle)
-            .expect("right child must exist 591");
-        let right_node = self.get_node(right_child).expect("right child must exist");
+            .ink_expect("right child must exist 591");
+        let right_node = self
+            .get_node(right_child)
+            .ink_expect("right child must exist");
         let right_edges = right_node.edges;
         let right_pairs = right_node.pairs;
         let right_len = right_node.len();
@@ -694,10 +723,10 @@ where
         let (removed_pair_storage_index, old_node_len) =
             self.extract_handle_for_merge(handle);
 
-        let left_child = self.left_child(handle).expect("left child must exist");
+        let left_child = self.left_child(handle).ink_expect("left child must exist");
         let left_node = self
             .get_node_mut(left_child)
-            .expect("left child must exist");
+            .ink_expect("left child must exist");
         let left_len = left_node.len();
 
         debug_assert!(left_len + right_len < CAPACITY);
@@ -721,7 +750,7 @@ where
             let to = right_len + 1;
             let left_node = self
                 .get_node_mut(left_child)
-                .expect("left child must exist");
+                .ink_expect("left child must exist");
             left_node.set_edges_range(&right_edges[..to], from..from + to);
 
             for i in left_len + 1..left_len + right_len + 2 {
@@ -739,7 +768,9 @@ where
         &mut self,
         handle: KVHandle,
     ) -> (Option<KVStorageIndex>, usize) {
-        let node = self.get_node_mut(handle.node()).expect("node must exist");
+        let node = self
+            .get_node_mut(handle.node())
+            .ink_expect("node must exist");
         let _removed_edge = node.remove_edge_with_shift(handle.idx() + 1);
         let removed_pair_storage_index = node.remove_pair_with_shift(handle.idx());
         let old_len = node.len();
@@ -754,10 +785,10 @@ where
     fn pop(&mut self, handle: NodeHandle) -> (KVStorageIndex, Option<NodeHandle>) {
         let handle_type = self.get_handle_type(handle);
         let (pair_storage_index, idx) = {
-            let node = self.get_node_mut(handle).expect("node must exist");
+            let node = self.get_node_mut(handle).ink_expect("node must exist");
             debug_assert!(node.len() > 0);
             let idx = node.len() - 1;
-            let pair_index = node.take_pair(idx).expect("pair must exist");
+            let pair_index = node.take_pair(idx).ink_expect("pair must exist");
             node.set_len(node.len() - 1);
             (pair_index, idx)
         };
@@ -767,8 +798,8 @@ where
                 // If `handle` is a reference to a branch node we also remove the edge right
                 // of it.
                 let edge = {
-                    let node = self.get_node_mut(handle).expect("node must exist");
-                    node.take_edge(idx + 1).expect("edge must exist")
+                    let node = self.get_node_mut(handle).ink_expect("node must exist");
+                    node.take_edge(idx + 1).ink_expect("edge must exist")
                 };
                 let edge_handle = NodeHandle::new(edge.node());
                 self.set_parent(edge_handle, None, None);
@@ -783,23 +814,25 @@ where
     /// storage index pointed to by `handle` while pushing the old storage index
     /// of `handle` into the right child.
     fn steal_left(&mut self, handle: KVHandle) {
-        let left_child = self.left_child(handle).expect("left child must exist");
+        let left_child = self.left_child(handle).ink_expect("left child must exist");
         let (pair_index, edge) = self.pop(left_child);
 
-        let node = self.get_node_mut(handle.node()).expect("node must exist");
+        let node = self
+            .get_node_mut(handle.node())
+            .ink_expect("node must exist");
         let pair_storage_index = node
             .set_pair(handle.idx(), Some(pair_index))
-            .expect("pair must exist");
+            .ink_expect("pair must exist");
 
         let right = self.right_edge(handle);
-        let child = self.descend(right).expect("child must exist");
+        let child = self.descend(right).ink_expect("child must exist");
         match self.get_handle_type(child) {
             Leaf => self.push_front_leaf(child, pair_storage_index),
             Branch => {
                 self.push_front_branch(
                     child,
                     pair_storage_index,
-                    edge.expect("edge always exists for branch nodes"),
+                    edge.ink_expect("edge always exists for branch nodes"),
                 )
             }
         }
@@ -810,22 +843,24 @@ where
     /// pair index of `handle` into the left child.
     fn steal_right(&mut self, handle: KVHandle) {
         let right = self.right_edge(handle);
-        let child = self.descend(right).expect("child must exist");
+        let child = self.descend(right).ink_expect("child must exist");
         let (k, edge) = self.pop_front(child);
 
-        let node = self.get_node_mut(handle.node()).expect("node must exist");
+        let node = self
+            .get_node_mut(handle.node())
+            .ink_expect("node must exist");
         let pair_storage_index = node
             .set_pair(handle.idx(), Some(k))
-            .expect("pair must exist");
+            .ink_expect("pair must exist");
 
-        let left_child = self.left_child(handle).expect("left child must exist");
+        let left_child = self.left_child(handle).ink_expect("left child must exist");
         match self.get_handle_type(left_child) {
             Leaf => self.push_leaf(left_child, pair_storage_index),
             Branch => {
                 self.push_branch(
                     left_child,
                     pair_storage_index,
-                    edge.expect("edge always exists for branch node"),
+                    edge.ink_expect("edge always exists for branch node"),
                 )
             }
         }
@@ -840,17 +875,18 @@ where
     /// Returns `(removed_storage_index, Option<removed_edge>)`.
     fn pop_front(&mut self, handle: NodeHandle) -> (KVStorageIndex, Option<NodeHandle>) {
         let typ = self.get_handle_type(handle);
-        let node = self.get_node_mut(handle).expect("node must exist");
+        let node = self.get_node_mut(handle).ink_expect("node must exist");
 
         debug_assert!(node.len() > 0);
         let old_len = node.len();
 
-        let pair_storage_index = node.remove_pair_with_shift(0).expect("pair must exist");
+        let pair_storage_index =
+            node.remove_pair_with_shift(0).ink_expect("pair must exist");
 
         let edge = match typ {
             Leaf => None,
             Branch => {
-                let edge = node.remove_edge_with_shift(0).expect("edge must exist");
+                let edge = node.remove_edge_with_shift(0).ink_expect("edge must exist");
 
                 // Create a new `NodeHandle` to the new root
                 let new_root = NodeHandle::new(edge.node());
@@ -865,7 +901,7 @@ where
             }
         };
 
-        let node = self.get_node_mut(handle).expect("node must exist");
+        let node = self.get_node_mut(handle).ink_expect("node must exist");
         node.set_len(node.len() - 1);
 
         (pair_storage_index, edge)
@@ -877,7 +913,7 @@ where
         handle: NodeHandle,
         pair_storage_index: KVStorageIndex,
     ) {
-        let node = self.get_node_mut(handle).expect("node must exist");
+        let node = self.get_node_mut(handle).ink_expect("node must exist");
         debug_assert!(node.len() < CAPACITY);
 
         node.push_pair_to(0, pair_storage_index);
@@ -893,7 +929,7 @@ where
         pair_storage_index: KVStorageIndex,
         edge: NodeHandle,
     ) {
-        let node = self.get_node_mut(handle).expect("node must exist");
+        let node = self.get_node_mut(handle).ink_expect("node must exist");
         debug_assert!(node.len() < CAPACITY);
 
         node.push_pair_to(0, pair_storage_index);
@@ -914,7 +950,7 @@ where
                 match self
                     .nodes
                     .put(n, InternalEntry::Vacant(self.header.next_vacant))
-                    .expect(
+                    .ink_expect(
                         "[ink_core::BTreeMap::remove_node] Error: \
                          we already asserted that the entry at `n` exists",
                     ) {
@@ -925,7 +961,7 @@ where
                         Some(val)
                     }
                     InternalEntry::Vacant(_) => {
-                        unreachable!(
+                        ink_unreachable!(
                             "[ink_core::BTreeMap::remove_node] Error: \
                              we already asserted that the entry is occupied"
                         )
@@ -948,7 +984,7 @@ where
                         storage_index,
                         InternalKVEntry::Vacant(self.header.next_vacant_pair),
                     )
-                    .expect(
+                    .ink_expect(
                         "[ink_core::BTreeMap::remove_pair] Error: \
                          we already asserted that the pair at `n` exists",
                     ) {
@@ -958,7 +994,7 @@ where
                         Some(val)
                     }
                     InternalKVEntry::Vacant(_) => {
-                        unreachable!(
+                        ink_unreachable!(
                             "[ink_core::BTreeMap::remove_pair] Error: \
                              we already asserted that the pair is occupied"
                         )
@@ -966,7 +1002,7 @@ where
                 }
             }
         };
-        pair.expect("must exist")
+        pair.ink_expect("must exist")
     }
 
     /// Extracts the key/value pair storage index pointed to by `handle`, returning
@@ -974,10 +1010,12 @@ where
     ///
     /// Returns `(left_edge, removed_pair_storage_index, old_val, new_node_len)`.
     fn extract_handle(&mut self, handle: KVHandle) -> (KVHandle, KVStorageIndex, usize) {
-        let node = self.get_node_mut(handle.node()).expect("node must exist");
+        let node = self
+            .get_node_mut(handle.node())
+            .ink_expect("node must exist");
         let pair_storage_index = node
             .remove_pair_with_shift(handle.idx())
-            .expect("pair must exist");
+            .ink_expect("pair must exist");
         node.set_len(node.len() - 1);
         let new_len = node.len();
         (self.left_edge(handle), pair_storage_index, new_len)
@@ -1008,13 +1046,13 @@ where
                 let next_vacant = match self
                     .nodes
                     .put(current_vacant.node(), InternalEntry::Occupied(node))
-                    .expect(
+                    .ink_expect(
                         "[ink_core::BTreeMap::put] Error: \
                          expected a vacant entry here, but no entry was found",
                     ) {
                     InternalEntry::Vacant(next_vacant) => next_vacant,
                     InternalEntry::Occupied(_) => {
-                        unreachable!(
+                        ink_unreachable!(
                             "[ink_core::BTreeMap::put] Error: \
                              a next_vacant index can never point to an occupied entry"
                         )
@@ -1045,13 +1083,13 @@ where
                 let next_vacant = match self
                     .kv_pairs
                     .put(current_vacant, InternalKVEntry::Occupied(pair))
-                    .expect(
+                    .ink_expect(
                         "[ink_core::BTreeMap::put_pair] Error: \
                          expected a vacant entry here, but no entry was found",
                     ) {
                     InternalKVEntry::Vacant(next_vacant) => next_vacant,
                     InternalKVEntry::Occupied(_) => {
-                        unreachable!(
+                        ink_unreachable!(
                             "[ink_core::BTreeMap::put_pair] Error: \
                              a next_vacant index can never point to an occupied entry"
                         )
@@ -1072,7 +1110,9 @@ where
         pair_storage_index: KVStorageIndex,
         edge: NodeHandle,
     ) {
-        let node = self.get_node_mut(dst).expect("destination node must exist");
+        let node = self
+            .get_node_mut(dst)
+            .ink_expect("destination node must exist");
         node.set_pair(node.len(), Some(pair_storage_index));
         node.set_edge(node.len() + 1, Some(edge));
 
@@ -1083,7 +1123,9 @@ where
 
     /// Adds the storage index of a key/value pair to the end of the `dst` node.
     fn push_leaf(&mut self, dst: NodeHandle, pair_storage_index: KVStorageIndex) {
-        let node = self.get_node_mut(dst).expect("destination node must exist");
+        let node = self
+            .get_node_mut(dst)
+            .ink_expect("destination node must exist");
 
         debug_assert!(node.len() < CAPACITY);
 
@@ -1106,7 +1148,9 @@ where
         handle: NodeHandle,
         idx: usize,
     ) -> (KVStorageIndex, NodeHandle) {
-        let node = self.get_node_mut(handle).expect("node to split must exist");
+        let node = self
+            .get_node_mut(handle)
+            .ink_expect("node to split must exist");
 
         // We can only start splitting at leaf nodes.
         debug_assert_eq!(node.edges_count(), 0);
@@ -1114,7 +1158,7 @@ where
         let mut right = Node::new();
         let pair_storage_index = node
             .take_pair(idx)
-            .expect("pair must exist at split location");
+            .ink_expect("pair must exist at split location");
         node.set_len(node.len() - 1);
 
         let from = idx + 1;
@@ -1147,7 +1191,9 @@ where
         parent: NodeHandle,
         idx: usize,
     ) -> (KVStorageIndex, NodeHandle) {
-        let node = self.get_node_mut(parent).expect("node to split must exist");
+        let node = self
+            .get_node_mut(parent)
+            .ink_expect("node to split must exist");
 
         let count = node.len();
         let new_len = count - idx - 1;
@@ -1158,7 +1204,7 @@ where
 
         let pair_storage_index = node
             .take_pair(idx)
-            .expect("pair must exist at split location");
+            .ink_expect("pair must exist at split location");
         node.set_len(node.len() - 1);
 
         let from = idx + 1;
@@ -1189,14 +1235,14 @@ where
     ///
     /// Returns a handle to the new root node.
     fn root_push_level(&mut self) -> NodeHandle {
-        let current_root_handle = self.header.root.expect("node must exist");
+        let current_root_handle = self.header.root.ink_expect("node must exist");
 
         let mut new_root = Node::<K, V>::new();
         new_root.set_edge(0, Some(current_root_handle));
         let new_root_handle = self.put(new_root);
 
         self.set_parent(
-            self.header.root.expect("root must exist"),
+            self.header.root.ink_expect("root must exist"),
             Some(new_root_handle),
             Some(0),
         );
@@ -1210,16 +1256,16 @@ where
     /// has only one edge, no cleanup is done on any of the other children are elements of the root.
     /// This decreases the height by 1 and is the opposite of `push_level`.
     fn root_pop_level(&mut self) {
-        let handle = self.header.root.expect("root must exist");
+        let handle = self.header.root.ink_expect("root must exist");
         debug_assert!(
             self.get_node(handle)
-                .expect("root must exist")
+                .ink_expect("root must exist")
                 .edges_count()
                 == 1
         );
         let edge = self.first_edge(handle);
 
-        let child = self.descend(edge).expect("child must exist");
+        let child = self.descend(edge).ink_expect("child must exist");
         self.set_parent(child, None, None);
 
         self.header.root = Some(child);
@@ -1234,7 +1280,7 @@ where
         parent_node: Option<NodeHandle>,
         parent_idx: Option<usize>,
     ) {
-        let node = self.get_node_mut(handle).expect("node must exist");
+        let node = self.get_node_mut(handle).ink_expect("node must exist");
         node.set_parent(parent_node);
         node.set_parent_idx(parent_idx);
     }
@@ -1250,12 +1296,12 @@ where
     ) -> (InsertResult, KVStorageIndex) {
         let node = self
             .get_node(handle.node())
-            .expect("node to insert into must exist");
+            .ink_expect("node to insert into must exist");
         let len = node.len();
 
         let pair = self
             .get_kv_ref(pair_storage_index)
-            .expect("requested pair must always exist");
+            .ink_expect("requested pair must always exist");
         let k = &pair.key_ref();
 
         if len < CAPACITY {
@@ -1298,12 +1344,12 @@ where
     ) -> InsertResult {
         let pair = self
             .get_kv_ref(pair_storage_index)
-            .expect("requested pair must always exist");
+            .ink_expect("requested pair must always exist");
         let k = &pair.key_ref();
 
         let node = self
             .get_node(handle.node())
-            .expect("parent to insert into must exist");
+            .ink_expect("parent to insert into must exist");
 
         if node.len() < CAPACITY {
             let kv_handle = match search::search_node(
@@ -1343,7 +1389,7 @@ where
     ) -> KVStorageIndex {
         let node = self
             .get_node_mut(handle.node())
-            .expect("node to insert_fit into must exist");
+            .ink_expect("node to insert_fit into must exist");
         debug_assert!(node.len() < CAPACITY);
 
         node.push_pair_to(handle.idx(), pair_storage_index);
@@ -1365,7 +1411,7 @@ where
 
         let node = self
             .get_node_mut(handle.node())
-            .expect("node to insert (k, v, edge) into must exist");
+            .ink_expect("node to insert (k, v, edge) into must exist");
 
         node.push_edge_to(handle.idx() + 1, edge);
 
@@ -1406,7 +1452,7 @@ where
     fn correct_parent_link(&mut self, handle: KVHandle) {
         let child = self
             .descend(handle)
-            .expect("child in which to correct parent link must exist");
+            .ink_expect("child in which to correct parent link must exist");
         self.set_parent(child, Some(handle.node()), Some(handle.idx()));
     }
 
@@ -1415,7 +1461,7 @@ where
     /// `insert` operations) the order in the `edges` array changes and the child pointers
     /// have to be corrected.
     fn correct_all_children_parent_links(&mut self, handle: NodeHandle) {
-        let node = self.get_node(handle).expect("node must exist");
+        let node = self.get_node(handle).ink_expect("node must exist");
         let len = node.len();
 
         for i in 0..=len {
@@ -1426,7 +1472,7 @@ where
 
     /// Returns `true` if a node has children.
     fn has_children(&mut self, handle: NodeHandle) -> bool {
-        let node = self.get_node(handle).expect("node must exist");
+        let node = self.get_node(handle).ink_expect("node must exist");
         node.edges_count() > 0
     }
 }
@@ -1462,7 +1508,7 @@ where
             Found(handle) => {
                 let v = self
                     .get_value(handle)
-                    .expect("if found there is always a value; qed");
+                    .ink_expect("if found there is always a value; qed");
                 Some(v)
             }
             NotFound(_) => None,
@@ -1520,6 +1566,41 @@ where
         self.get(key).is_some()
     }
 
+    /// Copies all key/value pairs of the map into a new map allocated
+    /// using the given storage allocator.
+    ///
+    /// # Note
+    ///
+    /// The pairs are inserted into the copy one by one and thus the
+    /// copy does not necessarily have the same node layout as `self`.
+    /// Use this to take snapshots or backups of the map within a contract.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the storage region handed out by the allocator is
+    /// overwritten and thus must not be in use by other storage entities.
+    pub unsafe fn deep_clone_into<A>(&self, alloc: &mut A) -> Self
+    where
+        A: Allocate,
+        K: Clone,
+        V: Clone,
+    {
+        let mut copy = Self::allocate_using(alloc).initialize_into(());
+        let mut pending: ink_prelude::vec::Vec<NodeHandle> =
+            self.root().iter().copied().collect();
+        while let Some(handle) = pending.pop() {
+            let node = self.get_node(handle).ink_expect("node must exist");
+            for kv_storage_index in node.pairs().iter().flatten() {
+                let pair = self
+                    .get_kv_ref(*kv_storage_index)
+                    .ink_expect("key/value pair must exist");
+                copy.insert(pair.key_ref().clone(), pair.value_ref().clone());
+            }
+            pending.extend(node.edges.iter().flatten());
+        }
+        copy
+    }
+
     /// Inserts a key/value pair into the map.
     ///
     /// If the map did not have this key present, `None` is returned.
@@ -1801,7 +1882,7 @@ where
     pub fn key(&self) -> &K {
         self.key
             .as_ref()
-            .expect("entry does always have a key; qed")
+            .ink_expect("entry does always have a key; qed")
     }
 
     /// Sets the value of the entry with the `VacantEntry`'s key,
@@ -1825,7 +1906,7 @@ where
         let key = self
             .key
             .take()
-            .expect("key is only taken when inserting, so must be there; qed");
+            .ink_expect("key is only taken when inserting, so must be there; qed");
 
         let pair_index = if self.tree.is_empty() && self.tree.root().is_none() {
             self.tree.create_root(key, val)
@@ -1834,7 +1915,7 @@ where
         };
         self.tree
             .get_value_mut_ref(pair_index)
-            .expect("value was just inserted; qed")
+            .ink_expect("value was just inserted; qed")
     }
 }
 
@@ -1857,7 +1938,7 @@ where
     pub fn key(&self) -> &K {
         self.tree
             .get_kv(self.handle)
-            .expect("every occupied entry always has a key/value pair; qed")
+            .ink_expect("every occupied entry always has a key/value pair; qed")
             .key()
     }
 
@@ -1879,7 +1960,7 @@ where
     pub fn get(&self) -> &V {
         self.tree
             .get_value(self.handle)
-            .expect("every occupied entry always has a key/value pair; qed")
+            .ink_expect("every occupied entry always has a key/value pair; qed")
     }
 
     /// Gets a mutable reference to the value in the entry.
@@ -1908,13 +1989,13 @@ where
         let node = self
             .tree
             .get_node_mut(self.handle.node())
-            .expect("every occupied entry always belongs to a node; qed");
+            .ink_expect("every occupied entry always belongs to a node; qed");
         let storage_index = node
             .pair(idx)
-            .expect("every occupied entry always has a pair stored in it; qed");
+            .ink_expect("every occupied entry always has a pair stored in it; qed");
         self.tree
             .get_value_mut_ref(storage_index)
-            .expect("every pair always has a value; qed")
+            .ink_expect("every pair always has a value; qed")
     }
 
     /// Converts the entry into a mutable reference to its value.
@@ -1940,7 +2021,7 @@ where
     /// ```
     pub fn into_mut(self) -> &'a mut V {
         self.into_value_mut()
-            .expect("every occupied entry always has a key/value pair; qed")
+            .ink_expect("every occupied entry always has a key/value pair; qed")
     }
 
     /// Takes the value of the entry out of the map, and returns it.
@@ -1970,19 +2051,19 @@ where
         let node = self
             .tree
             .get_node_mut(self.handle.node())
-            .expect("every occupied entry always belongs to a node; qed");
+            .ink_expect("every occupied entry always belongs to a node; qed");
 
         let storage_index = node
             .pair(self.handle.idx())
-            .expect("each occupied entry must already have a pair");
+            .ink_expect("each occupied entry must already have a pair");
         let entry = self
             .tree
             .kv_pairs
             .take(storage_index)
-            .expect("each occupied entry must already have a pair");
+            .ink_expect("each occupied entry must already have a pair");
         match entry {
             InternalKVEntry::Vacant(_) => {
-                unreachable!(
+                ink_unreachable!(
                     "[ink_core::BTreeMap::insert] Error: \
                      we already asserted that the entry is occupied"
                 )
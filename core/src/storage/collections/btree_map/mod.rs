@@ -48,6 +48,9 @@ mod test_utils;
 #[cfg(test)]
 mod tests;
 
+#[cfg(all(test, feature = "ink-fuzz-tests"))]
+mod fuzz_tests;
+
 mod impls;
 mod node;
 mod search;
@@ -16,20 +16,23 @@ use self::SearchResult::{
     Found,
     NotFound,
 };
-use crate::storage::btree_map::{
-    impls::{
-        BTreeMap,
-        HandleType::{
-            Branch,
-            Leaf,
+use crate::storage::{
+    btree_map::{
+        impls::{
+            BTreeMap,
+            HandleType::{
+                Branch,
+                Leaf,
+            },
+            CAPACITY,
+        },
+        node::{
+            KVHandle,
+            Node,
+            NodeHandle,
         },
-        CAPACITY,
-    },
-    node::{
-        KVHandle,
-        Node,
-        NodeHandle,
     },
+    InkExpect as _,
 };
 use core::{
     borrow::Borrow,
@@ -64,9 +67,9 @@ where
     }
 
     let mut cur =
-        current_root.expect("we would already have returned if no root exists; qed");
+        current_root.ink_expect("we would already have returned if no root exists; qed");
     loop {
-        let node = tree.get_node(cur).expect(
+        let node = tree.get_node(cur).ink_expect(
             "node which is iterated over is either root or child node, \
              but it always exists; qed",
         );
@@ -79,7 +82,7 @@ where
                         // Go down then
                         cur = tree
                             .descend(handle)
-                            .expect("a branch node always has a child; qed");
+                            .ink_expect("a branch node always has a child; qed");
                         continue
                     }
                 }
@@ -469,3 +469,29 @@ fn extend_works() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn deep_clone_into() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = empty_map();
+        for i in 0..100 {
+            map.insert(i, i * 10);
+        }
+        assert!(map.node_count() > 1);
+        let mut copy = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x1; 32]));
+            map.deep_clone_into(&mut alloc)
+        };
+        for i in 0..50 {
+            assert_eq!(map.remove(&i), Some(i * 10));
+        }
+        map.flush();
+        copy.flush();
+        copy.invalidate_cache();
+        assert_eq!(copy.len(), 100);
+        for i in 0..100 {
+            assert_eq!(copy.get(&i), Some(&(i * 10)));
+        }
+        Ok(())
+    })
+}
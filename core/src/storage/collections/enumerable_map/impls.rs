@@ -0,0 +1,305 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::{
+    borrow::Borrow,
+    hash::Hash,
+};
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use scale::{
+    Codec,
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    self,
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    vec::Iter,
+    Flush,
+    InkExpect as _,
+};
+
+/// A hash map that can enumerate its keys.
+///
+/// # Note
+///
+/// The keys are enumerated in no particular order since
+/// removing a key moves the last key into its place.
+#[derive(Debug)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct EnumerableMap<K, V> {
+    /// The keys of the map.
+    keys: storage::Vec<K>,
+    /// The values of the map together with the indices of their keys.
+    entries: storage::HashMap<K, Entry<V>>,
+}
+
+/// A value of the map together with the index of its key.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+struct Entry<V> {
+    /// The value.
+    val: V,
+    /// The index of the key in the keys of the map.
+    index: u32,
+}
+
+impl<V> Flush for Entry<V>
+where
+    V: Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.val.flush()
+    }
+}
+
+impl<K, V> Flush for EnumerableMap<K, V>
+where
+    K: Encode + Flush,
+    V: Encode + Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.keys.flush();
+        self.entries.flush();
+    }
+}
+
+impl<K, V> EnumerableMap<K, V>
+where
+    K: Encode + Flush,
+    V: Encode + Flush,
+{
+    /// Writes the cached state of the map back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the map so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.keys.invalidate_cache();
+        self.entries.invalidate_cache();
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<K, V> HasLayout for EnumerableMap<K, V>
+where
+    K: Metadata + 'static,
+    V: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(
+            Self::meta_type(),
+            vec![
+                LayoutField::of("keys", &self.keys),
+                LayoutField::of("entries", &self.entries),
+            ],
+        )
+        .into()
+    }
+}
+
+impl<K, V> Encode for EnumerableMap<K, V> {
+    fn encode_to<W: scale::Output>(&self, dest: &mut W) {
+        self.keys.encode_to(dest);
+        self.entries.encode_to(dest);
+    }
+}
+
+impl<K, V> Decode for EnumerableMap<K, V> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let keys = storage::Vec::decode(input)?;
+        let entries = storage::HashMap::decode(input)?;
+        Ok(Self { keys, entries })
+    }
+}
+
+impl<K, V> AllocateUsing for EnumerableMap<K, V> {
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            keys: storage::Vec::allocate_using(alloc),
+            entries: storage::HashMap::allocate_using(alloc),
+        }
+    }
+}
+
+impl<K, V> Initialize for EnumerableMap<K, V> {
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline]
+    fn initialize(&mut self, _args: Self::Args) {
+        self.keys.initialize(());
+        self.entries.initialize(());
+    }
+}
+
+impl<K, V> EnumerableMap<K, V> {
+    /// Returns the number of key-value pairs in the map.
+    pub fn len(&self) -> u32 {
+        self.keys.len()
+    }
+
+    /// Returns `true` if the map contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K, V> EnumerableMap<K, V>
+where
+    K: Codec + Hash + Eq + Clone,
+    V: Codec,
+{
+    /// Inserts a key-value pair into the map.
+    ///
+    /// Returns the previous value associated with the same key if any.
+    /// If the map did not have this key present, `None` is returned.
+    pub fn insert(&mut self, key: K, val: V) -> Option<V> {
+        if let Some(entry) = self.entries.get_mut(&key) {
+            return Some(core::mem::replace(&mut entry.val, val))
+        }
+        let index = self.len();
+        self.keys.push(key.clone());
+        self.entries.insert(key, Entry { val, index });
+        None
+    }
+
+    /// Copies all key-value pairs of the map into a new map allocated
+    /// using the given storage allocator.
+    ///
+    /// # Note
+    ///
+    /// The copy enumerates its keys in the same order as `self`.
+    /// Use this to take snapshots or backups of the map within a contract.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the storage region handed out by the allocator is
+    /// overwritten and thus must not be in use by other storage entities.
+    pub unsafe fn deep_clone_into<A>(&self, alloc: &mut A) -> Self
+    where
+        A: Allocate,
+        V: Clone,
+    {
+        let mut copy = Self::allocate_using(alloc).initialize_into(());
+        for (key, val) in self.iter() {
+            copy.insert(key.clone(), val.clone());
+        }
+        copy
+    }
+
+    /// Removes the key from the map and returns its value if any.
+    ///
+    /// # Note
+    ///
+    /// The last key of the map takes the place of the removed key.
+    pub fn remove<Q>(&mut self, key: &Q) -> Option<V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        let Entry { val, index } = self.entries.remove(key)?;
+        let last = self.len() - 1;
+        if index != last {
+            self.keys.swap(index, last);
+            let moved = self.key_at(index).ink_expect("missing moved key").clone();
+            self.entries
+                .get_mut::<K>(&moved)
+                .ink_expect("missing entry of moved key")
+                .index = index;
+        }
+        let _ = self.keys.pop();
+        Some(val)
+    }
+}
+
+impl<K, V> EnumerableMap<K, V>
+where
+    K: Codec + Hash + Eq,
+    V: Codec,
+{
+    /// Returns an immutable reference to the value corresponding to the key.
+    pub fn get<Q>(&self, key: &Q) -> Option<&V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get(key).map(|entry| &entry.val)
+    }
+
+    /// Returns a mutable reference to the value corresponding to the key.
+    pub fn get_mut<Q>(&mut self, key: &Q) -> Option<&mut V>
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.get_mut(key).map(|entry| &mut entry.val)
+    }
+
+    /// Returns `true` if there is an entry corresponding to the key in the map.
+    pub fn contains_key<Q>(&self, key: &Q) -> bool
+    where
+        K: Borrow<Q>,
+        Q: Hash + Eq + ?Sized,
+    {
+        self.entries.contains_key(key)
+    }
+
+    /// Returns the `n`-th key of the map if any.
+    pub fn key_at(&self, n: u32) -> Option<&K> {
+        self.keys.get(n)
+    }
+
+    /// Returns an iterator over the keys of the map.
+    pub fn keys(&self) -> Iter<'_, K> {
+        self.keys.iter()
+    }
+
+    /// Returns an iterator over the key-value pairs of the map.
+    pub fn iter(&self) -> impl Iterator<Item = (&K, &V)> {
+        self.keys().map(move |key| {
+            let val = self.get(key).ink_expect("missing entry of enumerated key");
+            (key, val)
+        })
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A hash map that can enumerate its keys.
+//!
+//! Combines a hash map with a vector of its keys that are kept consistent
+//! upon insertion and removal. Removal moves the last key into the place
+//! of the removed one so that all operations remain `O(1)`.
+
+#[cfg(test)]
+mod tests;
+
+mod impls;
+
+pub use self::impls::EnumerableMap;
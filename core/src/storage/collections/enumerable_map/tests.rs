@@ -0,0 +1,114 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+    storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+        EnumerableMap,
+    },
+};
+use ink_primitives::Key;
+
+fn empty_map() -> EnumerableMap<u8, i32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        EnumerableMap::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Asserts that the enumerated pairs of the map are the given ones in any order.
+fn assert_pairs(map: &EnumerableMap<u8, i32>, expected: &[(u8, i32)]) {
+    let mut pairs = map.iter().map(|(&k, &v)| (k, v)).collect::<Vec<_>>();
+    pairs.sort();
+    assert_eq!(pairs, expected);
+    assert_eq!(map.len(), expected.len() as u32);
+}
+
+#[test]
+fn init() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let map = empty_map();
+        assert!(map.is_empty());
+        assert_eq!(map.keys().next(), None);
+        assert_eq!(map.get(&1), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn insert_get() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = empty_map();
+        assert_eq!(map.insert(1, 10), None);
+        assert_eq!(map.insert(2, 20), None);
+        assert_eq!(map.insert(1, 11), Some(10));
+        *map.get_mut(&2).unwrap() += 1;
+        assert_eq!(map.get(&1), Some(&11));
+        assert!(map.contains_key(&2));
+        assert!(!map.contains_key(&3));
+        assert_eq!(map.keys().copied().collect::<Vec<_>>(), vec![1, 2]);
+        assert_pairs(&map, &[(1, 11), (2, 21)]);
+        Ok(())
+    })
+}
+
+#[test]
+fn remove_keeps_keys_consistent() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = empty_map();
+        for k in 0..5 {
+            map.insert(k, i32::from(k) * 10);
+        }
+        // Removing a key in the middle moves the last key into its place.
+        assert_eq!(map.remove(&1), Some(10));
+        assert_eq!(map.key_at(1), Some(&4));
+        assert_eq!(map.remove(&1), None);
+        // Removing the last key.
+        assert_eq!(map.remove(&3), Some(30));
+        // The moved key is still removed consistently.
+        assert_eq!(map.remove(&4), Some(40));
+        assert_pairs(&map, &[(0, 0), (2, 20)]);
+        map.insert(4, 41);
+        assert_pairs(&map, &[(0, 0), (2, 20), (4, 41)]);
+        Ok(())
+    })
+}
+
+#[test]
+fn deep_clone_into() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = empty_map();
+        map.insert(1, 10);
+        map.insert(2, 20);
+        map.insert(3, 30);
+        let mut copy = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x1; 32]));
+            map.deep_clone_into(&mut alloc)
+        };
+        assert_eq!(map.remove(&1), Some(10));
+        map.insert(2, 21);
+        map.flush();
+        copy.flush();
+        copy.invalidate_cache();
+        assert_eq!(copy.keys().cloned().collect::<Vec<_>>(), vec![1, 2, 3]);
+        assert_pairs(&copy, &[(1, 10), (2, 20), (3, 30)]);
+        Ok(())
+    })
+}
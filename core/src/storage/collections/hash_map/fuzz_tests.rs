@@ -0,0 +1,110 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::{
+    self,
+    alloc::{
+        AllocateUsing,
+        BumpAlloc,
+        Initialize,
+    },
+    test_utils::{
+        self,
+        gen_key,
+    },
+};
+use ink_primitives::Key;
+use quickcheck::{
+    Arbitrary,
+    Gen,
+};
+use rand::Rng;
+use std::collections::HashMap;
+
+/// An operation on a storage hash map.
+#[derive(Debug, Clone)]
+enum Op {
+    Insert(i32, i32),
+    Remove(i32),
+    Get(i32),
+    Mutate(i32, i32),
+}
+
+impl Arbitrary for Op {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        match g.gen_range(0, 6) {
+            0 | 1 => Op::Insert(gen_key(g), g.gen()),
+            2 | 3 => Op::Remove(gen_key(g)),
+            4 => Op::Get(gen_key(g)),
+            _ => Op::Mutate(gen_key(g), g.gen()),
+        }
+    }
+}
+
+/// Returns an empty storage hash map.
+fn new_empty() -> storage::HashMap<i32, i32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        storage::HashMap::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Applies the operations to a storage hash map and a `std` hash map
+/// and asserts that both always contain the same key-value pairs.
+fn cross_check(ops: Vec<Op>) -> bool {
+    test_utils::run_isolated(|| {
+        let mut map = new_empty();
+        let mut model = HashMap::new();
+        for op in ops {
+            match op {
+                Op::Insert(key, value) => {
+                    assert_eq!(map.insert(key, value), model.insert(key, value));
+                }
+                Op::Remove(key) => assert_eq!(map.remove(&key), model.remove(&key)),
+                Op::Get(key) => {
+                    assert_eq!(map.get(&key), model.get(&key));
+                    assert_eq!(map.contains_key(&key), model.contains_key(&key));
+                }
+                Op::Mutate(key, by) => {
+                    let expected = model.get_mut(&key).map(|value| {
+                        *value = value.wrapping_add(by);
+                        *value
+                    });
+                    let mutated =
+                        map.mutate_with(&key, |value| *value = value.wrapping_add(by));
+                    assert_eq!(mutated.cloned(), expected);
+                }
+            }
+            assert_eq!(map.len(), model.len() as u32);
+        }
+        for (key, value) in &model {
+            assert_eq!(map.get(key), Some(value));
+        }
+    })
+}
+
+#[test]
+fn hash_map_behaves_like_std_hash_map() {
+    test_utils::check(cross_check)
+}
+
+/// Applies the operations of the reusable harness to a storage hash map.
+fn generic_cross_check(ops: Vec<test_utils::MapOp<i32, i32>>) -> bool {
+    test_utils::run_isolated(|| test_utils::cross_check_map(&mut new_empty(), ops))
+}
+
+#[test]
+fn hash_map_passes_generic_cross_check() {
+    test_utils::check(generic_cross_check)
+}
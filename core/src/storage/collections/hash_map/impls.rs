@@ -41,6 +41,7 @@ use crate::storage::{
     },
     chunk::SyncChunk,
     Flush,
+    InkExpect as _,
 };
 
 /// Mapping stored in the contract storage.
@@ -152,6 +153,28 @@ where
     }
 }
 
+impl<K, V> HashMap<K, V>
+where
+    K: scale::Encode + Flush,
+    V: scale::Encode + Flush,
+{
+    /// Writes the cached state of the map back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the map so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.len.invalidate_cache();
+        self.entries.invalidate_cache();
+    }
+}
+
 impl<K, V> scale::Encode for HashMap<K, V> {
     fn encode_to<W: scale::Output>(&self, dest: &mut W) {
         self.len.encode_to(dest);
@@ -236,6 +259,17 @@ impl<K, V> HashMap<K, V> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Limits the number of elements the map keeps cached or removes the limit if `None`.
+    ///
+    /// # Note
+    ///
+    /// Useful for long off-chain executions that read many elements.
+    /// Clean elements exceeding the limit are evicted in least recently used
+    /// order upon mutable accesses and flushes of the map.
+    pub fn set_cache_limit(&mut self, limit: Option<u32>) {
+        self.entries.set_cache_limit(limit)
+    }
 }
 
 /// Converts the given slice into an array with fixed size of 4.
@@ -288,7 +322,9 @@ where
                 None
             }
             None => {
-                panic!("[ink_core::HashMap::insert] Error: failed finding a valid entry")
+                ink_panic!(
+                    "[ink_core::HashMap::insert] Error: failed finding a valid entry"
+                )
             }
         }
     }
@@ -374,7 +410,7 @@ where
         // Convert the first 4 bytes in the keccak256 hash
         // of the key into a big-endian unsigned integer.
         let probe_start = u32::from_be_bytes(
-            slice_as_array4(&(hash::keccak256(key.borrow())[0..4])).expect(
+            slice_as_array4(&(hash::keccak256(key.borrow())[0..4])).ink_expect(
                 "[ink_core::HashMap::insert] Error \
                  couldn't convert to probe_start byte array",
             ),
@@ -442,6 +478,8 @@ where
 
     /// Removes a key from the map, returning the value at the key if the key was previously in the map.
     ///
+    /// Returns `None` if the key is not in the map.
+    ///
     /// # Note
     ///
     /// The key may be any borrowed form of the map's key type,
@@ -451,10 +489,7 @@ where
         K: Borrow<Q>,
         Q: Hash + Eq + ?Sized,
     {
-        let probe_index = self.probe_inspecting(key).expect(
-            "[ink_core::HashMap::remove] Error: \
-             failed at finding a valid entry",
-        );
+        let probe_index = self.probe_inspecting(key)?;
         match self.entries.take(probe_index) {
             Some(Entry::Removed) | None => None,
             Some(Entry::Occupied(OccupiedEntry { val, .. })) => {
@@ -543,7 +578,7 @@ where
     type Output = V;
 
     fn index(&self, index: &Q) -> &Self::Output {
-        self.get(index).expect(
+        self.get(index).ink_expect(
             "[ink_core::HashMap::index] Error: \
              expected `index` to be within bounds",
         )
@@ -557,7 +592,7 @@ where
     Q: Eq + Hash,
 {
     fn index_mut(&mut self, index: &Q) -> &mut Self::Output {
-        self.get_mut(index).expect(
+        self.get_mut(index).ink_expect(
             "[ink_core::HashMap::index_mut] Error: \
              expected `index` to be within bounds",
         )
@@ -20,6 +20,9 @@
 #[cfg(test)]
 mod tests;
 
+#[cfg(all(test, feature = "ink-fuzz-tests"))]
+mod fuzz_tests;
+
 mod impls;
 
 pub use self::impls::HashMap;
@@ -214,3 +214,15 @@ fn extend_works() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn remove_missing_key() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = new_empty::<i32, i32>();
+        assert_eq!(map.remove(&1), None);
+        assert_eq!(map.insert(1, 10), None);
+        assert_eq!(map.remove(&2), None);
+        assert_eq!(map.len(), 1);
+        Ok(())
+    })
+}
@@ -0,0 +1,44 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Internal consistency checks of the storage collections.
+
+/// An inconsistency of the internal state of a storage collection.
+///
+/// # Note
+///
+/// These can only be caused by a bug in the collection or a corrupted
+/// contract storage. Like all other storage panics their messages are stripped
+/// from `no_std` builds with `ink-minimal-panics` enabled since that bloats the
+/// contract. They still trap so that the inconsistent state is never committed.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum InternalError {
+    /// An entry that must exist within bounds was not found.
+    MissingEntry,
+    /// An entry that must be vacant was found to be occupied.
+    UnexpectedOccupied,
+    /// An entry that must be occupied was found to be vacant.
+    UnexpectedVacant,
+}
+
+impl InternalError {
+    /// Reports the inconsistency found by the operation and traps.
+    ///
+    /// The message is stripped from `no_std` builds with `ink-minimal-panics` enabled.
+    #[cold]
+    #[inline(never)]
+    pub fn report(self, operation: &'static str) -> ! {
+        ink_panic!("[ink_core::{}] Error: {:?}", operation, self)
+    }
+}
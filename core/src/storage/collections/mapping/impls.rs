@@ -0,0 +1,191 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::marker::PhantomData;
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use ink_primitives::Key;
+use scale::{
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::{
+    HasTypeDef,
+    Metadata,
+    NamedField,
+    TypeDef,
+    TypeDefStruct,
+    TypeId,
+};
+
+use crate::{
+    env::{
+        self,
+        hash::Blake2x256,
+    },
+    storage::{
+        alloc::{
+            Allocate,
+            AllocateUsing,
+            Initialize,
+        },
+        chunk::hashed_entry_key,
+        Flush,
+        InkExpect as _,
+    },
+};
+
+/// A keyed store without any bookkeeping.
+///
+/// The value of a key is stored in the cell at the BLAKE2 hash of the
+/// mapping's key and the encoded key, i.e. at the same key as the entries
+/// of a [`LazyHashMap`](chunk/struct.LazyHashMap.html).
+///
+/// # Note
+///
+/// Unlike the other storage collections the mapping neither tracks its
+/// length nor its keys and therefore cannot be iterated. It does not cache
+/// anything either: every access reads or writes the contract storage
+/// directly, so there is nothing to flush. Prefer it over
+/// [`HashMap`](struct.HashMap.html) if only single entries are accessed.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(TypeId))]
+pub struct Mapping<K, V> {
+    /// The key from which the keys of the entries are derived.
+    key: Key,
+    /// The types of the keys and values.
+    marker: PhantomData<fn() -> (K, V)>,
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<K, V> HasTypeDef for Mapping<K, V> {
+    fn type_def() -> TypeDef {
+        TypeDefStruct::new(vec![NamedField::of::<Key>("key")]).into()
+    }
+}
+
+/// No need to forward flush since the entries are not cached.
+impl<K, V> Flush for Mapping<K, V> {}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<K, V> HasLayout for Mapping<K, V>
+where
+    K: Metadata + 'static,
+    V: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(Self::meta_type(), vec![LayoutField::of("key", &self.key)])
+            .into()
+    }
+}
+
+impl<K, V> AllocateUsing for Mapping<K, V> {
+    #[inline]
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            key: alloc.alloc(1),
+            marker: PhantomData,
+        }
+    }
+}
+
+impl<K, V> Initialize for Mapping<K, V> {
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline(always)]
+    fn initialize(&mut self, _args: Self::Args) {}
+}
+
+impl<K, V> Mapping<K, V>
+where
+    K: Encode,
+{
+    /// Returns the key of the cell storing the value of the key.
+    fn entry_key(&self, key: &K) -> Key {
+        hashed_entry_key::<Blake2x256, K>(self.key, key)
+    }
+
+    /// Returns `true` if there is a value stored for the key.
+    ///
+    /// # Note
+    ///
+    /// This does not decode the stored value.
+    pub fn contains(&self, key: &K) -> bool {
+        env::get_contract_storage::<()>(self.entry_key(key)).is_some()
+    }
+
+    /// Removes the value stored for the key without returning it.
+    pub fn remove(&mut self, key: &K) {
+        env::clear_contract_storage(self.entry_key(key))
+    }
+}
+
+impl<K, V> Mapping<K, V>
+where
+    K: Encode,
+    V: Encode,
+{
+    /// Stores the value for the key.
+    ///
+    /// # Note
+    ///
+    /// Overwrites the value previously stored for the key without reading it.
+    pub fn insert(&mut self, key: &K, val: &V) {
+        env::set_contract_storage(self.entry_key(key), val)
+    }
+}
+
+impl<K, V> Mapping<K, V>
+where
+    K: Encode,
+    V: Decode,
+{
+    /// Returns the value stored for the key if any.
+    ///
+    /// # Panics
+    ///
+    /// If the stored value cannot be decoded as `V`.
+    pub fn get(&self, key: &K) -> Option<V> {
+        env::get_contract_storage::<V>(self.entry_key(key))
+            .map(|result| result.ink_expect("could not decode V from mapping entry"))
+    }
+
+    /// Removes the value stored for the key and returns it if any.
+    ///
+    /// # Panics
+    ///
+    /// If the stored value cannot be decoded as `V`.
+    pub fn take(&mut self, key: &K) -> Option<V> {
+        let entry_key = self.entry_key(key);
+        let val = env::get_contract_storage::<V>(entry_key)
+            .map(|result| result.ink_expect("could not decode V from mapping entry"))?;
+        env::clear_contract_storage(entry_key);
+        Some(val)
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A keyed store without any bookkeeping.
+//!
+//! Stores every value in its own cell at a key derived from the hash of its
+//! encoded key. This makes it the cheapest map in terms of storage accesses
+//! at the cost of not knowing its length or its keys.
+
+#[cfg(test)]
+mod tests;
+
+mod impls;
+
+pub use self::impls::Mapping;
@@ -0,0 +1,100 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+    storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+        chunk::LazyHashMap,
+        Mapping,
+    },
+};
+use ink_primitives::Key;
+
+fn new_alloc() -> BumpAlloc {
+    unsafe { BumpAlloc::from_raw_parts(Key([0x0; 32])) }
+}
+
+fn new_mapping<K, V>(alloc: &mut BumpAlloc) -> Mapping<K, V> {
+    unsafe { Mapping::allocate_using(alloc).initialize_into(()) }
+}
+
+#[test]
+fn insert_get_contains() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut alloc = new_alloc();
+        let mut map = new_mapping::<u8, i32>(&mut alloc);
+        assert_eq!(map.get(&1), None);
+        assert!(!map.contains(&1));
+        map.insert(&1, &10);
+        map.insert(&2, &20);
+        assert_eq!(map.get(&1), Some(10));
+        assert_eq!(map.get(&2), Some(20));
+        assert!(map.contains(&1));
+        // Inserting overwrites.
+        map.insert(&1, &11);
+        assert_eq!(map.get(&1), Some(11));
+        Ok(())
+    })
+}
+
+#[test]
+fn take_remove() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut alloc = new_alloc();
+        let mut map = new_mapping::<u8, i32>(&mut alloc);
+        map.insert(&1, &10);
+        map.insert(&2, &20);
+        assert_eq!(map.take(&1), Some(10));
+        assert_eq!(map.take(&1), None);
+        assert!(!map.contains(&1));
+        map.remove(&2);
+        assert_eq!(map.get(&2), None);
+        // Removing a missing key is a no-op.
+        map.remove(&3);
+        Ok(())
+    })
+}
+
+#[test]
+fn entries_are_hashed_and_separate() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut alloc = new_alloc();
+        let mut a = new_mapping::<u8, i32>(&mut alloc);
+        let mut b = new_mapping::<u8, i32>(&mut alloc);
+        a.insert(&1, &10);
+        b.insert(&1, &20);
+        assert_eq!(a.get(&1), Some(10));
+        assert_eq!(b.get(&1), Some(20));
+        // The entry of key `1` of `a` is stored at the same key as the entry
+        // of a lazy hash map at the first allocated cell.
+        let entry_key = unsafe {
+            LazyHashMap::<u8, i32>::allocate_using(&mut new_alloc()).entry_key(&1)
+        };
+        assert_eq!(env::get_contract_storage::<i32>(entry_key), Some(Ok(10)));
+        // The mapping does not write any bookkeeping cells.
+        assert_eq!(
+            env::test::count_used_storage_cells::<env::DefaultEnvTypes>(
+                &env::account_id::<env::DefaultEnvTypes>()?
+            )?,
+            2
+        );
+        Ok(())
+    })
+}
@@ -12,9 +12,17 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+mod internal;
+
 pub mod binary_heap;
+pub mod bitstash;
 pub mod bitvec;
 pub mod btree_map;
+pub mod enumerable_map;
 pub mod hash_map;
+pub mod mapping;
+pub mod nested_map;
+pub mod priority_queue;
+pub mod sorted_vec;
 pub mod stash;
 pub mod vec;
@@ -0,0 +1,289 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::hash::Hash;
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use scale::{
+    Codec,
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    self,
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    Flush,
+    InkExpect as _,
+};
+
+/// A hash map with composite keys `(K1, K2)` that can enumerate entries by prefix.
+///
+/// # Note
+///
+/// The entries of a prefix are enumerated in no particular order since
+/// removing an entry moves the last entry of its prefix into its place.
+#[derive(Debug)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct NestedMap<K1, K2, V> {
+    /// The values together with the indices of their second keys in their prefix.
+    entries: storage::HashMap<(K1, K2), Entry<V>>,
+    /// The second keys of every prefix indexed by their position in the prefix.
+    prefix_keys: storage::HashMap<(K1, u32), K2>,
+    /// The number of entries of every non-empty prefix.
+    prefix_lens: storage::HashMap<K1, u32>,
+}
+
+/// A value of the map together with the index of its second key in its prefix.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+struct Entry<V> {
+    /// The value.
+    val: V,
+    /// The index of the second key in the prefix of the first key.
+    index: u32,
+}
+
+impl<V> Flush for Entry<V>
+where
+    V: Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.val.flush()
+    }
+}
+
+impl<K1, K2, V> Flush for NestedMap<K1, K2, V>
+where
+    K1: Encode + Flush,
+    K2: Encode + Flush,
+    V: Encode + Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.entries.flush();
+        self.prefix_keys.flush();
+        self.prefix_lens.flush();
+    }
+}
+
+impl<K1, K2, V> NestedMap<K1, K2, V>
+where
+    K1: Encode + Flush,
+    K2: Encode + Flush,
+    V: Encode + Flush,
+{
+    /// Writes the cached state of the map back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the map so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.entries.invalidate_cache();
+        self.prefix_keys.invalidate_cache();
+        self.prefix_lens.invalidate_cache();
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<K1, K2, V> HasLayout for NestedMap<K1, K2, V>
+where
+    K1: Metadata + 'static,
+    K2: Metadata + 'static,
+    V: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(
+            Self::meta_type(),
+            vec![
+                LayoutField::of("entries", &self.entries),
+                LayoutField::of("prefix_keys", &self.prefix_keys),
+                LayoutField::of("prefix_lens", &self.prefix_lens),
+            ],
+        )
+        .into()
+    }
+}
+
+impl<K1, K2, V> Encode for NestedMap<K1, K2, V> {
+    fn encode_to<W: scale::Output>(&self, dest: &mut W) {
+        self.entries.encode_to(dest);
+        self.prefix_keys.encode_to(dest);
+        self.prefix_lens.encode_to(dest);
+    }
+}
+
+impl<K1, K2, V> Decode for NestedMap<K1, K2, V> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let entries = storage::HashMap::decode(input)?;
+        let prefix_keys = storage::HashMap::decode(input)?;
+        let prefix_lens = storage::HashMap::decode(input)?;
+        Ok(Self {
+            entries,
+            prefix_keys,
+            prefix_lens,
+        })
+    }
+}
+
+impl<K1, K2, V> AllocateUsing for NestedMap<K1, K2, V> {
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            entries: storage::HashMap::allocate_using(alloc),
+            prefix_keys: storage::HashMap::allocate_using(alloc),
+            prefix_lens: storage::HashMap::allocate_using(alloc),
+        }
+    }
+}
+
+impl<K1, K2, V> Initialize for NestedMap<K1, K2, V> {
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline]
+    fn initialize(&mut self, _args: Self::Args) {
+        self.entries.initialize(());
+        self.prefix_keys.initialize(());
+        self.prefix_lens.initialize(());
+    }
+}
+
+impl<K1, K2, V> NestedMap<K1, K2, V> {
+    /// Returns the number of entries in the map.
+    pub fn len(&self) -> u32 {
+        self.entries.len()
+    }
+
+    /// Returns `true` if the map contains no entries.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<K1, K2, V> NestedMap<K1, K2, V>
+where
+    K1: Codec + Hash + Eq + Clone,
+    K2: Codec + Hash + Eq + Clone,
+    V: Codec,
+{
+    /// Returns the value of the entry with the given keys if any.
+    pub fn get(&self, k1: &K1, k2: &K2) -> Option<&V> {
+        self.entries
+            .get(&(k1.clone(), k2.clone()))
+            .map(|entry| &entry.val)
+    }
+
+    /// Returns a mutable reference to the value of the entry with the given keys if any.
+    pub fn get_mut(&mut self, k1: &K1, k2: &K2) -> Option<&mut V> {
+        self.entries
+            .get_mut(&(k1.clone(), k2.clone()))
+            .map(|entry| &mut entry.val)
+    }
+
+    /// Returns `true` if there is an entry with the given keys.
+    pub fn contains_key(&self, k1: &K1, k2: &K2) -> bool {
+        self.get(k1, k2).is_some()
+    }
+
+    /// Returns the number of entries whose first key is `k1`.
+    pub fn prefix_len(&self, k1: &K1) -> u32 {
+        self.prefix_lens.get(k1).copied().unwrap_or(0)
+    }
+
+    /// Returns an iterator over the second keys and values of all entries
+    /// whose first key is `k1`.
+    pub fn prefix_iter<'a>(
+        &'a self,
+        k1: &'a K1,
+    ) -> impl Iterator<Item = (&'a K2, &'a V)> + 'a {
+        (0..self.prefix_len(k1)).map(move |index| {
+            let k2 = self
+                .prefix_keys
+                .get(&(k1.clone(), index))
+                .ink_expect("missing prefix key");
+            let val = self.get(k1, k2).ink_expect("missing entry of prefix key");
+            (k2, val)
+        })
+    }
+
+    /// Inserts the value for the given keys.
+    ///
+    /// Returns the previous value of the entry if any.
+    pub fn insert(&mut self, k1: K1, k2: K2, val: V) -> Option<V> {
+        let key = (k1, k2);
+        if let Some(entry) = self.entries.get_mut(&key) {
+            return Some(core::mem::replace(&mut entry.val, val))
+        }
+        let (k1, k2) = key;
+        let index = self.prefix_len(&k1);
+        self.prefix_lens.insert(k1.clone(), index + 1);
+        self.prefix_keys.insert((k1.clone(), index), k2.clone());
+        self.entries.insert((k1, k2), Entry { val, index });
+        None
+    }
+
+    /// Removes the entry with the given keys and returns its value if any.
+    ///
+    /// # Note
+    ///
+    /// The last entry of the prefix takes the place of the removed entry.
+    pub fn remove(&mut self, k1: &K1, k2: &K2) -> Option<V> {
+        let Entry { val, index } = self.entries.remove(&(k1.clone(), k2.clone()))?;
+        let last = self.prefix_len(k1) - 1;
+        let last_k2 = self
+            .prefix_keys
+            .remove(&(k1.clone(), last))
+            .ink_expect("missing last prefix key");
+        if index != last {
+            // Move the last second key of the prefix into the vacated place.
+            self.entries
+                .get_mut(&(k1.clone(), last_k2.clone()))
+                .ink_expect("missing entry of moved prefix key")
+                .index = index;
+            self.prefix_keys.insert((k1.clone(), index), last_k2);
+        }
+        if last == 0 {
+            self.prefix_lens.remove(k1);
+        } else {
+            self.prefix_lens.insert(k1.clone(), last);
+        }
+        Some(val)
+    }
+}
@@ -0,0 +1,26 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A hash map with composite keys that can enumerate entries by prefix.
+//!
+//! Maps pairs of keys `(K1, K2)` to values and keeps an index of the second
+//! keys for every first key. This allows to enumerate all entries that share
+//! the same first key, e.g. all allowances `(owner, spender)` of an owner.
+
+#[cfg(test)]
+mod tests;
+
+mod impls;
+
+pub use self::impls::NestedMap;
@@ -0,0 +1,102 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+    storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+        NestedMap,
+    },
+};
+use ink_primitives::Key;
+
+fn empty_map() -> NestedMap<u8, u8, i32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        NestedMap::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Returns the sorted entries with the given first key.
+fn prefix_entries(map: &NestedMap<u8, u8, i32>, k1: u8) -> Vec<(u8, i32)> {
+    let mut entries = map
+        .prefix_iter(&k1)
+        .map(|(&k2, &val)| (k2, val))
+        .collect::<Vec<_>>();
+    entries.sort();
+    assert_eq!(map.prefix_len(&k1), entries.len() as u32);
+    entries
+}
+
+#[test]
+fn init() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let map = empty_map();
+        assert!(map.is_empty());
+        assert_eq!(map.get(&1, &2), None);
+        assert_eq!(map.prefix_len(&1), 0);
+        assert_eq!(map.prefix_iter(&1).next(), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn insert_get() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = empty_map();
+        assert_eq!(map.insert(1, 1, 11), None);
+        assert_eq!(map.insert(1, 2, 12), None);
+        assert_eq!(map.insert(2, 1, 21), None);
+        assert_eq!(map.insert(1, 2, 120), Some(12));
+        *map.get_mut(&2, &1).unwrap() += 1;
+        assert_eq!(map.len(), 3);
+        assert_eq!(map.get(&1, &2), Some(&120));
+        assert!(map.contains_key(&2, &1));
+        assert!(!map.contains_key(&2, &2));
+        assert_eq!(prefix_entries(&map, 1), vec![(1, 11), (2, 120)]);
+        assert_eq!(prefix_entries(&map, 2), vec![(1, 22)]);
+        assert_eq!(prefix_entries(&map, 3), vec![]);
+        Ok(())
+    })
+}
+
+#[test]
+fn remove() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut map = empty_map();
+        for k2 in 0..4 {
+            map.insert(7, k2, i32::from(k2));
+        }
+        map.insert(8, 0, 80);
+        // Removing from the middle of a prefix moves its last entry.
+        assert_eq!(map.remove(&7, &1), Some(1));
+        assert_eq!(map.remove(&7, &1), None);
+        assert_eq!(prefix_entries(&map, 7), vec![(0, 0), (2, 2), (3, 3)]);
+        assert_eq!(map.remove(&7, &3), Some(3));
+        assert_eq!(map.remove(&7, &0), Some(0));
+        assert_eq!(map.remove(&7, &2), Some(2));
+        assert_eq!(prefix_entries(&map, 7), vec![]);
+        assert_eq!(prefix_entries(&map, 8), vec![(0, 80)]);
+        // Prefixes can be filled again after they have been emptied.
+        map.insert(7, 5, 5);
+        assert_eq!(prefix_entries(&map, 7), vec![(5, 5)]);
+        assert_eq!(map.len(), 2);
+        Ok(())
+    })
+}
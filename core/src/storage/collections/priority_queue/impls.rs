@@ -0,0 +1,314 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use scale::{
+    Codec,
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    self,
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    Flush,
+    InkExpect as _,
+};
+
+/// An addressable priority queue.
+///
+/// Pushing an element returns a handle to it that can be used to
+/// update or remove the element later on. This allows for example
+/// to adjust the priorities of scheduled jobs.
+///
+/// # Note
+///
+/// The queue depends on `Ord` and is a max-queue by default. In order to
+/// make it a min-queue implement the `Ord` trait explicitly on the type
+/// which is stored in the queue.
+#[derive(Debug)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct PriorityQueue<T> {
+    /// The handles of the queued elements in heap order.
+    heap: storage::Vec<u32>,
+    /// The queued elements indexed by their handles.
+    nodes: storage::Stash<Node<T>>,
+}
+
+/// A queued element together with its position in the heap.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+struct Node<T> {
+    /// The queued element.
+    val: T,
+    /// The position of the element's handle in the heap.
+    pos: u32,
+}
+
+impl<T> Flush for Node<T>
+where
+    T: Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.val.flush()
+    }
+}
+
+impl<T> Flush for PriorityQueue<T>
+where
+    T: Encode + Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.heap.flush();
+        self.nodes.flush();
+    }
+}
+
+impl<T> PriorityQueue<T>
+where
+    T: Encode + Flush,
+{
+    /// Writes the cached state of the queue back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the queue so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.heap.invalidate_cache();
+        self.nodes.invalidate_cache();
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasLayout for PriorityQueue<T>
+where
+    T: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(
+            Self::meta_type(),
+            vec![
+                LayoutField::of("heap", &self.heap),
+                LayoutField::of("nodes", &self.nodes),
+            ],
+        )
+        .into()
+    }
+}
+
+impl<T> Encode for PriorityQueue<T> {
+    fn encode_to<W: scale::Output>(&self, dest: &mut W) {
+        self.heap.encode_to(dest);
+        self.nodes.encode_to(dest);
+    }
+}
+
+impl<T> Decode for PriorityQueue<T> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let heap = storage::Vec::decode(input)?;
+        let nodes = storage::Stash::decode(input)?;
+        Ok(Self { heap, nodes })
+    }
+}
+
+impl<T> AllocateUsing for PriorityQueue<T> {
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            heap: storage::Vec::allocate_using(alloc),
+            nodes: storage::Stash::allocate_using(alloc),
+        }
+    }
+}
+
+impl<T> Initialize for PriorityQueue<T> {
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline]
+    fn initialize(&mut self, _args: Self::Args) {
+        self.heap.initialize(());
+        self.nodes.initialize(());
+    }
+}
+
+impl<T> PriorityQueue<T> {
+    /// Returns the number of elements in the queue.
+    pub fn len(&self) -> u32 {
+        self.heap.len()
+    }
+
+    /// Returns `true` if the queue contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> PriorityQueue<T>
+where
+    T: Codec + Ord,
+{
+    /// Returns the element with the given handle if any.
+    pub fn get(&self, handle: u32) -> Option<&T> {
+        self.nodes.get(handle).map(|node| &node.val)
+    }
+
+    /// Returns the greatest element and its handle if the queue is not empty.
+    pub fn peek(&self) -> Option<(u32, &T)> {
+        self.heap
+            .get(0)
+            .map(|&handle| (handle, self.val_of(handle)))
+    }
+
+    /// Pushes the element into the queue and returns its handle.
+    ///
+    /// Complexity is `O(log(n))`.
+    pub fn push(&mut self, val: T) -> u32 {
+        let pos = self.len();
+        let handle = self.nodes.put(Node { val, pos });
+        self.heap.push(handle);
+        self.sift_up(pos);
+        handle
+    }
+
+    /// Removes the greatest element from the queue and returns it with its handle.
+    ///
+    /// Complexity is `O(log(n))`.
+    pub fn pop(&mut self) -> Option<(u32, T)> {
+        let handle = *self.heap.get(0)?;
+        self.remove(handle).map(|val| (handle, val))
+    }
+
+    /// Replaces the element with the given handle and restores the queue order.
+    ///
+    /// Returns the replaced element or `None` if there is no element with
+    /// the given handle in which case the queue is left unchanged.
+    ///
+    /// Complexity is `O(log(n))`.
+    pub fn update(&mut self, handle: u32, val: T) -> Option<T> {
+        let node = self.nodes.get_mut(handle)?;
+        let old = core::mem::replace(&mut node.val, val);
+        let pos = node.pos;
+        let pos = self.sift_up(pos);
+        self.sift_down(pos);
+        Some(old)
+    }
+
+    /// Removes the element with the given handle from the queue and returns it.
+    ///
+    /// Complexity is `O(log(n))`.
+    pub fn remove(&mut self, handle: u32) -> Option<T> {
+        let pos = self.nodes.get(handle)?.pos;
+        let last = self.len() - 1;
+        if pos != last {
+            self.swap(pos, last);
+        }
+        self.heap.pop();
+        let node = self.nodes.take(handle).ink_expect("missing queued node");
+        if pos != last {
+            let pos = self.sift_up(pos);
+            self.sift_down(pos);
+        }
+        Some(node.val)
+    }
+
+    /// Returns the handle stored at the `pos`-th position of the heap.
+    fn handle_at(&self, pos: u32) -> u32 {
+        *self.heap.get(pos).ink_expect("missing heap handle")
+    }
+
+    /// Returns the element with the given handle.
+    fn val_of(&self, handle: u32) -> &T {
+        &self.nodes.get(handle).ink_expect("missing queued node").val
+    }
+
+    /// Returns `true` if the element at position `a` is greater than the one at `b`.
+    fn is_greater(&self, a: u32, b: u32) -> bool {
+        self.val_of(self.handle_at(a)) > self.val_of(self.handle_at(b))
+    }
+
+    /// Swaps the elements at positions `a` and `b` of the heap.
+    fn swap(&mut self, a: u32, b: u32) {
+        self.heap.swap(a, b);
+        for &pos in &[a, b] {
+            let handle = self.handle_at(pos);
+            self.nodes
+                .get_mut(handle)
+                .ink_expect("missing queued node")
+                .pos = pos;
+        }
+    }
+
+    /// Moves the element at position `pos` up until its parent is not less.
+    ///
+    /// Returns the new position of the element.
+    fn sift_up(&mut self, mut pos: u32) -> u32 {
+        while pos > 0 {
+            let parent = (pos - 1) / 2;
+            if !self.is_greater(pos, parent) {
+                break
+            }
+            self.swap(pos, parent);
+            pos = parent;
+        }
+        pos
+    }
+
+    /// Moves the element at position `pos` down until no child is greater.
+    fn sift_down(&mut self, mut pos: u32) {
+        let len = self.len();
+        loop {
+            let left = pos.saturating_mul(2).saturating_add(1);
+            let right = left.saturating_add(1);
+            let mut greatest = pos;
+            if left < len && self.is_greater(left, greatest) {
+                greatest = left;
+            }
+            if right < len && self.is_greater(right, greatest) {
+                greatest = right;
+            }
+            if greatest == pos {
+                return
+            }
+            self.swap(pos, greatest);
+            pos = greatest;
+        }
+    }
+}
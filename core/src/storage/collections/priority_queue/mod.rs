@@ -0,0 +1,28 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! An addressable priority queue collection.
+//!
+//! The queue depends on `Ord` and is a max-queue just like the binary heap.
+//! Every pushed element is identified by a handle that stays valid until the
+//! element is popped or removed, so that it can be updated or removed later.
+//!
+//! Provides `O(log(n))` push, pop, update and remove operations.
+
+#[cfg(test)]
+mod tests;
+
+mod impls;
+
+pub use self::impls::PriorityQueue;
@@ -0,0 +1,116 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+    storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+        PriorityQueue,
+    },
+};
+use ink_primitives::Key;
+
+fn empty_queue() -> PriorityQueue<i32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        PriorityQueue::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Pops all elements of the queue in order.
+fn drain(queue: &mut PriorityQueue<i32>) -> Vec<i32> {
+    let mut vals = Vec::new();
+    while let Some((_, val)) = queue.pop() {
+        vals.push(val);
+    }
+    vals
+}
+
+#[test]
+fn init() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut queue = empty_queue();
+        assert_eq!(queue.len(), 0);
+        assert!(queue.is_empty());
+        assert_eq!(queue.peek(), None);
+        assert_eq!(queue.pop(), None);
+        assert_eq!(queue.get(0), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn push_pop() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut queue = empty_queue();
+        let vals = [5, 42, -1, 1337, 77, 0, 42];
+        let handles = vals.iter().map(|&val| queue.push(val)).collect::<Vec<_>>();
+        assert_eq!(queue.len(), vals.len() as u32);
+        for (&handle, val) in handles.iter().zip(&vals) {
+            assert_eq!(queue.get(handle), Some(val));
+        }
+        assert_eq!(queue.peek(), Some((handles[3], &1337)));
+        assert_eq!(drain(&mut queue), vec![1337, 77, 42, 42, 5, 0, -1]);
+        assert!(queue.is_empty());
+        Ok(())
+    })
+}
+
+#[test]
+fn update() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut queue = empty_queue();
+        let handles = [10, 20, 30, 40, 50]
+            .iter()
+            .map(|&val| queue.push(val))
+            .collect::<Vec<_>>();
+        // Increase a priority.
+        assert_eq!(queue.update(handles[0], 60), Some(10));
+        assert_eq!(queue.peek(), Some((handles[0], &60)));
+        // Decrease a priority.
+        assert_eq!(queue.update(handles[0], 25), Some(60));
+        assert_eq!(queue.update(handles[4], 5), Some(50));
+        assert_eq!(queue.peek(), Some((handles[3], &40)));
+        assert_eq!(queue.get(handles[4]), Some(&5));
+        // Unknown handles leave the queue unchanged.
+        assert_eq!(queue.update(42, 100), None);
+        assert_eq!(drain(&mut queue), vec![40, 30, 25, 20, 5]);
+        Ok(())
+    })
+}
+
+#[test]
+fn remove() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut queue = empty_queue();
+        let handles = [3, 1, 4, 1, 5, 9, 2, 6]
+            .iter()
+            .map(|&val| queue.push(val))
+            .collect::<Vec<_>>();
+        assert_eq!(queue.remove(handles[5]), Some(9));
+        assert_eq!(queue.remove(handles[5]), None);
+        assert_eq!(queue.remove(handles[1]), Some(1));
+        assert_eq!(queue.get(handles[1]), None);
+        assert_eq!(queue.len(), 6);
+        // Handles of removed elements are reused.
+        assert_eq!(queue.push(7), handles[1]);
+        assert_eq!(drain(&mut queue), vec![7, 6, 5, 4, 3, 2, 1]);
+        Ok(())
+    })
+}
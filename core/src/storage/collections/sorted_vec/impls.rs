@@ -0,0 +1,266 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::cmp::Ordering;
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use scale::{
+    Codec,
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    self,
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    vec::Iter,
+    Flush,
+    InkExpect as _,
+};
+
+/// A storage vector that keeps its elements sorted in ascending order.
+///
+/// # Note
+///
+/// All operations only touch the cells visited by the binary search
+/// and the cells of the shifted elements.
+#[derive(Debug)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct SortedVec<T> {
+    /// The sorted elements.
+    elems: storage::Vec<T>,
+}
+
+impl<T> Flush for SortedVec<T>
+where
+    T: Encode + Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.elems.flush()
+    }
+}
+
+impl<T> SortedVec<T>
+where
+    T: Encode + Flush,
+{
+    /// Writes the cached state of the sorted vector back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the sorted vector so that it is loaded again
+    /// from the contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.elems.invalidate_cache()
+    }
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasLayout for SortedVec<T>
+where
+    T: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(
+            Self::meta_type(),
+            vec![LayoutField::of("elems", &self.elems)],
+        )
+        .into()
+    }
+}
+
+impl<T> Encode for SortedVec<T> {
+    fn encode_to<W: scale::Output>(&self, dest: &mut W) {
+        self.elems.encode_to(dest)
+    }
+}
+
+impl<T> Decode for SortedVec<T> {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        storage::Vec::decode(input).map(|elems| Self { elems })
+    }
+}
+
+impl<T> AllocateUsing for SortedVec<T> {
+    #[inline]
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            elems: storage::Vec::allocate_using(alloc),
+        }
+    }
+}
+
+impl<T> Initialize for SortedVec<T> {
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline]
+    fn initialize(&mut self, _args: Self::Args) {
+        self.elems.initialize(());
+    }
+}
+
+impl<T> SortedVec<T> {
+    /// Returns the number of elements in the sorted vector.
+    pub fn len(&self) -> u32 {
+        self.elems.len()
+    }
+
+    /// Returns `true` if the sorted vector contains no elements.
+    pub fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+}
+
+impl<T> SortedVec<T>
+where
+    T: Codec + Ord,
+{
+    /// Returns an iterator over the elements in ascending order.
+    pub fn iter(&self) -> Iter<'_, T> {
+        self.elems.iter()
+    }
+
+    /// Returns the `n`-th smallest element if any.
+    pub fn get(&self, n: u32) -> Option<&T> {
+        self.elems.get(n)
+    }
+
+    /// Returns the smallest element if any.
+    pub fn first(&self) -> Option<&T> {
+        self.get(0)
+    }
+
+    /// Returns the greatest element if any.
+    pub fn last(&self) -> Option<&T> {
+        self.len().checked_sub(1).and_then(|n| self.get(n))
+    }
+
+    /// Returns the `n`-th element expecting it to be within bounds.
+    fn elem_at(&self, n: u32) -> &T {
+        self.get(n).ink_expect("missing sorted vector element")
+    }
+
+    /// Returns the index of the first element for which `pred` returns `false`.
+    ///
+    /// The elements must be partitioned with respect to `pred`.
+    fn partition_point<F>(&self, pred: F) -> u32
+    where
+        F: Fn(&T) -> bool,
+    {
+        let (mut lo, mut hi) = (0, self.len());
+        while lo < hi {
+            let mid = lo + (hi - lo) / 2;
+            if pred(self.elem_at(mid)) {
+                lo = mid + 1;
+            } else {
+                hi = mid;
+            }
+        }
+        lo
+    }
+
+    /// Binary searches the sorted vector for the given value.
+    ///
+    /// Returns `Ok` with the index of a matching element if any,
+    /// otherwise `Err` with the index where the value could be inserted.
+    pub fn binary_search(&self, val: &T) -> Result<u32, u32> {
+        let n = self.partition_point(|elem| elem < val);
+        match self.get(n).map(|elem| elem.cmp(val)) {
+            Some(Ordering::Equal) => Ok(n),
+            _ => Err(n),
+        }
+    }
+
+    /// Returns `true` if the sorted vector contains the given value.
+    pub fn contains(&self, val: &T) -> bool {
+        self.binary_search(val).is_ok()
+    }
+
+    /// Returns the greatest element that is less than or equal to the value
+    /// together with its index if any.
+    pub fn le_neighbor(&self, val: &T) -> Option<(u32, &T)> {
+        let n = self.partition_point(|elem| elem <= val).checked_sub(1)?;
+        Some((n, self.elem_at(n)))
+    }
+
+    /// Returns the smallest element that is greater than or equal to the value
+    /// together with its index if any.
+    pub fn ge_neighbor(&self, val: &T) -> Option<(u32, &T)> {
+        let n = self.partition_point(|elem| elem < val);
+        self.get(n).map(|elem| (n, elem))
+    }
+
+    /// Inserts the value keeping the elements sorted and returns its index.
+    ///
+    /// The value is inserted after all elements equal to it.
+    ///
+    /// Complexity is `O(log(n))` plus the number of shifted elements.
+    pub fn insert_sorted(&mut self, val: T) -> u32 {
+        let n = self.partition_point(|elem| elem <= &val);
+        self.elems.push(val);
+        // Shift the greater elements towards the back.
+        for i in (n..self.len() - 1).rev() {
+            self.elems.swap(i, i + 1);
+        }
+        n
+    }
+
+    /// Removes the `n`-th element and returns it.
+    ///
+    /// Returns `None` and does not remove if `n` is out of bounds.
+    ///
+    /// Complexity is the number of shifted elements.
+    pub fn remove(&mut self, n: u32) -> Option<T> {
+        if n >= self.len() {
+            return None
+        }
+        // Shift the greater elements towards the front.
+        for i in n..self.len() - 1 {
+            self.elems.swap(i, i + 1);
+        }
+        self.elems.pop()
+    }
+
+    /// Removes an element equal to the given value and returns it if any.
+    pub fn remove_value(&mut self, val: &T) -> Option<T> {
+        let n = self.binary_search(val).ok()?;
+        self.remove(n)
+    }
+}
@@ -0,0 +1,28 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! A storage vector that keeps its elements sorted.
+//!
+//! Elements are located by binary search and inserted or removed by
+//! shifting their successors. This makes it suitable for order books
+//! and other ranked data that needs neighbor queries.
+//!
+//! Provides `O(log(n))` lookups and `O(log(n) + shift)` insertions and removals.
+
+#[cfg(test)]
+mod tests;
+
+mod impls;
+
+pub use self::impls::SortedVec;
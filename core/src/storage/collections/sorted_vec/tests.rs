@@ -0,0 +1,119 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::{
+    env,
+    env::Result,
+    storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+        SortedVec,
+    },
+};
+use ink_primitives::Key;
+
+fn empty_vec() -> SortedVec<i32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        SortedVec::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Returns a sorted vector with the elements `[-5, 3, 3, 10, 42]`.
+fn filled_vec() -> SortedVec<i32> {
+    let mut vec = empty_vec();
+    for &val in &[10, 3, 42, -5, 3] {
+        vec.insert_sorted(val);
+    }
+    vec
+}
+
+#[test]
+fn init() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let vec = empty_vec();
+        assert!(vec.is_empty());
+        assert_eq!(vec.first(), None);
+        assert_eq!(vec.last(), None);
+        assert_eq!(vec.binary_search(&1), Err(0));
+        assert_eq!(vec.le_neighbor(&1), None);
+        assert_eq!(vec.ge_neighbor(&1), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn insert_sorted() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = filled_vec();
+        assert_eq!(
+            vec.iter().copied().collect::<Vec<_>>(),
+            vec![-5, 3, 3, 10, 42]
+        );
+        assert_eq!(vec.insert_sorted(3), 3);
+        assert_eq!(vec.insert_sorted(100), 6);
+        assert_eq!(vec.insert_sorted(-100), 0);
+        assert_eq!(
+            vec.iter().copied().collect::<Vec<_>>(),
+            vec![-100, -5, 3, 3, 3, 10, 42, 100]
+        );
+        assert_eq!((vec.first(), vec.last()), (Some(&-100), Some(&100)));
+        Ok(())
+    })
+}
+
+#[test]
+fn search() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let vec = filled_vec();
+        assert_eq!(vec.binary_search(&10), Ok(3));
+        assert_eq!(vec.binary_search(&4), Err(3));
+        assert_eq!(vec.binary_search(&50), Err(5));
+        assert!(vec.contains(&-5));
+        assert!(!vec.contains(&0));
+        Ok(())
+    })
+}
+
+#[test]
+fn neighbors() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let vec = filled_vec();
+        assert_eq!(vec.le_neighbor(&3), Some((2, &3)));
+        assert_eq!(vec.ge_neighbor(&3), Some((1, &3)));
+        assert_eq!(vec.le_neighbor(&9), Some((2, &3)));
+        assert_eq!(vec.ge_neighbor(&9), Some((3, &10)));
+        assert_eq!(vec.le_neighbor(&-6), None);
+        assert_eq!(vec.ge_neighbor(&-6), Some((0, &-5)));
+        assert_eq!(vec.le_neighbor(&43), Some((4, &42)));
+        assert_eq!(vec.ge_neighbor(&43), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn remove() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = filled_vec();
+        assert_eq!(vec.remove(5), None);
+        assert_eq!(vec.remove(1), Some(3));
+        assert_eq!(vec.remove_value(&42), Some(42));
+        assert_eq!(vec.remove_value(&42), None);
+        assert_eq!(vec.iter().copied().collect::<Vec<_>>(), vec![-5, 3, 10]);
+        Ok(())
+    })
+}
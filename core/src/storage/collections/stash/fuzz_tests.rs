@@ -0,0 +1,99 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::{
+    alloc::{
+        AllocateUsing,
+        BumpAlloc,
+        Initialize,
+    },
+    test_utils::{
+        self,
+        bounded_index,
+    },
+    Stash,
+};
+use ink_primitives::Key;
+use quickcheck::{
+    Arbitrary,
+    Gen,
+};
+use rand::Rng;
+use std::collections::BTreeMap;
+
+/// An operation on a storage stash.
+#[derive(Debug, Clone)]
+enum Op {
+    Put(i32),
+    Take(u32),
+    Get(u32),
+}
+
+impl Arbitrary for Op {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        match g.gen_range(0, 5) {
+            0 | 1 => Op::Put(g.gen()),
+            2 | 3 => Op::Take(g.gen()),
+            _ => Op::Get(g.gen()),
+        }
+    }
+}
+
+/// Returns an empty storage stash.
+fn empty_stash() -> Stash<i32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        Stash::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Applies the operations to a storage stash and a `std` map from the
+/// stash indices to their elements and asserts that both always agree.
+///
+/// Also asserts that put elements are always stored at a vacant index
+/// below the maximum number of elements stored at the same time.
+fn cross_check(ops: Vec<Op>) -> bool {
+    test_utils::run_isolated(|| {
+        let mut stash = empty_stash();
+        let mut model = BTreeMap::new();
+        let mut max_len = 0;
+        for op in ops {
+            match op {
+                Op::Put(value) => {
+                    let n = stash.put(value);
+                    assert_eq!(model.insert(n, value), None);
+                    max_len = max_len.max(model.len() as u32);
+                    assert!(n < max_len);
+                }
+                Op::Take(n) => {
+                    let n = bounded_index(n, max_len);
+                    assert_eq!(stash.take(n), model.remove(&n));
+                }
+                Op::Get(n) => {
+                    let n = bounded_index(n, max_len);
+                    assert_eq!(stash.get(n), model.get(&n));
+                }
+            }
+            assert_eq!(stash.len(), model.len() as u32);
+            assert_eq!(stash.max_len(), max_len);
+            stash.debug_assert_invariants();
+        }
+        assert!(stash.iter().eq(model.iter().map(|(n, value)| (*n, value))));
+    })
+}
+
+#[test]
+fn stash_behaves_like_std_map() {
+    test_utils::check(cross_check)
+}
@@ -12,7 +12,9 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use super::StashSnapshot;
 use crate::{
+    env,
     ink_core,
     storage::{
         self,
@@ -22,6 +24,7 @@ use crate::{
             Initialize,
         },
         chunk::SyncChunk,
+        collections::internal::InternalError,
         Flush,
     },
 };
@@ -67,6 +70,13 @@ pub struct Stash<T> {
     header: storage::Value<StashHeader>,
     /// The entries of the stash.
     entries: SyncChunk<Entry<T>>,
+    /// Marks the occupied entries of the stash.
+    ///
+    /// Allows to skip vacant entries without loading and decoding them.
+    /// Stashes without vacant entries do not benefit from this and pay for
+    /// additional reads of the bitmap upon iteration and additional writes
+    /// of it upon `put` and `take`.
+    occupied: storage::BitVec,
 }
 
 /// Densely stored general information required by a stash.
@@ -118,6 +128,29 @@ where
     fn flush(&mut self) {
         self.header.flush();
         self.entries.flush();
+        self.occupied.flush();
+    }
+}
+
+impl<T> Stash<T>
+where
+    T: Encode + Flush,
+{
+    /// Writes the cached state of the stash back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the stash so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.header.invalidate_cache();
+        self.entries.invalidate_cache();
+        self.occupied.invalidate_cache();
     }
 }
 
@@ -132,12 +165,27 @@ where
             vec![
                 LayoutField::of("header", &self.header),
                 LayoutField::of("entries", &self.entries),
+                LayoutField::of("occupied", &self.occupied),
             ],
         )
         .into()
     }
 }
 
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<T> serde::Serialize for Stash<T>
+where
+    T: scale::Codec + serde::Serialize,
+{
+    /// Serializes the occupied entries of the stash as map from their indices.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_map(self.iter())
+    }
+}
+
 impl<'a, T> Iterator for Values<'a, T>
 where
     T: scale::Codec,
@@ -193,6 +241,97 @@ impl<'a, T> Iter<'a, T> {
     }
 }
 
+impl<'a, T> Iter<'a, T> {
+    /// Turns the iterator into one that yields copies of the remaining entries
+    /// without caching them.
+    ///
+    /// # Note
+    ///
+    /// Use this for read-only scans over large stashes so that the cache does
+    /// not hold every decoded entry until the end of the execution.
+    /// Entries that already are cached are cloned from the cache.
+    pub fn streaming(self) -> Streaming<'a, T> {
+        Streaming { iter: self }
+    }
+}
+
+/// Iterator over copies of the entries of a stash that does not cache them.
+///
+/// Created by [`Iter::streaming`](struct.Iter.html#method.streaming).
+#[derive(Debug)]
+pub struct Streaming<'a, T> {
+    /// The state of the iteration.
+    ///
+    /// Only its indices are used, the entries are never accessed through it.
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T> Streaming<'a, T>
+where
+    T: scale::Decode + Clone,
+{
+    /// Returns a copy of the element stored at index `n` if any.
+    fn get(&self, n: u32) -> Option<T> {
+        if !self.iter.stash.is_occupied(n) {
+            return None
+        }
+        match self.iter.stash.entries.get_uncached(n)? {
+            Entry::Occupied(val) => Some(val),
+            Entry::Vacant(_) => None,
+        }
+    }
+}
+
+impl<'a, T> Iterator for Streaming<'a, T>
+where
+    T: scale::Decode + Clone,
+{
+    type Item = (u32, T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.iter.begin <= self.iter.end);
+        if self.iter.yielded == self.iter.stash.len() {
+            return None
+        }
+        while self.iter.begin < self.iter.end {
+            let cur = self.iter.begin;
+            self.iter.begin += 1;
+            if let Some(elem) = self.get(cur) {
+                self.iter.yielded += 1;
+                return Some((cur, elem))
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.iter.stash.len() - self.iter.yielded) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Streaming<'a, T> where T: scale::Decode + Clone {}
+
+impl<'a, T> DoubleEndedIterator for Streaming<'a, T>
+where
+    T: scale::Decode + Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.iter.begin <= self.iter.end);
+        if self.iter.yielded == self.iter.stash.len() {
+            return None
+        }
+        while self.iter.begin < self.iter.end {
+            self.iter.end -= 1;
+            if let Some(elem) = self.get(self.iter.end) {
+                self.iter.yielded += 1;
+                return Some((self.iter.end, elem))
+            }
+        }
+        None
+    }
+}
+
 impl<'a, T> Iterator for Iter<'a, T>
 where
     T: scale::Codec,
@@ -207,7 +346,7 @@ where
         while self.begin < self.end {
             let cur = self.begin;
             self.begin += 1;
-            if let Some(elem) = self.stash.get(cur) {
+            if let Some(elem) = self.stash.get_occupied(cur) {
                 self.yielded += 1;
                 return Some((cur, elem))
             }
@@ -234,7 +373,7 @@ where
         }
         while self.begin < self.end {
             self.end -= 1;
-            if let Some(elem) = self.stash.get(self.end) {
+            if let Some(elem) = self.stash.get_occupied(self.end) {
                 self.yielded += 1;
                 return Some((self.end, elem))
             }
@@ -243,11 +382,68 @@ where
     }
 }
 
+/// Iterator over the entries of a stash that visits a bounded number of indices.
+///
+/// Created by [`Stash::iter_bounded`](struct.Stash.html#method.iter_bounded).
+///
+/// # Note
+///
+/// Vacant indices count towards the budget as well even though visiting
+/// them only reads the occupancy bitmap of the stash.
+#[derive(Debug)]
+pub struct BoundedIter<'a, T> {
+    /// The stash that is iterated over.
+    stash: &'a Stash<T>,
+    /// The index of the next visited entry.
+    begin: u32,
+    /// The index one past the last entry within the budget.
+    end: u32,
+}
+
+impl<'a, T> BoundedIter<'a, T> {
+    /// Returns the index to resume the iteration from if there are entries
+    /// beyond the budget, otherwise `None` if the iteration is complete.
+    ///
+    /// # Note
+    ///
+    /// If called before the iterator is exhausted this returns the index
+    /// of the next entry to visit.
+    pub fn resume_index(&self) -> Option<u32> {
+        if self.begin < self.stash.max_len() {
+            Some(self.begin)
+        } else {
+            None
+        }
+    }
+}
+
+impl<'a, T> Iterator for BoundedIter<'a, T>
+where
+    T: scale::Codec,
+{
+    type Item = (u32, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        while self.begin < self.end {
+            let cur = self.begin;
+            self.begin += 1;
+            if let Some(elem) = self.stash.get_occupied(cur) {
+                return Some((cur, elem))
+            }
+        }
+        None
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        (0, Some((self.end - self.begin) as usize))
+    }
+}
+
 /// An entry within a stash collection.
 ///
 /// This represents either an occupied entry with its associated value
 /// or a vacant entry pointing to the next vacant entry.
-#[derive(Debug, Encode, Decode)]
+#[derive(Debug, Clone, Encode, Decode)]
 #[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
 enum Entry<T> {
     /// A vacant entry pointing to the next vacant index.
@@ -269,10 +465,37 @@ where
     }
 }
 
+impl<T> Extend<T> for Stash<T>
+where
+    T: scale::Codec,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = T>,
+    {
+        for i in iter {
+            self.put(i);
+        }
+    }
+}
+
+impl<'a, T> Extend<&'a T> for Stash<T>
+where
+    T: scale::Codec + Copy + 'a,
+{
+    fn extend<I>(&mut self, iter: I)
+    where
+        I: IntoIterator<Item = &'a T>,
+    {
+        self.extend(iter.into_iter().copied())
+    }
+}
+
 impl<T> Encode for Stash<T> {
     fn encode_to<W: scale::Output>(&self, dest: &mut W) {
         self.header.encode_to(dest);
         self.entries.encode_to(dest);
+        self.occupied.encode_to(dest);
     }
 }
 
@@ -280,7 +503,12 @@ impl<T> Decode for Stash<T> {
     fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
         let header = storage::Value::decode(input)?;
         let entries = SyncChunk::decode(input)?;
-        Ok(Self { header, entries })
+        let occupied = storage::BitVec::decode(input)?;
+        Ok(Self {
+            header,
+            entries,
+            occupied,
+        })
     }
 }
 
@@ -293,6 +521,7 @@ impl<T> AllocateUsing for Stash<T> {
         Self {
             header: storage::Value::allocate_using(alloc),
             entries: SyncChunk::allocate_using(alloc),
+            occupied: storage::BitVec::allocate_using(alloc),
         }
     }
 }
@@ -312,6 +541,7 @@ impl<T> Initialize for Stash<T> {
             len: 0,
             max_len: 0,
         });
+        self.occupied.initialize(());
     }
 }
 
@@ -364,12 +594,124 @@ impl<T> Stash<T> {
         self.len() == 0
     }
 
+    /// Returns `true` if the entry at index `n` is occupied.
+    ///
+    /// # Note
+    ///
+    /// Only reads the occupancy bitmap of the stash and thus
+    /// never loads or decodes the entry itself.
+    pub fn is_occupied(&self, n: u32) -> bool {
+        self.occupied.get(n).unwrap_or(false)
+    }
+
+    /// Limits the number of elements the stash keeps cached or removes the limit if `None`.
+    ///
+    /// # Note
+    ///
+    /// Useful for long off-chain executions that read many elements.
+    /// Clean elements exceeding the limit are evicted in least recently used
+    /// order upon mutable accesses and flushes of the stash.
+    pub fn set_cache_limit(&mut self, limit: Option<u32>) {
+        self.entries.set_cache_limit(limit)
+    }
+
     /// Returns the next vacant index.
     fn next_vacant(&self) -> u32 {
         self.header.next_vacant
     }
 }
 
+#[cfg(feature = "ink-debug-invariants")]
+impl<T> Stash<T>
+where
+    T: scale::Codec,
+{
+    /// Asserts that the internal invariants of the stash hold.
+    ///
+    /// # Note
+    ///
+    /// - This walks all entries of the stash and is therefore very expensive.
+    /// - Meant to be called from tests or after storage migrations.
+    ///
+    /// # Panics
+    ///
+    /// - If an entry within `0..max_len` is missing or an entry at `max_len` exists.
+    /// - If the number of occupied entries does not match the length.
+    /// - If the occupancy bitmap does not match the entries.
+    /// - If the vacant list does not link all vacant entries exactly once.
+    pub fn debug_assert_invariants(&self) {
+        let max_len = self.max_len();
+        let mut occupied = 0;
+        assert_eq!(
+            self.occupied.len(),
+            max_len,
+            "[ink_core::Stash::debug_assert_invariants] Error: \
+             occupancy bitmap length does not match max_len",
+        );
+        for n in 0..max_len {
+            let is_occupied = match self.entries.get(n) {
+                Some(Entry::Occupied(_)) => true,
+                Some(Entry::Vacant(_)) => false,
+                None => {
+                    ink_panic!(
+                        "[ink_core::Stash::debug_assert_invariants] Error: \
+                         missing entry at index {}",
+                        n
+                    )
+                }
+            };
+            assert_eq!(
+                self.is_occupied(n),
+                is_occupied,
+                "[ink_core::Stash::debug_assert_invariants] Error: \
+                 occupancy bitmap mismatch at index {}",
+                n,
+            );
+            if is_occupied {
+                occupied += 1;
+            }
+        }
+        assert!(
+            self.entries.get(max_len).is_none(),
+            "[ink_core::Stash::debug_assert_invariants] Error: \
+             found an entry at max_len {}",
+            max_len,
+        );
+        assert_eq!(
+            occupied,
+            self.len(),
+            "[ink_core::Stash::debug_assert_invariants] Error: \
+             number of occupied entries does not match the length",
+        );
+        let vacant = max_len - self.len();
+        let mut visited = 0;
+        let mut current = self.next_vacant();
+        while current != max_len {
+            assert!(
+                visited < vacant,
+                "[ink_core::Stash::debug_assert_invariants] Error: \
+                 vacant list contains a cycle",
+            );
+            current = match self.entries.get(current) {
+                Some(Entry::Vacant(next)) => *next,
+                _ => {
+                    ink_panic!(
+                        "[ink_core::Stash::debug_assert_invariants] Error: \
+                         vacant list links to non-vacant index {}",
+                        current
+                    )
+                }
+            };
+            visited += 1;
+        }
+        assert_eq!(
+            visited, vacant,
+            "[ink_core::Stash::debug_assert_invariants] Error: \
+             vacant list does not link all vacant entries",
+        );
+    }
+}
+
 impl<T> Stash<T>
 where
     T: scale::Codec,
@@ -384,62 +726,226 @@ where
         })
     }
 
+    /// Returns the element stored at index `n` if the occupancy bitmap marks it.
+    ///
+    /// Used by scans in order to not load and decode vacant entries.
+    fn get_occupied(&self, n: u32) -> Option<&T> {
+        if !self.is_occupied(n) {
+            return None
+        }
+        self.get(n)
+    }
+
+    /// Returns the element stored at index `n` if any.
+    ///
+    /// # Note
+    ///
+    /// Use this instead of [`get`](struct.Stash.html#method.get) in order to
+    /// detect entries with an outdated encoding, e.g. after an upgrade.
+    ///
+    /// # Errors
+    ///
+    /// If the entry cannot be decoded.
+    pub fn try_get(&self, n: u32) -> Option<env::Result<&T>> {
+        match self.entries.try_get(n)? {
+            Ok(Entry::Occupied(val)) => Some(Ok(val)),
+            Ok(Entry::Vacant(_)) => None,
+            Err(err) => Some(Err(err)),
+        }
+    }
+
+    /// Returns a mutable reference to the element stored at index `n` if any.
+    pub fn get_mut(&mut self, n: u32) -> Option<&mut T> {
+        self.entries.get_mut(n).and_then(|entry| {
+            match entry {
+                Entry::Occupied(val) => Some(val),
+                Entry::Vacant(_) => None,
+            }
+        })
+    }
+
+    /// Returns an iterator over the occupied entries of at most `max_entries`
+    /// indices starting at `start`.
+    ///
+    /// # Note
+    ///
+    /// Use this to paginate over a fragmented stash within bounded gas.
+    /// Resume with the index returned by
+    /// [`BoundedIter::resume_index`](struct.BoundedIter.html#method.resume_index)
+    /// once the iterator is exhausted.
+    pub fn iter_bounded(&self, start: u32, max_entries: u32) -> BoundedIter<'_, T> {
+        let max_len = self.max_len();
+        let begin = core::cmp::min(start, max_len);
+        let end = core::cmp::min(begin.saturating_add(max_entries), max_len);
+        BoundedIter {
+            stash: self,
+            begin,
+            end,
+        }
+    }
+
+    /// Copies the occupied entries of at most `limit` indices starting at `start`.
+    ///
+    /// # Note
+    ///
+    /// The returned snapshot is encodable and thus can be returned from messages.
+    /// The limit bounds the number of contract storage reads.
+    pub fn snapshot(&self, start: u32, limit: u32) -> StashSnapshot<T>
+    where
+        T: Clone,
+    {
+        let max_len = self.max_len();
+        let start = core::cmp::min(start, max_len);
+        let end = core::cmp::min(start.saturating_add(limit), max_len);
+        let entries = (start..end)
+            .filter_map(|n| self.get_occupied(n).map(|val| (n, val.clone())))
+            .collect();
+        StashSnapshot::new(start, end, max_len, entries)
+    }
+
+    /// Copies all entries of the stash into a new stash allocated
+    /// using the given storage allocator.
+    ///
+    /// # Note
+    ///
+    /// Vacant entries are copied as well so that the elements keep their
+    /// indices and the copy refills vacant indices in the same order.
+    /// Use this to take snapshots or backups of the stash within a contract.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the storage region handed out by the allocator is
+    /// overwritten and thus must not be in use by other storage entities.
+    pub unsafe fn deep_clone_into<A>(&self, alloc: &mut A) -> Self
+    where
+        A: Allocate,
+        T: Clone,
+    {
+        let mut copy = Self::allocate_using(alloc);
+        copy.header.set(StashHeader {
+            next_vacant: self.header.next_vacant,
+            len: self.header.len,
+            max_len: self.header.max_len,
+        });
+        copy.occupied.initialize(());
+        for n in 0..self.max_len() {
+            copy.occupied.push(self.is_occupied(n));
+            match self.entries.get(n) {
+                Some(entry) => copy.entries.set(n, entry.clone()),
+                None => InternalError::MissingEntry.report("Stash::deep_clone_into"),
+            }
+        }
+        copy
+    }
+
     /// Put the element into the stash at the next vacant position.
     ///
+    /// Vacant entries are reused before the stash grows, even if the
+    /// next vacant entry is at an index equal to the length of the stash.
+    ///
     /// Returns the stash index that the element was put into.
     pub fn put(&mut self, val: T) -> u32 {
         let current_vacant = self.header.next_vacant;
-        debug_assert!(current_vacant <= self.len());
-        if current_vacant == self.len() {
+        debug_assert!(current_vacant <= self.max_len());
+        if current_vacant == self.max_len() {
             self.entries.set(current_vacant, Entry::Occupied(val));
+            self.occupied.push(true);
             self.header.next_vacant = current_vacant + 1;
             self.header.max_len += 1;
         } else {
-            let next_vacant = match self
-                .entries
-                .put(current_vacant, Entry::Occupied(val))
-                .expect(
-                    "[ink_core::Stash::put] Error: \
-                     expected a vacant entry here, but no entry was found",
-                ) {
-                Entry::Vacant(next_vacant) => next_vacant,
-                Entry::Occupied(_) => {
-                    unreachable!(
-                        "[ink_core::Stash::put] Error: \
-                         a next_vacant index can never point to an occupied entry"
-                    )
+            let next_vacant = match self.entries.put(current_vacant, Entry::Occupied(val))
+            {
+                Some(Entry::Vacant(next_vacant)) => next_vacant,
+                Some(Entry::Occupied(_)) => {
+                    InternalError::UnexpectedOccupied.report("Stash::put")
                 }
+                None => InternalError::MissingEntry.report("Stash::put"),
             };
+            self.occupied.set(current_vacant, true);
             self.header.next_vacant = next_vacant;
         }
         self.header.len += 1;
         current_vacant
     }
 
+    /// Calls `f` with the index and a mutable reference of every element
+    /// in the order of their indices.
+    ///
+    /// # Note
+    ///
+    /// This loads every occupied entry and marks it as mutated.
+    /// Try to mutate only the required elements through
+    /// [`get_mut`](#method.get_mut) instead.
+    pub fn for_each_indexed_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(u32, &mut T),
+    {
+        let mut visited = 0;
+        for n in 0..self.max_len() {
+            if visited == self.len() {
+                break
+            }
+            if !self.is_occupied(n) {
+                continue
+            }
+            if let Some(elem) = self.get_mut(n) {
+                visited += 1;
+                f(n, elem)
+            }
+        }
+    }
+
+    /// Puts the element created from the index it is going to be put into.
+    ///
+    /// Returns the stash index that the element was put into.
+    ///
+    /// # Note
+    ///
+    /// Use this for elements that embed their own stash index.
+    pub fn put_with<F>(&mut self, f: F) -> u32
+    where
+        F: FnOnce(u32) -> T,
+    {
+        let index = self.header.next_vacant;
+        let put_index = self.put(f(index));
+        debug_assert_eq!(put_index, index);
+        put_index
+    }
+
+    /// Puts the elements into the stash filling vacant positions first and
+    /// then appending.
+    ///
+    /// Returns the stash indices that the elements were put into in order.
+    ///
+    /// # Note
+    ///
+    /// The indices are only contiguous if the stash had no vacant positions.
+    /// The header is updated in the cache only, so it is written back to the
+    /// contract storage once for the whole batch upon the next flush.
+    pub fn put_iter<I>(&mut self, values: I) -> ink_prelude::vec::Vec<u32>
+    where
+        I: IntoIterator<Item = T>,
+    {
+        values.into_iter().map(|val| self.put(val)).collect()
+    }
+
     /// Takes the element stored at index `n`-th if any.
     pub fn take(&mut self, n: u32) -> Option<T> {
+        if !self.is_occupied(n) {
+            return None
+        }
         match self.entries.get(n) {
             None | Some(Entry::Vacant(_)) => None,
             Some(Entry::Occupied(_)) => {
-                match self
-                    .entries
-                    .put(n, Entry::Vacant(self.next_vacant()))
-                    .expect(
-                        "[ink_core::Stash::take] Error: \
-                         we already asserted that the entry at `n` exists",
-                    ) {
-                    Entry::Occupied(val) => {
+                match self.entries.put(n, Entry::Vacant(self.next_vacant())) {
+                    Some(Entry::Occupied(val)) => {
+                        self.occupied.set(n, false);
                         self.header.next_vacant = n;
                         debug_assert!(!self.is_empty());
                         self.header.len -= 1;
                         Some(val)
                     }
-                    Entry::Vacant(_) => {
-                        unreachable!(
-                            "[ink_core::Stash::take] Error: \
-                             we already asserted that the entry is occupied"
-                        )
-                    }
+                    _ => InternalError::UnexpectedVacant.report("Stash::take"),
                 }
             }
         }
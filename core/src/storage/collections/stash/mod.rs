@@ -32,10 +32,19 @@
 #[cfg(test)]
 mod tests;
 
+#[cfg(all(test, feature = "ink-fuzz-tests"))]
+mod fuzz_tests;
+
 mod impls;
+mod snapshot;
 
-pub use self::impls::{
-    Iter,
-    Stash,
-    Values,
+pub use self::{
+    impls::{
+        BoundedIter,
+        Iter,
+        Stash,
+        Streaming,
+        Values,
+    },
+    snapshot::StashSnapshot,
 };
@@ -0,0 +1,86 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_prelude::vec::Vec;
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+/// A copy of the occupied entries within a range of indices of a storage `Stash`.
+///
+/// # Note
+///
+/// Is encodable and thus can be returned from messages to let
+/// callers page through the entries of a storage stash.
+/// Created by [`Stash::snapshot`](struct.Stash.html#method.snapshot).
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct StashSnapshot<T> {
+    /// The first index of the copied range.
+    start: u32,
+    /// The index after the last index of the copied range.
+    end: u32,
+    /// The maximum length of the storage stash at the time of copying.
+    max_len: u32,
+    /// The copied occupied entries and their indices.
+    entries: Vec<(u32, T)>,
+}
+
+impl<T> StashSnapshot<T> {
+    /// Creates a new snapshot from the copied entries.
+    pub(crate) fn new(
+        start: u32,
+        end: u32,
+        max_len: u32,
+        entries: Vec<(u32, T)>,
+    ) -> Self {
+        Self {
+            start,
+            end,
+            max_len,
+            entries,
+        }
+    }
+
+    /// Returns the first index of the copied range.
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the index after the last index of the copied range.
+    ///
+    /// This is where the next snapshot starts when paging through the storage stash.
+    pub fn end(&self) -> u32 {
+        self.end
+    }
+
+    /// Returns the maximum length of the storage stash at the time of copying.
+    pub fn max_len(&self) -> u32 {
+        self.max_len
+    }
+
+    /// Returns `true` if the storage stash has indices after this snapshot.
+    pub fn has_more(&self) -> bool {
+        self.end < self.max_len
+    }
+
+    /// Returns the copied occupied entries and their indices.
+    pub fn entries(&self) -> &[(u32, T)] {
+        &self.entries
+    }
+
+    /// Returns the copied occupied entries and their indices by value.
+    pub fn into_entries(self) -> Vec<(u32, T)> {
+        self.entries
+    }
+}
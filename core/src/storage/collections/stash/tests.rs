@@ -67,6 +67,39 @@ fn put_empty() -> Result<()> {
     })
 }
 
+#[test]
+fn extend() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(1), Some(42));
+        // Extending fills up vacant entries before appending.
+        stash.extend(ink_prelude::vec![1, 2]);
+        stash.extend(&[3]);
+        assert_eq!(stash.get(1), Some(&1));
+        assert_eq!(stash.get(4), Some(&2));
+        assert_eq!(stash.get(5), Some(&3));
+        assert_eq!(stash.len(), 6);
+        Ok(())
+    })
+}
+
+#[test]
+fn snapshot() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(1), Some(42));
+        let snapshot = stash.snapshot(0, 3);
+        assert_eq!(snapshot.entries(), &[(0, 5), (2, 1337)]);
+        assert_eq!((snapshot.start(), snapshot.end()), (0, 3));
+        assert!(snapshot.has_more());
+        let snapshot = stash.snapshot(snapshot.end(), 3);
+        assert_eq!(snapshot.entries(), &[(3, 77)]);
+        assert_eq!((snapshot.end(), snapshot.max_len()), (4, 4));
+        assert!(!snapshot.has_more());
+        Ok(())
+    })
+}
+
 #[test]
 fn put_filled() -> Result<()> {
     env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
@@ -281,3 +314,238 @@ fn iter_size_hint() -> Result<()> {
         Ok(())
     })
 }
+
+#[test]
+fn put_into_vacant_entry_at_len() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        stash.put(123);
+        assert_eq!(stash.take(1), Some(42));
+        assert_eq!(stash.take(3), Some(77));
+        // The next vacant entry is at index 3 which equals the length.
+        assert_eq!(stash.len(), 3);
+        assert_eq!(stash.put(1), 3);
+        assert_eq!(stash.put(2), 1);
+        assert_eq!(stash.get(4), Some(&123));
+        assert_eq!(stash.max_len(), 5);
+        Ok(())
+    })
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serialize_snapshot() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(1), Some(42));
+        assert_eq!(
+            serde_json::to_string(&stash).unwrap(),
+            r#"{"0":5,"2":1337,"3":77}"#,
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn get_mut() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        *stash.get_mut(1).unwrap() += 1;
+        assert_eq!(stash.get(1), Some(&43));
+        assert_eq!(stash.take(1), Some(43));
+        assert_eq!(stash.get_mut(1), None);
+        assert_eq!(stash.get_mut(4), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn streaming() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(1), Some(42));
+        stash.flush();
+        stash.invalidate_cache();
+        assert_eq!(stash.len(), 3);
+        let reads = || -> Result<usize> {
+            Ok(env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(&contract)?.0)
+        };
+        let before = reads()?;
+        let streamed = stash.iter().streaming().collect::<Vec<_>>();
+        assert_eq!(streamed, vec![(0, 5), (2, 1337), (3, 77)]);
+        assert_eq!(stash.iter().streaming().next_back(), Some((3, 77)));
+        // Streamed entries are not cached and thus read again every time.
+        // The vacant entry is skipped after reading the occupancy bitmap once.
+        assert_eq!(reads()? - before, 6);
+        Ok(())
+    })
+}
+
+#[test]
+fn put_iter() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(2), Some(1337));
+        assert_eq!(stash.take(0), Some(5));
+        stash.flush();
+        let (indices, ops) = env::test::count_storage_ops::<env::DefaultEnvTypes, _, _>(
+            &contract,
+            || {
+                let indices = stash.put_iter(vec![10, 20, 30, 40]);
+                stash.flush();
+                indices
+            },
+        )?;
+        // Vacant positions are filled first in the order they were vacated.
+        assert_eq!(indices, vec![0, 2, 4, 5]);
+        assert_eq!(stash.len(), 6);
+        assert_eq!(stash.get(2), Some(&20));
+        assert_eq!(stash.get(5), Some(&40));
+        // One write per element and single writes of the header
+        // as well as of the length and block of the occupancy bitmap.
+        assert_eq!(ops.writes, 7);
+        assert_eq!(stash.put_iter(None), vec![]);
+        Ok(())
+    })
+}
+
+#[test]
+fn put_with() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(1), Some(42));
+        // Reuses the vacant position and then appends.
+        assert_eq!(stash.put_with(|index| index as i32 * 100), 1);
+        assert_eq!(stash.put_with(|index| index as i32 * 100), 4);
+        assert_eq!(stash.get(1), Some(&100));
+        assert_eq!(stash.get(4), Some(&400));
+        assert_eq!(stash.len(), 5);
+        Ok(())
+    })
+}
+
+#[test]
+fn for_each_indexed_mut() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(1), Some(42));
+        let mut visited = Vec::new();
+        stash.for_each_indexed_mut(|n, elem| {
+            visited.push(n);
+            *elem = -(n as i32);
+        });
+        assert_eq!(visited, vec![0, 2, 3]);
+        assert_eq!(
+            stash.iter().collect::<Vec<_>>(),
+            vec![(0, &0), (2, &-2), (3, &-3)]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn deep_clone_into() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(1), Some(42));
+        let mut copy = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x1; 32]));
+            stash.deep_clone_into(&mut alloc)
+        };
+        assert_eq!(stash.take(0), Some(5));
+        stash.flush();
+        copy.flush();
+        copy.invalidate_cache();
+        assert_eq!(
+            copy.iter().collect::<Vec<_>>(),
+            vec![(0, &5), (2, &1337), (3, &77)]
+        );
+        // The copy refills the vacant index of the original.
+        assert_eq!(copy.put(1), 1);
+        assert_eq!(copy.put(4), 4);
+        Ok(())
+    })
+}
+
+#[test]
+fn try_get() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = filled_stash();
+        assert_eq!(stash.take(2), Some(1337));
+        stash.flush();
+        stash.invalidate_cache();
+        assert_eq!(stash.try_get(0).map(|res| res.ok()), Some(Some(&5)));
+        assert!(stash.try_get(2).is_none());
+        assert!(stash.try_get(4).is_none());
+        let key = stash.entries_key() + 1u32;
+        unsafe { crate::storage::raw::write_raw(key, &[0x02]) }
+        match stash.try_get(1) {
+            Some(Err(env::EnvError::Decode(_))) => (),
+            _ => panic!("expected a decoding error"),
+        }
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_bounded() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = empty_stash();
+        stash.extend(0..10);
+        for n in 2..7 {
+            assert_eq!(stash.take(n), Some(n as i32));
+        }
+        // Visits the vacant indices 2 and 3 within the budget.
+        let mut iter = stash.iter_bounded(0, 4);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![(0, &0), (1, &1)]);
+        assert_eq!(iter.resume_index(), Some(4));
+        let mut iter = stash.iter_bounded(4, 4);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![(7, &7)]);
+        assert_eq!(iter.resume_index(), Some(8));
+        let mut iter = stash.iter_bounded(8, 4);
+        assert_eq!(iter.by_ref().collect::<Vec<_>>(), vec![(8, &8), (9, &9)]);
+        assert_eq!(iter.resume_index(), None);
+        assert_eq!(stash.iter_bounded(42, 4).resume_index(), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn is_occupied() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut stash = holey_stash();
+        let occupied = (0..6).map(|n| stash.is_occupied(n)).collect::<Vec<_>>();
+        assert_eq!(occupied, vec![true, false, true, false, true, false]);
+        assert_eq!(stash.put(99), 3);
+        assert!(stash.is_occupied(3));
+        assert_eq!(stash.take(3), Some(99));
+        assert!(!stash.is_occupied(3));
+        assert_eq!(stash.take(3), None);
+        Ok(())
+    })
+}
+
+#[test]
+fn iter_skips_vacant_entries() -> Result<()> {
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let mut stash = empty_stash();
+        stash.extend(0..10);
+        for n in 1..9 {
+            assert_eq!(stash.take(n), Some(n as i32));
+        }
+        stash.flush();
+        stash.invalidate_cache();
+        assert_eq!(stash.len(), 2);
+        let (values, ops) = env::test::count_storage_ops::<env::DefaultEnvTypes, _, _>(
+            &contract,
+            || stash.iter().collect::<Vec<_>>(),
+        )?;
+        assert_eq!(values, vec![(0, &0), (9, &9)]);
+        // The occupied entries and the length and single block of the bitmap.
+        assert_eq!(ops.reads, 4);
+        Ok(())
+    })
+}
@@ -0,0 +1,141 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::storage::{
+    alloc::{
+        AllocateUsing,
+        BumpAlloc,
+        Initialize,
+    },
+    test_utils::{
+        self,
+        bounded_index,
+    },
+    Vec as StorageVec,
+};
+use ink_primitives::Key;
+use quickcheck::{
+    Arbitrary,
+    Gen,
+};
+use rand::Rng;
+
+/// An operation on a storage vector.
+#[derive(Debug, Clone)]
+enum Op {
+    Push(i32),
+    Pop,
+    Get(u32),
+    Mutate(u32, i32),
+    Replace(u32, i32),
+    Swap(u32, u32),
+    SwapRemove(u32),
+}
+
+impl Arbitrary for Op {
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        match g.gen_range(0, 8) {
+            0 | 1 => Op::Push(g.gen()),
+            2 => Op::Pop,
+            3 => Op::Get(g.gen()),
+            4 => Op::Mutate(g.gen(), g.gen()),
+            5 => Op::Replace(g.gen(), g.gen()),
+            6 => Op::Swap(g.gen(), g.gen()),
+            _ => Op::SwapRemove(g.gen()),
+        }
+    }
+}
+
+/// Returns an empty storage vector.
+fn new_empty_vec() -> StorageVec<i32> {
+    unsafe {
+        let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+        StorageVec::allocate_using(&mut alloc).initialize_into(())
+    }
+}
+
+/// Applies the operations to a storage vector and a `std` vector
+/// and asserts that both always contain the same elements.
+fn cross_check(ops: std::vec::Vec<Op>) -> bool {
+    test_utils::run_isolated(|| {
+        let mut vec = new_empty_vec();
+        let mut model = std::vec::Vec::new();
+        for op in ops {
+            let len = model.len() as u32;
+            match op {
+                Op::Push(value) => {
+                    vec.push(value);
+                    model.push(value);
+                }
+                Op::Pop => assert_eq!(vec.pop(), model.pop()),
+                Op::Get(n) => {
+                    let n = bounded_index(n, len);
+                    assert_eq!(vec.get(n), model.get(n as usize));
+                }
+                Op::Mutate(n, by) => {
+                    let n = bounded_index(n, len);
+                    let expected = model.get_mut(n as usize).map(|value| {
+                        *value = value.wrapping_add(by);
+                        *value
+                    });
+                    let mutated = vec.mutate(n, |value| *value = value.wrapping_add(by));
+                    assert_eq!(mutated.cloned(), expected);
+                }
+                Op::Replace(n, value) => {
+                    let n = bounded_index(n, len);
+                    let expected = model
+                        .get_mut(n as usize)
+                        .map(|old| core::mem::replace(old, value));
+                    assert_eq!(vec.replace(n, || value), expected);
+                }
+                Op::Swap(a, b) => {
+                    if len == 0 {
+                        continue
+                    }
+                    let (a, b) = (a % len, b % len);
+                    vec.swap(a, b);
+                    model.swap(a as usize, b as usize);
+                }
+                Op::SwapRemove(n) => {
+                    let n = bounded_index(n, len);
+                    let expected = if n < len {
+                        Some(model.swap_remove(n as usize))
+                    } else {
+                        None
+                    };
+                    assert_eq!(vec.swap_remove(n), expected);
+                }
+            }
+            assert_eq!(vec.len(), model.len() as u32);
+            assert_eq!(vec.is_empty(), model.is_empty());
+            vec.debug_assert_invariants();
+        }
+        assert!(vec.iter().eq(model.iter()));
+    })
+}
+
+#[test]
+fn vec_behaves_like_std_vec() {
+    test_utils::check(cross_check)
+}
+
+/// Applies the operations of the reusable harness to a storage vector.
+fn generic_cross_check(ops: std::vec::Vec<test_utils::VecOp<i32>>) -> bool {
+    test_utils::run_isolated(|| test_utils::cross_check_vec(&mut new_empty_vec(), ops))
+}
+
+#[test]
+fn vec_passes_generic_cross_check() {
+    test_utils::check(generic_cross_check)
+}
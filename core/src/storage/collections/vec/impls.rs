@@ -22,15 +22,21 @@ use ink_abi::{
 #[cfg(feature = "ink-generate-abi")]
 use type_metadata::Metadata;
 
-use crate::storage::{
-    self,
-    alloc::{
-        Allocate,
-        AllocateUsing,
-        Initialize,
+use super::VecSlice;
+use crate::{
+    env,
+    storage::{
+        self,
+        alloc::{
+            Allocate,
+            AllocateUsing,
+            Initialize,
+        },
+        chunk::SyncChunk,
+        collections::internal::InternalError,
+        Flush,
+        InkExpect as _,
     },
-    chunk::SyncChunk,
-    Flush,
 };
 
 /// A contiguous growable array type, written `Vec<T>` but pronounced 'vector'.
@@ -78,6 +84,85 @@ impl<'a, T> Iter<'a, T> {
     }
 }
 
+impl<'a, T> Iter<'a, T> {
+    /// Turns the iterator into one that yields copies of the remaining elements
+    /// without caching them.
+    ///
+    /// # Note
+    ///
+    /// Use this for read-only scans over large vectors so that the cache does
+    /// not hold every decoded element until the end of the execution.
+    /// Elements that already are cached are cloned from the cache.
+    pub fn streaming(self) -> Streaming<'a, T> {
+        Streaming {
+            vec: self.vec,
+            begin: self.begin,
+            end: self.end,
+        }
+    }
+}
+
+impl<'a, T> Iter<'a, T> {
+    /// Turns the iterator into one that also yields the `u32` index of
+    /// every element.
+    ///
+    /// # Note
+    ///
+    /// Prefer this over `Iterator::enumerate` which yields `usize` indices.
+    pub fn enumerate_u32(self) -> EnumerateU32<'a, T> {
+        EnumerateU32 { iter: self }
+    }
+}
+
+/// An iterator over the indices and values of a storage `Vec`.
+///
+/// Created by [`Iter::enumerate_u32`](struct.Iter.html#method.enumerate_u32).
+#[derive(Debug)]
+pub struct EnumerateU32<'a, T> {
+    /// The underlying iterator.
+    iter: Iter<'a, T>,
+}
+
+impl<'a, T> Iterator for EnumerateU32<'a, T>
+where
+    T: scale::Codec,
+{
+    type Item = (u32, &'a T);
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let index = self.iter.begin;
+        self.iter.next().map(|elem| (index, elem))
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        self.iter.size_hint()
+    }
+}
+
+impl<'a, T> ExactSizeIterator for EnumerateU32<'a, T> where T: scale::Codec {}
+
+impl<'a, T> DoubleEndedIterator for EnumerateU32<'a, T>
+where
+    T: scale::Codec,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        self.iter.next_back().map(|elem| (self.iter.end, elem))
+    }
+}
+
+/// An iterator over copies of the values of a storage `Vec` that does not cache them.
+///
+/// Created by [`Iter::streaming`](struct.Iter.html#method.streaming).
+#[derive(Debug)]
+pub struct Streaming<'a, T> {
+    /// The storage vector to iterate over.
+    vec: &'a Vec<T>,
+    /// The current begin of the iteration.
+    begin: u32,
+    /// The current end of the iteration.
+    end: u32,
+}
+
 impl<T> Flush for Vec<T>
 where
     T: scale::Encode + Flush,
@@ -88,6 +173,27 @@ where
     }
 }
 
+impl<T> Vec<T>
+where
+    T: scale::Encode + Flush,
+{
+    /// Writes the cached state of the vector back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the vector so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.len.invalidate_cache();
+        self.cells.invalidate_cache();
+    }
+}
+
 #[cfg(feature = "ink-generate-abi")]
 impl<T> HasLayout for Vec<T>
 where
@@ -105,6 +211,20 @@ where
     }
 }
 
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<T> serde::Serialize for Vec<T>
+where
+    T: scale::Codec + serde::Serialize,
+{
+    /// Serializes the elements of the vector as sequence.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        serializer.collect_seq(self.iter())
+    }
+}
+
 impl<'a, T> Iterator for Iter<'a, T>
 where
     T: scale::Codec,
@@ -144,6 +264,45 @@ where
     }
 }
 
+impl<'a, T> Iterator for Streaming<'a, T>
+where
+    T: scale::Decode + Clone,
+{
+    type Item = T;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+        if self.begin == self.end {
+            return None
+        }
+        let cur = self.begin;
+        self.begin += 1;
+        self.vec.cells.get_uncached(cur)
+    }
+
+    fn size_hint(&self) -> (usize, Option<usize>) {
+        let remaining = (self.end - self.begin) as usize;
+        (remaining, Some(remaining))
+    }
+}
+
+impl<'a, T> ExactSizeIterator for Streaming<'a, T> where T: scale::Decode + Clone {}
+
+impl<'a, T> DoubleEndedIterator for Streaming<'a, T>
+where
+    T: scale::Decode + Clone,
+{
+    fn next_back(&mut self) -> Option<Self::Item> {
+        debug_assert!(self.begin <= self.end);
+        if self.begin == self.end {
+            return None
+        }
+        debug_assert_ne!(self.end, 0);
+        self.end -= 1;
+        self.vec.cells.get_uncached(self.end)
+    }
+}
+
 impl<T> Extend<T> for Vec<T>
 where
     T: scale::Codec,
@@ -222,6 +381,17 @@ impl<T> Vec<T> {
     pub fn is_empty(&self) -> bool {
         self.len() == 0
     }
+
+    /// Limits the number of elements the vector keeps cached or removes the limit if `None`.
+    ///
+    /// # Note
+    ///
+    /// Useful for long off-chain executions that read many elements.
+    /// Clean elements exceeding the limit are evicted in least recently used
+    /// order upon mutable accesses and flushes of the vector.
+    pub fn set_cache_limit(&mut self, limit: Option<u32>) {
+        self.cells.set_cache_limit(limit)
+    }
 }
 
 impl<T> Vec<T>
@@ -247,6 +417,62 @@ where
         Iter::new(self)
     }
 
+    /// Copies at most the first `limit` elements of the vector into a `Vec`.
+    ///
+    /// # Note
+    ///
+    /// The limit bounds the number of contract storage reads which
+    /// is important since the storage vector might be arbitrarily long.
+    pub fn to_vec(&self, limit: u32) -> ink_prelude::vec::Vec<T>
+    where
+        T: Clone,
+    {
+        self.iter().take(limit as usize).cloned().collect()
+    }
+
+    /// Copies at most `limit` elements of the vector starting at `start`.
+    ///
+    /// # Note
+    ///
+    /// The returned slice is encodable and thus can be returned from messages.
+    /// The limit bounds the number of contract storage reads.
+    pub fn slice(&self, start: u32, limit: u32) -> VecSlice<T>
+    where
+        T: Clone,
+    {
+        let len = self.len();
+        let start = core::cmp::min(start, len);
+        let end = core::cmp::min(start.saturating_add(limit), len);
+        let elems = (start..end)
+            .filter_map(|n| self.cells.get(n))
+            .cloned()
+            .collect();
+        VecSlice::new(start, len, elems)
+    }
+
+    /// Copies all elements of the vector into a new vector allocated
+    /// using the given storage allocator.
+    ///
+    /// # Note
+    ///
+    /// Use this to take snapshots or backups of the vector within a contract.
+    /// The copy is independent of `self` and is written to the contract
+    /// storage upon its own flush.
+    ///
+    /// # Safety
+    ///
+    /// Unsafe because the storage region handed out by the allocator is
+    /// overwritten and thus must not be in use by other storage entities.
+    pub unsafe fn deep_clone_into<A>(&self, alloc: &mut A) -> Self
+    where
+        A: Allocate,
+        T: Clone,
+    {
+        let mut copy = Self::allocate_using(alloc).initialize_into(());
+        copy.extend(self.iter().cloned());
+        copy
+    }
+
     /// Returns a reference to the `n`-th element of the vector.
     ///
     /// Returns `None` if `n` is out of bounds.
@@ -254,6 +480,35 @@ where
         self.within_bounds(n).and_then(|n| self.cells.get(n))
     }
 
+    /// Returns a reference to the `n`-th element of the vector.
+    ///
+    /// Returns `None` if `n` is out of bounds.
+    ///
+    /// # Note
+    ///
+    /// Use this instead of [`get`](struct.Vec.html#method.get) in order to
+    /// detect elements with an outdated encoding, e.g. after an upgrade.
+    ///
+    /// # Errors
+    ///
+    /// If the element cannot be decoded as `T`.
+    pub fn try_get(&self, n: u32) -> Option<env::Result<&T>> {
+        self.within_bounds(n).and_then(|n| self.cells.try_get(n))
+    }
+
+    /// Returns a typed reference to the cell of the `n`-th element of the vector.
+    ///
+    /// Returns `None` if `n` is out of bounds.
+    ///
+    /// # Note
+    ///
+    /// The reference stays valid as long as the element is not moved,
+    /// e.g. by `swap_remove`, and can be persisted in the contract storage.
+    pub fn key_ref(&self, n: u32) -> Option<storage::KeyRef<T>> {
+        self.within_bounds(n)
+            .map(|n| storage::KeyRef::new(self.cells.cells_key() + n))
+    }
+
     /// Returns a mutable reference to the `n`-th element of the vector.
     ///
     /// Returns `None` if `n` is out of bounds.
@@ -279,10 +534,54 @@ where
         })
     }
 
+    /// Calls `f` with the index and a mutable reference of every element in order.
+    ///
+    /// # Note
+    ///
+    /// This loads and marks every element as mutated. Try to mutate only
+    /// the required elements through [`get_mut`](#method.get_mut) instead.
+    pub fn for_each_indexed_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(u32, &mut T),
+    {
+        for n in 0..self.len() {
+            if let Some(elem) = self.cells.get_mut(n) {
+                f(n, elem)
+            }
+        }
+    }
+
+    /// Calls `f` with the index of the first element and mutable references
+    /// of every pair of adjacent elements in order.
+    ///
+    /// # Note
+    ///
+    /// Pairs overlap, e.g. `(0, 1)`, `(1, 2)` and so on, so that updates of an
+    /// element are observed by the next call. Use this for in-place prefix
+    /// sums or to re-link neighbouring elements.
+    /// Elements are loaded one after another as the pairs advance and are
+    /// marked as mutated.
+    pub fn for_each_pair_mut<F>(&mut self, mut f: F)
+    where
+        F: FnMut(u32, &mut T, &mut T),
+    {
+        for n in 1..self.len() {
+            let mut first = match self.cells.take(n - 1) {
+                Some(first) => first,
+                None => InternalError::MissingEntry.report("Vec::for_each_pair_mut"),
+            };
+            match self.cells.get_mut(n) {
+                Some(second) => f(n - 1, &mut first, second),
+                None => InternalError::MissingEntry.report("Vec::for_each_pair_mut"),
+            }
+            self.cells.set(n - 1, first);
+        }
+    }
+
     /// Appends an element to the back of the vector.
     pub fn push(&mut self, val: T) {
         if self.len() == u32::max_value() {
-            panic!(
+            ink_panic!(
                 "[ink_core::Vec::push] Error: \
                  cannot push more elements than `u32::MAX`"
             )
@@ -310,46 +609,62 @@ where
     where
         F: FnOnce() -> T,
     {
-        self.within_bounds(n).map(|n| {
-            self.cells.put(n, f()).expect(
-                "[ink_core::Vec::replace] Error: \
-                 expected success due to access within bounds",
-            )
-        })
+        let n = self.within_bounds(n)?;
+        match self.cells.put(n, f()) {
+            Some(old) => Some(old),
+            None => InternalError::MissingEntry.report("Vec::replace"),
+        }
     }
 
     /// Swaps the `a`-th and the `b`-th elements.
     ///
     /// # Panics
     ///
-    /// If one or both indices are out of bounds.
+    /// If one or both indices are out of bounds, even if they are equal.
     pub fn swap(&mut self, a: u32, b: u32) {
-        // Bail out if both indices are equal.
-        if a == b {
-            return
-        }
-        self.within_bounds(a).expect(
+        self.within_bounds(a).ink_expect(
             "[ink_core::Vec::swap] Error: \
              expected a to be within bounds",
         );
-        self.within_bounds(b).expect(
+        self.within_bounds(b).ink_expect(
             "[ink_core::Vec::swap] Error: \
              expected b to be within bounds",
         );
-        let item_a = self.cells.take(a).expect(
-            "[ink_core::Vec::swap] Error: \
-             expected succes due to `a` being within bounds",
-        );
-        let item_b = self.cells.put(b, item_a).expect(
-            "[ink_core::Vec::swap] Error: \
-             expected success due to `b` being within bounds",
-        );
-        self.cells.set(a, item_b);
+        // Bail out if both indices are equal.
+        if a == b {
+            return
+        }
+        let item_a = match self.cells.take(a) {
+            Some(item_a) => item_a,
+            None => InternalError::MissingEntry.report("Vec::swap"),
+        };
+        match self.cells.put(b, item_a) {
+            Some(item_b) => self.cells.set(a, item_b),
+            None => InternalError::MissingEntry.report("Vec::swap"),
+        }
+    }
+
+    /// Swaps the cells of the `a`-th and the `b`-th elements without checking
+    /// whether they are within bounds.
+    ///
+    /// # Note
+    ///
+    /// Cells beyond the length of the vector are vacant and are swapped
+    /// just like the cells of elements within bounds.
+    ///
+    /// # Safety
+    ///
+    /// Both indices must be within bounds. Otherwise an element is moved out
+    /// of the vector and a vacant cell takes its place which breaks the
+    /// invariants of the vector.
+    pub unsafe fn swap_unchecked(&mut self, a: u32, b: u32) {
+        self.cells.swap(a, b)
     }
 
     /// Removes the `n`-th element from the vector and returns it.
     ///
     /// The removed element is replaced by the last element of the vector.
+    /// Removing the last element leaves all other elements in place.
     /// Returns `None` and does not remove if `n` is out of bounds.
     ///
     /// # Note
@@ -360,18 +675,51 @@ where
             return None
         }
         self.within_bounds(n)?;
-        let popped = self.pop().expect(
-            "[ink_core::Vec::swap_remove] Error: \
-             expected `Some` value since vector is not empty",
-        );
-        if self.is_empty() {
-            // This is true if the vec had only 1 element.
+        let popped = match self.pop() {
+            Some(popped) => popped,
+            None => InternalError::MissingEntry.report("Vec::swap_remove"),
+        };
+        if n == self.len() {
+            // This is true if the removed element was the last element.
             return Some(popped)
         }
-        Some(self.cells.put(n, popped).expect(
-            "[ink_core::Vec::swap_remove] Error: \
-             expected success since the vector is not empty",
-        ))
+        match self.cells.put(n, popped) {
+            Some(removed) => Some(removed),
+            None => InternalError::MissingEntry.report("Vec::swap_remove"),
+        }
+    }
+}
+
+#[cfg(feature = "ink-debug-invariants")]
+impl<T> Vec<T>
+where
+    T: scale::Codec,
+{
+    /// Asserts that the internal invariants of the vector hold.
+    ///
+    /// # Note
+    ///
+    /// - This walks all elements of the vector and is therefore very expensive.
+    /// - Meant to be called from tests or after storage migrations.
+    ///
+    /// # Panics
+    ///
+    /// If an element within bounds is missing or an element at `len` exists.
+    pub fn debug_assert_invariants(&self) {
+        for n in 0..self.len() {
+            assert!(
+                self.cells.get(n).is_some(),
+                "[ink_core::Vec::debug_assert_invariants] Error: \
+                 missing element at index {}",
+                n,
+            );
+        }
+        assert!(
+            self.cells.get(self.len()).is_none(),
+            "[ink_core::Vec::debug_assert_invariants] Error: \
+             found an element at len {}",
+            self.len(),
+        );
     }
 }
 
@@ -382,7 +730,7 @@ where
     type Output = T;
 
     fn index(&self, index: u32) -> &Self::Output {
-        self.get(index).expect(
+        self.get(index).ink_expect(
             "[ink_core::Vec::index] Error: \
              expected `index` to be within bounds",
         )
@@ -394,7 +742,7 @@ where
     T: scale::Codec,
 {
     fn index_mut(&mut self, index: u32) -> &mut Self::Output {
-        self.get_mut(index).expect(
+        self.get_mut(index).ink_expect(
             "[ink_core::Vec::index] Error: \
              expected `index` to be within bounds",
         )
@@ -20,9 +20,18 @@
 #[cfg(test)]
 mod tests;
 
+#[cfg(all(test, feature = "ink-fuzz-tests"))]
+mod fuzz_tests;
+
 mod impls;
+mod slice;
 
-pub use self::impls::{
-    Iter,
-    Vec,
+pub use self::{
+    impls::{
+        EnumerateU32,
+        Iter,
+        Streaming,
+        Vec,
+    },
+    slice::VecSlice,
 };
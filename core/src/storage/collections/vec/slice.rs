@@ -0,0 +1,78 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_prelude::vec::Vec;
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+/// A copy of a range of elements of a storage `Vec`.
+///
+/// # Note
+///
+/// Is encodable and thus can be returned from messages to let
+/// callers page through the elements of a storage vector.
+/// Created by [`Vec::slice`](struct.Vec.html#method.slice).
+#[derive(Debug, Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct VecSlice<T> {
+    /// The index of the first copied element.
+    start: u32,
+    /// The length of the storage vector at the time of copying.
+    total_len: u32,
+    /// The copied elements.
+    elems: Vec<T>,
+}
+
+impl<T> VecSlice<T> {
+    /// Creates a new slice from the copied elements.
+    pub(crate) fn new(start: u32, total_len: u32, elems: Vec<T>) -> Self {
+        Self {
+            start,
+            total_len,
+            elems,
+        }
+    }
+
+    /// Returns the index of the first copied element.
+    pub fn start(&self) -> u32 {
+        self.start
+    }
+
+    /// Returns the index after the last copied element.
+    ///
+    /// This is where the next slice starts when paging through the storage vector.
+    pub fn end(&self) -> u32 {
+        self.start + self.elems.len() as u32
+    }
+
+    /// Returns the length of the storage vector at the time of copying.
+    pub fn total_len(&self) -> u32 {
+        self.total_len
+    }
+
+    /// Returns `true` if the storage vector has elements after this slice.
+    pub fn has_more(&self) -> bool {
+        self.end() < self.total_len
+    }
+
+    /// Returns the copied elements.
+    pub fn as_slice(&self) -> &[T] {
+        &self.elems
+    }
+
+    /// Returns the copied elements by value.
+    pub fn into_vec(self) -> Vec<T> {
+        self.elems
+    }
+}
@@ -214,6 +214,32 @@ fn swap_invalid() {
     vec.swap(0, u32::max_value());
 }
 
+#[test]
+#[should_panic]
+fn swap_same_invalid() {
+    let mut vec = new_filled_vec();
+    vec.swap(4, 4);
+}
+
+#[test]
+fn swap_unchecked() -> crate::env::Result<()> {
+    use crate::env;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = new_filled_vec();
+        unsafe { vec.swap_unchecked(0, 2) };
+        assert_eq!(vec.get(0), Some(&1337));
+        assert_eq!(vec.get(2), Some(&5));
+        // Swapping with a vacant cell beyond the length moves the element
+        // out of the vector until it is swapped back.
+        unsafe { vec.swap_unchecked(1, 4) };
+        assert_eq!(vec.get(1), None);
+        assert_eq!(vec.len(), 4);
+        unsafe { vec.swap_unchecked(4, 1) };
+        assert_eq!(vec.get(1), Some(&42));
+        Ok(())
+    })
+}
+
 #[test]
 fn swap_remove() {
     let mut vec = new_filled_vec();
@@ -226,6 +252,15 @@ fn swap_remove() {
     assert_eq!(vec.len(), 3);
 }
 
+#[test]
+fn swap_remove_last() {
+    let mut vec = new_filled_vec();
+    assert_eq!(vec.swap_remove(3), Some(77));
+    assert_eq!(vec.get(2), Some(&1337));
+    assert_eq!(vec.get(3), None);
+    assert_eq!(vec.len(), 3);
+}
+
 #[test]
 fn swap_remove_empty() {
     let mut vec = new_empty_vec::<i32>();
@@ -246,7 +281,7 @@ fn extend() {
     let mut vec1 = new_filled_vec();
     let arr = [1, 2, 3];
 
-    let mut expected = ink_prelude::vec::Vec::new();
+    let mut expected = ink_prelude::vec::Vec::<i32>::new();
     expected.extend(vec1.iter());
     expected.extend(&arr);
 
@@ -255,6 +290,14 @@ fn extend() {
     assert!(vec1.iter().eq(expected.iter()));
 }
 
+#[test]
+fn to_vec() {
+    let vec = new_filled_vec();
+    assert_eq!(vec.to_vec(0), ink_prelude::vec![]);
+    assert_eq!(vec.to_vec(2), ink_prelude::vec![5, 42]);
+    assert_eq!(vec.to_vec(10), ink_prelude::vec![5, 42, 1337, 77]);
+}
+
 #[test]
 fn regression_issue_193() {
     let mut vec = new_empty_vec();
@@ -263,3 +306,246 @@ fn regression_issue_193() {
     assert_eq!(vec.swap_remove(0), Some(5));
     assert_eq!(vec.len(), 0);
 }
+
+#[test]
+fn header_is_read_once() -> crate::env::Result<()> {
+    use crate::env;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let mut vec = new_filled_vec();
+        storage::Flush::flush(&mut vec);
+        // Reloads the same storage vector without its caches.
+        let vec = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            storage::Vec::<i32>::allocate_using(&mut alloc)
+        };
+        let ((), ops) = env::test::count_storage_ops::<env::DefaultEnvTypes, _, _>(
+            &contract,
+            || {
+                assert_eq!(vec.len(), 4);
+                assert!(!vec.is_empty());
+                assert_eq!(vec.get(1), Some(&42));
+                assert_eq!(vec.get(1), Some(&42));
+                assert_eq!(vec.len(), 4);
+            },
+        )?;
+        // One read for the header and one for the accessed element.
+        assert_eq!(ops.reads, 2);
+        Ok(())
+    })
+}
+
+#[test]
+fn eager_flush() -> crate::env::Result<()> {
+    use crate::env;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = new_filled_vec();
+        vec.flush();
+        // The flushed state is visible to a fresh view of the contract storage.
+        let reloaded = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            storage::Vec::<i32>::allocate_using(&mut alloc)
+        };
+        assert_eq!(reloaded.len(), 4);
+        assert_eq!(reloaded.get(3), Some(&77));
+        // Changes after the flush remain pending until the next flush.
+        vec.push(99);
+        let reloaded = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            storage::Vec::<i32>::allocate_using(&mut alloc)
+        };
+        assert_eq!(reloaded.len(), 4);
+        Ok(())
+    })
+}
+
+#[test]
+fn invalidate_cache() -> crate::env::Result<()> {
+    use crate::env;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = new_filled_vec();
+        vec.flush();
+        assert_eq!(vec.get(0), Some(&5));
+        // Mutates the contract storage behind the back of `vec`.
+        let mut other = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            storage::Vec::<i32>::allocate_using(&mut alloc)
+        };
+        other.replace(0, || 6);
+        other.push(99);
+        other.flush();
+        // Stale state is served until the cache is invalidated.
+        assert_eq!(vec.get(0), Some(&5));
+        assert_eq!(vec.len(), 4);
+        vec.invalidate_cache();
+        assert_eq!(vec.get(0), Some(&6));
+        assert_eq!(vec.len(), 5);
+        Ok(())
+    })
+}
+
+#[test]
+#[cfg(feature = "serde")]
+fn serialize_snapshot() {
+    let vec = new_filled_vec();
+    assert_eq!(serde_json::to_string(&vec).unwrap(), "[5,42,1337,77]");
+    assert_eq!(
+        serde_json::to_string(&new_empty_vec::<i32>()).unwrap(),
+        "[]"
+    );
+}
+
+#[test]
+fn slice() {
+    let vec = new_filled_vec();
+    let slice = vec.slice(1, 2);
+    assert_eq!(slice.as_slice(), &[42, 1337]);
+    assert_eq!((slice.start(), slice.end(), slice.total_len()), (1, 3, 4));
+    assert!(slice.has_more());
+    let slice = vec.slice(slice.end(), 2);
+    assert_eq!(slice.as_slice(), &[77]);
+    assert!(!slice.has_more());
+    assert!(vec.slice(5, 2).as_slice().is_empty());
+    // Slices are returned from messages in their encoded form.
+    use scale::{
+        Decode as _,
+        Encode as _,
+    };
+    let encoded = slice.encode();
+    assert_eq!(VecSlice::decode(&mut &encoded[..]), Ok(slice));
+}
+
+#[test]
+fn streaming() -> crate::env::Result<()> {
+    use crate::env;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let contract = env::account_id::<env::DefaultEnvTypes>()?;
+        let reads = || -> env::Result<usize> {
+            Ok(env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(&contract)?.0)
+        };
+        let mut vec = new_filled_vec();
+        vec.flush();
+        vec.invalidate_cache();
+        assert_eq!(vec.len(), 4);
+        let before = reads()?;
+        // Streamed elements are not cached and thus read again every time.
+        let streamed = vec.iter().streaming().collect::<std::vec::Vec<_>>();
+        assert_eq!(streamed, vec![5, 42, 1337, 77]);
+        assert_eq!(vec.iter().streaming().next_back(), Some(77));
+        assert_eq!(reads()? - before, 5);
+        // Cached elements are served from the cache.
+        assert_eq!(vec.iter().count(), 4);
+        let before = reads()?;
+        assert_eq!(vec.iter().streaming().sum::<i32>(), 5 + 42 + 1337 + 77);
+        assert_eq!(reads()?, before);
+        Ok(())
+    })
+}
+
+#[test]
+fn enumerate_u32() {
+    let vec = new_filled_vec();
+    let mut iter = vec.iter().enumerate_u32();
+    assert_eq!(iter.next(), Some((0, &5)));
+    assert_eq!(iter.next_back(), Some((3, &77)));
+    assert_eq!(iter.len(), 2);
+    assert_eq!(
+        iter.collect::<std::vec::Vec<_>>(),
+        vec![(1, &42), (2, &1337)]
+    );
+}
+
+#[test]
+fn for_each_indexed_mut() {
+    let mut vec = new_filled_vec();
+    vec.for_each_indexed_mut(|n, elem| *elem += n as i32);
+    assert_eq!(
+        vec.iter().cloned().collect::<std::vec::Vec<_>>(),
+        vec![5, 43, 1339, 80]
+    );
+}
+
+#[test]
+fn for_each_pair_mut() {
+    let mut vec = new_filled_vec();
+    let mut pairs = std::vec::Vec::new();
+    // Prefix sums in place.
+    vec.for_each_pair_mut(|n, first, second| {
+        pairs.push((n, *first, *second));
+        *second += *first;
+    });
+    assert_eq!(pairs, vec![(0, 5, 42), (1, 47, 1337), (2, 1384, 77)]);
+    assert_eq!(
+        vec.iter().cloned().collect::<std::vec::Vec<_>>(),
+        vec![5, 47, 1384, 1461]
+    );
+    // No pairs for vectors with less than two elements.
+    let mut vec = new_empty_vec::<i32>();
+    vec.push(1);
+    vec.for_each_pair_mut(|_, _, _| panic!("unexpected pair"));
+    assert_eq!(vec.get(0), Some(&1));
+}
+
+#[test]
+fn deep_clone_into() -> crate::env::Result<()> {
+    use crate::env;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = new_filled_vec();
+        let mut copy = unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x1; 32]));
+            vec.deep_clone_into(&mut alloc)
+        };
+        vec.push(1);
+        assert_eq!(vec.replace(0, || 6), Some(5));
+        vec.flush();
+        copy.flush();
+        copy.invalidate_cache();
+        assert_eq!(
+            copy.iter().cloned().collect::<std::vec::Vec<_>>(),
+            vec![5, 42, 1337, 77]
+        );
+        Ok(())
+    })
+}
+
+#[test]
+fn try_get() -> crate::env::Result<()> {
+    use crate::env;
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = new_filled_vec();
+        vec.flush();
+        vec.invalidate_cache();
+        let outdated = vec.key_ref(1).unwrap();
+        unsafe { storage::raw::write_raw(outdated.key(), &[0x01, 0x02]) }
+        assert_eq!(vec.try_get(0).map(|res| res.ok()), Some(Some(&5)));
+        match vec.try_get(1) {
+            Some(Err(env::EnvError::Decode(_))) => (),
+            _ => panic!("expected a decoding error"),
+        }
+        assert!(vec.try_get(4).is_none());
+        // Undecodable elements are not cached and thus can be migrated.
+        outdated.store(&0x0201);
+        assert_eq!(vec.try_get(1).map(|res| res.ok()), Some(Some(&0x0201)));
+        Ok(())
+    })
+}
+
+#[test]
+fn flush_traps_on_injected_fault() -> crate::env::Result<()> {
+    use crate::env::{
+        self,
+        test::{
+            Fault,
+            HostFn,
+        },
+    };
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        let mut vec = new_filled_vec();
+        // The writes of the elements are counted one by one.
+        env::test::inject_fault(HostFn::SetStorage, 3, Fault::Trap);
+        let flushed =
+            std::panic::catch_unwind(std::panic::AssertUnwindSafe(|| vec.flush()));
+        assert!(flushed.is_err());
+        Ok(())
+    })
+}
@@ -0,0 +1,86 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Panicking utilities of the storage primitives and collections.
+//!
+//! # Note
+//!
+//! With the `ink-minimal-panics` feature enabled the messages of all panics
+//! are stripped from `no_std` builds. This way contracts do not contain the
+//! messages and the formatting machinery required to render them while
+//! `std` builds, e.g. for tests, still panic with the full messages.
+
+/// Panics with the given message.
+///
+/// The message is stripped from `no_std` builds with `ink-minimal-panics` enabled.
+macro_rules! ink_panic {
+    ( $($msg:tt)* ) => {{
+        #[cfg(all(feature = "ink-minimal-panics", not(feature = "std")))]
+        {
+            let _ = format_args!($($msg)*);
+            panic!()
+        }
+        #[cfg(not(all(feature = "ink-minimal-panics", not(feature = "std"))))]
+        {
+            panic!($($msg)*)
+        }
+    }};
+}
+
+/// Marks unreachable code with the given message.
+///
+/// The message is stripped from `no_std` builds with `ink-minimal-panics` enabled.
+macro_rules! ink_unreachable {
+    ( $($msg:tt)* ) => {{
+        #[cfg(all(feature = "ink-minimal-panics", not(feature = "std")))]
+        {
+            let _ = format_args!($($msg)*);
+            panic!()
+        }
+        #[cfg(not(all(feature = "ink-minimal-panics", not(feature = "std"))))]
+        {
+            unreachable!($($msg)*)
+        }
+    }};
+}
+
+/// Unwraps values with messages that are stripped from `no_std` builds
+/// with `ink-minimal-panics` enabled.
+pub(crate) trait InkExpect<T> {
+    /// Returns the contained value or panics with the given message.
+    fn ink_expect(self, msg: &str) -> T;
+}
+
+impl<T> InkExpect<T> for Option<T> {
+    #[inline]
+    fn ink_expect(self, msg: &str) -> T {
+        match self {
+            Some(value) => value,
+            None => ink_panic!("{}", msg),
+        }
+    }
+}
+
+impl<T, E> InkExpect<T> for Result<T, E>
+where
+    E: core::fmt::Debug,
+{
+    #[inline]
+    fn ink_expect(self, msg: &str) -> T {
+        match self {
+            Ok(value) => value,
+            Err(_error) => ink_panic!("{}: {:?}", msg, _error),
+        }
+    }
+}
@@ -12,6 +12,11 @@
 // See the License for the specific language governing permissions and
 // limitations under the License.
 
+use crate::env::{
+    self,
+    hash::Blake2x128,
+};
+
 /// Types that are able to flush their state into the contract storage.
 ///
 /// # Note
@@ -21,6 +26,18 @@
 /// or interrupted (e.g. due to calling a remote contract) we have to flush
 /// all cached state into the contract storage.
 ///
+/// Flushing happens automatically at the end of every mutating message.
+/// Flush explicitly before calling into a contract that might call back
+/// into this contract so that it observes the up-to-date contract storage.
+/// The storage collections provide an inherent `flush` method for this so
+/// that contracts do not have to import this trait.
+///
+/// Likewise, after calling into a contract that might have called back into
+/// this contract and mutated its storage, the storage collections provide an
+/// inherent `invalidate_cache` method that drops their cached state so that it
+/// is loaded again from the contract storage upon the next access. Changes that
+/// have not yet been flushed are lost.
+///
 /// # Implementation Hints
 ///
 /// Caching types provided by ink! are `SyncCell` for caching of a single data
@@ -43,6 +60,23 @@ pub trait Flush {
 
 pub use ink_core_derive::Flush;
 
+/// The fingerprint of the encoding of a cached storage cell value.
+///
+/// # Note
+///
+/// Caches remember the fingerprint of a value that is in sync with the
+/// contract storage before it is mutated. Upon flushing they skip writing
+/// back values whose encoding still has the same fingerprint.
+pub(crate) type Fingerprint = [u8; 16];
+
+/// Returns the fingerprint of the encoded cell value.
+pub(crate) fn fingerprint<T>(cell_val: &Option<T>) -> Fingerprint
+where
+    T: scale::Encode,
+{
+    env::hash_encoded::<Blake2x128, _>(cell_val)
+}
+
 macro_rules! impl_empty_flush_for {
     ( $($ty:ty),* ) => {
         $(
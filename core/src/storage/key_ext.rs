@@ -0,0 +1,74 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_prelude::vec::Vec;
+use ink_primitives::Key;
+
+use crate::env;
+
+/// Extends [`Key`] with key derivations that require the environment.
+///
+/// # Note
+///
+/// `Key` is defined in `ink_primitives` which has no access to the hash
+/// functions of the environment, hence this extension trait.
+pub trait KeyExt {
+    /// Derives the child key for the given label.
+    ///
+    /// The child key is the BLAKE2 256-bit hash of the parent key's bytes
+    /// followed by the label. This is the standard derivation for storage
+    /// regions that are addressed by hashed keys, e.g. namespaces.
+    ///
+    /// # Note
+    ///
+    /// Uses the hash function of the chain in Wasm builds and its pure
+    /// Rust implementation off-chain, so both yield the same keys.
+    fn child(&self, label: &[u8]) -> Key;
+}
+
+impl KeyExt for Key {
+    fn child(&self, label: &[u8]) -> Key {
+        let mut preimage = Vec::with_capacity(32 + label.len());
+        preimage.extend_from_slice(self.as_bytes());
+        preimage.extend_from_slice(label);
+        let mut output = [0x00; 32];
+        env::hash::blake2_256(&preimage, &mut output);
+        Key(output)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn child_is_hash_of_parent_and_label() {
+        let parent = Key([0x42; 32]);
+        let mut preimage = [0x42; 32].to_vec();
+        preimage.extend_from_slice(b"balances");
+        let expected = Key(env::hash_bytes::<env::hash::Blake2x256>(&preimage));
+        assert_eq!(parent.child(b"balances"), expected);
+    }
+
+    #[test]
+    fn child_is_deterministic_and_distinct() {
+        let parent = Key([0x42; 32]);
+        assert_eq!(parent.child(b"a"), parent.child(b"a"));
+        assert_ne!(parent.child(b"a"), parent.child(b"b"));
+        assert_ne!(parent.child(b"a"), Key([0x43; 32]).child(b"a"));
+        assert_ne!(parent.child(b""), parent);
+        // Derivations can be nested.
+        assert_ne!(parent.child(b"a").child(b"b"), parent.child(b"ab"));
+    }
+}
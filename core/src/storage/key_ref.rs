@@ -0,0 +1,173 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use core::marker::PhantomData;
+
+use ink_primitives::Key;
+use scale::{
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::{
+    HasTypeDef,
+    NamedField,
+    TypeDef,
+    TypeDefStruct,
+    TypeId,
+};
+
+use crate::{
+    env,
+    storage::{
+        Flush,
+        InkExpect as _,
+    },
+};
+
+/// A typed reference to a single cell of the contract storage.
+///
+/// Can be stored in the contract storage itself in order to durably refer
+/// to an element that is owned by another storage entity, e.g. an element
+/// of a storage `Vec` obtained through
+/// [`Vec::key_ref`](struct.Vec.html#method.key_ref).
+///
+/// # Note
+///
+/// Loads and stores go directly to the contract storage and bypass the
+/// cache of the storage entity owning the cell:
+///
+/// - Loads do not observe changes of the owner that have not been flushed.
+/// - Stores are not observed by the owner until it invalidates its cache.
+///   Changes of the owner to the same cell overwrite them upon its flush.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(TypeId))]
+pub struct KeyRef<T> {
+    /// The key of the referenced cell.
+    key: Key,
+    /// The type of the referenced value.
+    marker: PhantomData<fn() -> T>,
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasTypeDef for KeyRef<T> {
+    fn type_def() -> TypeDef {
+        TypeDefStruct::new(vec![NamedField::of::<Key>("key")]).into()
+    }
+}
+
+impl<T> Clone for KeyRef<T> {
+    fn clone(&self) -> Self {
+        *self
+    }
+}
+
+impl<T> Copy for KeyRef<T> {}
+
+impl<T> PartialEq for KeyRef<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.key == other.key
+    }
+}
+
+impl<T> Eq for KeyRef<T> {}
+
+/// No need to forward flush since the referenced cell is not cached.
+impl<T> Flush for KeyRef<T> {}
+
+impl<T> KeyRef<T> {
+    /// Creates a reference to the cell at the given key.
+    pub fn new(key: Key) -> Self {
+        Self {
+            key,
+            marker: PhantomData,
+        }
+    }
+
+    /// Returns the key of the referenced cell.
+    pub fn key(&self) -> Key {
+        self.key
+    }
+
+    /// Clears the referenced cell.
+    pub fn clear(&self) {
+        env::clear_contract_storage(self.key)
+    }
+}
+
+impl<T> KeyRef<T>
+where
+    T: scale::Decode,
+{
+    /// Loads the value of the referenced cell if any.
+    ///
+    /// # Panics
+    ///
+    /// If the stored value cannot be decoded as `T`.
+    pub fn load(&self) -> Option<T> {
+        env::get_contract_storage::<T>(self.key)
+            .map(|result| result.ink_expect("could not decode T from referenced cell"))
+    }
+}
+
+impl<T> KeyRef<T>
+where
+    T: scale::Encode,
+{
+    /// Stores the value into the referenced cell.
+    pub fn store(&self, val: &T) {
+        env::set_contract_storage(self.key, val)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        self,
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+    };
+
+    #[test]
+    fn refer_to_vec_element() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let (mut vec, mut refs) = unsafe {
+                let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+                let vec = storage::Vec::<u32>::allocate_using(&mut alloc);
+                let refs = storage::Vec::<KeyRef<u32>>::allocate_using(&mut alloc);
+                (vec.initialize_into(()), refs.initialize_into(()))
+            };
+            vec.extend(vec![10, 20, 30]);
+            assert_eq!(vec.key_ref(3), None);
+            refs.push(vec.key_ref(1).unwrap());
+            vec.flush();
+            refs.flush();
+            // The reference survives being persisted.
+            refs.invalidate_cache();
+            let elem = *refs.get(0).unwrap();
+            assert_eq!(elem, vec.key_ref(1).unwrap());
+            assert_eq!(elem.load(), Some(20));
+            elem.store(&21);
+            vec.invalidate_cache();
+            assert_eq!(vec.get(1), Some(&21));
+            elem.clear();
+            assert_eq!(elem.load(), None);
+            Ok(())
+        })
+    }
+}
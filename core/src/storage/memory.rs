@@ -0,0 +1,179 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutStruct,
+    StorageLayout,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    Flush,
+};
+
+/// A value that lives in the contract storage struct without being stored.
+///
+/// # Note
+///
+/// The wrapped value is never written to nor read from the contract storage
+/// and it does not occupy any storage cells. It is reset to its default
+/// value upon every contract execution and thus serves as scratch memory
+/// that lives only as long as a single call, e.g. for memoized computations.
+///
+/// Use [`Value`](struct.Value.html) for values that must persist between calls.
+#[derive(Debug, Default, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct Memory<T> {
+    /// The transient value.
+    value: T,
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasLayout for Memory<T>
+where
+    T: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        // Occupies no storage cells.
+        LayoutStruct::new(Self::meta_type(), vec![]).into()
+    }
+}
+
+impl<T> AllocateUsing for Memory<T>
+where
+    T: Default,
+{
+    #[inline]
+    unsafe fn allocate_using<A>(_alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Default::default()
+    }
+}
+
+impl<T> Initialize for Memory<T>
+where
+    T: Default,
+{
+    type Args = ();
+
+    #[inline(always)]
+    fn default_value() -> Option<Self::Args> {
+        Some(())
+    }
+
+    #[inline]
+    fn initialize(&mut self, _args: Self::Args) {
+        self.value = Default::default();
+    }
+}
+
+impl<T> Flush for Memory<T> {}
+
+impl<T> Memory<T> {
+    /// Creates a new transient value.
+    pub fn new(value: T) -> Self {
+        Self { value }
+    }
+
+    /// Returns a shared reference to the transient value.
+    pub fn get(&self) -> &T {
+        &self.value
+    }
+
+    /// Returns an exclusive reference to the transient value.
+    pub fn get_mut(&mut self) -> &mut T {
+        &mut self.value
+    }
+
+    /// Sets the transient value.
+    pub fn set(&mut self, value: T) {
+        self.value = value;
+    }
+
+    /// Returns the transient value, consuming `self`.
+    pub fn into_inner(self) -> T {
+        self.value
+    }
+}
+
+impl<T> core::ops::Deref for Memory<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        self.get()
+    }
+}
+
+impl<T> core::ops::DerefMut for Memory<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.get_mut()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        env,
+        storage::{
+            alloc::BumpAlloc,
+            Value,
+        },
+    };
+    use ink_primitives::Key;
+
+    #[test]
+    fn occupies_no_storage() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let contract = env::account_id::<env::DefaultEnvTypes>()?;
+            let (mut memory, mut value) = unsafe {
+                let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+                let memory: Memory<u32> = Memory::allocate_using(&mut alloc);
+                let value: Value<u32> = Value::allocate_using(&mut alloc);
+                (memory, value)
+            };
+            // The transient value occupies no cells so the value got the first key.
+            let key = Key([0x0; 32]);
+            value.initialize(5);
+            value.flush();
+            assert_eq!(env::get_contract_storage::<u32>(key), Some(Ok(5)));
+
+            assert_eq!(*memory, 0);
+            *memory += 42;
+            memory.flush();
+            assert_eq!(memory.get(), &42);
+            assert_eq!(
+                env::test::get_contract_storage_rw::<env::DefaultEnvTypes>(&contract)?,
+                (1, 1)
+            );
+
+            // Transient values start out with their default upon every execution.
+            let memory: Memory<u32> = unsafe {
+                Memory::allocate_using(&mut BumpAlloc::from_raw_parts(Key([0x0; 32])))
+            };
+            assert_eq!(memory.into_inner(), 0);
+            Ok(())
+        })
+    }
+}
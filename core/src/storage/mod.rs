@@ -69,11 +69,22 @@
 //! - `TypedChunk`
 //! - `SyncChunk`
 
+#[macro_use]
+mod expect;
+
 pub mod alloc;
 pub mod cell;
+mod checked_cell;
 pub mod chunk;
 mod collections;
 mod flush;
+mod key_ext;
+mod key_ref;
+mod memory;
+mod packed;
+pub mod raw;
+#[cfg(feature = "ink-test-utils")]
+pub mod test_utils;
 mod value;
 
 pub use self::{
@@ -82,6 +93,10 @@ pub use self::{
             self,
             BinaryHeap,
         },
+        bitstash::{
+            self,
+            BitStash,
+        },
         bitvec::{
             self,
             BitVec,
@@ -90,10 +105,30 @@ pub use self::{
             self,
             BTreeMap,
         },
+        enumerable_map::{
+            self,
+            EnumerableMap,
+        },
         hash_map::{
             self,
             HashMap,
         },
+        mapping::{
+            self,
+            Mapping,
+        },
+        nested_map::{
+            self,
+            NestedMap,
+        },
+        priority_queue::{
+            self,
+            PriorityQueue,
+        },
+        sorted_vec::{
+            self,
+            SortedVec,
+        },
         stash::{
             self,
             Stash,
@@ -106,8 +141,34 @@ pub use self::{
     flush::Flush,
 };
 
+pub(crate) use self::{
+    expect::InkExpect,
+    flush::{
+        fingerprint,
+        Fingerprint,
+    },
+};
+
 #[doc(inline)]
 pub use self::alloc::Allocator;
 
+#[doc(inline)]
+pub use self::checked_cell::{
+    ArithmeticError,
+    CheckedCell,
+};
+
+#[doc(inline)]
+pub use self::key_ext::KeyExt;
+
+#[doc(inline)]
+pub use self::key_ref::KeyRef;
+
+#[doc(inline)]
+pub use self::memory::Memory;
+
+#[doc(inline)]
+pub use self::packed::Packed;
+
 #[doc(inline)]
 pub use self::value::Value;
@@ -0,0 +1,234 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+#[cfg(feature = "ink-generate-abi")]
+use ink_abi::{
+    HasLayout,
+    LayoutField,
+    LayoutStruct,
+    StorageLayout,
+};
+use scale::{
+    Decode,
+    Encode,
+};
+#[cfg(feature = "ink-generate-abi")]
+use type_metadata::Metadata;
+
+use crate::storage::{
+    alloc::{
+        Allocate,
+        AllocateUsing,
+        Initialize,
+    },
+    cell::SyncCell,
+    Flush,
+    InkExpect as _,
+};
+
+/// Several small fields packed into a single storage cell.
+///
+/// Wraps a tuple, e.g. `Packed<(AccountId, bool, Balance)>` for the owner,
+/// a paused flag and the total supply of a token, so that all of its fields
+/// are loaded with a single read and written back with a single write.
+///
+/// # Note
+///
+/// The fields are accessed through `get_0`, `get_mut_0`, `set_0` etc.
+/// Mutating any field marks the whole cell as dirty. The cell is only
+/// written back upon flush if one of its fields actually changed.
+#[derive(Debug, Encode, Decode)]
+#[cfg_attr(feature = "ink-generate-abi", derive(Metadata))]
+pub struct Packed<T> {
+    /// The cell storing all fields.
+    cell: SyncCell<T>,
+}
+
+#[cfg(feature = "ink-generate-abi")]
+impl<T> HasLayout for Packed<T>
+where
+    T: Metadata + 'static,
+{
+    fn layout(&self) -> StorageLayout {
+        LayoutStruct::new(Self::meta_type(), vec![LayoutField::of("cell", &self.cell)])
+            .into()
+    }
+}
+
+impl<T> AllocateUsing for Packed<T> {
+    #[inline]
+    unsafe fn allocate_using<A>(alloc: &mut A) -> Self
+    where
+        A: Allocate,
+    {
+        Self {
+            cell: SyncCell::allocate_using(alloc),
+        }
+    }
+}
+
+impl<T> Initialize for Packed<T>
+where
+    T: Encode,
+{
+    type Args = T;
+
+    #[inline]
+    fn initialize(&mut self, args: Self::Args) {
+        self.cell.set(args);
+    }
+}
+
+impl<T> Flush for Packed<T>
+where
+    T: Encode + Flush,
+{
+    #[inline]
+    fn flush(&mut self) {
+        self.cell.flush()
+    }
+}
+
+impl<T> Packed<T>
+where
+    T: scale::Codec,
+{
+    /// Returns a reference to all fields.
+    pub fn get(&self) -> &T {
+        self.cell
+            .get()
+            .ink_expect("packed fields have not been initialized")
+    }
+
+    /// Returns a mutable reference to all fields.
+    pub fn get_mut(&mut self) -> &mut T {
+        self.cell
+            .get_mut()
+            .ink_expect("packed fields have not been initialized")
+    }
+
+    /// Sets all fields.
+    pub fn set(&mut self, val: T) {
+        self.cell.set(val)
+    }
+
+    /// Drops the cached fields so that they are loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// # Note
+    ///
+    /// Changes that have not yet been flushed are lost.
+    pub fn invalidate_cache(&mut self) {
+        self.cell.invalidate_cache()
+    }
+}
+
+macro_rules! impl_field_accessors_for_packed {
+    ( $( ($n:tt, $ty:ident) ),* ) => {
+        impl<$($ty),*> Packed<($($ty,)*)>
+        where
+            $( $ty: scale::Codec, )*
+        {
+            $(
+                paste::item! {
+                    /// Returns a reference to the field with the index in the name.
+                    pub fn [<get_ $n>](&self) -> &$ty {
+                        &self.get().$n
+                    }
+
+                    /// Returns a mutable reference to the field with the index in the name.
+                    pub fn [<get_mut_ $n>](&mut self) -> &mut $ty {
+                        &mut self.get_mut().$n
+                    }
+
+                    /// Sets the field with the index in the name.
+                    pub fn [<set_ $n>](&mut self, val: $ty) {
+                        self.get_mut().$n = val;
+                    }
+                }
+            )*
+        }
+    };
+}
+
+impl_field_accessors_for_packed!((0, A), (1, B));
+impl_field_accessors_for_packed!((0, A), (1, B), (2, C));
+impl_field_accessors_for_packed!((0, A), (1, B), (2, C), (3, D));
+impl_field_accessors_for_packed!((0, A), (1, B), (2, C), (3, D), (4, E));
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{
+        env,
+        storage::alloc::BumpAlloc,
+    };
+    use ink_primitives::Key;
+
+    type Header = Packed<(u32, bool, u128)>;
+
+    fn new_header() -> Header {
+        unsafe {
+            let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+            Header::allocate_using(&mut alloc).initialize_into((7, false, 1_000))
+        }
+    }
+
+    #[test]
+    fn field_accessors() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let mut header = new_header();
+            assert_eq!(header.get(), &(7, false, 1_000));
+            header.set_1(true);
+            *header.get_mut_2() += 1;
+            assert_eq!(header.get_0(), &7);
+            assert_eq!(header.get_1(), &true);
+            assert_eq!(header.get_2(), &1_001);
+            header.flush();
+            header.invalidate_cache();
+            assert_eq!(header.get(), &(7, true, 1_001));
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn single_cell() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let account_id = env::account_id::<env::DefaultEnvTypes>()?;
+            let mut header = new_header();
+            header.flush();
+            assert_eq!(
+                env::test::count_used_storage_cells::<env::DefaultEnvTypes>(&account_id)?,
+                1
+            );
+            // Setting all fields writes back a single cell.
+            header.set_0(8);
+            header.set_1(true);
+            header.set_2(0);
+            let (_, ops) = env::test::count_storage_ops::<env::DefaultEnvTypes, _, _>(
+                &account_id,
+                || header.flush(),
+            )?;
+            assert_eq!(ops.writes, 1);
+            // Fields that did not change are not written back.
+            header.set_0(8);
+            let (_, ops) = env::test::count_storage_ops::<env::DefaultEnvTypes, _, _>(
+                &account_id,
+                || header.flush(),
+            )?;
+            assert_eq!(ops.writes, 0);
+            Ok(())
+        })
+    }
+}
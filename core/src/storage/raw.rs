@@ -0,0 +1,139 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Untyped access to the cells of the contract storage.
+//!
+//! Meant for migrations and custom data structures that need to read or
+//! write cells bypassing the typed storage entities. The bytes are stored
+//! as they are without any SCALE length prefix.
+//!
+//! # Safety
+//!
+//! Writing cells is unsafe since the storage entities owning them neither
+//! notice the changes in their caches nor check that the bytes decode to
+//! their types. Their cached state may overwrite the written bytes upon the
+//! next flush and decoding the bytes may fail with a panic. Only write
+//! cells that are not owned by any storage entity that is alive at the time
+//! or call `invalidate_cache` on the owning entities afterwards.
+
+use ink_prelude::vec::Vec;
+use ink_primitives::Key;
+
+use crate::{
+    env,
+    storage::InkExpect as _,
+};
+
+/// Decodes the whole remaining input as raw bytes.
+struct RawDecode(Vec<u8>);
+
+impl scale::Decode for RawDecode {
+    fn decode<I: scale::Input>(input: &mut I) -> Result<Self, scale::Error> {
+        let len = input
+            .remaining_len()?
+            .ok_or_else(|| scale::Error::from("unknown length of the raw cell"))?;
+        let mut bytes = ink_prelude::vec![0x00; len];
+        input.read(&mut bytes)?;
+        Ok(Self(bytes))
+    }
+}
+
+/// Encodes the raw bytes as they are.
+struct RawEncode<'a>(&'a [u8]);
+
+impl scale::Encode for RawEncode<'_> {
+    fn size_hint(&self) -> usize {
+        self.0.len()
+    }
+
+    fn encode_to<W: scale::Output>(&self, dest: &mut W) {
+        dest.write(self.0)
+    }
+}
+
+/// Returns the raw bytes stored in the cell at the given key if any.
+///
+/// # Note
+///
+/// Changes of storage entities that have not yet been flushed are not visible.
+pub fn read_raw(key: Key) -> Option<Vec<u8>> {
+    env::get_contract_storage::<RawDecode>(key).map(|result| {
+        result
+            .ink_expect("could not read raw bytes from storage cell")
+            .0
+    })
+}
+
+/// Writes the raw bytes to the cell at the given key.
+///
+/// # Safety
+///
+/// See the [module documentation](index.html#safety).
+pub unsafe fn write_raw(key: Key, bytes: &[u8]) {
+    env::set_contract_storage(key, &RawEncode(bytes))
+}
+
+/// Clears the cell at the given key.
+///
+/// # Safety
+///
+/// See the [module documentation](index.html#safety).
+pub unsafe fn clear_raw(key: Key) {
+    env::clear_contract_storage(key)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::storage::{
+        alloc::{
+            AllocateUsing,
+            BumpAlloc,
+            Initialize,
+        },
+        Value,
+    };
+
+    #[test]
+    fn read_write_clear() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let key = Key([0x42; 32]);
+            assert_eq!(read_raw(key), None);
+            unsafe { write_raw(key, &[1, 2, 3]) };
+            assert_eq!(read_raw(key), Some(vec![1, 2, 3]));
+            unsafe { write_raw(key, &[]) };
+            assert_eq!(read_raw(key), Some(vec![]));
+            unsafe { clear_raw(key) };
+            assert_eq!(read_raw(key), None);
+            Ok(())
+        })
+    }
+
+    #[test]
+    fn interop_with_typed_cells() -> env::Result<()> {
+        env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+            let key = Key([0x0; 32]);
+            let mut value = unsafe {
+                let mut alloc = BumpAlloc::from_raw_parts(key);
+                Value::<u32>::allocate_using(&mut alloc).initialize_into(0x0102_0304)
+            };
+            value.flush();
+            assert_eq!(read_raw(key), Some(vec![4, 3, 2, 1]));
+            unsafe { write_raw(key, &5_u32.to_le_bytes()) };
+            value.invalidate_cache();
+            assert_eq!(*value.get(), 5);
+            Ok(())
+        })
+    }
+}
@@ -0,0 +1,367 @@
+// Copyright 2019-2020 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Harness for cross-checking the storage collections against their
+//! `std` counterparts using randomly generated operation sequences.
+//!
+//! Authors of custom storage collections can reuse it by enabling the
+//! `ink-test-utils` feature: implement [`VecLike`] or [`MapLike`] for the
+//! collection and pass generated [`VecOp`] or [`MapOp`] sequences to
+//! [`cross_check_vec`] or [`cross_check_map`] from within [`check`].
+//!
+//! # Example
+//!
+//! ```no_compile
+//! fn behaves_like_std_vec(ops: Vec<VecOp<u8>>) -> bool {
+//!     test_utils::run_isolated(|| cross_check_vec(&mut new_my_vec(), ops))
+//! }
+//!
+//! #[test]
+//! fn my_vec_behaves_like_std_vec() {
+//!     test_utils::check(behaves_like_std_vec)
+//! }
+//! ```
+//!
+//! # Note
+//!
+//! The operations are generated from a fixed seed so that failures are
+//! reproducible. Set `INK_FUZZ_SEED` in order to explore other sequences
+//! and `INK_FUZZ_TESTS` in order to change the number of sequences.
+
+use crate::{
+    env,
+    storage,
+};
+use core::{
+    fmt::Debug,
+    hash::Hash,
+};
+use quickcheck::{
+    Arbitrary,
+    Gen,
+    QuickCheck,
+    StdGen,
+};
+use rand::{
+    rngs::StdRng,
+    Rng,
+    SeedableRng,
+};
+use std::collections::HashMap;
+
+/// The seed used if `INK_FUZZ_SEED` is not set.
+const DEFAULT_SEED: u64 = 0x1337;
+
+/// The number of operation sequences checked if `INK_FUZZ_TESTS` is not set.
+const DEFAULT_TESTS: u64 = 200;
+
+/// The maximum number of operations in a sequence.
+const MAX_OPS: usize = 300;
+
+/// Returns the value of the environment variable or the default.
+fn env_var_or(name: &str, default: u64) -> u64 {
+    std::env::var(name)
+        .ok()
+        .and_then(|value| value.parse().ok())
+        .unwrap_or(default)
+}
+
+/// Checks the property for many randomly generated sequences of operations.
+///
+/// # Panics
+///
+/// If the property fails for any of the sequences. The failing sequence
+/// is shrunk to a minimal reproduction before it is reported.
+pub fn check<Op>(property: fn(Vec<Op>) -> bool)
+where
+    Op: Arbitrary + Debug,
+{
+    let rng = StdRng::seed_from_u64(env_var_or("INK_FUZZ_SEED", DEFAULT_SEED));
+    let tests = env_var_or("INK_FUZZ_TESTS", DEFAULT_TESTS);
+    env::test::run_test::<env::DefaultEnvTypes, _>(|_| {
+        QuickCheck::with_gen(StdGen::new(rng, MAX_OPS))
+            .tests(tests)
+            .max_tests(tests * 10)
+            .quickcheck(property);
+        Ok(())
+    })
+    .expect("the off-chain environment has not yet been initialized")
+}
+
+/// Runs the cross-check and resets the off-chain environment afterwards.
+///
+/// Returns `true` if the cross-check finished. Mismatches between a
+/// storage collection and its model are reported by panicking.
+pub fn run_isolated<F>(f: F) -> bool
+where
+    F: FnOnce(),
+{
+    let initial = env::test::snapshot();
+    let outcome = std::panic::catch_unwind(std::panic::AssertUnwindSafe(f));
+    env::test::restore(initial).expect("the snapshot has been taken above");
+    if let Err(payload) = outcome {
+        std::panic::resume_unwind(payload)
+    }
+    true
+}
+
+/// Maps the randomly generated index into `0..=len`.
+///
+/// Also yields the first out of bounds index in order to cover
+/// the out of bounds behaviour of the collections.
+pub fn bounded_index(index: u32, len: u32) -> u32 {
+    index % (len + 1)
+}
+
+/// Returns a key drawn from a small domain so that keys collide often.
+pub fn gen_key<G>(g: &mut G) -> i32
+where
+    G: Gen,
+{
+    g.gen_range(-32, 32)
+}
+
+/// An operation on a vector-like collection.
+#[derive(Debug, Clone)]
+pub enum VecOp<T> {
+    Push(T),
+    Pop,
+    Get(u32),
+    SwapRemove(u32),
+}
+
+impl<T> Arbitrary for VecOp<T>
+where
+    T: Arbitrary,
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        match g.gen_range(0, 5) {
+            0 | 1 => VecOp::Push(T::arbitrary(g)),
+            2 => VecOp::Pop,
+            3 => VecOp::Get(g.gen()),
+            _ => VecOp::SwapRemove(g.gen()),
+        }
+    }
+}
+
+/// An operation on a map-like collection.
+///
+/// # Note
+///
+/// Use key types with a small domain, e.g. `u8`, so that keys collide often.
+#[derive(Debug, Clone)]
+pub enum MapOp<K, V> {
+    Insert(K, V),
+    Remove(K),
+    Get(K),
+}
+
+impl<K, V> Arbitrary for MapOp<K, V>
+where
+    K: Arbitrary,
+    V: Arbitrary,
+{
+    fn arbitrary<G: Gen>(g: &mut G) -> Self {
+        match g.gen_range(0, 5) {
+            0 | 1 => MapOp::Insert(K::arbitrary(g), V::arbitrary(g)),
+            2 | 3 => MapOp::Remove(K::arbitrary(g)),
+            _ => MapOp::Get(K::arbitrary(g)),
+        }
+    }
+}
+
+/// A vector-like collection that can be cross-checked against a `std` vector.
+pub trait VecLike<T> {
+    /// Returns the number of elements.
+    fn len(&self) -> u32;
+    /// Returns `true` if there are no elements.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Appends an element to the back.
+    fn push(&mut self, val: T);
+    /// Removes the last element and returns it if any.
+    fn pop(&mut self) -> Option<T>;
+    /// Returns the `n`-th element if any.
+    fn get(&self, n: u32) -> Option<&T>;
+    /// Removes the `n`-th element and replaces it with the last element.
+    fn swap_remove(&mut self, n: u32) -> Option<T>;
+    /// Asserts the internal invariants of the collection.
+    fn assert_invariants(&self) {}
+}
+
+/// A map-like collection that can be cross-checked against a `std` hash map.
+pub trait MapLike<K, V> {
+    /// Returns the number of entries.
+    fn len(&self) -> u32;
+    /// Returns `true` if there are no entries.
+    fn is_empty(&self) -> bool {
+        self.len() == 0
+    }
+    /// Inserts the value for the key and returns the previous value if any.
+    fn insert(&mut self, key: K, val: V) -> Option<V>;
+    /// Removes the entry for the key and returns its value if any.
+    fn remove(&mut self, key: &K) -> Option<V>;
+    /// Returns the value for the key if any.
+    fn get(&self, key: &K) -> Option<&V>;
+    /// Asserts the internal invariants of the collection.
+    fn assert_invariants(&self) {}
+}
+
+/// Applies the operations to the collection and a `std` vector and asserts
+/// that both always contain the same elements and that the invariants hold.
+///
+/// # Panics
+///
+/// Upon the first mismatch between the collection and the `std` vector.
+pub fn cross_check_vec<C, T>(collection: &mut C, ops: Vec<VecOp<T>>)
+where
+    C: VecLike<T>,
+    T: Debug + PartialEq + Clone,
+{
+    let mut model = Vec::new();
+    for op in ops {
+        let len = model.len() as u32;
+        match op {
+            VecOp::Push(val) => {
+                collection.push(val.clone());
+                model.push(val);
+            }
+            VecOp::Pop => assert_eq!(collection.pop(), model.pop()),
+            VecOp::Get(n) => {
+                let n = bounded_index(n, len);
+                assert_eq!(collection.get(n), model.get(n as usize));
+            }
+            VecOp::SwapRemove(n) => {
+                let n = bounded_index(n, len);
+                let expected = if n < len {
+                    Some(model.swap_remove(n as usize))
+                } else {
+                    None
+                };
+                assert_eq!(collection.swap_remove(n), expected);
+            }
+        }
+        assert_eq!(collection.len(), model.len() as u32);
+        collection.assert_invariants();
+    }
+    for (n, val) in model.iter().enumerate() {
+        assert_eq!(collection.get(n as u32), Some(val));
+    }
+}
+
+/// Applies the operations to the collection and a `std` hash map and asserts
+/// that both always contain the same entries and that the invariants hold.
+///
+/// # Panics
+///
+/// Upon the first mismatch between the collection and the `std` hash map.
+pub fn cross_check_map<C, K, V>(collection: &mut C, ops: Vec<MapOp<K, V>>)
+where
+    C: MapLike<K, V>,
+    K: Debug + Hash + Eq + Clone,
+    V: Debug + PartialEq + Clone,
+{
+    let mut model = HashMap::new();
+    for op in ops {
+        match op {
+            MapOp::Insert(key, val) => {
+                assert_eq!(
+                    collection.insert(key.clone(), val.clone()),
+                    model.insert(key, val)
+                );
+            }
+            MapOp::Remove(key) => assert_eq!(collection.remove(&key), model.remove(&key)),
+            MapOp::Get(key) => assert_eq!(collection.get(&key), model.get(&key)),
+        }
+        assert_eq!(collection.len(), model.len() as u32);
+        collection.assert_invariants();
+    }
+    for (key, val) in &model {
+        assert_eq!(collection.get(key), Some(val));
+    }
+}
+
+impl<T> VecLike<T> for storage::Vec<T>
+where
+    T: scale::Codec,
+{
+    fn len(&self) -> u32 {
+        storage::Vec::len(self)
+    }
+
+    fn push(&mut self, val: T) {
+        storage::Vec::push(self, val)
+    }
+
+    fn pop(&mut self) -> Option<T> {
+        storage::Vec::pop(self)
+    }
+
+    fn get(&self, n: u32) -> Option<&T> {
+        storage::Vec::get(self, n)
+    }
+
+    fn swap_remove(&mut self, n: u32) -> Option<T> {
+        storage::Vec::swap_remove(self, n)
+    }
+
+    fn assert_invariants(&self) {
+        self.debug_assert_invariants()
+    }
+}
+
+impl<K, V> MapLike<K, V> for storage::HashMap<K, V>
+where
+    K: scale::Codec + Hash + Eq,
+    V: scale::Codec,
+{
+    fn len(&self) -> u32 {
+        storage::HashMap::len(self)
+    }
+
+    fn insert(&mut self, key: K, val: V) -> Option<V> {
+        storage::HashMap::insert(self, key, val)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        storage::HashMap::remove(self, key)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        storage::HashMap::get(self, key)
+    }
+}
+
+impl<K, V> MapLike<K, V> for storage::BTreeMap<K, V>
+where
+    K: scale::Codec + Ord,
+    V: scale::Codec,
+{
+    fn len(&self) -> u32 {
+        storage::BTreeMap::len(self)
+    }
+
+    fn insert(&mut self, key: K, val: V) -> Option<V> {
+        storage::BTreeMap::insert(self, key, val)
+    }
+
+    fn remove(&mut self, key: &K) -> Option<V> {
+        storage::BTreeMap::remove(self, key)
+    }
+
+    fn get(&self, key: &K) -> Option<&V> {
+        storage::BTreeMap::get(self, key)
+    }
+}
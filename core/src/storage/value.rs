@@ -35,6 +35,7 @@ use crate::storage::{
     },
     cell::SyncCell,
     Flush,
+    InkExpect as _,
 };
 
 // Missing traits:
@@ -74,6 +75,20 @@ where
     }
 }
 
+#[cfg(all(feature = "std", feature = "serde"))]
+impl<T> serde::Serialize for Value<T>
+where
+    T: scale::Codec + serde::Serialize,
+{
+    /// Serializes the wrapped value or `None` if it has not yet been set.
+    fn serialize<S>(&self, serializer: S) -> Result<S::Ok, S::Error>
+    where
+        S: serde::Serializer,
+    {
+        self.cell.get().serialize(serializer)
+    }
+}
+
 impl<T> AllocateUsing for Value<T> {
     #[inline]
     unsafe fn allocate_using<A>(alloc: &mut A) -> Self
@@ -151,6 +166,57 @@ where
     }
 }
 
+impl<T> Value<Option<T>>
+where
+    T: scale::Codec,
+{
+    /// Returns `true` if the optional value is `Some`.
+    ///
+    /// # Note
+    ///
+    /// This does not decode the wrapped `T` if it has not yet been loaded.
+    pub fn is_some(&self) -> bool {
+        self.cell.is_some()
+    }
+
+    /// Returns `true` if the optional value is `None`.
+    pub fn is_none(&self) -> bool {
+        !self.is_some()
+    }
+
+    /// Returns a reference to the wrapped `T` if any.
+    pub fn as_option(&self) -> Option<&T> {
+        self.get().as_ref()
+    }
+
+    /// Returns a copy of the wrapped `T` or its default if the value is `None`.
+    ///
+    /// # Note
+    ///
+    /// Unlike [`get_or_insert_with`](#method.get_or_insert_with) this never
+    /// writes to the contract storage.
+    pub fn get_or_default(&self) -> T
+    where
+        T: Clone + Default,
+    {
+        self.as_option().cloned().unwrap_or_default()
+    }
+
+    /// Returns a mutable reference to the wrapped `T` after setting it to the
+    /// result of `f` if the value is `None`.
+    pub fn get_or_insert_with<F>(&mut self, f: F) -> &mut T
+    where
+        F: FnOnce() -> T,
+    {
+        if self.is_none() {
+            self.set(Some(f()));
+        }
+        self.get_mut()
+            .as_mut()
+            .ink_expect("the optional value has just been set to `Some`")
+    }
+}
+
 impl<T, R> AsRef<R> for Value<T>
 where
     T: AsRef<R> + scale::Codec,
@@ -190,6 +256,26 @@ where
     }
 }
 
+impl<T> Value<T>
+where
+    T: Encode + Flush,
+{
+    /// Writes the cached state of the value back to the contract storage.
+    ///
+    /// See [`Flush`] for when to flush explicitly.
+    pub fn flush(&mut self) {
+        Flush::flush(self)
+    }
+
+    /// Drops the cached state of the value so that it is loaded again from the
+    /// contract storage upon the next access.
+    ///
+    /// See [`Flush`] for when to invalidate the cache.
+    pub fn invalidate_cache(&mut self) {
+        self.cell.invalidate_cache();
+    }
+}
+
 impl<T> Drop for Value<T> {
     #[inline]
     fn drop(&mut self) {
@@ -533,6 +619,36 @@ mod tests {
         assert!(val1 <= val3);
     }
 
+    #[test]
+    fn test_option() -> crate::env::Result<()> {
+        crate::env::test::run_test::<crate::env::DefaultEnvTypes, _>(|_| {
+            let (mut some, mut none) = unsafe {
+                let mut alloc = BumpAlloc::from_raw_parts(Key([0x0; 32]));
+                let some: Value<Option<bool>> = Value::allocate_using(&mut alloc);
+                let none: Value<Option<bool>> = Value::allocate_using(&mut alloc);
+                (
+                    some.initialize_into(Some(false)),
+                    none.initialize_into(None),
+                )
+            };
+            some.flush();
+            none.flush();
+            // Inspect the contract storage instead of the caches.
+            some.invalidate_cache();
+            none.invalidate_cache();
+            assert!(some.is_some());
+            assert!(none.is_none());
+            assert_eq!(some.as_option(), Some(&false));
+            assert_eq!(none.as_option(), None);
+            assert!(!none.get_or_default());
+            assert!(none.is_none());
+            *none.get_or_insert_with(|| true) = false;
+            assert_eq!(none.get_or_insert_with(|| true), &mut false);
+            assert!(none.is_some());
+            Ok(())
+        })
+    }
+
     #[test]
     fn test_index() {
         let val1 = unsafe {
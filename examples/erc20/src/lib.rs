@@ -213,27 +213,11 @@ mod erc20 {
                 .expect("Cannot get accounts");
 
             assert_eq!(erc20.balance_of(accounts.bob), 0);
-            // Get contract address.
-            let callee =
-                env::account_id::<env::DefaultEnvTypes>().unwrap_or([0x0; 32].into());
-            // Create call
-            let mut data =
-                env::call::CallData::new(env::call::Selector::from_str("balance_of"));
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller
-            assert_eq!(
-                env::test::push_execution_context::<env::DefaultEnvTypes>(
-                    accounts.bob,
-                    callee,
-                    1000000,
-                    1000000,
-                    data
-                ),
-                ()
-            );
-
             // Bob fails to transfers 10 tokens to Eve.
-            assert_eq!(erc20.transfer(accounts.eve, 10), false);
+            env::test::ExecutionContextBuilder::<env::DefaultEnvTypes>::new()
+                .caller(accounts.bob)
+                .run(|| assert_eq!(erc20.transfer(accounts.eve, 10), false))
+                .expect("Cannot run as Bob");
             // Alice owns all the tokens.
             assert_eq!(erc20.balance_of(accounts.alice), 100);
             assert_eq!(erc20.balance_of(accounts.bob), 0);
@@ -257,27 +241,16 @@ mod erc20 {
             // The approve event takes place.
             assert_eq!(env::test::recorded_events().count(), 2);
 
-            // Get contract address.
-            let callee =
-                env::account_id::<env::DefaultEnvTypes>().unwrap_or([0x0; 32].into());
-            // Create call.
-            let mut data =
-                env::call::CallData::new(env::call::Selector::from_str("balance_of"));
-            data.push_arg(&accounts.bob);
-            // Push the new execution context to set Bob as caller.
-            assert_eq!(
-                env::test::push_execution_context::<env::DefaultEnvTypes>(
-                    accounts.bob,
-                    callee,
-                    1000000,
-                    1000000,
-                    data
-                ),
-                ()
-            );
-
             // Bob transfers tokens from Alice to Eve.
-            assert_eq!(erc20.transfer_from(accounts.alice, accounts.eve, 10), true);
+            env::test::ExecutionContextBuilder::<env::DefaultEnvTypes>::new()
+                .caller(accounts.bob)
+                .run(|| {
+                    assert_eq!(
+                        erc20.transfer_from(accounts.alice, accounts.eve, 10),
+                        true
+                    )
+                })
+                .expect("Cannot run as Bob");
             // The third event takes place.
             assert_eq!(env::test::recorded_events().count(), 3);
             // Eve owns tokens.
@@ -495,9 +495,9 @@ mod erc721 {
             let callee =
                 env::account_id::<env::DefaultEnvTypes>().unwrap_or([0x0; 32].into());
             // Create call
-            let mut data =
-                env::call::CallData::new(env::call::Selector::from_str("balance_of"));
-            data.push_arg(&accounts.bob);
+            let data =
+                env::call::CallData::new(env::call::Selector::from_label("balance_of"))
+                    .push_arg(&accounts.bob);
             // Push the new execution context to set Bob as caller
             assert_eq!(
                 env::test::push_execution_context::<env::DefaultEnvTypes>(
@@ -529,9 +529,9 @@ mod erc721 {
             let callee =
                 env::account_id::<env::DefaultEnvTypes>().unwrap_or([0x0; 32].into());
             // Create call
-            let mut data =
-                env::call::CallData::new(env::call::Selector::from_str("balance_of"));
-            data.push_arg(&accounts.bob);
+            let data =
+                env::call::CallData::new(env::call::Selector::from_label("balance_of"))
+                    .push_arg(&accounts.bob);
             // Push the new execution context to set Bob as caller
             assert_eq!(
                 env::test::push_execution_context::<env::DefaultEnvTypes>(
@@ -581,9 +581,9 @@ mod erc721 {
             let callee =
                 env::account_id::<env::DefaultEnvTypes>().unwrap_or([0x0; 32].into());
             // Create call
-            let mut data =
-                env::call::CallData::new(env::call::Selector::from_str("balance_of"));
-            data.push_arg(&accounts.bob);
+            let data =
+                env::call::CallData::new(env::call::Selector::from_label("balance_of"))
+                    .push_arg(&accounts.bob);
             // Push the new execution context to set Bob as caller
             assert_eq!(
                 env::test::push_execution_context::<env::DefaultEnvTypes>(
@@ -642,9 +642,9 @@ mod erc721 {
             let callee =
                 env::account_id::<env::DefaultEnvTypes>().unwrap_or([0x0; 32].into());
             // Create call
-            let mut data =
-                env::call::CallData::new(env::call::Selector::from_str("balance_of"));
-            data.push_arg(&accounts.bob);
+            let data =
+                env::call::CallData::new(env::call::Selector::from_label("balance_of"))
+                    .push_arg(&accounts.bob);
             // Push the new execution context to set Eve as caller
             assert_eq!(
                 env::test::push_execution_context::<env::DefaultEnvTypes>(
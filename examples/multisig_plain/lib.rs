@@ -270,8 +270,7 @@ mod multisig_plain {
         ///
         /// // first create the transaction that adds `alice` through `add_owner`
         /// let alice: AccountId = [1u8; 32].into();
-        /// let mut call = CallData::new(Selector::from_str("add_owner"));
-        /// call.push_arg(&alice);
+        /// let call = CallData::new(Selector::from_label("add_owner")).push_arg(&alice);
         /// let transaction = Transaction {
         ///     callee: wallet_id,
         ///     selector: call.selector().to_bytes(),
@@ -281,13 +280,13 @@ mod multisig_plain {
         /// };
         ///
         /// // submit the transaction for confirmation
-        /// let mut submit = CallParams::eval(wallet_id, Selector::from_str("submit_transaction"));
+        /// let mut submit = CallParams::eval(wallet_id, Selector::from_label("submit_transaction"));
         /// let (id, _)  = submit.push_arg(&transaction)
         ///     .fire()
         ///     .expect("submit_transaction won't panic.");
         ///
         /// // wait until all required owners have confirmed and then execute the transaction
-        /// let mut invoke = CallParams::eval(wallet_id, Selector::from_str("invoke_transaction"));
+        /// let mut invoke = CallParams::eval(wallet_id, Selector::from_label("invoke_transaction"));
         /// invoke.push_arg(&id).fire();
         /// ```
         #[ink(message)]
@@ -431,6 +430,7 @@ mod multisig_plain {
         /// Invoke a confirmed execution without getting its output.
         ///
         /// Its return value indicates whether the called transaction was successful.
+        /// A failed transaction reverts all state changes and thus remains invokable.
         /// This can be called by anyone.
         #[ink(message)]
         fn invoke_transaction(&mut self, trans_id: TransactionId) -> Result<(), ()> {
@@ -453,6 +453,7 @@ mod multisig_plain {
         ///
         /// Its return value indicates whether the called transaction was successful and contains
         /// its output when sucesful.
+        /// A failed transaction reverts all state changes and thus remains invokable.
         /// This can be called by anyone.
         #[ink(message)]
         fn eval_transaction(&mut self, trans_id: TransactionId) -> Result<Vec<u8>, ()> {
@@ -608,9 +609,9 @@ mod multisig_plain {
 
         impl Transaction {
             fn change_requirement(requirement: u32) -> Self {
-                let mut call =
-                    call::CallData::new(call::Selector::from_str("change_requirement"));
-                call.push_arg(&requirement);
+                let call =
+                    call::CallData::new(call::Selector::from_label("change_requirement"))
+                        .push_arg(&requirement);
                 Self {
                     callee: WALLET.into(),
                     selector: call.selector().to_bytes(),
@@ -627,7 +628,7 @@ mod multisig_plain {
                 WALLET.into(),
                 1000000,
                 1000000,
-                call::CallData::new(call::Selector::from_str("dummy")),
+                call::CallData::new(call::Selector::from_label("dummy")),
             );
         }
 
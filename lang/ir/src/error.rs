@@ -20,9 +20,10 @@
 ///
 /// Takes some tokens that implement `ToTokens` trait in order to form a `Span`
 /// and also takes a format string plus arbitrary many formatting parameters.
+#[macro_export]
 macro_rules! bail {
     ($($args:tt)*) => {
-        return Err(format_err!($($args)*).into())
+        return Err($crate::format_err!($($args)*).into())
     }
 }
 
@@ -32,6 +33,7 @@ macro_rules! bail {
 ///
 /// Takes some tokens that implement `ToTokens` trait in order to form a `Span`
 /// and also takes a format string plus arbitrary many formatting parameters.
+#[macro_export]
 macro_rules! format_err {
     ($tokens:expr, $($msg:tt)*) => {
         syn::parse::Error::new_spanned(&$tokens, format_args!($($msg)*))
@@ -44,9 +46,10 @@ macro_rules! format_err {
 ///
 /// Takes a concrete span as first argument followed by some format string plus
 /// some additional format parameters.
+#[macro_export]
 macro_rules! bail_span {
     ($($args:tt)*) => {
-        return Err(format_err_span!($($args)*).into())
+        return Err($crate::format_err_span!($($args)*).into())
     }
 }
 
@@ -56,6 +59,7 @@ macro_rules! bail_span {
 ///
 /// Takes a concrete span as first argument followed by some format string plus
 /// some additional format parameters.
+#[macro_export]
 macro_rules! format_err_span {
     ($span:expr, $($msg:tt)*) => {
         syn::parse::Error::new($span, format_args!($($msg)*))
@@ -15,6 +15,7 @@
 //! Contains all ink! IR data structures and definitions.
 
 use derive_more::From;
+use either::Either;
 use proc_macro2::{
     Ident,
     Span,
@@ -27,6 +28,8 @@ use syn::{
     Token,
 };
 
+use crate::ir::utils;
+
 /// The contract with all required information.
 pub struct Contract {
     /// Outer and inner attributes of the module.
@@ -161,6 +164,8 @@ pub enum InkItem {
 pub enum Marker {
     /// A simple ink! marker without additional data.
     Simple(SimpleMarker),
+    /// An ink! marker consisting of multiple or valued identifiers.
+    Compound(CompoundMarker),
 }
 
 impl Marker {
@@ -168,6 +173,7 @@ impl Marker {
     pub fn span(&self) -> Span {
         match self {
             Marker::Simple(marker_simple) => marker_simple.span(),
+            Marker::Compound(marker_compound) => marker_compound.span(),
         }
     }
 
@@ -177,12 +183,40 @@ impl Marker {
     ///
     /// Every ink! marker has an identifier.
     /// For `#[ink(storage)]` this is `storage`, for `#[ink(event)]` it is `event`.
+    /// For `#[ink(message, payable)]` it is the first identifier `message`.
     pub fn ident(&self) -> &Ident {
         match self {
             Marker::Simple(marker_simple) => &marker_simple.ident,
+            Marker::Compound(marker_compound) => {
+                &marker_compound
+                    .args
+                    .first()
+                    .expect("compound markers have at least one argument")
+                    .ident
+            }
         }
     }
 
+    /// Returns all identifiers of `self` in order together with their values.
+    ///
+    /// # Example
+    ///
+    /// For `#[ink(message, selector = 0xCAFEBABE)]` these are `message`
+    /// without a value and `selector` with the value `0xCAFEBABE`.
+    pub fn args(&self) -> impl Iterator<Item = (&Ident, Option<&MarkerValue>)> + '_ {
+        let args = match self {
+            Marker::Simple(marker_simple) => {
+                Either::Left(core::iter::once((&marker_simple.ident, None)))
+            }
+            Marker::Compound(marker_compound) => {
+                Either::Right(marker_compound.args.iter().map(|arg| {
+                    (&arg.ident, arg.value.as_ref().map(|(_eq, value)| value))
+                }))
+            }
+        };
+        args.into_iter()
+    }
+
     /// Returns the ink! attribute if it is simple and the idents match or `None`.
     ///
     /// # Examples
@@ -233,6 +267,64 @@ impl PartialEq<str> for SimpleMarker {
     }
 }
 
+/// An ink! marker that consists of multiple comma separated arguments
+/// or of a single argument with a value.
+///
+/// # Examples
+///
+/// - `#[ink(message, payable)]`
+/// - `#[ink(selector = 0xCAFEBABE)]`
+pub struct CompoundMarker {
+    /// The parentheses around the arguments.
+    pub paren_token: syn::token::Paren,
+    /// The comma separated arguments.
+    pub args: Punctuated<MarkerArg, Token![,]>,
+}
+
+/// An argument of an ink! marker: an identifier with an optional value.
+///
+/// # Examples
+///
+/// - `payable`
+/// - `selector = 0xCAFEBABE`
+pub struct MarkerArg {
+    /// The identifier of the argument.
+    pub ident: Ident,
+    /// The `=` token and the value of the argument if any.
+    pub value: Option<(Token![=], MarkerValue)>,
+}
+
+/// The value of an ink! marker argument.
+pub enum MarkerValue {
+    /// An integer value, e.g. `0xCAFEBABE`.
+    Int(syn::LitInt),
+    /// The wildcard value `_`.
+    Wildcard(Token![_]),
+    /// An identifier, e.g. `only_owner`.
+    Ident(Ident),
+    /// A string literal, e.g. `"erc20"`.
+    Str(syn::LitStr),
+}
+
+impl MarkerValue {
+    /// Returns the span of `self`.
+    pub fn span(&self) -> Span {
+        match self {
+            MarkerValue::Int(lit_int) => lit_int.span(),
+            MarkerValue::Wildcard(underscore) => underscore.span,
+            MarkerValue::Ident(ident) => ident.span(),
+            MarkerValue::Str(lit_str) => lit_str.span(),
+        }
+    }
+}
+
+impl CompoundMarker {
+    /// Returns the span of `self`.
+    pub fn span(&self) -> Span {
+        self.paren_token.span
+    }
+}
+
 /// The state struct of the contract.
 pub struct ItemStorage {
     /// The `struct` token.
@@ -250,6 +342,14 @@ pub struct ItemStorage {
 }
 
 impl ItemStorage {
+    /// Returns `true` if the field has been marked as `#[ink(default)]`.
+    ///
+    /// These fields are initialized with their default value upon instantiation.
+    pub fn is_default_field(field: &syn::Field) -> bool {
+        utils::filter_map_ink_attributes(&field.attrs)
+            .any(|marker| marker.is_simple("default"))
+    }
+
     /// Returns the span of the original `struct` definition.
     pub fn span(&self) -> Span {
         self.span
@@ -266,9 +366,29 @@ pub struct ItemEvent {
     pub attrs: Vec<syn::Attribute>,
     /// Fields of the event struct.
     pub fields: syn::FieldsNamed,
+    /// If the event has been marked as `#[ink(anonymous)]`.
+    ///
+    /// Anonymous events do not have the event signature as their first topic.
+    pub anonymous: bool,
 }
 
 impl ItemEvent {
+    /// Returns the fields that have been marked as `#[ink(topic)]`.
+    pub fn topic_fields(&self) -> impl Iterator<Item = &syn::Field> {
+        self.fields.named.iter().filter(|field| {
+            utils::filter_map_ink_attributes(&field.attrs)
+                .any(|marker| marker.is_simple("topic"))
+        })
+    }
+
+    /// Returns the signature of the event, e.g. `erc20::Transfer`.
+    ///
+    /// Unless the event is anonymous the topic of its signature
+    /// is its first topic so that events can be filtered by type.
+    pub fn signature(&self, contract_ident: &Ident) -> String {
+        format!("{}::{}", contract_ident, self.ident)
+    }
+
     /// Returns the span of the original `struct` definition.
     pub fn span(&self) -> Span {
         self.struct_token
@@ -302,6 +422,12 @@ pub struct Function {
     pub sig: Signature,
     /// The statements of the function.
     pub block: syn::Block,
+    /// The ink! trait the function implements if any.
+    pub trait_path: Option<syn::Path>,
+    /// The namespace of the ink! trait implementation if any.
+    pub namespace: Option<syn::LitStr>,
+    /// The guards checked in order before the body of a message is executed.
+    pub guards: Vec<Ident>,
     /// The span of the original function definition.
     pub span: Span,
 }
@@ -329,6 +455,14 @@ pub enum FunctionKind {
 pub struct KindConstructor {
     /// The function selector.
     pub selector: FunctionSelector,
+    /// If the constructor is dispatched for all unknown selectors.
+    pub is_wildcard: bool,
+    /// If the constructor accepts the endowment of the instantiation.
+    ///
+    /// # Note
+    ///
+    /// This is always `true` since constructors are payable by default.
+    pub is_payable: bool,
 }
 
 /// A function that is a contract message.
@@ -336,14 +470,21 @@ pub struct KindConstructor {
 pub struct KindMessage {
     /// The function selector.
     pub selector: FunctionSelector,
+    /// If the message is dispatched for all unknown selectors.
+    pub is_wildcard: bool,
+    /// If the message accepts transferred value.
+    pub is_payable: bool,
+    /// If the message rejects calls reentering the contract while it executes.
+    pub is_non_reentrant: bool,
 }
 
 /// A function selector.
 ///
 /// # Note
 ///
-/// This is equal to the first four bytes of the SHA-3 hash of a function's name.
-#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+/// This is equal to the first four bytes of the SHA-3 hash of a function's name
+/// unless given explicitly via `#[ink(selector = ..)]`.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, From)]
 pub struct FunctionSelector([u8; 4]);
 
 impl FunctionSelector {
@@ -358,6 +499,34 @@ impl FunctionSelector {
     }
 }
 
+impl FunctionSelector {
+    /// Returns the selector of a function defined in an ink! trait definition.
+    ///
+    /// # Note
+    ///
+    /// This is derived from `Trait::function` so that all contracts
+    /// implementing the trait share the selector and so that functions
+    /// of different traits do not collide.
+    ///
+    /// The namespace is prepended as in `namespace::Trait::function` if given
+    /// so that identically named traits can be told apart.
+    pub fn for_trait_fn(
+        namespace: Option<&syn::LitStr>,
+        trait_ident: &Ident,
+        fn_ident: &Ident,
+    ) -> Self {
+        match namespace {
+            Some(namespace) => {
+                Self::from(
+                    format!("{}::{}::{}", namespace.value(), trait_ident, fn_ident)
+                        .as_str(),
+                )
+            }
+            None => Self::from(format!("{}::{}", trait_ident, fn_ident).as_str()),
+        }
+    }
+}
+
 impl From<&'_ Ident> for FunctionSelector {
     fn from(ident: &Ident) -> Self {
         Self::from(ident.to_string().as_str())
@@ -366,8 +535,7 @@ impl From<&'_ Ident> for FunctionSelector {
 
 impl From<&'_ str> for FunctionSelector {
     fn from(name: &str) -> Self {
-        let sha3_hash = ink_primitives::hash::keccak256(name.as_bytes());
-        Self([sha3_hash[0], sha3_hash[1], sha3_hash[2], sha3_hash[3]])
+        Self(ink_primitives::hash::selector(name))
     }
 }
 
@@ -381,6 +549,15 @@ impl Function {
         }
     }
 
+    /// Returns `true` if the constructor or message is dispatched for all unknown selectors.
+    pub fn is_wildcard(&self) -> bool {
+        match self.kind() {
+            FunctionKind::Constructor(constructor) => constructor.is_wildcard,
+            FunctionKind::Message(message) => message.is_wildcard,
+            _ => false,
+        }
+    }
+
     /// Returns the kind of the function.
     ///
     /// The kind also provides special information associated to the concrete kind, e.g. function selector.
@@ -404,6 +581,44 @@ impl Function {
         }
     }
 
+    /// Returns the error type `E` of a constructor returning `Result<(), E>`.
+    ///
+    /// Returns `None` for infallible constructors, messages and methods.
+    pub fn constructor_error(&self) -> Option<&syn::Type> {
+        if !self.is_constructor() {
+            return None
+        }
+        match &self.sig.output {
+            syn::ReturnType::Type(_, ty) => utils::result_error_type(ty),
+            syn::ReturnType::Default => None,
+        }
+    }
+
+    /// Returns the error type `E` of a message returning `Result<T, E>`.
+    ///
+    /// # Note
+    ///
+    /// The error type of aliases that do not name it, such as `Result<T>`,
+    /// is resolved through `ink_lang::MessageResult`.
+    ///
+    /// Returns `None` for infallible messages, constructors and methods.
+    pub fn message_error(&self) -> Option<syn::Type> {
+        if !self.is_message() {
+            return None
+        }
+        let ty = match &self.sig.output {
+            syn::ReturnType::Type(_, ty) => ty,
+            syn::ReturnType::Default => return None,
+        };
+        match utils::result_types(ty) {
+            Some((_value, error)) => Some(error.clone()),
+            None if utils::is_result_type(ty) => {
+                Some(syn::parse_quote! { <#ty as ink_lang::MessageResult>::Error })
+            }
+            None => None,
+        }
+    }
+
     /// Returns `true` if the function is a contract constructor.
     pub fn is_constructor(&self) -> bool {
         self.filter_constructor().is_some()
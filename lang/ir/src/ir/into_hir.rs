@@ -23,6 +23,7 @@ use proc_macro2::{
     Ident,
     Span,
 };
+use quote::ToTokens as _;
 use syn::{
     parse::{
         Parse,
@@ -43,14 +44,55 @@ impl Parse for ir::Marker {
     fn parse(input: ParseStream) -> Result<Self> {
         let content;
         let paren_token = syn::parenthesized!(content in input);
-        let ident = content.parse::<Ident>()?;
-        if content.is_empty() {
+        let args = Punctuated::<ir::MarkerArg, Token![,]>::parse_terminated(&content)?;
+        if args.is_empty() {
+            bail_span!(
+                paren_token.span,
+                "invalid ink! attribute in the given context",
+            )
+        }
+        if args.len() == 1 && args[0].value.is_none() {
+            let ident = args
+                .into_iter()
+                .next()
+                .expect("there is exactly one argument; qed")
+                .ident;
             return Ok(ir::Marker::Simple(ir::SimpleMarker { paren_token, ident }))
         }
-        bail_span!(
-            paren_token.span,
-            "invalid ink! attribute in the given context",
-        )
+        Ok(ir::Marker::Compound(ir::CompoundMarker {
+            paren_token,
+            args,
+        }))
+    }
+}
+
+impl Parse for ir::MarkerArg {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let ident = input.parse::<Ident>()?;
+        let value = if input.peek(Token![=]) {
+            Some((
+                input.parse::<Token![=]>()?,
+                input.parse::<ir::MarkerValue>()?,
+            ))
+        } else {
+            None
+        };
+        Ok(Self { ident, value })
+    }
+}
+
+impl Parse for ir::MarkerValue {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.peek(Token![_]) {
+            return Ok(ir::MarkerValue::Wildcard(input.parse()?))
+        }
+        if input.peek(syn::Ident) {
+            return Ok(ir::MarkerValue::Ident(input.parse()?))
+        }
+        if input.peek(syn::LitStr) {
+            return Ok(ir::MarkerValue::Str(input.parse()?))
+        }
+        Ok(ir::MarkerValue::Int(input.parse()?))
     }
 }
 
@@ -98,6 +140,7 @@ impl TryFrom<(ir::Params, syn::ItemMod)> for ir::Contract {
                 "ink! contracts require at least one `#[ink(message)]`"
             )
         }
+        ensure_unique_selectors(&functions)?;
         let meta_info = ir::MetaInfo::try_from(params)?;
         Ok(Self {
             mod_token: item_mod.mod_token,
@@ -112,6 +155,72 @@ impl TryFrom<(ir::Params, syn::ItemMod)> for ir::Contract {
     }
 }
 
+/// Ensures that constructors and messages have unique selectors.
+///
+/// Also ensures that there is at most one wildcard constructor and one wildcard
+/// message and that they have no inputs since the calls they catch can have
+/// arbitrary inputs.
+///
+/// # Note
+///
+/// Functions carrying different `#[cfg(..)]` attributes are not checked against
+/// each other since their conditions may be mutually exclusive, e.g. for the
+/// same message under `#[cfg(feature = "x")]` and `#[cfg(not(feature = "x"))]`.
+/// They are still checked against all functions that are compiled unconditionally.
+fn ensure_unique_selectors(functions: &[ir::Function]) -> Result<()> {
+    /// Returns the `#[cfg(..)]` attributes of the function as string.
+    fn cfg_of(function: &ir::Function) -> String {
+        utils::filter_cfg_attributes(&function.attrs)
+            .map(|attr| attr.to_token_stream().to_string())
+            .collect()
+    }
+    /// Returns `true` if the functions with the given `#[cfg(..)]` attributes
+    /// may both be compiled.
+    fn may_coexist(lhs: &str, rhs: &str) -> bool {
+        lhs.is_empty() || rhs.is_empty() || lhs == rhs
+    }
+    for &constructors in &[true, false] {
+        // The selectors, `#[cfg(..)]` attributes and wildcard flags
+        // of the already checked functions.
+        let mut checked = Vec::new();
+        for function in functions.iter().filter(|function| {
+            function.selector().is_some() && function.is_constructor() == constructors
+        }) {
+            let selector = function
+                .selector()
+                .expect("this is either a message or constructor at this point; qed");
+            let cfg = cfg_of(function);
+            let is_wildcard = function.is_wildcard();
+            for (other_selector, other_cfg, other_is_wildcard) in &checked {
+                if !may_coexist(&cfg, other_cfg) {
+                    continue
+                }
+                if selector == *other_selector {
+                    bail!(
+                        function.sig.ident,
+                        "encountered duplicate ink! selector {:02X?}",
+                        selector.as_bytes(),
+                    )
+                }
+                if is_wildcard && *other_is_wildcard {
+                    bail!(
+                        function.sig.ident,
+                        "encountered multiple ink! wildcard selectors",
+                    )
+                }
+            }
+            if is_wildcard && function.sig.inputs().next().is_some() {
+                bail!(
+                    function.sig.ident,
+                    "ink! functions with a wildcard selector must not have inputs",
+                )
+            }
+            checked.push((selector, cfg, is_wildcard));
+        }
+    }
+    Ok(())
+}
+
 impl TryFrom<ir::Params> for ir::MetaInfo {
     type Error = syn::Error;
 
@@ -210,6 +319,17 @@ impl TryFrom<syn::ItemStruct> for ir::ItemStorage {
                 bail!(item_struct, "`#[ink(storage)]` unit-structs are forbidden")
             }
         };
+        for field in fields.named.iter() {
+            for marker in utils::filter_map_ink_attributes(&field.attrs) {
+                if !marker.is_simple("default") {
+                    bail_span!(
+                        marker.span(),
+                        "invalid ink! attribute found for `#[ink(storage)]` field, \
+                         expected `#[ink(default)]`",
+                    )
+                }
+            }
+        }
         Ok(ir::ItemStorage {
             struct_token: item_struct.struct_token,
             ident: item_struct.ident,
@@ -224,16 +344,24 @@ impl TryFrom<syn::ItemStruct> for ir::ItemEvent {
     type Error = syn::Error;
 
     fn try_from(item_struct: syn::ItemStruct) -> Result<Self> {
-        if let Some(invalid_meta) = item_struct
-            .attrs
-            .iter()
-            .filter_map(|attr| ir::Marker::try_from(attr.clone()).ok())
-            .find(|ink_meta| !ink_meta.is_simple("event"))
-        {
-            bail_span!(
-                invalid_meta.span(),
-                "invalid ink! attribute found for `#[ink(event)]` struct",
-            )
+        let mut anonymous = false;
+        for marker in utils::filter_map_ink_attributes(&item_struct.attrs) {
+            for (ident, value) in marker.args() {
+                if let Some(value) = value {
+                    bail_span!(value.span(), "unexpected value for ink! marker")
+                }
+                match ident.to_string().as_str() {
+                    "event" => (),
+                    "anonymous" if !anonymous => anonymous = true,
+                    "anonymous" => bail_span!(marker.span(), "conflicting ink! marker"),
+                    _ => {
+                        bail_span!(
+                            marker.span(),
+                            "invalid ink! attribute found for `#[ink(event)]` struct",
+                        )
+                    }
+                }
+            }
         }
         if item_struct.vis != syn::Visibility::Inherited {
             bail!(
@@ -253,11 +381,23 @@ impl TryFrom<syn::ItemStruct> for ir::ItemEvent {
                 bail!(item_struct, "`#[ink(event)]` unit-structs are forbidden",)
             }
         };
+        for field in fields.named.iter() {
+            for marker in utils::filter_map_ink_attributes(&field.attrs) {
+                if !marker.is_simple("topic") {
+                    bail_span!(
+                        marker.span(),
+                        "invalid ink! attribute found for `#[ink(event)]` field, \
+                         expected `#[ink(topic)]`",
+                    )
+                }
+            }
+        }
         Ok(ir::ItemEvent {
             struct_token: item_struct.struct_token,
             ident: item_struct.ident,
             attrs: item_struct.attrs,
             fields,
+            anonymous,
         })
     }
 }
@@ -283,8 +423,22 @@ impl TryFrom<syn::ItemImpl> for ir::ItemImpl {
                 "generic implementation blocks are not supported in ink!",
             )
         }
-        if item_impl.trait_.is_some() {
-            bail!(item_impl, "trait implementations are not supported in ink!",)
+        let trait_path = match &item_impl.trait_ {
+            None => None,
+            Some((Some(bang), _path, _for)) => {
+                bail!(
+                    bang,
+                    "negative trait implementations are not supported in ink!",
+                )
+            }
+            Some((None, path, _for)) => Some(path.clone()),
+        };
+        let namespace = impl_namespace(&item_impl.attrs)?;
+        if let (Some(namespace), None) = (&namespace, &trait_path) {
+            bail!(
+                namespace,
+                "ink! namespaces are only supported for trait implementations",
+            )
         }
         let type_path = match &*item_impl.self_ty {
             syn::Type::Path(type_path) => type_path,
@@ -326,7 +480,7 @@ impl TryFrom<syn::ItemImpl> for ir::ItemImpl {
                 }
             }
         }
-        let functions = item_impl
+        let mut functions = item_impl
             .items
             .into_iter()
             .filter_map(|impl_item| {
@@ -335,8 +489,16 @@ impl TryFrom<syn::ItemImpl> for ir::ItemImpl {
                 }
                 None
             })
-            .map(ir::Function::try_from)
+            .map(|method| {
+                if trait_path.is_some() {
+                    utils::ensure_no_selector_override(&method.attrs)?;
+                }
+                ir::Function::try_from(method)
+            })
             .collect::<Result<Vec<_>>>()?;
+        if let Some(trait_path) = &trait_path {
+            implement_trait_fns(trait_path, namespace.as_ref(), &mut functions)?;
+        }
         Ok(Self {
             attrs: item_impl.attrs,
             impl_token: item_impl.impl_token,
@@ -347,25 +509,128 @@ impl TryFrom<syn::ItemImpl> for ir::ItemImpl {
     }
 }
 
+/// Returns the namespace given as `#[ink(namespace = "..")]` to an implementation block.
+///
+/// # Errors
+///
+/// - If there are ink! markers other than a single `namespace`.
+/// - If the namespace is not made of identifiers separated by `::`, e.g. `erc20` or
+///   `tokens::erc20`.
+fn impl_namespace(attrs: &[syn::Attribute]) -> Result<Option<syn::LitStr>> {
+    let mut namespace = None;
+    for marker in utils::filter_map_ink_attributes(attrs) {
+        for (ident, value) in marker.args() {
+            match (ident.to_string().as_str(), value) {
+                ("namespace", Some(ir::MarkerValue::Str(lit_str))) => {
+                    if namespace.is_some() {
+                        bail!(lit_str, "conflicting ink! namespaces")
+                    }
+                    utils::ensure_valid_namespace(lit_str)?;
+                    namespace = Some(lit_str.clone());
+                }
+                _ => {
+                    bail_span!(
+                        marker.span(),
+                        "unknown ink! marker for implementation blocks, \
+                         expected `namespace = \"..\"`",
+                    )
+                }
+            }
+        }
+    }
+    Ok(namespace)
+}
+
+/// Marks the functions as implementing the ink! trait at the given path.
+///
+/// # Errors
+///
+/// - If the trait path has generic arguments.
+/// - If one of the functions is neither a message nor a constructor.
+fn implement_trait_fns(
+    trait_path: &syn::Path,
+    namespace: Option<&syn::LitStr>,
+    functions: &mut [ir::Function],
+) -> Result<()> {
+    let trait_segment = trait_path
+        .segments
+        .last()
+        .expect("paths have at least one segment; qed");
+    if !trait_segment.arguments.is_empty() {
+        bail!(
+            trait_segment.arguments,
+            "generic ink! trait implementations are not supported",
+        )
+    }
+    for function in functions {
+        let selector = ir::FunctionSelector::for_trait_fn(
+            namespace,
+            &trait_segment.ident,
+            &function.sig.ident,
+        );
+        match &mut function.kind {
+            ir::FunctionKind::Constructor(constructor) => constructor.selector = selector,
+            ir::FunctionKind::Message(message) => message.selector = selector,
+            ir::FunctionKind::Method => {
+                bail!(
+                    function.sig.ident,
+                    "ink! trait implementations must only contain messages and constructors",
+                )
+            }
+        }
+        function.trait_path = Some(trait_path.clone());
+        function.namespace = namespace.cloned();
+    }
+    Ok(())
+}
+
+/// Returns the selector given as value of `#[ink(selector = ..)]`.
+///
+/// Returns `None` for the wildcard selector `_`.
+fn selector_override(value: &ir::MarkerValue) -> Result<Option<ir::FunctionSelector>> {
+    let lit_int = match value {
+        ir::MarkerValue::Wildcard(_) => return Ok(None),
+        ir::MarkerValue::Int(lit_int) => lit_int,
+        invalid @ ir::MarkerValue::Ident(_) | invalid @ ir::MarkerValue::Str(_) => {
+            bail_span!(
+                invalid.span(),
+                "expected an integer or `_` for the ink! selector, \
+                 e.g. `selector = 0xCAFEBABE` or `selector = _`",
+            )
+        }
+    };
+    if !lit_int.suffix().is_empty() {
+        bail!(lit_int, "ink! selectors must not have a type suffix")
+    }
+    let selector = lit_int.base10_parse::<u32>().map_err(|_| {
+        format_err!(
+            lit_int,
+            "ink! selectors must fit into 4 bytes, e.g. `0xCAFEBABE`"
+        )
+    })?;
+    Ok(Some(ir::FunctionSelector::from(selector.to_be_bytes())))
+}
+
 impl TryFrom<syn::ImplItemMethod> for ir::Function {
     type Error = syn::Error;
 
     fn try_from(method: syn::ImplItemMethod) -> Result<Self> {
         let span = method.span();
-        // Partitions ink! attributes into simple and non-simple.
+        // Flattens the ink! attributes into their identifiers.
         //
-        // Simple attributes are e.g. `#[ink(event)]` that have only
-        // one simple identifier in their `(` and `)` body.
-        let simple = method
+        // Compound attributes such as `#[ink(message, payable)]` are
+        // equivalent to their identifiers given as separate attributes.
+        let markers = method
             .attrs
             .iter()
             .cloned()
             .filter_map(|attr| ir::Marker::try_from(attr).ok())
-            .map(|attr| {
-                match attr {
-                    ir::Marker::Simple(simple) => simple,
-                }
-            });
+            .collect::<Vec<_>>();
+        let args = markers.iter().flat_map(|marker| {
+            marker
+                .args()
+                .map(move |(ident, value)| (ident, value, marker.span()))
+        });
         // Checks for ink! attributes concerning ink! functions.
         //
         // Bails out into error upon unknown or unsupported found ink! attributes.
@@ -373,28 +638,91 @@ impl TryFrom<syn::ImplItemMethod> for ir::Function {
         // `#[ink(constructor)]` and `#[ink(message)]` attribute or if there is
         // the same attribute multiple times.
         let mut kind = ir::FunctionKind::Method;
-        if let Some(err) = simple
-            .map(|attr| {
-                let new_kind = match attr.ident.to_string().as_str() {
+        let mut payable: Option<Span> = None;
+        let mut non_reentrant: Option<Span> = None;
+        let mut selector: Option<(Option<ir::FunctionSelector>, Span)> = None;
+        let mut guards: Vec<(Ident, Span)> = Vec::new();
+        if let Some(err) = args
+            .map(|(ident, value, span)| {
+                let name = ident.to_string();
+                match (name.as_str(), value) {
+                    ("selector", None) => {
+                        return Err(format_err_span!(
+                            span,
+                            "expected a value for the ink! selector, \
+                             e.g. `selector = 0xCAFEBABE` or `selector = _`",
+                        ))
+                    }
+                    ("selector", Some(_)) if selector.is_some() => {
+                        return Err(format_err_span!(span, "conflicting ink! marker",))
+                    }
+                    ("selector", Some(value)) => {
+                        selector = Some((selector_override(value)?, span));
+                        return Ok(())
+                    }
+                    ("guard", Some(ir::MarkerValue::Ident(guard))) => {
+                        guards.push((guard.clone(), span));
+                        return Ok(())
+                    }
+                    ("guard", _) => {
+                        return Err(format_err_span!(
+                            span,
+                            "expected the name of a guard method, \
+                             e.g. `guard = only_owner`",
+                        ))
+                    }
+                    (_, Some(value)) => {
+                        return Err(format_err_span!(
+                            value.span(),
+                            "unexpected value for ink! marker",
+                        ))
+                    }
+                    (_, None) => (),
+                }
+                let new_kind = match name.as_str() {
                     "constructor" => {
-                        Ok(ir::FunctionKind::Constructor(ir::KindConstructor {
+                        ir::FunctionKind::Constructor(ir::KindConstructor {
                             selector: ir::FunctionSelector::from(&method.sig.ident),
-                        }))
+                            is_wildcard: false,
+                            // Constructors always accept the endowment of the
+                            // instantiation. Marking them as payable only
+                            // makes this explicit.
+                            is_payable: true,
+                        })
                     }
                     "message" => {
-                        Ok(ir::FunctionKind::Message(ir::KindMessage {
+                        ir::FunctionKind::Message(ir::KindMessage {
                             selector: ir::FunctionSelector::from(&method.sig.ident),
-                        }))
+                            is_wildcard: false,
+                            // Messages reject transferred value unless they
+                            // are marked as payable, see below.
+                            is_payable: false,
+                            is_non_reentrant: false,
+                        })
+                    }
+                    "payable" if payable.is_none() => {
+                        payable = Some(span);
+                        return Ok(())
+                    }
+                    "payable" => {
+                        return Err(format_err_span!(span, "conflicting ink! marker",))
+                    }
+                    "non_reentrant" if non_reentrant.is_none() => {
+                        non_reentrant = Some(span);
+                        return Ok(())
+                    }
+                    "non_reentrant" => {
+                        return Err(format_err_span!(span, "conflicting ink! marker",))
                     }
                     _unknown => {
-                        Err(format_err_span!(attr.span(), "unknown ink! marker",))
+                        return Err(format_err_span!(span, "unknown ink! marker",))
                     }
-                }?;
+                };
                 if kind == ir::FunctionKind::Method {
                     kind = new_kind;
                     Ok(())
                 } else {
-                    Err(format_err_span!(attr.span(), "conflicting ink! marker",))
+                    Err(format_err_span!(span, "conflicting ink! marker",))
                 }
             })
             .filter_map(Result::err)
@@ -405,6 +733,62 @@ impl TryFrom<syn::ImplItemMethod> for ir::Function {
         {
             return Err(err)
         }
+        // Only messages and constructors can be marked as payable.
+        if let Some(span) = payable {
+            match &mut kind {
+                ir::FunctionKind::Constructor(constructor) => {
+                    constructor.is_payable = true
+                }
+                ir::FunctionKind::Message(message) => message.is_payable = true,
+                ir::FunctionKind::Method => {
+                    bail_span!(
+                        span,
+                        "only ink! messages and constructors can be marked as payable"
+                    )
+                }
+            }
+        }
+        // Only messages can reject reentrant calls since constructors
+        // are executed before the contract can be called at all.
+        if let Some(span) = non_reentrant {
+            match &mut kind {
+                ir::FunctionKind::Message(message) => message.is_non_reentrant = true,
+                _ => {
+                    bail_span!(span, "only ink! messages can be marked as non-reentrant")
+                }
+            }
+        }
+        // Guards are checked before the message body and revert the call upon failure.
+        if let Some((_guard, span)) = guards.first() {
+            match kind {
+                ir::FunctionKind::Message(_) => (),
+                _ => bail_span!(*span, "only ink! messages can have guards"),
+            }
+        }
+        // Explicit selectors replace the selectors derived from the function name.
+        //
+        // Wildcard functions keep their derived selectors for identification
+        // but are dispatched for every selector that matches no other function.
+        if let Some((explicit, span)) = selector {
+            let (kind_selector, is_wildcard) = match &mut kind {
+                ir::FunctionKind::Constructor(constructor) => {
+                    (&mut constructor.selector, &mut constructor.is_wildcard)
+                }
+                ir::FunctionKind::Message(message) => {
+                    (&mut message.selector, &mut message.is_wildcard)
+                }
+                ir::FunctionKind::Method => {
+                    bail_span!(
+                        span,
+                        "only ink! messages and constructors can have selectors"
+                    )
+                }
+            };
+            match explicit {
+                Some(explicit) => *kind_selector = explicit,
+                None => *is_wildcard = true,
+            }
+        }
         // Visibility modifiers are currently not supported for ink! functions.
         if method.vis != syn::Visibility::Inherited {
             bail!(
@@ -431,11 +815,15 @@ impl TryFrom<syn::ImplItemMethod> for ir::Function {
                         "#[ink(constructor)] functions must have a `&mut self` receiver",
                     )
                 }
-                if sig.output != syn::ReturnType::Default {
-                    bail!(
-                        sig.output,
-                        "#[ink(constructor)] functions must not have a return type",
-                    )
+                // Fallible constructors revert the instantiation upon errors.
+                if let syn::ReturnType::Type(_, ty) = &sig.output {
+                    if utils::result_error_type(ty).is_none() {
+                        bail!(
+                            sig.output,
+                            "#[ink(constructor)] functions must either return nothing \
+                             or `Result<(), E>`",
+                        )
+                    }
                 }
             }
             ir::FunctionKind::Message(_) | ir::FunctionKind::Method => {
@@ -459,6 +847,9 @@ impl TryFrom<syn::ImplItemMethod> for ir::Function {
             kind,
             sig,
             block: method.block,
+            trait_path: None,
+            namespace: None,
+            guards: guards.into_iter().map(|(guard, _span)| guard).collect(),
             span,
         })
     }
@@ -594,8 +985,10 @@ impl TryFrom<syn::Item> for ir::Item {
                 if markers.is_empty() {
                     return Ok(ir::RustItem::from(syn::Item::Struct(item_struct)).into())
                 }
-                let event_marker =
-                    markers.iter().position(|marker| marker.is_simple("event"));
+                // Events might be marked as `#[ink(event, anonymous)]`.
+                let event_marker = markers.iter().position(|marker| {
+                    marker.args().any(|(ident, _value)| ident == "event")
+                });
                 let storage_marker = markers
                     .iter()
                     .position(|marker| marker.is_simple("storage"));
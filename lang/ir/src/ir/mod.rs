@@ -28,6 +28,7 @@ mod tests;
 
 pub use self::{
     data::{
+        CompoundMarker,
         Contract,
         FnArg,
         Function,
@@ -42,6 +43,8 @@ pub use self::{
         KindConstructor,
         KindMessage,
         Marker,
+        MarkerArg,
+        MarkerValue,
         MetaInfo,
         MetaTypes,
         MetaVersion,
@@ -0,0 +1,537 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Tests for the ink! IR module.
+
+use core::convert::TryFrom;
+
+use crate::ir::{
+    Contract,
+    Function,
+    FunctionKind,
+    FunctionSelector,
+    ItemEvent,
+    ItemStorage,
+    Marker,
+    Params,
+};
+
+#[test]
+fn parse_meta_storage() {
+    let input: syn::Attribute = syn::parse_quote! { #[ink(storage)] };
+    let result = Marker::try_from(input);
+    assert!(result.is_ok());
+    assert!(result.unwrap().is_simple("storage"));
+}
+
+#[test]
+fn parse_meta_event() {
+    let input: syn::Attribute = syn::parse_quote! { #[ink(event)] };
+    let result = Marker::try_from(input);
+    assert!(result.is_ok());
+    assert!(result.unwrap().is_simple("event"));
+}
+
+#[test]
+fn parse_params() {
+    let _input: Params = syn::parse_quote! {
+        env = DefaultEnvTypes, version = "0.1.0"
+    };
+}
+
+#[test]
+fn parse_meta_compound() {
+    let input: syn::Attribute = syn::parse_quote! { #[ink(message, payable)] };
+    let result = Marker::try_from(input).unwrap();
+    assert!(!result.is_simple("message"));
+    assert_eq!(
+        result
+            .args()
+            .map(|(ident, _value)| ident.to_string())
+            .collect::<Vec<_>>(),
+        vec!["message", "payable"],
+    );
+}
+
+#[test]
+fn messages_are_not_payable_by_default() {
+    let is_payable = |method: syn::ImplItemMethod| {
+        match Function::try_from(method).unwrap().kind() {
+            FunctionKind::Message(message) => message.is_payable,
+            _ => panic!("expected an ink! message"),
+        }
+    };
+    assert!(!is_payable(syn::parse_quote! {
+        #[ink(message)]
+        fn get(&self) {}
+    }));
+    assert!(is_payable(syn::parse_quote! {
+        #[ink(message, payable)]
+        fn get(&self) {}
+    }));
+    assert!(is_payable(syn::parse_quote! {
+        #[ink(payable)]
+        #[ink(message)]
+        fn get(&self) {}
+    }));
+    let payable_twice: syn::ImplItemMethod = syn::parse_quote! {
+        #[ink(message, payable, payable)]
+        fn get(&self) {}
+    };
+    assert!(Function::try_from(payable_twice).is_err());
+}
+
+#[test]
+fn constructors_may_be_payable_and_fallible() {
+    let function = |method: syn::ImplItemMethod| Function::try_from(method);
+    let is_payable = |function: &Function| {
+        match function.kind() {
+            FunctionKind::Constructor(constructor) => constructor.is_payable,
+            _ => panic!("expected an ink! constructor"),
+        }
+    };
+    let infallible = function(syn::parse_quote! {
+        #[ink(constructor)]
+        fn new(&mut self) {}
+    })
+    .unwrap();
+    // Constructors accept the endowment by default.
+    assert!(is_payable(&infallible));
+    assert!(infallible.constructor_error().is_none());
+    let fallible = function(syn::parse_quote! {
+        #[ink(constructor, payable)]
+        fn new(&mut self) -> Result<(), Error> {}
+    })
+    .unwrap();
+    assert!(is_payable(&fallible));
+    let expected: syn::Type = syn::parse_quote! { Error };
+    assert_eq!(fallible.constructor_error(), Some(&expected));
+    assert!(function(syn::parse_quote! {
+        #[ink(constructor)]
+        fn new(&mut self) -> Result<u32, Error> {}
+    })
+    .is_err());
+    assert!(function(syn::parse_quote! {
+        #[ink(payable)]
+        fn helper(&self) {}
+    })
+    .is_err());
+}
+
+#[test]
+fn fallible_messages() {
+    let function = |method: syn::ImplItemMethod| Function::try_from(method).unwrap();
+    let fallible = function(syn::parse_quote! {
+        #[ink(message)]
+        fn transfer(&mut self) -> core::result::Result<u32, Error> {}
+    });
+    let expected: syn::Type = syn::parse_quote! { Error };
+    assert_eq!(fallible.message_error(), Some(expected));
+    let aliased = function(syn::parse_quote! {
+        #[ink(message)]
+        fn transfer(&mut self) -> Result<()> {}
+    });
+    let expected: syn::Type =
+        syn::parse_quote! { <Result<()> as ink_lang::MessageResult>::Error };
+    assert_eq!(aliased.message_error(), Some(expected));
+    let infallible = function(syn::parse_quote! {
+        #[ink(message)]
+        fn get(&self) -> Option<u32> {}
+    });
+    assert!(infallible.message_error().is_none());
+    let method = function(syn::parse_quote! {
+        fn helper(&self) -> Result<(), Error> {}
+    });
+    assert!(method.message_error().is_none());
+}
+
+#[test]
+fn non_reentrant_messages() {
+    let function = |method: syn::ImplItemMethod| Function::try_from(method);
+    let is_non_reentrant = |method: syn::ImplItemMethod| {
+        match function(method).unwrap().kind() {
+            FunctionKind::Message(message) => message.is_non_reentrant,
+            _ => panic!("expected an ink! message"),
+        }
+    };
+    assert!(!is_non_reentrant(syn::parse_quote! {
+        #[ink(message)]
+        fn withdraw(&mut self) {}
+    }));
+    assert!(is_non_reentrant(syn::parse_quote! {
+        #[ink(message, non_reentrant)]
+        fn withdraw(&mut self) {}
+    }));
+    assert!(function(syn::parse_quote! {
+        #[ink(constructor, non_reentrant)]
+        fn new(&mut self) {}
+    })
+    .is_err());
+}
+
+#[test]
+fn message_guards() {
+    let function = |method: syn::ImplItemMethod| Function::try_from(method);
+    let guarded = function(syn::parse_quote! {
+        #[ink(message, guard = only_owner)]
+        #[ink(guard = not_paused)]
+        fn set(&mut self) {}
+    })
+    .unwrap();
+    assert_eq!(
+        guarded
+            .guards
+            .iter()
+            .map(ToString::to_string)
+            .collect::<Vec<_>>(),
+        vec!["only_owner", "not_paused"],
+    );
+    assert!(function(syn::parse_quote! {
+        #[ink(constructor, guard = only_owner)]
+        fn new(&mut self) {}
+    })
+    .is_err());
+    assert!(function(syn::parse_quote! {
+        #[ink(message, guard = 1)]
+        fn set(&mut self) {}
+    })
+    .is_err());
+    assert!(function(syn::parse_quote! {
+        #[ink(message, selector = only_owner)]
+        fn set(&mut self) {}
+    })
+    .is_err());
+}
+
+#[test]
+fn custom_selectors() {
+    let function = |method: syn::ImplItemMethod| Function::try_from(method).unwrap();
+    let explicit = function(syn::parse_quote! {
+        #[ink(message, selector = 0xCAFEBABE)]
+        fn get(&self) {}
+    });
+    assert_eq!(
+        explicit.selector().unwrap().as_bytes(),
+        &[0xCA, 0xFE, 0xBA, 0xBE]
+    );
+    assert!(!explicit.is_wildcard());
+    let wildcard = function(syn::parse_quote! {
+        #[ink(constructor)]
+        #[ink(selector = _)]
+        fn new(&mut self) {}
+    });
+    assert!(wildcard.is_wildcard());
+    let invalid: Vec<syn::ImplItemMethod> = vec![
+        syn::parse_quote! {
+            #[ink(message, selector = 0x1_0000_0000)]
+            fn get(&self) {}
+        },
+        syn::parse_quote! {
+            #[ink(message, selector)]
+            fn get(&self) {}
+        },
+        syn::parse_quote! {
+            #[ink(message, payable = 1)]
+            fn get(&self) {}
+        },
+        syn::parse_quote! {
+            #[ink(selector = 1)]
+            fn get(&self) {}
+        },
+    ];
+    for method in invalid {
+        assert!(Function::try_from(method).is_err());
+    }
+}
+
+#[test]
+fn selectors_must_be_unique() {
+    let contract = |item_mod: syn::ItemMod| {
+        let params: Params = syn::parse_quote! { version = "0.1.0" };
+        Contract::try_from((params, item_mod))
+    };
+    assert!(contract(syn::parse_quote! {
+        mod renamed {
+            #[ink(storage)]
+            struct Renamed {}
+            impl Renamed {
+                #[ink(constructor, selector = 0x01)]
+                fn new(&mut self) {}
+                #[ink(message, selector = 0x01)]
+                fn get(&self) {}
+                #[ink(message, selector = _)]
+                fn fallback(&self) {}
+            }
+        }
+    })
+    .is_ok());
+    assert!(contract(syn::parse_quote! {
+        mod duplicate {
+            #[ink(storage)]
+            struct Duplicate {}
+            impl Duplicate {
+                #[ink(constructor)]
+                fn new(&mut self) {}
+                #[ink(message, selector = 0x01)]
+                fn get(&self) {}
+                #[ink(message, selector = 0x01)]
+                fn set(&mut self) {}
+            }
+        }
+    })
+    .is_err());
+    assert!(contract(syn::parse_quote! {
+        mod wildcard_inputs {
+            #[ink(storage)]
+            struct WildcardInputs {}
+            impl WildcardInputs {
+                #[ink(constructor)]
+                fn new(&mut self) {}
+                #[ink(message, selector = _)]
+                fn fallback(&self, value: i32) {}
+            }
+        }
+    })
+    .is_err());
+}
+
+#[test]
+fn trait_impls_use_trait_selectors() {
+    let contract = |item_mod: syn::ItemMod| {
+        let params: Params = syn::parse_quote! { version = "0.1.0" };
+        Contract::try_from((params, item_mod))
+    };
+    let flipper = contract(syn::parse_quote! {
+        mod flipper {
+            #[ink(storage)]
+            struct Flipper {}
+            impl traits::Flip for Flipper {
+                #[ink(constructor)]
+                fn new(&mut self) {}
+                #[ink(message)]
+                fn flip(&mut self) {}
+            }
+        }
+    })
+    .unwrap();
+    let trait_ident: syn::Ident = syn::parse_quote! { Flip };
+    for function in &flipper.functions {
+        assert_eq!(
+            function.selector(),
+            Some(FunctionSelector::for_trait_fn(
+                None,
+                &trait_ident,
+                &function.sig.ident
+            ))
+        );
+        assert!(function.trait_path.is_some());
+    }
+    assert!(contract(syn::parse_quote! {
+        mod flipper {
+            #[ink(storage)]
+            struct Flipper {}
+            impl Flipper {
+                #[ink(constructor)]
+                fn new(&mut self) {}
+            }
+            impl Flip for Flipper {
+                #[ink(message, selector = 0x01)]
+                fn flip(&mut self) {}
+            }
+        }
+    })
+    .is_err());
+    assert!(contract(syn::parse_quote! {
+        mod flipper {
+            #[ink(storage)]
+            struct Flipper {}
+            impl Flip for Flipper {
+                #[ink(constructor)]
+                fn new(&mut self) {}
+                #[ink(message)]
+                fn flip(&mut self) {}
+                fn helper(&self) {}
+            }
+        }
+    })
+    .is_err());
+}
+
+#[test]
+fn trait_impl_namespaces() {
+    let contract = |item_mod: syn::ItemMod| {
+        let params: Params = syn::parse_quote! { version = "0.1.0" };
+        Contract::try_from((params, item_mod))
+    };
+    let flipper = contract(syn::parse_quote! {
+        mod flipper {
+            #[ink(storage)]
+            struct Flipper {}
+            impl Flipper {
+                #[ink(constructor)]
+                fn new(&mut self) {}
+            }
+            #[ink(namespace = "first")]
+            impl first::Flip for Flipper {
+                #[ink(message)]
+                fn flip(&mut self) {}
+            }
+            #[ink(namespace = "second")]
+            impl second::Flip for Flipper {
+                #[ink(message)]
+                fn flip(&mut self) {}
+            }
+        }
+    })
+    .unwrap();
+    let trait_ident: syn::Ident = syn::parse_quote! { Flip };
+    let fn_ident: syn::Ident = syn::parse_quote! { flip };
+    let selectors = flipper
+        .functions
+        .iter()
+        .filter(|function| function.is_message())
+        .map(|function| function.selector())
+        .collect::<Vec<_>>();
+    assert_eq!(
+        selectors,
+        vec![
+            Some(FunctionSelector::for_trait_fn(
+                Some(&syn::parse_quote! { "first" }),
+                &trait_ident,
+                &fn_ident,
+            )),
+            Some(FunctionSelector::for_trait_fn(
+                Some(&syn::parse_quote! { "second" }),
+                &trait_ident,
+                &fn_ident,
+            )),
+        ]
+    );
+    assert_ne!(selectors[0], selectors[1]);
+    assert!(contract(syn::parse_quote! {
+        mod flipper {
+            #[ink(storage)]
+            struct Flipper {}
+            #[ink(namespace = "first")]
+            impl Flipper {
+                #[ink(constructor)]
+                fn new(&mut self) {}
+            }
+        }
+    })
+    .is_err());
+    for namespace in &["not a path", "erc20<T>", "r#type", "::erc20", "tokens :: erc20"] {
+        let namespace = syn::LitStr::new(namespace, proc_macro2::Span::call_site());
+        assert!(contract(syn::parse_quote! {
+            mod flipper {
+                #[ink(storage)]
+                struct Flipper {}
+                impl Flipper {
+                    #[ink(constructor)]
+                    fn new(&mut self) {}
+                }
+                #[ink(namespace = #namespace)]
+                impl Flip for Flipper {
+                    #[ink(message)]
+                    fn flip(&mut self) {}
+                }
+            }
+        })
+        .is_err());
+    }
+}
+
+#[test]
+fn anonymous_events() {
+    let event = |item_struct: syn::ItemStruct| ItemEvent::try_from(item_struct);
+    let transfer = event(syn::parse_quote! {
+        #[ink(event)]
+        struct Transfer {
+            #[ink(topic)]
+            from: AccountId,
+            value: Balance,
+        }
+    })
+    .unwrap();
+    assert!(!transfer.anonymous);
+    assert_eq!(
+        transfer
+            .topic_fields()
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect::<Vec<_>>(),
+        vec!["from"],
+    );
+    assert!(
+        event(syn::parse_quote! {
+            #[ink(event, anonymous)]
+            struct Transfer {}
+        })
+        .unwrap()
+        .anonymous
+    );
+    assert!(
+        event(syn::parse_quote! {
+            #[ink(event)]
+            #[ink(anonymous)]
+            struct Transfer {}
+        })
+        .unwrap()
+        .anonymous
+    );
+    assert!(event(syn::parse_quote! {
+        #[ink(event, anonymous, anonymous)]
+        struct Transfer {}
+    })
+    .is_err());
+    assert!(event(syn::parse_quote! {
+        #[ink(event)]
+        struct Transfer {
+            #[ink(indexed)]
+            from: AccountId,
+        }
+    })
+    .is_err());
+}
+
+#[test]
+fn storage_default_fields() {
+    let storage = |item_struct: syn::ItemStruct| ItemStorage::try_from(item_struct);
+    let counter = storage(syn::parse_quote! {
+        #[ink(storage)]
+        struct Counter {
+            #[ink(default)]
+            value: storage::Value<u32>,
+            owner: storage::Value<AccountId>,
+        }
+    })
+    .unwrap();
+    assert_eq!(
+        counter
+            .fields
+            .named
+            .iter()
+            .filter(|field| ItemStorage::is_default_field(field))
+            .map(|field| field.ident.as_ref().unwrap().to_string())
+            .collect::<Vec<_>>(),
+        vec!["value"],
+    );
+    assert!(storage(syn::parse_quote! {
+        #[ink(storage)]
+        struct Counter {
+            #[ink(topic)]
+            value: storage::Value<u32>,
+        }
+    })
+    .is_err());
+}
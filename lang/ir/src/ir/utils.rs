@@ -0,0 +1,261 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Contains general utilities for the ink! IR module.
+
+use proc_macro2::Span;
+use syn::{
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    spanned::Spanned,
+    Result,
+};
+
+use crate::ir;
+
+/// An unsuffixed integer literal: `0` or `42` or `1337`
+#[derive(Debug, Clone)]
+pub struct UnsuffixedLitInt {
+    pub(crate) lit_int: syn::LitInt,
+}
+
+impl Spanned for UnsuffixedLitInt {
+    fn span(&self) -> Span {
+        self.lit_int.span()
+    }
+}
+
+impl Parse for UnsuffixedLitInt {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let lit_int: syn::LitInt = input.parse()?;
+        if lit_int.suffix() != "" {
+            bail!(lit_int, "integer suffixes are not allowed here",)
+        }
+        Ok(Self { lit_int })
+    }
+}
+
+/// Returns `true` if the given attribute is any of `#[ink(..)]`.
+pub fn is_ink_attribute(attr: &syn::Attribute) -> bool {
+    attr.path.is_ident("ink")
+}
+
+/// Yields back all non-`#[ink(..)]` attributes if any.
+pub fn filter_non_ink_attributes<'a, I>(
+    attrs: I,
+) -> impl Iterator<Item = &'a syn::Attribute> + 'a
+where
+    I: IntoIterator<Item = &'a syn::Attribute> + 'a,
+{
+    attrs.into_iter().filter(|attr| !is_ink_attribute(attr))
+}
+
+/// Yields back the filtered `#[ink(..)]` markers if any.
+pub fn filter_ink_attributes<'a, I>(
+    attrs: I,
+) -> impl Iterator<Item = &'a syn::Attribute> + 'a
+where
+    I: IntoIterator<Item = &'a syn::Attribute> + 'a,
+{
+    attrs.into_iter().filter(|attr| is_ink_attribute(attr))
+}
+
+/// Yields back the filtered `#[ink(..)]` markers converted into their ink! form if any.
+pub fn filter_map_ink_attributes<'a, I>(attrs: I) -> impl Iterator<Item = ir::Marker>
+where
+    I: IntoIterator<Item = &'a syn::Attribute> + 'a,
+{
+    use core::convert::TryFrom as _;
+    attrs
+        .into_iter()
+        .cloned()
+        .filter_map(|attr| ir::Marker::try_from(attr).ok())
+}
+
+/// Yields back the `#[cfg(..)]` attributes if any.
+///
+/// # Note
+///
+/// These are forwarded to all code generated for an ink! function so
+/// that conditionally compiled functions are dispatched and described
+/// in the ABI only if they are compiled.
+pub fn filter_cfg_attributes<'a, I>(
+    attrs: I,
+) -> impl Iterator<Item = &'a syn::Attribute> + 'a
+where
+    I: IntoIterator<Item = &'a syn::Attribute> + 'a,
+{
+    attrs.into_iter().filter(|attr| attr.path.is_ident("cfg"))
+}
+
+/// Returns `true` if the attributes contain any `#[ink(..)]` markers.
+pub fn has_ink_attributes<'a, I>(attrs: I) -> bool
+where
+    I: IntoIterator<Item = &'a syn::Attribute> + 'a,
+{
+    filter_ink_attributes(attrs).count() > 0
+}
+
+/// Ensures that the ink! attributes do not override the selector of a function.
+///
+/// Functions of ink! trait definitions always use the selectors derived
+/// from the trait so that all implementers agree on them.
+pub fn ensure_no_selector_override(attrs: &[syn::Attribute]) -> Result<()> {
+    for marker in filter_map_ink_attributes(attrs) {
+        if marker.args().any(|(ident, _value)| ident == "selector") {
+            bail_span!(
+                marker.span(),
+                "ink! trait functions must not override their selectors",
+            )
+        }
+    }
+    Ok(())
+}
+
+/// Ensures that the namespace of an ink! trait consists of plain identifiers
+/// separated by `::`, e.g. `erc20` or `tokens::erc20`.
+///
+/// # Errors
+///
+/// If the namespace has generic arguments, raw identifiers, leading `::`
+/// or whitespace since it is mixed into the identifier of its marker.
+pub fn ensure_valid_namespace(namespace: &syn::LitStr) -> Result<()> {
+    let is_plain_ident = |segment: &str| {
+        !segment.starts_with("r#")
+            && syn::parse_str::<syn::Ident>(segment)
+                .map(|ident| ident == segment)
+                .unwrap_or(false)
+    };
+    if !namespace.value().split("::").all(is_plain_ident) {
+        bail!(
+            namespace,
+            "ink! namespaces must be identifiers separated by `::`, e.g. `erc20`",
+        )
+    }
+    Ok(())
+}
+
+/// Returns the identifier of the hidden trait function that marks the namespace
+/// of an ink! trait definition.
+///
+/// # Note
+///
+/// Implementations of ink! traits refer to the marker of their own namespace
+/// so that compilation fails if it differs from the namespace of the trait
+/// definition, e.g. `__ink_trait_namespace_is_erc20` for `namespace = "erc20"`.
+/// The namespace must have been checked by [`ensure_valid_namespace`].
+pub fn trait_namespace_marker(namespace: Option<&syn::LitStr>) -> syn::Ident {
+    match namespace {
+        Some(namespace) => {
+            syn::Ident::new(
+                &format!(
+                    "__ink_trait_namespace_is_{}",
+                    namespace.value().replace("::", "__"),
+                ),
+                namespace.span(),
+            )
+        }
+        None => syn::Ident::new("__ink_trait_without_namespace", Span::call_site()),
+    }
+}
+
+/// Returns the error type `E` if the type is of the form `Result<(), E>`.
+pub fn result_error_type(ty: &syn::Type) -> Option<&syn::Type> {
+    match result_types(ty)? {
+        (syn::Type::Tuple(unit), error) if unit.elems.is_empty() => Some(error),
+        _ => None,
+    }
+}
+
+/// Returns the last path segment of the type if it is named `Result`.
+///
+/// # Note
+///
+/// Only the last path segment is inspected so that `core::result::Result`
+/// and aliases named `Result` are accepted as well.
+fn result_segment(ty: &syn::Type) -> Option<&syn::PathSegment> {
+    let segment = match ty {
+        syn::Type::Path(type_path) if type_path.qself.is_none() => {
+            type_path.path.segments.last()?
+        }
+        _ => return None,
+    };
+    if segment.ident != "Result" {
+        return None
+    }
+    Some(segment)
+}
+
+/// Returns `true` if the type is named `Result`, e.g. `Result<T, E>` or `Result<T>`.
+pub fn is_result_type(ty: &syn::Type) -> bool {
+    result_segment(ty).is_some()
+}
+
+/// Returns the types `T` and `E` if the type is of the form `Result<T, E>`.
+pub fn result_types(ty: &syn::Type) -> Option<(&syn::Type, &syn::Type)> {
+    let segment = result_segment(ty)?;
+    let args = match &segment.arguments {
+        syn::PathArguments::AngleBracketed(generics) => &generics.args,
+        _ => return None,
+    };
+    let mut types = args.iter().map(|arg| {
+        match arg {
+            syn::GenericArgument::Type(ty) => Some(ty),
+            _ => None,
+        }
+    });
+    match (types.next(), types.next(), types.next()) {
+        (Some(Some(value)), Some(Some(error)), None) => Some((value, error)),
+        _ => None,
+    }
+}
+
+/// Filters the given attributes for `#[doc(..)]` attributes
+/// and trims them to human-readable documentation strings.
+///
+/// # Note
+///
+/// This is mainly used in the ABI generation routines.
+pub fn filter_map_trimmed_doc_strings<'a, I>(
+    attrs: I,
+) -> impl Iterator<Item = String> + 'a
+where
+    I: IntoIterator<Item = &'a syn::Attribute>,
+    <I as IntoIterator>::IntoIter: 'a,
+{
+    attrs
+        .into_iter()
+        .filter(move |attr| {
+            attr.style == syn::AttrStyle::Outer && attr.path.is_ident("doc")
+        })
+        .map(to_trimmed_doc_string)
+}
+
+/// Trims a doc string obtained from an attribute token stream into the actual doc string.
+///
+/// Practically speaking this method removes the trailing start `" = \""` and end `\"`
+/// of documentation strings coming from Syn attribute token streams.
+pub fn to_trimmed_doc_string(attr: &syn::Attribute) -> String {
+    attr.tokens
+        .to_string()
+        .trim_start_matches('=')
+        .trim_start()
+        .trim_start_matches('r')
+        .trim_start_matches('"')
+        .trim_end_matches('"')
+        .trim()
+        .into()
+}
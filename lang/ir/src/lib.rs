@@ -0,0 +1,34 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Parsing of ink! contracts into the ink! IR shared by the ink! procedural macros.
+//!
+//! Also derives the metadata of ink! contracts from their source code for
+//! tooling that does not compile the contracts, see [`generate_metadata`].
+
+#[macro_use]
+mod error;
+
+pub mod ir;
+mod metadata;
+
+pub use self::metadata::{
+    generate_metadata,
+    ConstructorMetadata,
+    ContractMetadata,
+    EventMetadata,
+    EventParamMetadata,
+    MessageMetadata,
+    ParamMetadata,
+};
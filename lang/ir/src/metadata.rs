@@ -0,0 +1,417 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Metadata of ink! contracts derived from their source code.
+//!
+//! Unlike the metadata generated through `ink_lang::GenerateAbi` this does not
+//! require compiling the contract. Types are thus described by their source
+//! code instead of their type definitions.
+
+use core::convert::TryFrom;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::ToTokens as _;
+use serde::{
+    Serialize,
+    Serializer,
+};
+use syn::Result;
+
+use crate::{
+    ir,
+    ir::utils,
+};
+
+/// Returns the metadata of the contract defined by the given parameters and module.
+///
+/// # Note
+///
+/// The parameters are the tokens within the parentheses of `#[ink::contract(..)]`
+/// and the module is the item annotated by it. Constructors and messages under
+/// `#[cfg(..)]` attributes are always included since their conditions are only
+/// known when compiling the contract. Their attributes are part of the metadata.
+///
+/// # Errors
+///
+/// If the parameters or the module do not define a valid ink! contract.
+///
+/// # Example
+///
+/// ```
+/// # use quote::quote;
+/// let metadata = ink_lang_ir::generate_metadata(
+///     quote! { version = "0.1.0" },
+///     quote! {
+///         mod flipper {
+///             #[ink(storage)]
+///             struct Flipper {
+///                 value: storage::Value<bool>,
+///             }
+///
+///             impl Flipper {
+///                 #[ink(constructor)]
+///                 fn new(&mut self) {
+///                     self.value.set(false)
+///                 }
+///
+///                 #[ink(message)]
+///                 fn get(&self) -> bool {
+///                     *self.value
+///                 }
+///             }
+///         }
+///     },
+/// )?;
+/// assert_eq!(metadata.name, "flipper");
+/// assert_eq!(metadata.messages[0].return_type.as_ref().unwrap(), "bool");
+/// # Ok::<(), syn::Error>(())
+/// ```
+pub fn generate_metadata(
+    attr: TokenStream2,
+    input: TokenStream2,
+) -> Result<ContractMetadata> {
+    let params = syn::parse2::<ir::Params>(attr)?;
+    let item_mod = syn::parse2::<syn::ItemMod>(input)?;
+    let contract = ir::Contract::try_from((params, item_mod))?;
+    Ok(ContractMetadata::from(&contract))
+}
+
+/// Describes a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ContractMetadata {
+    /// The name of the contract.
+    pub name: String,
+    /// The constructors of the contract.
+    pub constructors: Vec<ConstructorMetadata>,
+    /// The messages of the contract.
+    pub messages: Vec<MessageMetadata>,
+    /// The events of the contract.
+    pub events: Vec<EventMetadata>,
+    /// The contract documentation.
+    pub docs: Vec<String>,
+}
+
+/// Describes a constructor of a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ConstructorMetadata {
+    /// The name of the constructor.
+    pub name: String,
+    /// The selector of the constructor.
+    #[serde(serialize_with = "serialize_selector")]
+    pub selector: [u8; 4],
+    /// The namespace of the implemented ink! trait if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// If the constructor accepts the endowment of the instantiation.
+    pub payable: bool,
+    /// The parameters of the constructor.
+    pub args: Vec<ParamMetadata>,
+    /// The error type of a constructor returning `Result<(), E>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The `#[cfg(..)]` attributes of the constructor.
+    pub cfg: Vec<String>,
+    /// The constructor documentation.
+    pub docs: Vec<String>,
+}
+
+/// Describes a message of a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct MessageMetadata {
+    /// The name of the message.
+    pub name: String,
+    /// The selector of the message.
+    #[serde(serialize_with = "serialize_selector")]
+    pub selector: [u8; 4],
+    /// The namespace of the implemented ink! trait if any.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<String>,
+    /// If the message is allowed to mutate the contract state.
+    pub mutates: bool,
+    /// If the message accepts transferred value.
+    pub payable: bool,
+    /// The parameters of the message.
+    pub args: Vec<ParamMetadata>,
+    /// The return type of the message if any.
+    pub return_type: Option<String>,
+    /// The error type of a message returning `Result<T, E>`.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub error: Option<String>,
+    /// The `#[cfg(..)]` attributes of the message.
+    pub cfg: Vec<String>,
+    /// The message documentation.
+    pub docs: Vec<String>,
+}
+
+/// Describes a pair of parameter name and type.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct ParamMetadata {
+    /// The name of the parameter.
+    pub name: String,
+    /// The type of the parameter.
+    #[serde(rename = "type")]
+    pub ty: String,
+}
+
+/// Describes an event of a contract.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventMetadata {
+    /// The name of the event.
+    pub name: String,
+    /// If the event is anonymous and thus has no signature topic.
+    pub anonymous: bool,
+    /// The signature of a non-anonymous event that is hashed into its first topic.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub signature: Option<String>,
+    /// The event arguments.
+    pub args: Vec<EventParamMetadata>,
+    /// The event documentation.
+    pub docs: Vec<String>,
+}
+
+/// Describes an argument of an event.
+#[derive(Debug, Clone, PartialEq, Eq, Serialize)]
+pub struct EventParamMetadata {
+    /// The name of the argument.
+    pub name: String,
+    /// If the argument is indexed as a topic.
+    pub indexed: bool,
+    /// The type of the argument.
+    #[serde(rename = "type")]
+    pub ty: String,
+    /// The argument documentation.
+    pub docs: Vec<String>,
+}
+
+/// Returns the source code of the type.
+fn type_to_string(ty: &syn::Type) -> String {
+    ty.to_token_stream().to_string()
+}
+
+/// Returns the parameters of the function.
+fn params_of(function: &ir::Function) -> Vec<ParamMetadata> {
+    function
+        .sig
+        .inputs()
+        .map(|fn_arg| {
+            ParamMetadata {
+                name: fn_arg.ident.to_string(),
+                ty: type_to_string(&fn_arg.ty),
+            }
+        })
+        .collect()
+}
+
+/// Returns the `#[cfg(..)]` attributes of the function.
+fn cfg_of(function: &ir::Function) -> Vec<String> {
+    utils::filter_cfg_attributes(&function.attrs)
+        .map(|attr| attr.to_token_stream().to_string())
+        .collect()
+}
+
+impl From<&ir::Contract> for ContractMetadata {
+    fn from(contract: &ir::Contract) -> Self {
+        let constructors = contract
+            .functions
+            .iter()
+            .filter_map(|function| {
+                function.filter_constructor().map(|kind| (function, kind))
+            })
+            .map(|(constructor, kind)| {
+                ConstructorMetadata {
+                    name: constructor.sig.ident.to_string(),
+                    selector: *kind.selector.as_bytes(),
+                    namespace: constructor.namespace.as_ref().map(syn::LitStr::value),
+                    payable: kind.is_payable,
+                    args: params_of(constructor),
+                    error: constructor.constructor_error().map(type_to_string),
+                    cfg: cfg_of(constructor),
+                    docs: utils::filter_map_trimmed_doc_strings(&constructor.attrs)
+                        .collect(),
+                }
+            })
+            .collect();
+        let messages = contract
+            .functions
+            .iter()
+            .filter_map(|function| function.filter_message().map(|kind| (function, kind)))
+            .map(|(message, kind)| {
+                MessageMetadata {
+                    name: message.sig.ident.to_string(),
+                    selector: *kind.selector.as_bytes(),
+                    namespace: message.namespace.as_ref().map(syn::LitStr::value),
+                    mutates: message.sig.is_mut(),
+                    payable: kind.is_payable,
+                    args: params_of(message),
+                    return_type: match &message.sig.output {
+                        syn::ReturnType::Default => None,
+                        syn::ReturnType::Type(_, ty) => Some(type_to_string(ty)),
+                    },
+                    error: message.message_error().as_ref().map(type_to_string),
+                    cfg: cfg_of(message),
+                    docs: utils::filter_map_trimmed_doc_strings(&message.attrs).collect(),
+                }
+            })
+            .collect();
+        let events = contract
+            .events
+            .iter()
+            .map(|event| {
+                let args = event
+                    .fields
+                    .named
+                    .iter()
+                    .map(|field| {
+                        EventParamMetadata {
+                            name: field
+                                .ident
+                                .as_ref()
+                                .expect("we only operate on named fields")
+                                .to_string(),
+                            indexed: utils::filter_map_ink_attributes(&field.attrs)
+                                .any(|marker| marker.ident() == "topic"),
+                            ty: type_to_string(&field.ty),
+                            docs: utils::filter_map_trimmed_doc_strings(&field.attrs)
+                                .collect(),
+                        }
+                    })
+                    .collect();
+                EventMetadata {
+                    name: event.ident.to_string(),
+                    anonymous: event.anonymous,
+                    signature: if event.anonymous {
+                        None
+                    } else {
+                        Some(event.signature(&contract.ident))
+                    },
+                    args,
+                    docs: utils::filter_map_trimmed_doc_strings(&event.attrs).collect(),
+                }
+            })
+            .collect();
+        Self {
+            name: contract.ident.to_string(),
+            constructors,
+            messages,
+            events,
+            docs: utils::filter_map_trimmed_doc_strings(&contract.attrs).collect(),
+        }
+    }
+}
+
+/// Serializes the selector the same as the metadata generated by `ink_abi`.
+fn serialize_selector<S>(
+    s: &[u8; 4],
+    serializer: S,
+) -> core::result::Result<S::Ok, S::Error>
+where
+    S: Serializer,
+{
+    let hex = format!(
+        r#"["0x{:02X}","0x{:02X}","0x{:02X}","0x{:02X}"]"#,
+        s[0], s[1], s[2], s[3]
+    );
+    serializer.serialize_str(&hex)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use quote::quote;
+
+    #[test]
+    fn metadata_describes_contract() {
+        let metadata = generate_metadata(
+            quote! { version = "0.1.0" },
+            quote! {
+                /// Counts the calls of its messages.
+                mod counter {
+                    #[ink(storage)]
+                    struct Counter {
+                        value: storage::Value<u32>,
+                    }
+
+                    /// Emitted upon incrementing the counter.
+                    #[ink(event)]
+                    struct Incremented {
+                        #[ink(topic)]
+                        by: u32,
+                        value: u32,
+                    }
+
+                    impl Counter {
+                        #[ink(constructor)]
+                        fn new(&mut self, init_value: u32) {
+                            self.value.set(init_value)
+                        }
+
+                        /// Increments the counter.
+                        #[ink(message, payable, selector = 0xCAFEBABE)]
+                        fn inc(&mut self, by: u32) {
+                            *self.value += by;
+                        }
+
+                        #[cfg(feature = "admin")]
+                        #[ink(message)]
+                        fn get(&self) -> Result<u32, ()> {
+                            Ok(*self.value)
+                        }
+                    }
+                }
+            },
+        )
+        .unwrap();
+        assert_eq!(metadata.name, "counter");
+        assert_eq!(metadata.docs, vec!["Counts the calls of its messages."]);
+        assert_eq!(metadata.constructors.len(), 1);
+        assert_eq!(metadata.constructors[0].name, "new");
+        assert_eq!(
+            metadata.constructors[0].args,
+            vec![ParamMetadata {
+                name: "init_value".into(),
+                ty: "u32".into(),
+            }],
+        );
+        let (inc, get) = (&metadata.messages[0], &metadata.messages[1]);
+        assert_eq!(inc.selector, [0xCA, 0xFE, 0xBA, 0xBE]);
+        assert!(inc.mutates && inc.payable);
+        assert_eq!(inc.return_type, None);
+        assert_eq!(inc.docs, vec!["Increments the counter."]);
+        assert!(!get.mutates && !get.payable);
+        assert_eq!(
+            get.return_type,
+            Some(quote! { Result<u32, ()> }.to_string()),
+        );
+        assert_eq!(get.error, Some(quote! { () }.to_string()));
+        assert_eq!(
+            get.cfg,
+            vec![quote! { #[cfg(feature = "admin")] }.to_string()]
+        );
+        let event = &metadata.events[0];
+        assert_eq!(event.signature.as_ref().unwrap(), "counter::Incremented");
+        assert_eq!(
+            event
+                .args
+                .iter()
+                .map(|arg| (arg.name.as_str(), arg.indexed))
+                .collect::<Vec<_>>(),
+            vec![("by", true), ("value", false)],
+        );
+        let json = serde_json::to_value(&metadata).unwrap();
+        assert_eq!(
+            json["messages"][0]["selector"],
+            r#"["0xCA","0xFE","0xBA","0xBE"]"#,
+        );
+        assert_eq!(json["messages"][0]["args"][0]["type"], "u32");
+    }
+}
@@ -70,23 +70,38 @@ impl GenerateAbi<'_> {
                 let span = constructor.span();
                 let ident_lit = constructor.sig.ident.to_string();
                 let selector_bytes = kind.selector.as_bytes();
+                let is_payable = kind.is_payable;
 
                 let docs = utils::filter_map_trimmed_doc_strings(&constructor.attrs);
+                let namespace = constructor.namespace.as_ref().map(|namespace| {
+                    quote! { .namespace(#namespace) }
+                });
                 let args = constructor
                     .sig
                     .inputs()
                     .map(|fn_arg| self.generate_message_param(fn_arg));
+                let error_ty = constructor.constructor_error().map(|error| {
+                    let type_spec = self.generate_type_spec(error);
+                    quote! { .error(#type_spec) }
+                });
+
+                let cfg_attrs = utils::filter_cfg_attributes(&constructor.attrs);
 
                 quote_spanned!(span =>
-                    ink_abi::ConstructorSpec::new(#ident_lit)
+                    #( #cfg_attrs )*
+                    constructors.push(ink_abi::ConstructorSpec::new(#ident_lit)
                         .selector([#(#selector_bytes),*])
+                        #namespace
+                        .payable(#is_payable)
                         .args(vec![
                             #(#args ,)*
                         ])
+                        #error_ty
                         .docs(vec![
                             #(#docs ,)*
                         ])
                         .done()
+                    );
                 )
             })
     }
@@ -155,19 +170,32 @@ impl GenerateAbi<'_> {
                 let ident_lit = message.sig.ident.to_string();
                 let selector_bytes = kind.selector.as_bytes();
                 let is_mut = message.sig.is_mut();
+                let is_payable = kind.is_payable;
 
                 let docs = utils::filter_map_trimmed_doc_strings(&message.attrs);
+                let namespace = message.namespace.as_ref().map(|namespace| {
+                    quote! { .namespace(#namespace) }
+                });
 
                 let args = message
                     .sig
                     .inputs()
                     .map(|fn_arg| self.generate_message_param(fn_arg));
                 let ret_ty = self.generate_return_type(&message.sig.output);
+                let error_ty = message.message_error().map(|error| {
+                    let type_spec = self.generate_type_spec(&error);
+                    quote! { .error(#type_spec) }
+                });
+
+                let cfg_attrs = utils::filter_cfg_attributes(&message.attrs);
 
                 quote_spanned!(span =>
-                    ink_abi::MessageSpec::new(#ident_lit)
+                    #( #cfg_attrs )*
+                    messages.push(ink_abi::MessageSpec::new(#ident_lit)
                         .selector([#(#selector_bytes),*])
+                        #namespace
                         .mutates(#is_mut)
+                        .payable(#is_payable)
                         .args(vec![
                             #(#args ,)*
                         ])
@@ -177,7 +205,9 @@ impl GenerateAbi<'_> {
                         .returns(
                             #ret_ty
                         )
+                        #error_ty
                         .done()
+                    );
                 )
             })
     }
@@ -222,12 +252,23 @@ impl GenerateAbi<'_> {
             let span = event.span();
             let ident = &event.ident;
             let ident_lit = ident.to_string();
+            let anonymous = event.anonymous;
+            let signature_topic = if anonymous {
+                None
+            } else {
+                let signature = event.signature(&self.contract.ident);
+                Some(quote! {
+                    .signature_topic(ink_core::env::topic::<EnvTypes, _>(&#signature))
+                })
+            };
 
             let docs = utils::filter_map_trimmed_doc_strings(&event.attrs);
             let args = self.generate_event_args(event);
 
             quote_spanned!(span =>
                 ink_abi::EventSpec::new(#ident_lit)
+                    .anonymous(#anonymous)
+                    #signature_topic
                     .args(vec![
                         #( #args, )*
                     ])
@@ -243,25 +284,41 @@ impl GenerateAbi<'_> {
         utils::filter_map_trimmed_doc_strings(&self.contract.attrs)
     }
 
+    fn generate_environment(&self) -> TokenStream2 {
+        quote! {
+            ink_abi::EnvironmentSpec::new(
+                ink_abi::TypeSpec::with_name_str::<AccountId>("AccountId"),
+                ink_abi::TypeSpec::with_name_str::<Balance>("Balance"),
+                ink_abi::TypeSpec::with_name_str::<Hash>("Hash"),
+                ink_abi::TypeSpec::with_name_str::<Timestamp>("Timestamp"),
+                ink_abi::TypeSpec::with_name_str::<BlockNumber>("BlockNumber"),
+            )
+        }
+    }
+
     fn generate_contract(&self) -> TokenStream2 {
         let contract_ident_lit = self.contract.ident.to_string();
 
         let constructors = self.generate_constructors();
         let messages = self.generate_messages();
         let events = self.generate_events();
+        let environment = self.generate_environment();
         let docs = self.generate_docs();
 
+        // The specs are pushed one by one since `#[cfg]` attributes
+        // of constructors and messages are not allowed on expressions.
         quote! {
+            let mut constructors = Vec::new();
+            #( #constructors )*
+            let mut messages = Vec::new();
+            #( #messages )*
             ink_abi::ContractSpec::new(#contract_ident_lit)
-                .constructors(vec![
-                    #(#constructors ,)*
-                ])
-                .messages(vec![
-                    #(#messages ,)*
-                ])
+                .constructors(constructors)
+                .messages(messages)
                 .events(vec![
                     #(#events ,)*
                 ])
+                .environment(#environment)
                 .docs(vec![
                     #(#docs ,)*
                 ])
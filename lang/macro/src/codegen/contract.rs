@@ -14,7 +14,10 @@
 
 use derive_more::From;
 use proc_macro2::TokenStream as TokenStream2;
-use quote::quote;
+use quote::{
+    format_ident,
+    quote,
+};
 
 pub use crate::{
     codegen::{
@@ -22,6 +25,7 @@ pub use crate::{
         cross_calling::{
             CrossCalling,
             CrossCallingConflictCfg,
+            CrossCallingDependencyCfg,
         },
         dispatch::Dispatch,
         env_types::EnvTypes,
@@ -56,8 +60,11 @@ impl GenerateCode for ContractModule<'_> {
     fn generate_code(&self) -> TokenStream2 {
         let ident = &self.contract.ident;
         let storage_ident = &self.contract.storage.ident;
+        let ref_ident = format_ident!("{}Ref", storage_ident);
+        let ref_doc = format!(" Refers to an instantiated `{}` contract.", storage_ident);
 
         let conflic_depedency_cfg = self.generate_code_using::<CrossCallingConflictCfg>();
+        let depedency_cfg = self.generate_code_using::<CrossCallingDependencyCfg>();
         let env_types = self.generate_code_using::<EnvTypes>();
         let storage = self.generate_code_using::<Storage>();
         let dispatch = self.generate_code_using::<Dispatch>();
@@ -87,15 +94,19 @@ impl GenerateCode for ContractModule<'_> {
                 }
 
                 #[cfg(all(test, feature = "test-env"))]
+                #conflic_depedency_cfg
                 pub type #storage_ident = self::__ink_private::TestableStorage;
 
                 #[cfg(not(all(test, feature = "test-env")))]
                 #conflic_depedency_cfg
                 pub type #storage_ident = self::__ink_private::Storage;
 
-                #[cfg(feature = "ink-as-dependency")]
+                #depedency_cfg
                 pub type #storage_ident = self::__ink_private::StorageAsDependency;
 
+                #[doc = #ref_doc]
+                pub type #ref_ident = self::__ink_private::StorageAsDependency;
+
                 #event_structs
 
                 #(
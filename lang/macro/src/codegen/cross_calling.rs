@@ -14,6 +14,12 @@
 
 //! Generates code for contracts that are compiled as dependencies of other contracts.
 //!
+//! # Contract References
+//!
+//! The generated code is always available as `{Storage}Ref` within the contract
+//! module, e.g. `FlipperRef` for a `Flipper` contract. Its constructors build
+//! instantiations of the contract and its messages call an instantiated contract.
+//!
 //! # Triggers
 //!
 //! Compiling a contract as dependency is controlled by two different ways:
@@ -25,6 +31,9 @@
 //! So setting `compile_as_dependency` will always result in compiling the contract
 //! as dependency even though `ink-as-dependency` is not enabled or existent.
 //!
+//! Compiled as dependency the contract storage struct refers to the same
+//! type as the contract reference.
+//!
 //! # Codegen Conflicts
 //!
 //! Contracts that have been compiled as dependencies strip away most of the
@@ -78,13 +87,39 @@ use crate::{
 
 #[derive(From)]
 pub struct CrossCallingConflictCfg<'a> {
-    _contract: &'a ir::Contract,
+    contract: &'a ir::Contract,
 }
 
 impl GenerateCode for CrossCallingConflictCfg<'_> {
     fn generate_code(&self) -> TokenStream2 {
-        quote! {
-            #[cfg(not(feature = "ink-as-dependency"))]
+        if self.contract.meta_info.is_compiled_as_dependency() {
+            // The conflicting code is never compiled.
+            quote! {
+                #[cfg(any())]
+            }
+        } else {
+            quote! {
+                #[cfg(not(feature = "ink-as-dependency"))]
+            }
+        }
+    }
+}
+
+/// Generates the `cfg` under which the contract storage struct refers to the
+/// contract compiled as dependency.
+#[derive(From)]
+pub struct CrossCallingDependencyCfg<'a> {
+    contract: &'a ir::Contract,
+}
+
+impl GenerateCode for CrossCallingDependencyCfg<'_> {
+    fn generate_code(&self) -> TokenStream2 {
+        if self.contract.meta_info.is_compiled_as_dependency() {
+            quote! {}
+        } else {
+            quote! {
+                #[cfg(feature = "ink-as-dependency")]
+            }
         }
     }
 }
@@ -96,7 +131,6 @@ pub struct CrossCalling<'a> {
 
 impl GenerateCode for CrossCalling<'_> {
     fn generate_code(&self) -> TokenStream2 {
-        let cfg_bounds = self.generate_cfg_bounds();
         let storage = self.generate_storage();
         let storage_impls = self.generate_storage_impls();
         let storage_fns = self.generate_storage_fns();
@@ -104,7 +138,6 @@ impl GenerateCode for CrossCalling<'_> {
         let ref_mut_forwarder = self.generate_ref_mut_forwarder();
 
         quote! {
-            #cfg_bounds
             mod __ink_cross_calling {
                 use super::*;
 
@@ -115,7 +148,6 @@ impl GenerateCode for CrossCalling<'_> {
                 #ref_mut_forwarder
             }
 
-            #[cfg(feature = "ink-as-dependency")]
             pub use self::__ink_cross_calling::StorageAsDependency;
         }
     }
@@ -128,16 +160,6 @@ impl GenerateCodeUsing for CrossCalling<'_> {
 }
 
 impl CrossCalling<'_> {
-    fn generate_cfg_bounds(&self) -> TokenStream2 {
-        if self.contract.meta_info.is_compiled_as_dependency() {
-            quote! {}
-        } else {
-            quote! {
-                #[cfg(feature = "ink-as-dependency")]
-            }
-        }
-    }
-
     fn generate_storage(&self) -> TokenStream2 {
         let attrs = utils::filter_non_ink_attributes(&self.contract.storage.attrs);
 
@@ -195,6 +217,17 @@ impl CrossCalling<'_> {
         }
     }
 
+    /// Returns `true` if the function is made callable on the contract reference.
+    ///
+    /// # Note
+    ///
+    /// Functions of namespaced trait implementations are skipped since their
+    /// names may clash with those of other traits. They are called through the
+    /// reference type generated by their ink! trait definition instead.
+    fn is_forwarded(function: &ir::Function) -> bool {
+        function.namespace.is_none()
+    }
+
     fn generate_storage_constructors<'a>(
         &'a self,
     ) -> impl Iterator<Item = TokenStream2> + 'a {
@@ -202,6 +235,7 @@ impl CrossCalling<'_> {
             .functions
             .iter()
             .filter(|function| function.is_constructor())
+            .filter(|function| Self::is_forwarded(function))
             .map(move |function| {
                 let span = function.span();
                 let ident = &function.sig.ident;
@@ -243,6 +277,7 @@ impl CrossCalling<'_> {
             .functions
             .iter()
             .filter(|function| function.is_message())
+            .filter(|function| Self::is_forwarded(function))
             .map(move |function| {
                 let span = function.span();
                 let ident = &function.sig.ident;
@@ -318,6 +353,7 @@ impl CrossCalling<'_> {
             .functions
             .iter()
             .filter(move |function| pred(function))
+            .filter(|function| Self::is_forwarded(function))
             .filter_map(|function| function.filter_message().map(|kind| (function, kind)))
             .map(|(function, kind)| {
                 let span = function.span();
@@ -29,7 +29,10 @@ use crate::{
         GenerateCode,
         GenerateCodeUsing,
     },
-    ir,
+    ir::{
+        self,
+        utils,
+    },
 };
 
 /// Generates code for the dispatch parts that dispatch constructors
@@ -55,11 +58,9 @@ impl GenerateCode for Dispatch<'_> {
         let entry_points = self.generate_entry_points();
 
         quote! {
-            // We do not generate contract dispatch code
-            // while the contract is being tested or the
-            // `test-env` has been enabled since both resulting
-            // compilations do not require dispatching.
-            #[cfg(not(any(test, feature = "test-env")))]
+            // The dispatch is also generated while the contract is being
+            // tested so that tests can dispatch calls in the off-chain
+            // environment. Only the entry points are left out.
             #conflic_depedency_cfg
             const _: () = {
                 #message_namespaces
@@ -77,6 +78,7 @@ impl Dispatch<'_> {
             return quote! {}
         }
         let span = function.span();
+        let cfg_attrs = utils::filter_cfg_attributes(&function.attrs).collect::<Vec<_>>();
         let selector = function
             .selector()
             .expect("this is either a message or constructor at this point; qed");
@@ -84,12 +86,21 @@ impl Dispatch<'_> {
         let sig = &function.sig;
         let inputs = sig.inputs().map(|ident_type| &ident_type.ty);
         let inputs_punct = inputs.collect::<Punctuated<_, Token![,]>>();
-        let output = &sig.output;
-        let output_type = match output {
-            syn::ReturnType::Default => quote! {},
-            syn::ReturnType::Type(_, ty) => quote! { #ty },
+        // Errors of fallible constructors are handled by their dispatch fragment.
+        let output_type = match &sig.output {
+            syn::ReturnType::Type(_, ty) if !function.is_constructor() => quote! { #ty },
+            _ => quote! {},
         };
         let is_mut = sig.is_mut();
+        let is_payable = match function.kind() {
+            ir::FunctionKind::Constructor(constructor) => constructor.is_payable,
+            ir::FunctionKind::Message(message) => message.is_payable,
+            ir::FunctionKind::Method => panic!("ICE: can't match a method at this point"),
+        };
+        let is_non_reentrant = match function.kind() {
+            ir::FunctionKind::Message(message) => message.is_non_reentrant,
+            _ => false,
+        };
 
         use syn::spanned::Spanned as _;
 
@@ -100,27 +111,35 @@ impl Dispatch<'_> {
         };
 
         let fn_input = quote_spanned!(sig.inputs.span() =>
+            #( #cfg_attrs )*
             impl ink_lang::FnInput for #namespace<[(); #selector_id]> {
                 #[allow(unused_parens)]
                 type Input = (#inputs_punct);
             }
         );
         let fn_output = quote_spanned!(sig.output.span() =>
+            #( #cfg_attrs )*
             impl ink_lang::FnOutput for #namespace<[(); #selector_id]> {
                 #[allow(unused_parens)]
                 type Output = (#output_type);
             }
         );
+        let is_wildcard = function.is_wildcard();
         let fn_selector = quote_spanned!(span =>
+            #( #cfg_attrs )*
             impl ink_lang::FnSelector for #namespace<[(); #selector_id]> {
                 const SELECTOR: ink_core::env::call::Selector = ink_core::env::call::Selector::new([
                     #( #selector_bytes ),*
                 ]);
+                const IS_WILDCARD: bool = #is_wildcard;
             }
         );
         let message_impl = quote_spanned!(span =>
+            #( #cfg_attrs )*
             impl ink_lang::Message for #namespace<[(); #selector_id]> {
                 const IS_MUT: bool = #is_mut;
+                const IS_PAYABLE: bool = #is_payable;
+                const IS_NON_REENTRANT: bool = #is_non_reentrant;
             }
         );
 
@@ -182,6 +201,7 @@ impl Dispatch<'_> {
             .selector()
             .expect("this is either a message or constructor at this point; qed");
         let selector_id = selector.unique_id();
+        let cfg_attrs = utils::filter_cfg_attributes(&function.attrs);
         let sig = &function.sig;
         let input_idents = sig
             .inputs()
@@ -207,20 +227,70 @@ impl Dispatch<'_> {
             ir::FunctionKind::Method => panic!("ICE: can't match a method at this point"),
         };
         let fn_name = &sig.ident;
+        // Trait functions are called fully qualified since the trait might not be in scope.
+        let fn_call = match &function.trait_path {
+            Some(trait_path) => {
+                quote! { <Storage as #trait_path>::#fn_name(storage, #fn_idents) }
+            }
+            None => quote! { storage.#fn_name(#fn_idents) },
+        };
+        // Fallible constructors revert the instantiation and hand back their error
+        // so that no state of the failed instantiation persists.
+        let fn_call = match function.constructor_error() {
+            Some(error_ty) => {
+                quote! {
+                    if let Err(error) = #fn_call {
+                        ink_core::env::return_value::<core::result::Result<(), #error_ty>>(
+                            ink_core::env::ReturnFlags::REVERT,
+                            &Err(error),
+                        )
+                    }
+                }
+            }
+            None => fn_call,
+        };
+        // Messages returning an error revert their state changes
+        // and still hand back the encoded error to the caller.
+        // This includes messages returning aliases such as `Result<T>`.
+        let fn_call = match function.message_error() {
+            Some(_) => {
+                quote! {
+                    let result = #fn_call;
+                    if result.is_err() {
+                        ink_core::env::return_value(ink_core::env::ReturnFlags::REVERT, &result)
+                    }
+                    result
+                }
+            }
+            None => fn_call,
+        };
 
         quote! {
-            .#builder_name::<#namespace<[(); #selector_id]>>(|storage, #pat_idents| {
-                storage.#fn_name(#fn_idents)
-            })
+            #( #cfg_attrs )*
+            let contract = contract.#builder_name::<#namespace<[(); #selector_id]>>(
+                |storage, #pat_idents| {
+                    #fn_call
+                }
+            );
         }
     }
 
     fn generate_dispatch_using_mode(&self) -> TokenStream2 {
-        let fragments = self
-            .contract
-            .functions
+        // Dispatchers are tried in reverse order of their registration.
+        // Wildcards are registered first so that they are tried last.
+        // All constructors have to be registered before the messages.
+        let functions = &self.contract.functions;
+        let (constructors, messages): (Vec<_>, Vec<_>) =
+            functions.iter().partition(|fun| fun.is_constructor());
+        let fragments = [constructors, messages]
             .iter()
-            .map(|fun| self.generate_dispatch_using_mode_fragment(fun));
+            .flat_map(|funs| {
+                funs.iter()
+                    .filter(|fun| fun.is_wildcard())
+                    .chain(funs.iter().filter(|fun| !fun.is_wildcard()))
+            })
+            .map(|fun| self.generate_dispatch_using_mode_fragment(fun))
+            .collect::<Vec<_>>();
 
         quote! {
             impl ink_lang::DispatchUsingMode for Storage {
@@ -228,12 +298,13 @@ impl Dispatch<'_> {
                 fn dispatch_using_mode(
                     mode: ink_lang::DispatchMode
                 ) -> core::result::Result<(), ink_lang::DispatchError> {
-                    ink_lang::Contract::with_storage::<Storage>()
-                        #(
-                            #fragments
-                        )*
-                        .done()
-                        .dispatch_using_mode::<EnvTypes>(mode)
+                    // Every fragment is a statement of its own so that
+                    // conditionally compiled functions can be left out.
+                    let contract = ink_lang::Contract::with_storage::<Storage>();
+                    #(
+                        #fragments
+                    )*
+                    contract.done().dispatch_using_mode::<EnvTypes>(mode)
                 }
             }
         }
@@ -241,7 +312,7 @@ impl Dispatch<'_> {
 
     fn generate_entry_points(&self) -> TokenStream2 {
         quote! {
-            #[cfg(not(test))]
+            #[cfg(not(any(test, feature = "test-env")))]
             #[no_mangle]
             fn deploy() -> u32 {
                 ink_lang::DispatchRetCode::from(
@@ -252,7 +323,7 @@ impl Dispatch<'_> {
                 .to_u32()
             }
 
-            #[cfg(not(test))]
+            #[cfg(not(any(test, feature = "test-env")))]
             #[no_mangle]
             fn call() -> u32 {
                 ink_lang::DispatchRetCode::from(
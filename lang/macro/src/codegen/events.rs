@@ -149,7 +149,7 @@ impl EventHelpers<'_> {
             )*
 
             impl ink_core::env::Topics<EnvTypes> for Event {
-                fn topics(&self) -> &'static [Hash] {
+                fn topics(&self) -> ink_core::env::EventTopics<Hash> {
                     match self {
                         #(
                             Event::#event_idents(event) => event.topics(),
@@ -161,14 +161,37 @@ impl EventHelpers<'_> {
     }
 
     fn generate_topics_impls<'a>(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
-        self.contract.events.iter().map(|item_event| {
+        self.contract.events.iter().map(move |item_event| {
             let span = item_event.span();
             let ident = &item_event.ident;
+            // Unless the event is anonymous its first topic is its signature.
+            let signature_topic = if item_event.anonymous {
+                quote! {}
+            } else {
+                let signature = item_event.signature(&self.contract.ident);
+                quote! { ink_core::env::topic::<EnvTypes, _>(&#signature), }
+            };
+            let topic_fields = item_event
+                .topic_fields()
+                .map(|field| {
+                    field
+                        .ident
+                        .as_ref()
+                        .expect("we only operate on named fields")
+                })
+                .collect::<Vec<_>>();
+            let len_topics = topic_fields.len() + if item_event.anonymous { 0 } else { 1 };
 
+            // The topics are collected from an array so that no heap vector is
+            // required if the `ink-alloc-free` crate feature is enabled.
             quote_spanned!(span =>
                 impl ink_core::env::Topics<EnvTypes> for #ident {
-                    fn topics(&self) -> &'static [Hash] {
-                        &[]
+                    fn topics(&self) -> ink_core::env::EventTopics<Hash> {
+                        let topics: [Hash; #len_topics] = [
+                            #signature_topic
+                            #( ink_core::env::topic::<EnvTypes, _>(&self.#topic_fields), )*
+                        ];
+                        topics.iter().copied().collect()
                     }
                 }
             )
@@ -18,6 +18,7 @@ use quote::{
     quote,
     quote_spanned,
 };
+use syn::spanned::Spanned as _;
 
 use crate::{
     codegen::{
@@ -110,6 +111,21 @@ impl Storage<'_> {
             .iter()
             .map(|named_field| &named_field.ident)
             .collect::<Vec<_>>();
+        let initializers = self.contract.storage.fields.named.iter().map(|field| {
+            let ident = &field.ident;
+            if ir::ItemStorage::is_default_field(field) {
+                quote! {
+                    ink_core::storage::alloc::Initialize::initialize(
+                        &mut self.#ident,
+                        Default::default(),
+                    );
+                }
+            } else {
+                quote! {
+                    self.#ident.try_default_initialize();
+                }
+            }
+        });
 
         quote! {
             impl ink_core::storage::alloc::AllocateUsing for Storage {
@@ -119,7 +135,13 @@ impl Storage<'_> {
                 {
                     Self {
                         #(
-                            #field_idents: ink_core::storage::alloc::AllocateUsing::allocate_using(alloc),
+                            #field_idents: {
+                                ink_core::storage::alloc::Allocate::enter_field(
+                                    alloc,
+                                    stringify!(#field_idents),
+                                );
+                                ink_core::storage::alloc::AllocateUsing::allocate_using(alloc)
+                            },
                         )*
                     }
                 }
@@ -142,7 +164,7 @@ impl Storage<'_> {
 
                 fn initialize(&mut self, _args: Self::Args) {
                     #(
-                        self.#field_idents.try_default_initialize();
+                        #initializers
                     )*
                 }
             }
@@ -158,7 +180,8 @@ impl Storage<'_> {
         fields.named.iter_mut().for_each(|field| {
             field.vis = syn::Visibility::Public(syn::VisPublic {
                 pub_token: Default::default(),
-            })
+            });
+            field.attrs.retain(|attr| !utils::is_ink_attribute(attr))
         });
 
         quote_spanned!( span =>
@@ -179,7 +202,11 @@ impl Storage<'_> {
     fn generate_message(&self, function: &Function) -> TokenStream2 {
         let span = function.span();
         // Generate `pub` functions for constructors and messages only.
-        let vis = if function.is_constructor() || function.is_message() {
+        //
+        // Functions implementing an ink! trait inherit the visibility of the trait.
+        let vis = if function.trait_path.is_some() {
+            quote_spanned!(span => )
+        } else if function.is_constructor() || function.is_message() {
             quote_spanned!(span => pub)
         } else {
             quote_spanned!(span => )
@@ -190,6 +217,16 @@ impl Storage<'_> {
         let inputs = &function.sig.inputs;
         let output = &function.sig.output;
         let block = &function.block;
+        // Failed guards revert the message before its body is executed.
+        let guards = &function.guards;
+        let block = if guards.is_empty() {
+            quote_spanned!(span => #block)
+        } else {
+            quote_spanned!(span => {
+                #( ink_lang::check_guard(self.#guards()); )*
+                #block
+            })
+        };
         quote_spanned!( span =>
             #( #attrs )*
             #vis fn #ident #type_generics (
@@ -201,14 +238,49 @@ impl Storage<'_> {
     }
 
     /// Generates all the constructors, messages and methods defined on the storage struct.
+    ///
+    /// Functions implementing ink! traits are grouped into one implementation
+    /// block per trait.
     fn generate_message_impls(&self) -> TokenStream2 {
         let storage = &self.contract.storage;
         let span = storage.span();
+        let mut trait_impls: Vec<(&syn::Path, Vec<&Function>)> = Vec::new();
+        for function in &self.contract.functions {
+            if let Some(trait_path) = &function.trait_path {
+                match trait_impls.iter_mut().find(|(path, _)| *path == trait_path) {
+                    Some((_, fns)) => fns.push(function),
+                    None => trait_impls.push((trait_path, vec![function])),
+                }
+            }
+        }
         let fns = self
             .contract
             .functions
             .iter()
+            .filter(|fun| fun.trait_path.is_none())
             .map(|fun| self.generate_message(fun));
+        let trait_impls = trait_impls.into_iter().map(|(trait_path, fns)| {
+            // All functions of an implementation block share its namespace.
+            let namespace = fns[0].namespace.as_ref();
+            let namespace_marker = utils::trait_namespace_marker(namespace);
+            let marker_span = namespace
+                .map(|namespace| namespace.span())
+                .unwrap_or_else(|| trait_path.span());
+            // Fails to compile if the namespace differs from the namespace of the trait.
+            let namespace_check = quote_spanned!( marker_span =>
+                const _: fn() = <Storage as #trait_path>::#namespace_marker;
+            );
+            let fns = fns.into_iter().map(|fun| self.generate_message(fun));
+            quote_spanned!( span =>
+                impl #trait_path for Storage {
+                    #(
+                        #fns
+                    )*
+                }
+
+                #namespace_check
+            )
+        });
         quote_spanned!( span =>
             #[cfg_attr(feature = "cargo-clippy", allow(clippy::new_ret_no_self))]
             impl Storage {
@@ -216,6 +288,10 @@ impl Storage<'_> {
                     #fns
                 )*
             }
+
+            #(
+                #trait_impls
+            )*
         )
     }
 }
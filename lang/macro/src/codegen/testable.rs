@@ -21,6 +21,7 @@ use quote::{
 
 use crate::{
     codegen::{
+        cross_calling::CrossCallingConflictCfg,
         GenerateCode,
         GenerateCodeUsing,
     },
@@ -43,12 +44,15 @@ impl GenerateCode for TestWrapper<'_> {
     fn generate_code(&self) -> TokenStream2 {
         let wrapped_constructors = self.generate_constructors();
         let testable_storage_and_env = self.generate_testable_storage_and_env();
+        let conflic_depedency_cfg = self.generate_code_using::<CrossCallingConflictCfg>();
 
         quote! {
             #[cfg(all(test, feature = "test-env"))]
+            #conflic_depedency_cfg
             pub use self::__ink_testable::TestableStorage;
 
             #[cfg(all(test, feature = "test-env"))]
+            #conflic_depedency_cfg
             mod __ink_testable {
                 use super::*;
 
@@ -56,8 +60,11 @@ impl GenerateCode for TestWrapper<'_> {
                     type Wrapped = TestableStorage;
 
                     fn instantiate() -> Self::Wrapped {
-                        ink_core::env::test::initialize_as_default::<ink_core::env::DefaultEnvTypes>()
-                            .expect("encountered already initialized off-chain environment");
+                        // Tests using `#[ink::test]` have already set up the environment.
+                        if !ink_core::env::test::is_running_test_setup() {
+                            ink_core::env::test::initialize_as_default::<ink_core::env::DefaultEnvTypes>()
+                                .expect("encountered already initialized off-chain environment");
+                        }
                         let mut contract: Self = unsafe {
                             let mut alloc =
                                 ink_core::storage::alloc::BumpAlloc::from_raw_parts(
@@ -86,6 +93,15 @@ impl GenerateCode for TestWrapper<'_> {
                         #wrapped_constructors
                     )*
                 }
+
+                // Allows tests to dispatch calls to the tested contract.
+                impl ink_lang::DispatchUsingMode for TestableStorage {
+                    fn dispatch_using_mode(
+                        mode: ink_lang::DispatchMode,
+                    ) -> core::result::Result<(), ink_lang::DispatchError> {
+                        <Storage as ink_lang::DispatchUsingMode>::dispatch_using_mode(mode)
+                    }
+                }
             }
         }
     }
@@ -99,16 +115,45 @@ impl TestWrapper<'_> {
                 .map(|kind| (function, kind))
         }).map(|(function, _kind)| {
             let span = function.span();
+            let cfg_attrs = utils::filter_cfg_attributes(&function.attrs);
             let ident = &function.sig.ident;
             let fn_args = function.sig.inputs();
             let arg_idents = function.sig.inputs().map(move |fn_arg| &fn_arg.ident);
+            let fn_path = match &function.trait_path {
+                Some(trait_path) => quote! { <Storage as #trait_path>::#ident },
+                None => quote! { Storage::#ident },
+            };
+
+            // Fallible constructors hand back their error instead of the contract.
+            if let Some(error_ty) = function.constructor_error() {
+                return quote_spanned!(span=>
+                    #( #cfg_attrs )*
+                    pub fn #ident(
+                        #(#fn_args),*
+                    ) -> core::result::Result<
+                        <Storage as ink_lang::InstantiateTestable>::Wrapped,
+                        #error_ty,
+                    > {
+                        let mut contract = <Storage as ink_lang::InstantiateTestable>::instantiate();
+                        #fn_path(
+                            &mut contract,
+                            #(
+                                #arg_idents
+                            ),*
+                        )?;
+                        Ok(contract)
+                    }
+                )
+            }
 
             quote_spanned!(span=>
+                #( #cfg_attrs )*
                 pub fn #ident(
                     #(#fn_args),*
                 ) -> <Storage as ink_lang::InstantiateTestable>::Wrapped {
                     let mut contract = <Storage as ink_lang::InstantiateTestable>::instantiate();
-                    contract.#ident(
+                    #fn_path(
+                        &mut contract,
                         #(
                             #arg_idents
                         ),*
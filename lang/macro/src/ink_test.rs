@@ -0,0 +1,128 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `#[ink::test]` attribute.
+
+use proc_macro2::TokenStream as TokenStream2;
+use quote::quote;
+use syn::{
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    Result,
+    Token,
+};
+
+/// Parameters given to ink!'s `#[test(..)]` attribute.
+///
+/// # Example
+///
+/// ```no_compile
+/// #[ink::test(setup = my_setup)]
+/// ```
+struct TestParams {
+    /// The function returning the `TestSetup` the test is run with.
+    setup: Option<syn::Path>,
+}
+
+impl Parse for TestParams {
+    fn parse(input: ParseStream) -> Result<Self> {
+        if input.is_empty() {
+            return Ok(Self { setup: None })
+        }
+        let ident = input.parse::<syn::Ident>()?;
+        if ident != "setup" {
+            bail!(ident, "unknown ink! test parameter, expected `setup`")
+        }
+        let _ = input.parse::<Token![=]>()?;
+        let setup = input.parse::<syn::Path>()?;
+        let _ = input.parse::<Option<Token![,]>>()?;
+        Ok(Self { setup: Some(setup) })
+    }
+}
+
+pub fn generate(attr: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    match generate_or_err(attr, input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+pub fn generate_or_err(attr: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    let params = syn::parse2::<TestParams>(attr)?;
+    let item_fn = syn::parse2::<syn::ItemFn>(input)?;
+    let sig = &item_fn.sig;
+    if let Some(asyncness) = sig.asyncness {
+        bail!(asyncness, "ink! tests must not be async")
+    }
+    if !sig.generics.params.is_empty() {
+        bail!(sig.generics, "ink! tests must not be generic")
+    }
+    let accounts = match sig.inputs.len() {
+        0 => quote! { _ },
+        1 => {
+            match &sig.inputs[0] {
+                syn::FnArg::Typed(arg) => quote! { #arg },
+                receiver => bail!(receiver, "ink! tests must not have a receiver"),
+            }
+        }
+        _ => {
+            bail!(
+                sig.inputs,
+                "ink! tests must have no parameters or only the default accounts"
+            )
+        }
+    };
+    let setup = match params.setup {
+        Some(setup) => quote! { #setup() },
+        None => {
+            quote! {
+                ink_core::env::test::TestSetup::<ink_core::env::DefaultEnvTypes>::new()
+            }
+        }
+    };
+    let attrs = &item_fn.attrs;
+    let vis = &item_fn.vis;
+    let ident = &sig.ident;
+    let block = &item_fn.block;
+    Ok(match &sig.output {
+        syn::ReturnType::Default => {
+            quote! {
+                #( #attrs )*
+                #[test]
+                #vis fn #ident() {
+                    #setup
+                        .run(|#accounts| {
+                            // The body is wrapped into its own closure so that
+                            // early `return`s keep working.
+                            let test_body = || #block;
+                            test_body();
+                            Ok(())
+                        })
+                        .expect("could not set up the off-chain environment")
+                }
+            }
+        }
+        output => {
+            quote! {
+                #( #attrs )*
+                #[test]
+                #vis fn #ident() #output {
+                    #setup.run(|#accounts| #block)
+                }
+            }
+        }
+    })
+}
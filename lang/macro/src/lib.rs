@@ -15,14 +15,16 @@
 extern crate proc_macro;
 
 #[macro_use]
-mod error;
+extern crate ink_lang_ir;
 
 mod codegen;
 mod contract;
 mod extensions;
-mod ir;
+mod ink_test;
 mod lint;
+mod trait_definition;
 
+use ink_lang_ir::ir;
 use proc_macro::TokenStream;
 
 #[proc_macro_attribute]
@@ -30,5 +32,73 @@ pub fn contract(attr: TokenStream, item: TokenStream) -> TokenStream {
     contract::generate(attr.into(), item.into()).into()
 }
 
+/// Runs the annotated function as a test in a fresh off-chain environment.
+///
+/// The test may take the default accounts as its only parameter and may
+/// return `ink_core::env::Result<()>`.
+///
+/// # Example
+///
+/// ```no_compile
+/// #[ink::test]
+/// fn it_works(accounts: DefaultAccounts<DefaultEnvTypes>) {
+///     assert_eq!(env::caller::<DefaultEnvTypes>(), Ok(accounts.alice));
+/// }
+///
+/// // Customizes the environment using an `ink_core::env::test::TestSetup`.
+/// fn setup() -> TestSetup<DefaultEnvTypes> {
+///     TestSetup::new().block_number(42)
+/// }
+///
+/// #[ink::test(setup = setup)]
+/// fn starts_at_block_42() -> ink_core::env::Result<()> {
+///     assert_eq!(env::block_number::<DefaultEnvTypes>()?.get(), 42);
+///     Ok(())
+/// }
+/// ```
+#[proc_macro_attribute]
+pub fn test(attr: TokenStream, item: TokenStream) -> TokenStream {
+    ink_test::generate(attr.into(), item.into()).into()
+}
+
+/// Defines a trait of messages and constructors shared by ink! contracts.
+///
+/// Contracts implement the trait within their module using `#[ink(message)]`
+/// and `#[ink(constructor)]` on the implemented functions. The selectors are
+/// derived from the trait and function names, e.g. `Erc20::transfer`, so
+/// all implementers can be called the same way.
+///
+/// Also defines a `{Trait}Ref` type for calling any contract implementing
+/// the trait. The environmental types of the reference default to
+/// `ink_core::env::DefaultEnvTypes` and can be set using `env = ..`.
+///
+/// Identically named traits can be told apart using `namespace = ".."` which
+/// is prepended to the selectors, e.g. `tokens::Erc20::transfer`. Contracts then
+/// implement the trait within an `#[ink(namespace = "..")]` implementation block.
+///
+/// # Example
+///
+/// ```no_compile
+/// #[ink::trait_definition]
+/// pub trait Flip {
+///     #[ink(constructor)]
+///     fn new(&mut self, init_value: bool);
+///
+///     #[ink(message)]
+///     fn flip(&mut self);
+///
+///     #[ink(message)]
+///     fn get(&self) -> bool;
+/// }
+///
+/// // Calls an instantiated contract implementing `Flip`.
+/// let mut flipper = FlipRef::from_account_id(account_id);
+/// flipper.flip();
+/// ```
+#[proc_macro_attribute]
+pub fn trait_definition(attr: TokenStream, item: TokenStream) -> TokenStream {
+    trait_definition::generate(attr.into(), item.into()).into()
+}
+
 #[cfg(test)]
 pub use contract::generate_or_err;
@@ -0,0 +1,437 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Implementation of the `#[ink::trait_definition]` attribute.
+
+use core::convert::TryFrom;
+use proc_macro2::TokenStream as TokenStream2;
+use quote::{
+    format_ident,
+    quote,
+    quote_spanned,
+};
+use syn::{
+    parse::{
+        Parse,
+        ParseStream,
+    },
+    Result,
+    Token,
+};
+
+use crate::{
+    ir,
+    ir::utils,
+};
+
+/// Parameters given to ink!'s `#[trait_definition(..)]` attribute.
+///
+/// # Example
+///
+/// ```no_compile
+/// #[ink::trait_definition(env = MyEnvTypes, namespace = "erc20")]
+/// ```
+struct TraitParams {
+    /// The environmental types used by the generated trait reference.
+    env_types: ir::MetaTypes,
+    /// The namespace mixed into the selectors of the trait if any.
+    ///
+    /// Implementations have to be marked with the same `#[ink(namespace = "..")]`
+    /// and fail to compile otherwise.
+    namespace: Option<syn::LitStr>,
+}
+
+impl Parse for TraitParams {
+    fn parse(input: ParseStream) -> Result<Self> {
+        let mut env_types = None;
+        let mut namespace = None;
+        while !input.is_empty() {
+            let ident = input.parse::<syn::Ident>()?;
+            let _ = input.parse::<Token![=]>()?;
+            if ident == "env" && env_types.is_none() {
+                env_types = Some(ir::MetaTypes {
+                    ty: input.parse::<syn::Type>()?,
+                });
+            } else if ident == "namespace" && namespace.is_none() {
+                let lit_str = input.parse::<syn::LitStr>()?;
+                utils::ensure_valid_namespace(&lit_str)?;
+                namespace = Some(lit_str);
+            } else if ident == "env" || ident == "namespace" {
+                bail!(ident, "conflicting ink! trait definition parameter")
+            } else {
+                bail!(
+                    ident,
+                    "unknown ink! trait definition parameter, expected `env` or `namespace`"
+                )
+            }
+            let _ = input.parse::<Option<Token![,]>>()?;
+        }
+        Ok(Self {
+            env_types: env_types.unwrap_or_default(),
+            namespace,
+        })
+    }
+}
+
+/// An ink! trait definition.
+struct TraitDefinition {
+    /// The trait with all ink! attributes removed.
+    item_trait: syn::ItemTrait,
+    /// The messages and constructors of the trait.
+    ///
+    /// Their selectors are derived from the trait identifier and the namespace if any.
+    functions: Vec<ir::Function>,
+}
+
+impl TryFrom<(&TraitParams, syn::ItemTrait)> for TraitDefinition {
+    type Error = syn::Error;
+
+    fn try_from(
+        (params, mut item_trait): (&TraitParams, syn::ItemTrait),
+    ) -> Result<Self> {
+        if let Some(unsafety) = item_trait.unsafety {
+            bail!(unsafety, "ink! trait definitions must not be unsafe")
+        }
+        if let Some(auto_token) = item_trait.auto_token {
+            bail!(auto_token, "ink! trait definitions must not be auto traits")
+        }
+        if !(item_trait.generics.params.is_empty()
+            && item_trait.generics.where_clause.is_none())
+        {
+            bail!(
+                item_trait.generics,
+                "ink! trait definitions must not be generic"
+            )
+        }
+        if !item_trait.supertraits.is_empty() {
+            bail!(
+                item_trait.supertraits,
+                "ink! trait definitions must not have supertraits"
+            )
+        }
+        let mut functions = Vec::new();
+        for trait_item in &mut item_trait.items {
+            let method = match trait_item {
+                syn::TraitItem::Method(method) => method,
+                unsupported => {
+                    bail!(
+                        unsupported,
+                        "only messages and constructors are supported in ink! trait definitions",
+                    )
+                }
+            };
+            if let Some(default) = &method.default {
+                bail!(
+                    default,
+                    "ink! trait definitions must not provide default implementations",
+                )
+            }
+            for marker in utils::filter_map_ink_attributes(&method.attrs) {
+                if marker.args().any(|(ident, _value)| {
+                    ident == "payable" || ident == "guard" || ident == "non_reentrant"
+                }) {
+                    bail_span!(
+                        marker.span(),
+                        "payable, guarded and non-reentrant messages are defined \
+                         by the implementing ink! contract",
+                    )
+                }
+            }
+            utils::ensure_no_selector_override(&method.attrs)?;
+            // Functions of ink! trait definitions are checked like the
+            // functions of ink! implementation blocks.
+            let mut function = ir::Function::try_from(syn::ImplItemMethod {
+                attrs: method.attrs.clone(),
+                vis: syn::Visibility::Inherited,
+                defaultness: None,
+                sig: method.sig.clone(),
+                block: syn::parse_quote! {{}},
+            })?;
+            let selector = ir::FunctionSelector::for_trait_fn(
+                params.namespace.as_ref(),
+                &item_trait.ident,
+                &function.sig.ident,
+            );
+            match &mut function.kind {
+                ir::FunctionKind::Constructor(constructor) => {
+                    constructor.selector = selector
+                }
+                ir::FunctionKind::Message(message) => message.selector = selector,
+                ir::FunctionKind::Method => {
+                    bail!(
+                        method.sig.ident,
+                        "ink! trait definitions must only contain messages and constructors",
+                    )
+                }
+            }
+            function.namespace = params.namespace.clone();
+            method.attrs.retain(|attr| !utils::is_ink_attribute(attr));
+            functions.push(function);
+        }
+        // Implementations refer to the marker of their namespace so that
+        // they fail to compile if their namespace does not match.
+        let namespace_marker = utils::trait_namespace_marker(params.namespace.as_ref());
+        item_trait.items.push(syn::parse_quote! {
+            #[doc(hidden)]
+            #[allow(non_snake_case)]
+            fn #namespace_marker()
+            where
+                Self: Sized,
+            {
+            }
+        });
+        Ok(Self {
+            item_trait,
+            functions,
+        })
+    }
+}
+
+pub fn generate(attr: TokenStream2, input: TokenStream2) -> TokenStream2 {
+    match generate_or_err(attr, input) {
+        Ok(tokens) => tokens,
+        Err(err) => err.to_compile_error(),
+    }
+}
+
+pub fn generate_or_err(attr: TokenStream2, input: TokenStream2) -> Result<TokenStream2> {
+    let params = syn::parse2::<TraitParams>(attr)?;
+    let item_trait = syn::parse2::<syn::ItemTrait>(input)?;
+    let trait_def = TraitDefinition::try_from((&params, item_trait))?;
+    Ok(trait_def.generate_code(&params.env_types.ty))
+}
+
+impl TraitDefinition {
+    /// Generates the trait and the reference type used to call its implementers.
+    fn generate_code(&self, env_types: &syn::Type) -> TokenStream2 {
+        let item_trait = &self.item_trait;
+        let vis = &item_trait.vis;
+        let trait_ident = &item_trait.ident;
+        let ref_ident = format_ident!("{}Ref", trait_ident);
+        let forwarder_ident = format_ident!("{}CallForwarder", ref_ident);
+        let forwarder_mut_ident = format_ident!("{}CallForwarderMut", ref_ident);
+        let ref_doc = format!(
+            " Refers to an instantiated contract implementing the `{}` ink! trait.",
+            trait_ident,
+        );
+        let account_id = quote! { <#env_types as ink_core::env::EnvTypes>::AccountId };
+        let constructors = self.generate_constructors(env_types);
+        let messages = self.generate_messages();
+        let forwarding_messages = self
+            .generate_forwarding_messages(env_types, |function| !function.sig.is_mut());
+        let forwarding_messages_mut = self
+            .generate_forwarding_messages(env_types, |function| function.sig.is_mut());
+
+        quote! {
+            #item_trait
+
+            #[doc = #ref_doc]
+            #[derive(Clone, PartialEq, Eq, scale::Encode, scale::Decode)]
+            #vis struct #ref_ident {
+                account_id: #account_id,
+            }
+
+            impl ink_core::env::call::FromAccountId<#env_types> for #ref_ident {
+                #[inline]
+                fn from_account_id(account_id: #account_id) -> Self {
+                    Self { account_id }
+                }
+            }
+
+            impl ink_lang::ToAccountId<#env_types> for #ref_ident {
+                #[inline]
+                fn to_account_id(&self) -> #account_id {
+                    self.account_id.clone()
+                }
+            }
+
+            impl #ref_ident {
+                #( #constructors )*
+                #( #messages )*
+            }
+
+            impl<'a> ink_lang::ForwardCall for &'a #ref_ident {
+                type Forwarder = #forwarder_ident<'a>;
+
+                #[inline]
+                fn call(self) -> Self::Forwarder {
+                    #forwarder_ident { contract: self }
+                }
+            }
+
+            #[doc(hidden)]
+            #vis struct #forwarder_ident<'a> {
+                contract: &'a #ref_ident,
+            }
+
+            impl #forwarder_ident<'_> {
+                #( #forwarding_messages )*
+            }
+
+            impl<'a> ink_lang::ForwardCallMut for &'a mut #ref_ident {
+                type Forwarder = #forwarder_mut_ident<'a>;
+
+                #[inline]
+                fn call_mut(self) -> Self::Forwarder {
+                    #forwarder_mut_ident { contract: self }
+                }
+            }
+
+            #[doc(hidden)]
+            #vis struct #forwarder_mut_ident<'a> {
+                contract: &'a #ref_ident,
+            }
+
+            impl #forwarder_mut_ident<'_> {
+                #( #forwarding_messages_mut )*
+            }
+        }
+    }
+
+    /// Generates the constructors instantiating implementers of the trait.
+    fn generate_constructors<'a>(
+        &'a self,
+        env_types: &'a syn::Type,
+    ) -> impl Iterator<Item = TokenStream2> + 'a {
+        self.functions
+            .iter()
+            .filter(|function| function.is_constructor())
+            .map(move |function| {
+                let span = function.span();
+                let ident = &function.sig.ident;
+                let attrs = &function.attrs;
+                let fn_args = function.sig.inputs();
+                let arg_idents = function.sig.inputs().map(|fn_arg| &fn_arg.ident);
+                let selector = function
+                    .selector()
+                    .expect("constructors always have selectors");
+                let selector_bytes = selector.as_bytes();
+
+                quote_spanned!(span=>
+                    #( #attrs )*
+                    pub fn #ident(
+                        #( #fn_args ),*
+                    ) -> ink_core::env::call::InstantiateBuilder<
+                        #env_types,
+                        Self,
+                        ink_core::env::call::state::Sealed,
+                        ink_core::env::call::state::CodeHashUnassigned,
+                    > {
+                        ink_core::env::call::InstantiateParams::<#env_types, Self>::build(
+                            ink_core::env::call::Selector::new([#( #selector_bytes ),*])
+                        )
+                        #(
+                            .push_arg(&#arg_idents)
+                        )*
+                        .seal()
+                    }
+                )
+            })
+    }
+
+    /// Generates the messages calling the referred contract.
+    fn generate_messages<'a>(&'a self) -> impl Iterator<Item = TokenStream2> + 'a {
+        let trait_ident_lit = self.item_trait.ident.to_string();
+        self.functions
+            .iter()
+            .filter(|function| function.is_message())
+            .map(move |function| {
+                let span = function.span();
+                let ident = &function.sig.ident;
+                let attrs = &function.attrs;
+                let fn_args = function.sig.inputs();
+                let arg_idents = function.sig.inputs().map(|fn_arg| &fn_arg.ident);
+                let output = &function.sig.output;
+                let (call_path, receiver) = if function.sig.is_mut() {
+                    (quote! { ForwardCallMut::call_mut }, quote! { &mut self })
+                } else {
+                    (quote! { ForwardCall::call }, quote! { &self })
+                };
+                let failure_msg = match output {
+                    syn::ReturnType::Default => "invocation",
+                    syn::ReturnType::Type(_, _) => "evaluation",
+                };
+                let failure_msg = format!(
+                    "{} of {}::{} message was invalid",
+                    failure_msg, trait_ident_lit, ident,
+                );
+
+                quote_spanned!(span=>
+                    #( #attrs )*
+                    pub fn #ident(
+                        #receiver ,
+                        #( #fn_args ),*
+                    ) #output {
+                        ink_lang::#call_path(self)
+                            .#ident( #( #arg_idents ),* )
+                            .fire()
+                            .expect(#failure_msg)
+                    }
+                )
+            })
+    }
+
+    /// Generates the messages of the call forwarders building calls to the referred contract.
+    fn generate_forwarding_messages<'a>(
+        &'a self,
+        env_types: &'a syn::Type,
+        pred: fn(function: &ir::Function) -> bool,
+    ) -> impl Iterator<Item = TokenStream2> + 'a {
+        self.functions
+            .iter()
+            .filter(|function| function.is_message())
+            .filter(move |function| pred(function))
+            .map(move |function| {
+                let span = function.span();
+                let ident = &function.sig.ident;
+                let attrs = &function.attrs;
+                let selector = function
+                    .selector()
+                    .expect("messages always have selectors");
+                let selector_bytes = selector.as_bytes();
+                let fn_args = function.sig.inputs();
+                let arg_idents = function.sig.inputs().map(|fn_arg| &fn_arg.ident);
+                let (ret_ty_sig, ret_ty_param, instantiate_fn) = match &function.sig.output
+                {
+                    syn::ReturnType::Default => (quote! { () }, quote! { () }, quote! { invoke }),
+                    syn::ReturnType::Type(_, ret_ty) => {
+                        (
+                            quote! { ink_core::env::call::ReturnType<#ret_ty> },
+                            quote! { #ret_ty },
+                            quote! { eval },
+                        )
+                    }
+                };
+
+                quote_spanned!(span=>
+                    #( #attrs )*
+                    pub fn #ident(
+                        self,
+                        #( #fn_args ),*
+                    ) -> ink_core::env::call::CallBuilder<
+                        #env_types, #ret_ty_sig, ink_core::env::call::state::Sealed
+                    > {
+                        ink_core::env::call::CallParams::<#env_types, #ret_ty_param>::#instantiate_fn(
+                            ink_lang::ToAccountId::to_account_id(self.contract),
+                            ink_core::env::call::Selector::new([ #( #selector_bytes ),* ]),
+                        )
+                        #(
+                            .push_arg(&#arg_idents)
+                        )*
+                        .seal()
+                    }
+                )
+            })
+    }
+}
@@ -22,6 +22,20 @@ fn compile_tests() {
     t.pass("tests/ui/pass/05-erc721-contract.rs");
     t.pass("tests/ui/pass/06-non-ink-items.rs");
     t.pass("tests/ui/pass/07-flipper-as-dependency.rs");
+    t.pass("tests/ui/pass/08-custom-env-types.rs");
+    t.pass("tests/ui/pass/09-ink-test.rs");
+    t.pass("tests/ui/pass/10-payable-message.rs");
+    t.pass("tests/ui/pass/11-custom-selectors.rs");
+    t.pass("tests/ui/pass/12-trait-definition.rs");
+    t.pass("tests/ui/pass/13-contract-ref.rs");
+    t.pass("tests/ui/pass/14-event-topics.rs");
+    t.pass("tests/ui/pass/15-constructors.rs");
+    t.pass("tests/ui/pass/16-message-guards.rs");
+    t.pass("tests/ui/pass/17-non-reentrant-message.rs");
+    t.pass("tests/ui/pass/18-fallible-messages.rs");
+    t.pass("tests/ui/pass/19-trait-namespaces.rs");
+    t.pass("tests/ui/pass/20-cfg-messages.rs");
+    t.pass("tests/ui/pass/21-storage-default-fields.rs");
     t.compile_fail("tests/ui/fail/01-constructor-returns.rs");
     t.compile_fail("tests/ui/fail/02-missing-constructor.rs");
     t.compile_fail("tests/ui/fail/03-invalid-version.rs");
@@ -41,4 +55,13 @@ fn compile_tests() {
     t.compile_fail("tests/ui/fail/18-conflicting-ink-markers-2.rs");
     t.compile_fail("tests/ui/fail/19-unknown-struct-ink-marker.rs");
     t.compile_fail("tests/ui/fail/20-unknown-method-marker.rs");
+    t.compile_fail("tests/ui/fail/21-payable-method.rs");
+    t.compile_fail("tests/ui/fail/22-duplicate-selectors.rs");
+    t.compile_fail("tests/ui/fail/23-trait-definition-default-impl.rs");
+    t.compile_fail("tests/ui/fail/24-constructor-guard.rs");
+    t.compile_fail("tests/ui/fail/25-ink-test-async.rs");
+    t.compile_fail("tests/ui/fail/26-ink-test-unknown-param.rs");
+    t.compile_fail("tests/ui/fail/27-ink-test-too-many-params.rs");
+    t.compile_fail("tests/ui/fail/28-trait-namespace-mismatch.rs");
+    t.compile_fail("tests/ui/fail/29-trait-namespace-generics.rs");
 }
@@ -0,0 +1,22 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod noop {
+    #[ink(storage)]
+    struct Noop {}
+
+    impl Noop {
+        #[ink(constructor)]
+        fn new(&mut self) {}
+
+        #[ink(message)]
+        fn noop(&self) {
+            self.helper()
+        }
+
+        #[ink(payable)]
+        fn helper(&self) {}
+    }
+}
+
+fn main() {}
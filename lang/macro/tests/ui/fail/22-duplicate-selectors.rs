@@ -0,0 +1,20 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod noop {
+    #[ink(storage)]
+    struct Noop {}
+
+    impl Noop {
+        #[ink(constructor)]
+        fn new(&mut self) {}
+
+        #[ink(message, selector = 0x01)]
+        fn noop(&self) {}
+
+        #[ink(message, selector = 0x01)]
+        fn noop_2(&self) {}
+    }
+}
+
+fn main() {}
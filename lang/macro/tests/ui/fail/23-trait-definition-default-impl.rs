@@ -0,0 +1,9 @@
+use ink_lang as ink;
+
+#[ink::trait_definition]
+pub trait Flip {
+    #[ink(message)]
+    fn flip(&mut self) {}
+}
+
+fn main() {}
@@ -0,0 +1,21 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod noop {
+    #[ink(storage)]
+    struct Noop {}
+
+    impl Noop {
+        #[ink(constructor, guard = check)]
+        fn new(&mut self) {}
+
+        #[ink(message)]
+        fn noop(&self) {}
+
+        fn check(&self) -> Result<(), ()> {
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,6 @@
+use ink_lang as ink;
+
+#[ink::test]
+async fn async_test() {}
+
+fn main() {}
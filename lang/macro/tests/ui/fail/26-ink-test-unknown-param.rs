@@ -0,0 +1,6 @@
+use ink_lang as ink;
+
+#[ink::test(env = DefaultEnvTypes)]
+fn unknown_param() {}
+
+fn main() {}
@@ -0,0 +1,10 @@
+use ink_core::env::{
+    test::DefaultAccounts,
+    DefaultEnvTypes,
+};
+use ink_lang as ink;
+
+#[ink::test]
+fn too_many_params(accounts: DefaultAccounts<DefaultEnvTypes>, value: u32) {}
+
+fn main() {}
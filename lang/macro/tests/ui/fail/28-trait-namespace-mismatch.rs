@@ -0,0 +1,35 @@
+use ink_lang as ink;
+
+mod erc20 {
+    use super::ink;
+
+    #[ink::trait_definition(namespace = "erc20")]
+    pub trait Token {
+        #[ink(message)]
+        fn decimals(&self) -> u8;
+    }
+}
+
+#[ink::contract(version = "0.1.0")]
+mod token {
+    use crate::erc20;
+
+    #[ink(storage)]
+    struct Token {}
+
+    impl Token {
+        #[ink(constructor)]
+        fn new(&mut self) {}
+    }
+
+    /// The namespace differs from the one of the trait definition.
+    #[ink(namespace = "erc777")]
+    impl erc20::Token for Token {
+        #[ink(message)]
+        fn decimals(&self) -> u8 {
+            18
+        }
+    }
+}
+
+fn main() {}
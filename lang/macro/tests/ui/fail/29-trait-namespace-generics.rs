@@ -0,0 +1,9 @@
+use ink_lang as ink;
+
+#[ink::trait_definition(namespace = "erc20<T>")]
+pub trait Token {
+    #[ink(message)]
+    fn decimals(&self) -> u8;
+}
+
+fn main() {}
@@ -35,4 +35,9 @@ mod flipper {
     }
 }
 
+/// Compiled as dependency the storage struct refers to an instantiated contract.
+fn _as_ref(flipper: flipper::Flipper) -> flipper::FlipperRef {
+    flipper
+}
+
 fn main() {}
@@ -0,0 +1,48 @@
+use ink_core::env::{
+    EnvTypes,
+    Hash,
+};
+use ink_lang as ink;
+
+/// Environmental types of a chain with 20 byte accounts and `u64` balances.
+#[derive(Debug, Clone, PartialEq, Eq)]
+#[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+pub enum CustomEnvTypes {}
+
+impl EnvTypes for CustomEnvTypes {
+    type AccountId = [u8; 20];
+    type Balance = u64;
+    type Hash = Hash;
+    type Timestamp = u64;
+    type BlockNumber = u32;
+    type Call = ();
+}
+
+#[ink::contract(version = "0.1.0", env = super::CustomEnvTypes)]
+mod custom_env_types {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct CustomEnvTypes {
+        owner: storage::Value<AccountId>,
+    }
+
+    impl CustomEnvTypes {
+        #[ink(constructor)]
+        fn new(&mut self) {
+            self.owner.set(self.env().caller());
+        }
+
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            *self.owner
+        }
+
+        #[ink(message)]
+        fn balance(&self) -> Balance {
+            self.env().balance()
+        }
+    }
+}
+
+fn main() {}
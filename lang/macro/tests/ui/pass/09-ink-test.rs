@@ -0,0 +1,96 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod flipper {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Flipper {
+        value: storage::Value<bool>,
+        owner: storage::Value<AccountId>,
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        fn new(&mut self, init_value: bool) {
+            self.value.set(init_value);
+            self.owner.set(self.env().caller());
+        }
+
+        #[ink(message)]
+        fn flip(&mut self) {
+            *self.value = !self.get();
+        }
+
+        #[ink(message)]
+        fn get(&self) -> bool {
+            *self.value
+        }
+
+        #[ink(message)]
+        fn owner(&self) -> AccountId {
+            *self.owner
+        }
+    }
+
+    // Not gated behind `#[cfg(test)]` so that the `#[ink::test]` attributes
+    // are always expanded when checking this file.
+    mod tests {
+        use super::*;
+        use ink_core::env::{
+            self,
+            test::{
+                DefaultAccounts,
+                TestSetup,
+            },
+            DefaultEnvTypes,
+        };
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn it_works() {
+            let mut flipper = Flipper::new(false);
+            assert_eq!(flipper.get(), false);
+            flipper.flip();
+            assert_eq!(flipper.get(), true);
+        }
+
+        #[ink::test]
+        fn starts_at_block_1(accounts: DefaultAccounts<DefaultEnvTypes>) {
+            let flipper = Flipper::new(false);
+            assert_eq!(flipper.owner(), accounts.alice);
+            assert_eq!(
+                env::block_number::<DefaultEnvTypes>().map(|number| number.get()),
+                Ok(1)
+            );
+        }
+
+        #[ink::test]
+        fn returns_early() {
+            let flipper = Flipper::new(false);
+            if !flipper.get() {
+                return
+            }
+            unreachable!("returned early");
+        }
+
+        fn bob_calls() -> TestSetup<DefaultEnvTypes> {
+            let accounts = env::test::default_accounts::<DefaultEnvTypes>()
+                .expect("default accounts are always available");
+            TestSetup::new().caller(accounts.bob).block_number(42)
+        }
+
+        #[ink::test(setup = bob_calls)]
+        fn custom_setup(accounts: DefaultAccounts<DefaultEnvTypes>) -> env::Result<()> {
+            let flipper = Flipper::new(true);
+            assert_eq!(flipper.owner(), accounts.bob);
+            // Further instantiations keep the setup.
+            let other = Flipper::new(false);
+            assert_eq!(other.owner(), accounts.bob);
+            assert_eq!(env::block_number::<DefaultEnvTypes>()?.get(), 42);
+            Ok(())
+        }
+    }
+}
+
+fn main() {}
@@ -0,0 +1,80 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod donations {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Donations {
+        total: storage::Value<Balance>,
+    }
+
+    impl Donations {
+        #[ink(constructor)]
+        fn new(&mut self) {
+            self.total.set(0);
+        }
+
+        /// Accepts transferred value.
+        #[ink(message, payable)]
+        fn donate(&mut self) {
+            *self.total += self.env().transferred_balance();
+        }
+
+        /// Rejects transferred value.
+        #[ink(message)]
+        fn total(&self) -> Balance {
+            *self.total
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            Selector,
+        },
+        test::ExecutionContextBuilder,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchError,
+        DispatchMode,
+        DispatchRetCode,
+        DispatchUsingMode,
+    };
+
+    type Balance = <DefaultEnvTypes as env::EnvTypes>::Balance;
+
+    fn dispatch(mode: DispatchMode, label: &str, value: Balance) -> u32 {
+        ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .call_data(CallData::new(Selector::from_label(label)))
+            .value(value)
+            .run(|| {
+                DispatchRetCode::from(
+                    <donations::Donations as DispatchUsingMode>::dispatch_using_mode(
+                        mode,
+                    ),
+                )
+                .to_u32()
+            })
+            .expect("could not run the dispatch")
+    }
+
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let success = DispatchRetCode::success().to_u32();
+        assert_eq!(dispatch(DispatchMode::Instantiate, "new", 0), success);
+        // Payable messages accept transferred value.
+        assert_eq!(dispatch(DispatchMode::Call, "donate", 10), success);
+        // Messages are not payable by default.
+        assert_eq!(
+            dispatch(DispatchMode::Call, "total", 10),
+            DispatchError::PaidUnpayableMessage.to_u32(),
+        );
+        assert_eq!(dispatch(DispatchMode::Call, "total", 0), success);
+        Ok(())
+    })
+    .unwrap()
+}
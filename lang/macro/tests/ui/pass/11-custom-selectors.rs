@@ -0,0 +1,80 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod custom_selectors {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct CustomSelectors {
+        value: storage::Value<i32>,
+    }
+
+    impl CustomSelectors {
+        #[ink(constructor, selector = 0xCAFEBABE)]
+        fn new(&mut self) {
+            self.value.set(0);
+        }
+
+        /// Dispatched for the explicitly given selector.
+        #[ink(message, selector = 0xDEADBEEF)]
+        fn get(&self) -> i32 {
+            *self.value
+        }
+
+        /// Dispatched for all selectors no other message matches.
+        #[ink(message, selector = _)]
+        fn fallback(&mut self) {
+            *self.value += 1;
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            Selector,
+        },
+        test::ExecutionContextBuilder,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+
+    fn dispatch(mode: DispatchMode, selector: [u8; 4]) {
+        ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .call_data(CallData::new(Selector::new(selector)))
+            .value(0)
+            .run(|| {
+                <custom_selectors::CustomSelectors as DispatchUsingMode>::dispatch_using_mode(
+                    mode,
+                )
+                .map_err(|_| "dispatch failed")
+            })
+            .expect("could not run the dispatch")
+            .unwrap()
+    }
+
+    fn value() -> i32 {
+        // The only storage field is stored at the first key.
+        env::get_contract_storage::<i32>(ink_primitives::Key([0x00; 32]))
+            .expect("the contract has been instantiated")
+            .expect("could not decode the value")
+    }
+
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        dispatch(DispatchMode::Instantiate, [0xCA, 0xFE, 0xBA, 0xBE]);
+        // The explicitly given selector wins over the wildcard.
+        dispatch(DispatchMode::Call, [0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(value(), 0);
+        // Unknown selectors are dispatched to the wildcard message.
+        dispatch(DispatchMode::Call, [0x00, 0x00, 0x00, 0x01]);
+        dispatch(DispatchMode::Call, Selector::from_label("get").to_bytes());
+        assert_eq!(value(), 2);
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,77 @@
+use ink_core::env::{
+    call::FromAccountId,
+    AccountId,
+};
+use ink_lang as ink;
+
+#[ink::trait_definition]
+pub trait Flip {
+    /// Creates a new flipper with the given initial value.
+    #[ink(constructor)]
+    fn new(&mut self, init_value: bool);
+
+    /// Flips the current value.
+    #[ink(message)]
+    fn flip(&mut self);
+
+    /// Returns the current value.
+    #[ink(message)]
+    fn get(&self) -> bool;
+}
+
+#[ink::contract(version = "0.1.0")]
+mod flipper {
+    use super::Flip;
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Flipper {
+        value: storage::Value<bool>,
+    }
+
+    impl Flip for Flipper {
+        #[ink(constructor)]
+        fn new(&mut self, init_value: bool) {
+            self.value.set(init_value);
+        }
+
+        #[ink(message)]
+        fn flip(&mut self) {
+            *self.value = !self.get();
+        }
+
+        #[ink(message)]
+        fn get(&self) -> bool {
+            *self.value
+        }
+    }
+
+    impl Flipper {
+        #[ink(message)]
+        fn reset(&mut self) {
+            self.value.set(false);
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn it_works() {
+            let mut flipper = Flipper::new(false);
+            assert_eq!(flipper.get(), false);
+            flipper.flip();
+            assert_eq!(flipper.get(), true);
+            flipper.reset();
+            assert_eq!(flipper.get(), false);
+        }
+    }
+}
+
+fn main() {
+    let flipper = FlipRef::from_account_id(AccountId::from([0x01; 32]));
+    let _ = ink_lang::ForwardCall::call(&flipper).get();
+    let _ = FlipRef::new(true);
+}
@@ -0,0 +1,49 @@
+use ink_core::env::{
+    call::FromAccountId,
+    AccountId,
+    Hash,
+};
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod flipper {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Flipper {
+        value: storage::Value<bool>,
+    }
+
+    impl Flipper {
+        #[ink(constructor)]
+        fn new(&mut self, init_value: bool) {
+            self.value.set(init_value);
+        }
+
+        #[ink(message)]
+        fn flip(&mut self) {
+            *self.value = !self.get();
+        }
+
+        #[ink(message)]
+        fn get(&self) -> bool {
+            *self.value
+        }
+    }
+}
+
+fn main() {
+    use flipper::FlipperRef;
+    // Builds the instantiation of a new contract.
+    let _ = FlipperRef::new(true)
+        .endowment(1000)
+        .using_code(Hash::from([0x01; 32]));
+    // Builds calls to an instantiated contract.
+    let mut flipper = FlipperRef::from_account_id(AccountId::from([0x02; 32]));
+    let _ = ink_lang::ForwardCall::call(&flipper).get();
+    let _ = ink_lang::ForwardCallMut::call_mut(&mut flipper).flip();
+    assert_eq!(
+        ink_lang::ToAccountId::to_account_id(&flipper),
+        AccountId::from([0x02; 32])
+    );
+}
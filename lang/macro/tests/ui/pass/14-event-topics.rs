@@ -0,0 +1,113 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod events {
+    #[ink(storage)]
+    struct Events {}
+
+    /// Has the event signature as its first topic.
+    #[ink(event)]
+    struct Transferred {
+        #[ink(topic)]
+        from: Option<AccountId>,
+        #[ink(topic)]
+        to: Option<AccountId>,
+        value: Balance,
+    }
+
+    /// Has no event signature topic.
+    #[ink(event, anonymous)]
+    struct Flipped {
+        #[ink(topic)]
+        value: bool,
+    }
+
+    impl Events {
+        #[ink(constructor)]
+        fn new(&mut self) {}
+
+        #[ink(message)]
+        fn transfer(&self, to: AccountId, value: Balance) {
+            self.env().emit_event(Transferred {
+                from: Some(self.env().caller()),
+                to: Some(to),
+                value,
+            });
+        }
+
+        #[ink(message)]
+        fn flip(&self, value: bool) {
+            self.env().emit_event(Flipped { value });
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            Selector,
+        },
+        test::ExecutionContextBuilder,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchRetCode,
+        DispatchUsingMode,
+    };
+
+    fn dispatch(mode: DispatchMode, call_data: CallData) -> u32 {
+        ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .call_data(call_data)
+            .run(|| {
+                DispatchRetCode::from(
+                    <events::Events as DispatchUsingMode>::dispatch_using_mode(mode),
+                )
+                .to_u32()
+            })
+            .expect("could not run the dispatch")
+    }
+
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let success = DispatchRetCode::success().to_u32();
+        assert_eq!(
+            dispatch(
+                DispatchMode::Instantiate,
+                CallData::new(Selector::from_label("new")),
+            ),
+            success,
+        );
+        assert_eq!(
+            dispatch(
+                DispatchMode::Call,
+                CallData::new(Selector::from_label("transfer"))
+                    .push_arg(&accounts.bob)
+                    .push_arg(&10u128),
+            ),
+            success,
+        );
+        assert_eq!(
+            dispatch(
+                DispatchMode::Call,
+                CallData::new(Selector::from_label("flip")).push_arg(&true),
+            ),
+            success,
+        );
+        let events = env::test::recorded_events().collect::<Vec<_>>();
+        let transferred = events[0].decoded_topics::<DefaultEnvTypes>().unwrap();
+        assert_eq!(
+            transferred,
+            vec![
+                env::topic::<DefaultEnvTypes, _>(&"events::Transferred"),
+                env::topic::<DefaultEnvTypes, _>(&Some(accounts.alice)),
+                env::topic::<DefaultEnvTypes, _>(&Some(accounts.bob)),
+            ]
+        );
+        let flipped = events[1].decoded_topics::<DefaultEnvTypes>().unwrap();
+        assert_eq!(flipped, vec![env::topic::<DefaultEnvTypes, _>(&true)]);
+        Ok(())
+    })
+    .unwrap()
+}
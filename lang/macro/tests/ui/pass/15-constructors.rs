@@ -0,0 +1,94 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod bounded_counter {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct BoundedCounter {
+        value: storage::Value<u32>,
+        limit: storage::Value<u32>,
+    }
+
+    /// The errors of failed instantiations.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    pub enum Error {
+        /// The initial value exceeds the limit.
+        ExceedsLimit,
+    }
+
+    impl BoundedCounter {
+        /// Instantiates the counter without a limit.
+        #[ink(constructor)]
+        fn new(&mut self) {
+            self.value.set(0);
+            self.limit.set(u32::max_value());
+        }
+
+        /// Instantiates the counter with a limit.
+        ///
+        /// Constructors accept the endowment anyway, `payable` only makes this explicit.
+        #[ink(constructor, payable, selector = 0xCAFEBABE)]
+        fn with_limit(&mut self, value: u32, limit: u32) -> Result<(), Error> {
+            if value > limit {
+                return Err(Error::ExceedsLimit)
+            }
+            self.value.set(value);
+            self.limit.set(limit);
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn get(&self) -> u32 {
+            *self.value
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn fallible_constructor_works() {
+            let counter = BoundedCounter::with_limit(5, 10).unwrap();
+            assert_eq!(counter.get(), 5);
+            assert_eq!(
+                BoundedCounter::with_limit(11, 10).err(),
+                Some(Error::ExceedsLimit)
+            );
+            assert_eq!(BoundedCounter::new().get(), 0);
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            Selector,
+        },
+        test::ExecutionContextBuilder,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchUsingMode,
+    };
+
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        // Constructors accept the endowment even if not marked as payable.
+        let result = ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .call_data(CallData::new(Selector::from_label("new")))
+            .value(1000)
+            .run(|| {
+                <bounded_counter::BoundedCounter as DispatchUsingMode>::dispatch_using_mode(
+                    DispatchMode::Instantiate,
+                )
+            })?;
+        assert!(result.is_ok());
+        Ok(())
+    })
+    .unwrap()
+}
@@ -0,0 +1,130 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod owned {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Owned {
+        owner: storage::Value<AccountId>,
+        value: storage::Value<u32>,
+    }
+
+    /// The errors of failed guards.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    pub enum Error {
+        /// The caller is not the owner.
+        NotOwner,
+        /// The value has already been set.
+        AlreadySet,
+    }
+
+    impl Owned {
+        #[ink(constructor)]
+        fn new(&mut self) {
+            self.owner.set(self.env().caller());
+            self.value.set(0);
+        }
+
+        /// Only the owner can set the value and only once.
+        #[ink(message, guard = only_owner, guard = not_set)]
+        fn set(&mut self, value: u32) {
+            self.value.set(value);
+        }
+
+        #[ink(message)]
+        fn get(&self) -> u32 {
+            *self.value
+        }
+
+        fn only_owner(&self) -> Result<(), Error> {
+            if self.env().caller() != *self.owner {
+                return Err(Error::NotOwner)
+            }
+            Ok(())
+        }
+
+        fn not_set(&self) -> Result<(), Error> {
+            if *self.value != 0 {
+                return Err(Error::AlreadySet)
+            }
+            Ok(())
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            Selector,
+        },
+        test::ExecutionContextBuilder,
+        DefaultEnvTypes,
+        ReturnFlags,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchRetCode,
+        DispatchUsingMode,
+    };
+
+    type AccountId = <DefaultEnvTypes as env::EnvTypes>::AccountId;
+
+    fn dispatch(mode: DispatchMode, caller: AccountId, call_data: CallData) -> u32 {
+        ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .caller(caller)
+            .call_data(call_data)
+            .run(|| {
+                DispatchRetCode::from(
+                    <owned::Owned as DispatchUsingMode>::dispatch_using_mode(mode),
+                )
+                .to_u32()
+            })
+            .expect("could not run the dispatch")
+    }
+
+    fn set(value: u32) -> CallData {
+        CallData::new(Selector::from_label("set")).push_arg(&value)
+    }
+
+    env::test::run_test::<DefaultEnvTypes, _>(|accounts| {
+        let success = DispatchRetCode::success().to_u32();
+        assert_eq!(
+            dispatch(
+                DispatchMode::Instantiate,
+                accounts.alice,
+                CallData::new(Selector::from_label("new")),
+            ),
+            success,
+        );
+        assert_eq!(dispatch(DispatchMode::Call, accounts.alice, set(42)), success);
+        // The value can only be set once.
+        env::test::assert_return_value(
+            || {
+                dispatch(DispatchMode::Call, accounts.alice, set(7));
+            },
+            ReturnFlags::REVERT,
+            Err::<(), _>(owned::Error::AlreadySet),
+        );
+        // Only the owner can set the value.
+        env::test::assert_return_value(
+            || {
+                dispatch(DispatchMode::Call, accounts.bob, set(7));
+            },
+            ReturnFlags::REVERT,
+            Err::<(), _>(owned::Error::NotOwner),
+        );
+        assert_eq!(
+            dispatch(
+                DispatchMode::Call,
+                accounts.bob,
+                CallData::new(Selector::from_label("get")),
+            ),
+            success,
+        );
+        Ok(())
+    })
+    .unwrap()
+}
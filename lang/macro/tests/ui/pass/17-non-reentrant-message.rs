@@ -0,0 +1,145 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod vault {
+    use ink_core::{
+        env::{
+            call::{
+                CallParams,
+                Selector,
+            },
+            ReturnFlags,
+        },
+        storage,
+    };
+
+    #[ink(storage)]
+    struct Vault {
+        balances: storage::HashMap<AccountId, Balance>,
+    }
+
+    impl Vault {
+        #[ink(constructor)]
+        fn new(&mut self) {}
+
+        #[ink(message, payable)]
+        fn deposit(&mut self) {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller) + self.env().transferred_balance();
+            self.balances.insert(caller, balance);
+        }
+
+        /// Rejects calls reentering the vault while the withdrawal executes.
+        #[ink(message, non_reentrant)]
+        fn withdraw(&mut self) {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller);
+            self.env()
+                .transfer(caller, balance)
+                .expect("could not transfer the balance");
+            self.balances.insert(caller, 0);
+        }
+
+        /// Returns the balance of the caller without reaching the end of the message.
+        #[ink(message, non_reentrant)]
+        fn balance_early(&mut self) {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller);
+            self.env().return_value(ReturnFlags::default(), &balance)
+        }
+
+        /// Returns `true` if the vault could reenter itself to withdraw.
+        #[ink(message, non_reentrant)]
+        fn reenter(&mut self) -> bool {
+            let vault = self.env().account_id();
+            CallParams::<EnvTypes, ()>::invoke(vault, Selector::from_label("withdraw"))
+                .fire()
+                .is_ok()
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            *self.balances.get(&owner).unwrap_or(&0)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use ink_lang as ink;
+        use ink_lang::DispatchError;
+
+        #[ink::test]
+        fn reentrant_calls_are_rejected() {
+            assert!(ink_lang::enter_non_reentrant().is_ok());
+            assert!(match ink_lang::enter_non_reentrant() {
+                Err(DispatchError::ReentrantCall) => true,
+                _ => false,
+            });
+            ink_lang::exit_non_reentrant();
+            assert!(ink_lang::enter_non_reentrant().is_ok());
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            CallParams,
+            Selector,
+        },
+        test::CallKind,
+        Clear,
+        DefaultEnvTypes,
+        Hash,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchRetCode,
+        DispatchUsingMode,
+    };
+
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        // The contract account of the off-chain environment has the cleared code hash.
+        env::test::register_contract::<DefaultEnvTypes, _>(Hash::clear(), |kind| {
+            let mode = match kind {
+                CallKind::Instantiate => DispatchMode::Instantiate,
+                CallKind::Call => DispatchMode::Call,
+            };
+            <vault::Vault as DispatchUsingMode>::dispatch_using_mode(mode)
+                .map_err(|error| DispatchRetCode::from(error).to_u32() as u8)
+        });
+        let instantiated = env::test::ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .call_data(CallData::new(Selector::from_label("new")))
+            .run(|| {
+                <vault::Vault as DispatchUsingMode>::dispatch_using_mode(
+                    DispatchMode::Instantiate,
+                )
+                .is_ok()
+            })
+            .expect("cannot instantiate the vault");
+        assert!(instantiated);
+        let vault = env::account_id::<DefaultEnvTypes>()?;
+        let call = |label| {
+            CallParams::<DefaultEnvTypes, ()>::invoke(vault, Selector::from_label(label))
+                .fire()
+        };
+        let reenter = || {
+            CallParams::<DefaultEnvTypes, bool>::eval(
+                vault,
+                Selector::from_label("reenter"),
+            )
+            .fire()
+        };
+        // The vault cannot withdraw while it executes a non-reentrant message.
+        assert_eq!(reenter(), Ok(false));
+        assert_eq!(call("withdraw"), Ok(()));
+        // Returning without reverting exits the non-reentrant message as well.
+        assert_eq!(call("balance_early"), Ok(()));
+        assert_eq!(call("withdraw"), Ok(()));
+        assert_eq!(reenter(), Ok(false));
+        Ok(())
+    })
+    .expect("the vault rejects reentrant calls")
+}
@@ -0,0 +1,159 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod bank {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Bank {
+        balances: storage::HashMap<AccountId, Balance>,
+    }
+
+    /// The errors of failed messages.
+    #[derive(Debug, PartialEq, Eq, scale::Encode, scale::Decode)]
+    #[cfg_attr(feature = "ink-generate-abi", derive(type_metadata::Metadata))]
+    pub enum Error {
+        /// The sender's balance is insufficient.
+        InsufficientBalance,
+    }
+
+    /// Aliases naming only the value type are fallible messages as well.
+    pub type Result<T> = core::result::Result<T, Error>;
+
+    impl Bank {
+        #[ink(constructor)]
+        fn new(&mut self, initial_balance: Balance) {
+            let caller = self.env().caller();
+            self.balances.insert(caller, initial_balance);
+        }
+
+        /// Reverts all state changes if the sender's balance is insufficient.
+        #[ink(message)]
+        fn transfer(
+            &mut self,
+            to: AccountId,
+            value: Balance,
+        ) -> core::result::Result<Balance, Error> {
+            let from = self.env().caller();
+            let from_balance = self.balance_of(from);
+            if from_balance < value {
+                return Err(Error::InsufficientBalance)
+            }
+            self.balances.insert(from, from_balance - value);
+            let to_balance = self.balance_of(to);
+            self.balances.insert(to, to_balance + value);
+            Ok(from_balance - value)
+        }
+
+        /// Burns the value from the sender's balance.
+        ///
+        /// Burning more than the balance empties it and fails
+        /// which reverts the emptied balance again.
+        #[ink(message)]
+        fn burn(&mut self, value: Balance) -> Result<()> {
+            let caller = self.env().caller();
+            let balance = self.balance_of(caller);
+            self.balances.insert(caller, balance.saturating_sub(value));
+            if balance < value {
+                return Err(Error::InsufficientBalance)
+            }
+            Ok(())
+        }
+
+        #[ink(message)]
+        fn balance_of(&self, owner: AccountId) -> Balance {
+            *self.balances.get(&owner).unwrap_or(&0)
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_core::env::{
+            test::DefaultAccounts,
+            DefaultEnvTypes,
+        };
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn transfer_works(accounts: DefaultAccounts<DefaultEnvTypes>) {
+            let mut bank = Bank::new(100);
+            assert_eq!(bank.transfer(accounts.bob, 10), Ok(90));
+            assert_eq!(
+                bank.transfer(accounts.bob, 1000),
+                Err(Error::InsufficientBalance)
+            );
+            assert_eq!(bank.burn(1000), Err(Error::InsufficientBalance));
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            CallParams,
+            Selector,
+        },
+        test::CallKind,
+        Clear,
+        DefaultEnvTypes,
+        EnvError,
+        EnvTypes,
+        Hash,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchRetCode,
+        DispatchUsingMode,
+    };
+    type Balance = <DefaultEnvTypes as EnvTypes>::Balance;
+
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        // The contract account of the off-chain environment has the cleared code hash.
+        env::test::register_contract::<DefaultEnvTypes, _>(Hash::clear(), |kind| {
+            let mode = match kind {
+                CallKind::Instantiate => DispatchMode::Instantiate,
+                CallKind::Call => DispatchMode::Call,
+            };
+            <bank::Bank as DispatchUsingMode>::dispatch_using_mode(mode)
+                .map_err(|error| DispatchRetCode::from(error).to_u32() as u8)
+        });
+        // The bank calls itself below so that its own account holds the balance.
+        let bank = env::account_id::<DefaultEnvTypes>()?;
+        let instantiated = env::test::ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .caller(bank)
+            .call_data(
+                CallData::new(Selector::from_label("new")).push_arg::<Balance>(&100),
+            )
+            .run(|| {
+                <bank::Bank as DispatchUsingMode>::dispatch_using_mode(
+                    DispatchMode::Instantiate,
+                )
+                .is_ok()
+            })
+            .expect("cannot instantiate the bank");
+        assert!(instantiated);
+        let balance = || {
+            CallParams::<DefaultEnvTypes, Balance>::eval(
+                bank,
+                Selector::from_label("balance_of"),
+            )
+            .push_arg(&bank)
+            .fire()
+        };
+        let burn = |value: Balance| {
+            CallParams::<DefaultEnvTypes, ()>::invoke(bank, Selector::from_label("burn"))
+                .push_arg(&value)
+                .fire()
+        };
+        assert_eq!(burn(10), Ok(()));
+        assert_eq!(balance(), Ok(90));
+        // The error reverts the emptied balance.
+        assert_eq!(burn(1000), Err(EnvError::ContractCallFailState(0)));
+        assert_eq!(balance(), Ok(90));
+        Ok(())
+    })
+    .expect("the bank reverts failed messages")
+}
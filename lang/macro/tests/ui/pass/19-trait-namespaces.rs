@@ -0,0 +1,57 @@
+use ink_lang as ink;
+
+mod erc20 {
+    use super::ink;
+
+    #[ink::trait_definition(namespace = "erc20")]
+    pub trait Token {
+        #[ink(message)]
+        fn decimals(&self) -> u8;
+    }
+}
+
+mod erc777 {
+    use super::ink;
+
+    #[ink::trait_definition(namespace = "erc777")]
+    pub trait Token {
+        #[ink(message)]
+        fn decimals(&self) -> u8;
+    }
+}
+
+#[ink::contract(version = "0.1.0")]
+mod token {
+    use crate::{
+        erc20,
+        erc777,
+    };
+
+    #[ink(storage)]
+    struct Token {}
+
+    impl Token {
+        #[ink(constructor)]
+        fn new(&mut self) {}
+    }
+
+    /// Both traits have a `decimals` message whose
+    /// selectors are told apart by their namespaces.
+    #[ink(namespace = "erc20")]
+    impl erc20::Token for Token {
+        #[ink(message)]
+        fn decimals(&self) -> u8 {
+            18
+        }
+    }
+
+    #[ink(namespace = "erc777")]
+    impl erc777::Token for Token {
+        #[ink(message)]
+        fn decimals(&self) -> u8 {
+            18
+        }
+    }
+}
+
+fn main() {}
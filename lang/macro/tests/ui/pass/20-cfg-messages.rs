@@ -0,0 +1,80 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod counter {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Counter {
+        value: storage::Value<u32>,
+    }
+
+    impl Counter {
+        #[ink(constructor)]
+        fn new(&mut self) {
+            self.value.set(0)
+        }
+
+        /// Only available to builds with the `admin` feature enabled.
+        #[cfg(feature = "admin")]
+        #[ink(constructor)]
+        fn with_value(&mut self, value: u32) {
+            self.value.set(value)
+        }
+
+        #[ink(message)]
+        fn inc(&mut self) {
+            *self.value += 1;
+        }
+
+        #[ink(message)]
+        fn get(&self) -> u32 {
+            *self.value
+        }
+
+        /// Excluded from production builds and thus never dispatched.
+        #[cfg(feature = "admin")]
+        #[ink(message)]
+        fn reset(&mut self) {
+            self.value.set(0)
+        }
+
+        /// Messages under mutually exclusive conditions may share their selector.
+        #[cfg(feature = "admin")]
+        #[ink(message)]
+        fn is_admin_build(&self) -> bool {
+            true
+        }
+
+        #[cfg(not(feature = "admin"))]
+        #[ink(message)]
+        fn is_admin_build(&self) -> bool {
+            false
+        }
+    }
+
+    #[cfg(test)]
+    mod tests {
+        use super::*;
+        use ink_lang as ink;
+
+        #[ink::test]
+        fn cfg_gated_functions_are_left_out() {
+            let mut counter = Counter::new();
+            counter.inc();
+            assert_eq!(counter.get(), 1);
+            assert!(!counter.is_admin_build());
+        }
+    }
+}
+
+fn main() {
+    #[cfg(feature = "ink-generate-abi")]
+    {
+        use ink_lang::GenerateAbi as _;
+        let abi = counter::Counter::generate_abi();
+        // The `admin` feature is never enabled so its functions are left out.
+        assert_eq!(abi.spec().constructors().len(), 1);
+        assert_eq!(abi.spec().messages().len(), 3);
+    }
+}
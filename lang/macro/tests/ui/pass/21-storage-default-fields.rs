@@ -0,0 +1,91 @@
+use ink_lang as ink;
+
+#[ink::contract(version = "0.1.0")]
+mod registry {
+    use ink_core::storage;
+
+    #[ink(storage)]
+    struct Registry {
+        /// Initialized with `0` upon instantiation.
+        #[ink(default)]
+        total: storage::Value<u32>,
+        #[ink(default)]
+        owner: storage::Value<AccountId>,
+        entries: storage::HashMap<AccountId, u32>,
+    }
+
+    impl Registry {
+        /// No field has to be set up by hand.
+        #[ink(constructor)]
+        fn new(&mut self) {}
+
+        #[ink(message)]
+        fn register(&mut self, value: u32) {
+            let caller = self.env().caller();
+            self.entries.insert(caller, value);
+            *self.total += 1;
+        }
+
+        #[ink(message)]
+        fn total(&self) -> u32 {
+            *self.total
+        }
+    }
+}
+
+fn main() {
+    use ink_core::env::{
+        self,
+        call::{
+            CallData,
+            Selector,
+        },
+        test::ExecutionContextBuilder,
+        DefaultEnvTypes,
+    };
+    use ink_lang::{
+        DispatchMode,
+        DispatchRetCode,
+        DispatchUsingMode,
+    };
+
+    fn dispatch(mode: DispatchMode, call_data: CallData) -> u32 {
+        ExecutionContextBuilder::<DefaultEnvTypes>::new()
+            .call_data(call_data)
+            .run(|| {
+                DispatchRetCode::from(
+                    <registry::Registry as DispatchUsingMode>::dispatch_using_mode(mode),
+                )
+                .to_u32()
+            })
+            .expect("could not run the dispatch")
+    }
+
+    env::test::run_test::<DefaultEnvTypes, _>(|_| {
+        let success = DispatchRetCode::success().to_u32();
+        assert_eq!(
+            dispatch(
+                DispatchMode::Instantiate,
+                CallData::new(Selector::from_label("new")),
+            ),
+            success,
+        );
+        // Reading and updating `total` panics unless it has been initialized.
+        assert_eq!(
+            dispatch(
+                DispatchMode::Call,
+                CallData::new(Selector::from_label("register")).push_arg(&42u32),
+            ),
+            success,
+        );
+        assert_eq!(
+            dispatch(
+                DispatchMode::Call,
+                CallData::new(Selector::from_label("total")),
+            ),
+            success,
+        );
+        Ok(())
+    })
+    .unwrap()
+}
@@ -13,6 +13,8 @@
 // limitations under the License.
 
 use crate::{
+    enter_non_reentrant,
+    exit_non_reentrant,
     DispatchError,
     FnInput,
     FnOutput,
@@ -112,7 +114,11 @@ where
     where
         T: EnvTypes,
     {
-        if <D as FnSelector>::SELECTOR == data.selector() {
+        // Wildcards are at the end of the list so that all
+        // other dispatchers have been tried before.
+        if <D as FnSelector>::IS_WILDCARD
+            || <D as FnSelector>::SELECTOR == data.selector()
+        {
             self.dispatcher.dispatch::<T>(storage, data)
         } else {
             self.rest.dispatch::<T>(storage, data)
@@ -149,6 +155,7 @@ macro_rules! impl_dispatcher_for {
             Msg: FnInput + FnOutput + FnSelector,
         {
             const SELECTOR: Selector = <Msg as FnSelector>::SELECTOR;
+            const IS_WILDCARD: bool = <Msg as FnSelector>::IS_WILDCARD;
         }
 
         impl<Msg, S> Copy for $name<Msg, S>
@@ -209,8 +216,15 @@ macro_rules! impl_dispatcher_for {
                 T: EnvTypes,
             {
                 use scale::Decode as _;
+                if !<Msg as Message>::IS_PAYABLE {
+                    ink_core::env::ensure_no_value::<T>()
+                        .map_err(|_| DispatchError::PaidUnpayableMessage)?;
+                }
                 let args = <Msg as FnInput>::Input::decode(&mut &data.params()[..])
                     .map_err(|_| DispatchError::InvalidParameters)?;
+                if <Msg as Message>::IS_NON_REENTRANT {
+                    enter_non_reentrant()?;
+                }
                 let result = self.eval(storage, args);
                 if TypeId::of::<<Msg as FnOutput>::Output>() != TypeId::of::<()>() {
                     ink_core::env::output::<<Msg as FnOutput>::Output>(&result)
@@ -219,6 +233,9 @@ macro_rules! impl_dispatcher_for {
                     // Flush the storage since the message might have mutated it.
                     Flush::flush(storage);
                 }
+                if <Msg as Message>::IS_NON_REENTRANT {
+                    exit_non_reentrant();
+                }
                 Ok(())
             }
         }
@@ -21,12 +21,19 @@ use ink_core::{
             InstantiateParams,
             ReturnType,
         },
+        EcdsaPublicKey,
         EnvTypes,
         Result,
+        ReturnFlags,
         Topics,
+        Weight,
     },
 };
-use ink_primitives::Key;
+use ink_primitives::{
+    BlockNumber,
+    Key,
+    Timestamp,
+};
 
 /// Allows to directly access the environment mutably.
 ///
@@ -88,6 +95,33 @@ where
         env::transferred_balance::<T>().expect("couldn't decode transferred balance")
     }
 
+    /// Returns the value transferred for the contract execution.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::transferred_value`]
+    pub fn transferred_value(self) -> T::Balance {
+        env::transferred_value::<T>().expect("couldn't decode transferred value")
+    }
+
+    /// Ensures that no value has been transferred for the contract execution.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::ensure_no_value`]
+    pub fn ensure_no_value(self) -> Result<()> {
+        env::ensure_no_value::<T>()
+    }
+
+    /// Ensures that at least `min_value` has been transferred for the contract execution.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::ensure_min_value`]
+    pub fn ensure_min_value(self, min_value: T::Balance) -> Result<()> {
+        env::ensure_min_value::<T>(min_value)
+    }
+
     /// Returns the current price for gas.
     ///
     /// # Note
@@ -97,6 +131,24 @@ where
         env::gas_price::<T>().expect("couldn't decode gas price")
     }
 
+    /// Returns `true` if the caller is the origin of the extrinsic.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::caller_is_origin`]
+    pub fn caller_is_origin(self) -> bool {
+        env::caller_is_origin()
+    }
+
+    /// Returns the depth of the current call stack.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::call_depth`]
+    pub fn call_depth(self) -> u32 {
+        env::call_depth()
+    }
+
     /// Returns the amount of gas left for the contract execution.
     ///
     /// # Note
@@ -106,12 +158,27 @@ where
         env::gas_left::<T>().expect("couldn't decode gas left")
     }
 
+    /// Returns the amount of gas left for the contract execution as weight.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::weight_left`]
+    pub fn weight_left(self) -> Weight
+    where
+        T::Balance: core::convert::TryInto<u64>,
+    {
+        env::weight_left::<T>().expect("couldn't decode weight left")
+    }
+
     /// Returns the timstamp of the current block.
     ///
     /// # Note
     ///
     /// For more details visit: [`ink_core::env::block_timestamp`]
-    pub fn block_timestamp(self) -> T::Timestamp {
+    pub fn block_timestamp(self) -> Timestamp
+    where
+        T::Timestamp: core::convert::TryInto<u64>,
+    {
         env::block_timestamp::<T>().expect("couldn't decode block time stamp")
     }
 
@@ -149,6 +216,15 @@ where
         env::balance::<T>().expect("couldn't decode contract balance")
     }
 
+    /// Returns the reserved balance of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::reserved_balance`]
+    pub fn reserved_balance(self) -> T::Balance {
+        env::reserved_balance::<T>().expect("couldn't decode contract reserved balance")
+    }
+
     /// Returns the current rent allowance for the executed contract.
     ///
     /// # Note
@@ -158,12 +234,33 @@ where
         env::rent_allowance::<T>().expect("couldn't decode contract rent allowance")
     }
 
+    /// Returns the code hash of the executed contract.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::own_code_hash`]
+    pub fn own_code_hash(self) -> T::Hash {
+        env::own_code_hash::<T>().expect("couldn't decode contract code hash")
+    }
+
+    /// Returns the code hash of the contract at the given account ID if any.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::code_hash`]
+    pub fn code_hash(self, account_id: &T::AccountId) -> Option<T::Hash> {
+        env::code_hash::<T>(account_id).expect("couldn't decode code hash")
+    }
+
     /// Returns the current block number.
     ///
     /// # Note
     ///
     /// For more details visit: [`ink_core::env::block_number`]
-    pub fn block_number(self) -> T::BlockNumber {
+    pub fn block_number(self) -> BlockNumber
+    where
+        T::BlockNumber: core::convert::TryInto<u64>,
+    {
         env::block_number::<T>().expect("couldn't decode block number")
     }
 
@@ -185,6 +282,15 @@ where
         env::tombstone_deposit::<T>().expect("couldn't decode tombstone deposits")
     }
 
+    /// Returns the price for the specified amount of gas.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::weight_to_fee`]
+    pub fn weight_to_fee(self, gas: u64) -> T::Balance {
+        env::weight_to_fee::<T>(gas).expect("couldn't decode weight fee")
+    }
+
     /// Emits an event with the given event data.
     ///
     /// # Note
@@ -248,6 +354,22 @@ where
         env::instantiate_contract::<T, C>(params)
     }
 
+    /// Computes the address of a contract instantiated by `deployer` from the
+    /// given code hash and salt.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::compute_contract_address`]
+    pub fn compute_contract_address(
+        self,
+        deployer: &T::AccountId,
+        code_hash: &T::Hash,
+        salt: &[u8],
+    ) -> T::AccountId {
+        env::compute_contract_address::<T>(deployer, code_hash, salt)
+            .expect("couldn't decode contract address")
+    }
+
     /// Restores a smart contract in tombstone state.
     ///
     /// # Note
@@ -275,6 +397,18 @@ where
         env::terminate_contract::<T>(beneficiary)
     }
 
+    /// Returns the value back to the caller and halts the contract execution.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::return_value`]
+    pub fn return_value<R>(self, flags: ReturnFlags, return_value: &R) -> !
+    where
+        R: scale::Encode,
+    {
+        env::return_value::<R>(flags, return_value)
+    }
+
     /// Transfers value from the contract to the destination account ID.
     ///
     /// # Note
@@ -310,4 +444,31 @@ where
     {
         env::get_runtime_storage::<R>(runtime_key)
     }
+
+    /// Recovers the compressed ECDSA public key from the signature
+    /// of the message hash.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::ecdsa_recover`]
+    pub fn ecdsa_recover(
+        self,
+        signature: &[u8; 65],
+        message_hash: &[u8; 32],
+    ) -> Result<EcdsaPublicKey> {
+        env::ecdsa_recover(signature, message_hash)
+    }
+
+    /// Calls the chain extension function with the given ID and input.
+    ///
+    /// # Note
+    ///
+    /// For more details visit: [`ink_core::env::call_chain_extension`]
+    pub fn call_chain_extension<I, O>(self, func_id: u32, input: &I) -> Result<O>
+    where
+        I: scale::Encode,
+        O: scale::Decode,
+    {
+        env::call_chain_extension::<I, O>(func_id, input)
+    }
 }
@@ -27,6 +27,8 @@ pub enum DispatchError {
     InvalidCallParameters,
 
     CouldNotReadInput,
+    PaidUnpayableMessage,
+    ReentrantCall,
 }
 
 impl DispatchError {
@@ -70,6 +72,8 @@ impl From<DispatchError> for DispatchRetCode {
             DispatchError::InvalidInstantiateParameters => Self(0x05),
             DispatchError::InvalidCallParameters => Self(0x06),
             DispatchError::CouldNotReadInput => Self(0x07),
+            DispatchError::PaidUnpayableMessage => Self(0x08),
+            DispatchError::ReentrantCall => Self(0x09),
         }
     }
 }
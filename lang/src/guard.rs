@@ -0,0 +1,36 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use ink_core::env::ReturnFlags;
+
+/// Reverts the executed message if its guard failed.
+///
+/// This is called for every `#[ink(message, guard = ..)]` before the
+/// body of the message is executed.
+///
+/// # Note
+///
+/// The error is handed back to the caller as `Err(error)` so that it
+/// decodes just like the output of messages returning `Result<T, E>`.
+pub fn check_guard<E>(result: core::result::Result<(), E>)
+where
+    E: scale::Encode,
+{
+    if let Err(error) = result {
+        ink_core::env::return_value::<core::result::Result<(), E>>(
+            ReturnFlags::REVERT,
+            &Err(error),
+        )
+    }
+}
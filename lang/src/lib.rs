@@ -23,10 +23,21 @@ mod cross_calling;
 mod dispatcher;
 mod env_access;
 mod error;
+mod guard;
+mod reentrancy;
 mod testable;
 mod traits;
 
-pub use ink_lang_macro::contract;
+pub use ink_lang_macro::{
+    contract,
+    test,
+    trait_definition,
+};
+
+// Re-exported for the ink! codegen so that contracts are not required
+// to depend on `ink_prelude` themselves.
+#[doc(hidden)]
+pub use ink_prelude;
 
 #[cfg(feature = "ink-generate-abi")]
 pub use self::abi::GenerateAbi;
@@ -63,12 +74,18 @@ pub use self::{
         DispatchResult,
         DispatchRetCode,
     },
+    guard::check_guard,
+    reentrancy::{
+        enter_non_reentrant,
+        exit_non_reentrant,
+    },
     testable::InstantiateTestable,
     traits::{
         FnInput,
         FnOutput,
         FnSelector,
         Message,
+        MessageResult,
         Storage,
     },
 };
@@ -0,0 +1,67 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::DispatchError;
+use ink_primitives::Key;
+
+/// Returns the key of the storage cell flagging an entered contract.
+///
+/// # Note
+///
+/// The key is derived from a hash so that it does not collide with
+/// the cells allocated for the contract storage.
+fn reentrancy_flag() -> Key {
+    Key(ink_primitives::hash::keccak256(
+        "ink_lang::reentrancy_guard",
+    ))
+}
+
+/// Marks the executed contract as entered by a non-reentrant message.
+///
+/// This is called by the dispatch of every `#[ink(message, non_reentrant)]`
+/// before the message is executed.
+///
+/// # Note
+///
+/// The flag is written to the contract storage directly so that it is
+/// visible to calls that reenter the contract while the message executes.
+/// It is also registered to be cleared if the message returns early through
+/// `return_value` without reverting and thus never reaches
+/// [`exit_non_reentrant`].
+///
+/// # Errors
+///
+/// If the contract has already been entered by a non-reentrant message
+/// that has not yet exited.
+pub fn enter_non_reentrant() -> Result<(), DispatchError> {
+    if ink_core::env::get_contract_storage::<bool>(reentrancy_flag()).is_some() {
+        return Err(DispatchError::ReentrantCall)
+    }
+    ink_core::env::set_contract_storage(reentrancy_flag(), &true);
+    ink_core::env::clear_contract_storage_on_return(Some(reentrancy_flag()));
+    Ok(())
+}
+
+/// Marks the executed contract as no longer entered by a non-reentrant message.
+///
+/// This is called by the dispatch of every `#[ink(message, non_reentrant)]`
+/// after the message has been executed.
+///
+/// # Note
+///
+/// The flag is cleared instead of being reset so that no storage remains occupied.
+pub fn exit_non_reentrant() {
+    ink_core::env::clear_contract_storage(reentrancy_flag());
+    ink_core::env::clear_contract_storage_on_return(None);
+}
@@ -35,15 +35,38 @@ pub trait FnOutput {
     type Output: scale::Encode + 'static;
 }
 
+/// Implemented by the `Result` types returned by fallible messages.
+///
+/// # Note
+///
+/// The ink! codegen resolves the error type of aliases that do not name it,
+/// such as `type Result<T> = core::result::Result<T, Error>`, through this trait.
+pub trait MessageResult {
+    /// The error type handed back by a reverted message.
+    type Error;
+}
+
+impl<T, E> MessageResult for core::result::Result<T, E> {
+    type Error = E;
+}
+
 /// The selector of dispatchable functions.
 pub trait FnSelector {
     /// The selector.
     const SELECTOR: Selector;
+    /// If `true` the function is dispatched for every selector
+    /// that is not handled by another function.
+    const IS_WILDCARD: bool = false;
 }
 
 /// Types implementing this are messages that may only read from storage.
 pub trait Message: FnInput + FnOutput + FnSelector {
     const IS_MUT: bool;
+    /// If `false` the dispatch rejects calls and instantiations that transfer value.
+    const IS_PAYABLE: bool;
+    /// If `true` the dispatch rejects calls that reenter the contract
+    /// while another non-reentrant message is executed.
+    const IS_NON_REENTRANT: bool;
 }
 
 /// Types implementing this trait are storage structs.
@@ -85,3 +85,39 @@ where
     val.hash(&mut hasher);
     hasher.finish256()
 }
+
+/// Returns the selector of the given function name.
+///
+/// This is equal to the first four bytes of `keccak256(name.as_bytes())`
+/// on 64-bit targets and thus to the selectors derived by the ink! contract macro.
+///
+/// # Note
+///
+/// Unlike [`keccak256`] the length prefix of the name always is 8 bytes wide
+/// so that selectors do not depend on the target pointer width.
+///
+/// This is not a `const fn` since hashing requires loops and branches which
+/// are not yet supported in constant functions by the toolchain of this
+/// repository. The ink! contract macro computes selectors at expansion time.
+pub fn selector(name: &str) -> [u8; 4] {
+    let mut hasher = Keccak256Hasher::default();
+    hasher.write(&(name.len() as u64).to_le_bytes());
+    hasher.write(name.as_bytes());
+    let hash = hasher.finish256();
+    [hash[0], hash[1], hash[2], hash[3]]
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// The length prefix of `keccak256` is 8 bytes wide on 64-bit targets only.
+    #[test]
+    #[cfg(target_pointer_width = "64")]
+    fn selector_matches_keccak256() {
+        for name in &["", "flip", "a_considerably_longer_name_of_a_message"] {
+            let hash = keccak256(name.as_bytes());
+            assert_eq!(selector(name), [hash[0], hash[1], hash[2], hash[3]]);
+        }
+    }
+}
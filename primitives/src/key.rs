@@ -109,6 +109,53 @@ impl Key {
     pub fn as_bytes_mut(&mut self) -> &mut [u8] {
         &mut self.0
     }
+
+    /// Creates a key from its raw bytes.
+    pub const fn from_bytes(bytes: [u8; 32]) -> Self {
+        Self(bytes)
+    }
+
+    /// Returns the raw bytes of this key.
+    pub const fn to_bytes(self) -> [u8; 32] {
+        self.0
+    }
+}
+
+impl From<[u8; 32]> for Key {
+    fn from(bytes: [u8; 32]) -> Self {
+        Self::from_bytes(bytes)
+    }
+}
+
+impl From<Key> for [u8; 32] {
+    fn from(key: Key) -> Self {
+        key.to_bytes()
+    }
+}
+
+impl core::ops::BitXor for Key {
+    type Output = Self;
+
+    /// Combines both keys byte-wise.
+    ///
+    /// # Note
+    ///
+    /// The result can be any key. Mixing a storage region's base key with a
+    /// hashed entry key this way does not keep the result within the region,
+    /// use offsets from the base key for that instead.
+    fn bitxor(self, rhs: Self) -> Self::Output {
+        let mut result = self;
+        result ^= rhs;
+        result
+    }
+}
+
+impl core::ops::BitXorAssign for Key {
+    fn bitxor_assign(&mut self, rhs: Self) {
+        for (lhs, rhs) in self.0.iter_mut().zip(rhs.0.iter()) {
+            *lhs ^= rhs;
+        }
+    }
 }
 
 impl core::ops::Sub for Key {
@@ -224,10 +271,65 @@ impl_add_sub_for_key!(u32);
 impl_add_sub_for_key!(u64);
 impl_add_sub_for_key!(u128);
 
+impl Key {
+    /// Adds the given offset to the key.
+    ///
+    /// Returns `None` if the addition overflows the key space
+    /// instead of wrapping around as the `+` operator does.
+    pub fn checked_add<T>(self, rhs: T) -> Option<Self>
+    where
+        T: Into<u128>,
+    {
+        let mut result = self;
+        let overflow =
+            byte_utils::bytes_add_bytes(result.as_bytes_mut(), &rhs.into().to_be_bytes());
+        if overflow {
+            return None
+        }
+        Some(result)
+    }
+
+    /// Subtracts the given offset from the key.
+    ///
+    /// Returns `None` if the subtraction underflows the key space
+    /// instead of wrapping around as the `-` operator does.
+    pub fn checked_sub<T>(self, rhs: T) -> Option<Self>
+    where
+        T: Into<u128>,
+    {
+        let mut result = self;
+        let underflow =
+            byte_utils::bytes_sub_bytes(result.as_bytes_mut(), &rhs.into().to_be_bytes());
+        if underflow {
+            return None
+        }
+        Some(result)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
 
+    #[test]
+    fn key_checked_add_sub() {
+        assert_eq!(
+            Key([0x00; 32]).checked_add(1_u32),
+            Some(Key([0x00; 32]) + 1_u32)
+        );
+        assert_eq!(Key([0xFF; 32]).checked_add(0_u32), Some(Key([0xFF; 32])));
+        assert_eq!(Key([0xFF; 32]).checked_add(1_u32), None);
+        assert_eq!(
+            (Key([0xFF; 32]) - 5_u64).checked_add(u128::max_value()),
+            None
+        );
+        assert_eq!(
+            Key([0x01; 32]).checked_sub(1_u64),
+            Some(Key([0x01; 32]) - 1_u64)
+        );
+        assert_eq!(Key([0x00; 32]).checked_sub(1_u8), None);
+    }
+
     #[test]
     fn key_sub() {
         assert_eq!(Key([0x42; 32]), Key([0x42; 32]));
@@ -271,6 +373,42 @@ mod tests {
         }
     }
 
+    #[test]
+    fn key_bytes_round_trip() {
+        let bytes = [
+            0x00, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0x09, 0x0A, 0x0B, 0x0C,
+            0x0D, 0x0E, 0x0F, 0x10, 0x11, 0x12, 0x13, 0x14, 0x15, 0x16, 0x17, 0x18, 0x19,
+            0x1A, 0x1B, 0x1C, 0x1D, 0x1E, 0x1F,
+        ];
+        const KEY: Key = Key::from_bytes([0x42; 32]);
+        assert_eq!(KEY.to_bytes(), [0x42; 32]);
+        assert_eq!(Key::from_bytes(bytes).to_bytes(), bytes);
+        assert_eq!(<[u8; 32]>::from(Key::from(bytes)), bytes);
+    }
+
+    #[test]
+    fn key_xor() {
+        let key = Key([0x5A; 32]);
+        assert_eq!(key ^ key, Key([0x00; 32]));
+        assert_eq!(key ^ Key([0x00; 32]), key);
+        assert_eq!(key ^ Key([0xFF; 32]), Key([0xA5; 32]));
+        let mut mixed = key;
+        mixed ^= Key([0x0F; 32]);
+        assert_eq!(mixed, Key([0x55; 32]));
+        mixed ^= Key([0x0F; 32]);
+        assert_eq!(mixed, key);
+    }
+
+    #[test]
+    fn key_add_wide_offsets() {
+        let key = Key([0x00; 32]);
+        let (max_u64, max_u128) = (u64::max_value(), u128::max_value());
+        assert_eq!((key + max_u64) - key, (key + u128::from(max_u64)) - key);
+        assert_eq!(((key + max_u128) - key).try_to_u128(), Some(max_u128));
+        assert_eq!(key.checked_add(max_u128), Some(key + max_u128));
+        assert_eq!(Key([0xFF; 32]).checked_add(1_u128), None);
+    }
+
     #[test]
     fn as_bytes() {
         let mut key = Key([0x42; 32]);
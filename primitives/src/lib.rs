@@ -26,8 +26,19 @@
 mod byte_utils;
 pub mod hash;
 mod key;
+mod selector;
+mod time;
 
-pub use self::key::{
-    Key,
-    KeyDiff,
+pub use self::{
+    key::{
+        Key,
+        KeyDiff,
+    },
+    selector::Selector,
+    time::{
+        BlockNumber,
+        Duration,
+        Moment,
+        Timestamp,
+    },
 };
@@ -0,0 +1,108 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+use crate::hash;
+use scale::{
+    Decode,
+    Encode,
+};
+
+/// The selector of a message or constructor.
+///
+/// The first four bytes of the call data of every call to a contract.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash, Encode, Decode)]
+pub struct Selector {
+    /// The 4 underlying bytes.
+    bytes: [u8; 4],
+}
+
+impl Selector {
+    /// Creates a selector directly from 4 bytes.
+    pub const fn new(bytes: [u8; 4]) -> Self {
+        Self { bytes }
+    }
+
+    /// Returns the selector for the given label.
+    ///
+    /// # Note
+    ///
+    /// This is equal to the selector derived by ink! for a message or
+    /// constructor of that name.
+    pub fn from_label(label: &str) -> Self {
+        Self::new(hash::selector(label))
+    }
+
+    /// Returns the selector for the given name.
+    ///
+    /// # Note
+    ///
+    /// Same as [`Selector::from_label`].
+    #[allow(clippy::should_implement_trait)]
+    pub fn from_str(name: &str) -> Self {
+        Self::from_label(name)
+    }
+
+    /// Returns the underlying bytes of the selector.
+    pub const fn to_bytes(self) -> [u8; 4] {
+        self.bytes
+    }
+}
+
+impl From<[u8; 4]> for Selector {
+    fn from(bytes: [u8; 4]) -> Self {
+        Self::new(bytes)
+    }
+}
+
+impl From<Selector> for [u8; 4] {
+    fn from(selector: Selector) -> Self {
+        selector.to_bytes()
+    }
+}
+
+impl<'a> From<&'a [u8]> for Selector {
+    /// Computes the selector from the given input bytes.
+    ///
+    /// # Note
+    ///
+    /// Normally selectors are created through `Selector::from_label`.
+    fn from(input: &'a [u8]) -> Self {
+        let keccak = hash::keccak256(input);
+        Self::new([keccak[0], keccak[1], keccak[2], keccak[3]])
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn from_label_works() {
+        let flip = Selector::from_label("flip");
+        assert_eq!(flip.to_bytes(), hash::selector("flip"));
+        assert_eq!(Selector::from_str("flip"), flip);
+        assert_ne!(Selector::from_label("flop"), flip);
+    }
+
+    #[test]
+    fn encoding_is_raw_bytes() {
+        let selector = Selector::new([0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(selector.encode(), vec![0xDE, 0xAD, 0xBE, 0xEF]);
+        assert_eq!(
+            Selector::decode(&mut &[0xDE, 0xAD, 0xBE, 0xEF][..]).ok(),
+            Some(selector)
+        );
+        assert_eq!(<[u8; 4]>::from(selector), [0xDE, 0xAD, 0xBE, 0xEF]);
+    }
+}
@@ -0,0 +1,379 @@
+// Copyright 2018-2019 Parity Technologies (UK) Ltd.
+//
+// Licensed under the Apache License, Version 2.0 (the "License");
+// you may not use this file except in compliance with the License.
+// You may obtain a copy of the License at
+//
+//     http://www.apache.org/licenses/LICENSE-2.0
+//
+// Unless required by applicable law or agreed to in writing, software
+// distributed under the License is distributed on an "AS IS" BASIS,
+// WITHOUT WARRANTIES OR CONDITIONS OF ANY KIND, either express or implied.
+// See the License for the specific language governing permissions and
+// limitations under the License.
+
+//! Strongly typed chain time primitives.
+//!
+//! These wrap the raw integers used by the chain so that mixing up points in
+//! time, block numbers and durations becomes a type error. All of them are
+//! encoded exactly like the `u64` they wrap.
+
+use core::ops::{
+    Add,
+    AddAssign,
+    Sub,
+    SubAssign,
+};
+use scale::{
+    Decode,
+    Encode,
+};
+
+/// A span of time in milliseconds.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode,
+)]
+pub struct Duration(u64);
+
+/// A point in time as milliseconds since the Unix epoch.
+///
+/// This is the time of the block as reported by the chain.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode,
+)]
+pub struct Timestamp(u64);
+
+/// The name of timestamps in Substrate.
+pub type Moment = Timestamp;
+
+/// The number of a block.
+#[derive(
+    Debug, Default, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash, Encode, Decode,
+)]
+pub struct BlockNumber(u64);
+
+#[cfg(feature = "std")]
+impl type_metadata::HasTypeId for Duration {
+    fn type_id() -> type_metadata::TypeId {
+        type_metadata::TypeIdCustom::new(
+            "Duration",
+            type_metadata::Namespace::from_module_path("ink_primitives")
+                .expect("non-empty Rust identifier namespaces cannot fail"),
+            Vec::new(),
+        )
+        .into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl type_metadata::HasTypeDef for Duration {
+    fn type_def() -> type_metadata::TypeDef {
+        use ink_prelude::vec;
+        type_metadata::TypeDefTupleStruct::new(vec![
+            type_metadata::UnnamedField::of::<u64>(),
+        ])
+        .into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl type_metadata::HasTypeId for Timestamp {
+    fn type_id() -> type_metadata::TypeId {
+        type_metadata::TypeIdCustom::new(
+            "Timestamp",
+            type_metadata::Namespace::from_module_path("ink_primitives")
+                .expect("non-empty Rust identifier namespaces cannot fail"),
+            Vec::new(),
+        )
+        .into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl type_metadata::HasTypeDef for Timestamp {
+    fn type_def() -> type_metadata::TypeDef {
+        use ink_prelude::vec;
+        type_metadata::TypeDefTupleStruct::new(vec![
+            type_metadata::UnnamedField::of::<u64>(),
+        ])
+        .into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl type_metadata::HasTypeId for BlockNumber {
+    fn type_id() -> type_metadata::TypeId {
+        type_metadata::TypeIdCustom::new(
+            "BlockNumber",
+            type_metadata::Namespace::from_module_path("ink_primitives")
+                .expect("non-empty Rust identifier namespaces cannot fail"),
+            Vec::new(),
+        )
+        .into()
+    }
+}
+
+#[cfg(feature = "std")]
+impl type_metadata::HasTypeDef for BlockNumber {
+    fn type_def() -> type_metadata::TypeDef {
+        use ink_prelude::vec;
+        type_metadata::TypeDefTupleStruct::new(vec![
+            type_metadata::UnnamedField::of::<u64>(),
+        ])
+        .into()
+    }
+}
+
+impl Duration {
+    /// The empty duration.
+    pub const ZERO: Self = Self(0);
+
+    /// Creates a duration of the given milliseconds.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis)
+    }
+
+    /// Creates a duration of the given seconds.
+    ///
+    /// Returns `None` if the milliseconds overflow.
+    pub fn from_secs(secs: u64) -> Option<Self> {
+        secs.checked_mul(1000).map(Self)
+    }
+
+    /// Returns the milliseconds of the duration.
+    pub const fn as_millis(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the sum of both durations or `None` upon overflow.
+    pub fn checked_add(self, rhs: Self) -> Option<Self> {
+        self.0.checked_add(rhs.0).map(Self)
+    }
+
+    /// Returns the difference of both durations or `None` upon underflow.
+    pub fn checked_sub(self, rhs: Self) -> Option<Self> {
+        self.0.checked_sub(rhs.0).map(Self)
+    }
+}
+
+impl Timestamp {
+    /// Creates a timestamp of the given milliseconds since the Unix epoch.
+    pub const fn from_millis(millis: u64) -> Self {
+        Self(millis)
+    }
+
+    /// Returns the milliseconds since the Unix epoch.
+    pub const fn as_millis(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the timestamp the duration after `self` or `None` upon overflow.
+    pub fn checked_add(self, duration: Duration) -> Option<Self> {
+        self.0.checked_add(duration.0).map(Self)
+    }
+
+    /// Returns the timestamp the duration before `self` or `None` upon underflow.
+    pub fn checked_sub(self, duration: Duration) -> Option<Self> {
+        self.0.checked_sub(duration.0).map(Self)
+    }
+
+    /// Returns the duration elapsed from `earlier` to `self`.
+    ///
+    /// Returns `None` if `earlier` is after `self`.
+    pub fn checked_duration_since(self, earlier: Self) -> Option<Duration> {
+        self.0.checked_sub(earlier.0).map(Duration)
+    }
+}
+
+impl BlockNumber {
+    /// Creates a block number from its raw value.
+    pub const fn new(number: u64) -> Self {
+        Self(number)
+    }
+
+    /// Returns the raw value of the block number.
+    pub const fn get(self) -> u64 {
+        self.0
+    }
+
+    /// Returns the block number `blocks` after `self` or `None` upon overflow.
+    pub fn checked_add(self, blocks: u64) -> Option<Self> {
+        self.0.checked_add(blocks).map(Self)
+    }
+
+    /// Returns the block number `blocks` before `self` or `None` upon underflow.
+    pub fn checked_sub(self, blocks: u64) -> Option<Self> {
+        self.0.checked_sub(blocks).map(Self)
+    }
+
+    /// Returns the number of blocks from `earlier` to `self`.
+    ///
+    /// Returns `None` if `earlier` is after `self`.
+    pub fn checked_blocks_since(self, earlier: Self) -> Option<u64> {
+        self.0.checked_sub(earlier.0)
+    }
+}
+
+macro_rules! impl_from_into_u64 {
+    ( $($ty:ident),* ) => {
+        $(
+            impl From<u64> for $ty {
+                fn from(raw: u64) -> Self {
+                    Self(raw)
+                }
+            }
+
+            impl From<$ty> for u64 {
+                fn from(value: $ty) -> Self {
+                    value.0
+                }
+            }
+        )*
+    };
+}
+
+impl_from_into_u64!(Duration, Timestamp, BlockNumber);
+
+/// Implements an arithmetic operator that panics upon overflow in terms of
+/// the given checked operation.
+macro_rules! impl_panicking_op {
+    (
+        $op:ident::$op_fn:ident, $op_assign:ident::$op_assign_fn:ident,
+        $lhs:ty, $rhs:ty, $checked:ident, $msg:literal
+    ) => {
+        impl $op<$rhs> for $lhs {
+            type Output = Self;
+
+            fn $op_fn(self, rhs: $rhs) -> Self::Output {
+                self.$checked(rhs).expect($msg)
+            }
+        }
+
+        impl $op_assign<$rhs> for $lhs {
+            fn $op_assign_fn(&mut self, rhs: $rhs) {
+                *self = $op::$op_fn(*self, rhs)
+            }
+        }
+    };
+}
+
+impl_panicking_op!(
+    Add::add,
+    AddAssign::add_assign,
+    Duration,
+    Duration,
+    checked_add,
+    "overflow when adding durations"
+);
+impl_panicking_op!(
+    Sub::sub,
+    SubAssign::sub_assign,
+    Duration,
+    Duration,
+    checked_sub,
+    "overflow when subtracting durations"
+);
+impl_panicking_op!(
+    Add::add,
+    AddAssign::add_assign,
+    Timestamp,
+    Duration,
+    checked_add,
+    "overflow when adding duration to timestamp"
+);
+impl_panicking_op!(
+    Sub::sub,
+    SubAssign::sub_assign,
+    Timestamp,
+    Duration,
+    checked_sub,
+    "overflow when subtracting duration from timestamp"
+);
+impl_panicking_op!(
+    Add::add,
+    AddAssign::add_assign,
+    BlockNumber,
+    u64,
+    checked_add,
+    "overflow when adding blocks to block number"
+);
+impl_panicking_op!(
+    Sub::sub,
+    SubAssign::sub_assign,
+    BlockNumber,
+    u64,
+    checked_sub,
+    "overflow when subtracting blocks from block number"
+);
+
+impl Sub for Timestamp {
+    type Output = Duration;
+
+    fn sub(self, earlier: Self) -> Self::Output {
+        self.checked_duration_since(earlier)
+            .expect("timestamp to subtract is later than self")
+    }
+}
+
+impl Sub for BlockNumber {
+    type Output = u64;
+
+    fn sub(self, earlier: Self) -> Self::Output {
+        self.checked_blocks_since(earlier)
+            .expect("block number to subtract is later than self")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn timestamp_arithmetic() {
+        let start = Timestamp::from_millis(1_000);
+        let later = start + Duration::from_secs(2).unwrap();
+        assert_eq!(later, Timestamp::from_millis(3_000));
+        assert_eq!(later - start, Duration::from_millis(2_000));
+        assert_eq!(
+            later - Duration::from_millis(500),
+            Timestamp::from_millis(2_500)
+        );
+        assert_eq!(start.checked_duration_since(later), None);
+        assert_eq!(start.checked_sub(Duration::from_millis(1_001)), None);
+        assert_eq!(
+            Timestamp::from_millis(u64::max_value())
+                .checked_add(Duration::from_millis(1)),
+            None
+        );
+        assert_eq!(Duration::from_secs(u64::max_value()), None);
+    }
+
+    #[test]
+    fn block_number_arithmetic() {
+        let mut number = BlockNumber::new(10);
+        number += 5;
+        assert_eq!(number, BlockNumber::new(15));
+        assert_eq!(number - BlockNumber::new(10), 5);
+        assert_eq!(number - 15, BlockNumber::default());
+        assert_eq!(number.checked_sub(16), None);
+        assert_eq!(number.checked_blocks_since(BlockNumber::new(16)), None);
+    }
+
+    #[test]
+    #[should_panic(expected = "overflow when subtracting durations")]
+    fn duration_underflow_panics() {
+        let _ = Duration::ZERO - Duration::from_millis(1);
+    }
+
+    #[test]
+    fn encoded_like_u64() {
+        let timestamp = Timestamp::from_millis(0x0102_0304);
+        assert_eq!(timestamp.encode(), 0x0102_0304_u64.encode());
+        assert_eq!(
+            Timestamp::decode(&mut &0x0102_0304_u64.encode()[..]).ok(),
+            Some(timestamp)
+        );
+        assert_eq!(BlockNumber::new(7).encode(), 7_u64.encode());
+        assert_eq!(Duration::from_millis(7).encode(), 7_u64.encode());
+    }
+}